@@ -0,0 +1,134 @@
+//! Benchmarks for decompressing and decoding Archive II volumes generated by
+//! [nexrad_data::volume::SyntheticVolume]. Criterion writes machine-readable estimates for each
+//! benchmark to `target/criterion/<group>/<benchmark>/new/estimates.json`.
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use nexrad_data::volume::{File, Record, SyntheticVolume};
+use nexrad_decode::messages::digital_radar_data::GenericDataBlock;
+use nexrad_decode::messages::Message;
+use std::hint::black_box;
+
+/// A volume comparable in size to an operational super-resolution volume's lowest sweeps.
+fn synthetic_volume() -> File {
+    SyntheticVolume::new()
+        .with_elevation_count(4)
+        .with_radials_per_sweep(720)
+        .with_gate_count(1832)
+        .generate()
+        .unwrap_or_else(|err| panic!("failed to generate synthetic volume: {err}"))
+}
+
+fn decompressed_records(volume: &File) -> Vec<Record<'static>> {
+    volume
+        .records()
+        .iter()
+        .filter_map(|record| record.decompress().ok())
+        .collect()
+}
+
+fn bench_decompression(c: &mut Criterion) {
+    let volume = synthetic_volume();
+
+    let mut group = c.benchmark_group("decompression");
+    group.throughput(Throughput::Bytes(volume.data().len() as u64));
+    group.sample_size(10);
+    group.bench_function("volume_records", |b| {
+        b.iter(|| decompressed_records(black_box(&volume)))
+    });
+    group.finish();
+}
+
+fn bench_decoding(c: &mut Criterion) {
+    let volume = synthetic_volume();
+    let records = decompressed_records(&volume);
+    let message_count = records
+        .iter()
+        .filter_map(|record| record.messages().ok())
+        .map(|messages| messages.len() as u64)
+        .sum();
+
+    let mut group = c.benchmark_group("decoding");
+    group.throughput(Throughput::Elements(message_count));
+    group.sample_size(10);
+    group.bench_function("messages", |b| {
+        b.iter(|| {
+            for record in black_box(&records) {
+                black_box(record.messages().ok());
+            }
+        })
+    });
+    group.bench_function("scan", |b| {
+        b.iter_batched(
+            || volume.clone(),
+            |volume| black_box(volume.scan().ok()),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+fn generic_data_blocks<'a>(records: &'a [Record]) -> Vec<GenericDataBlock<'a>> {
+    records
+        .iter()
+        .filter_map(|record| record.messages().ok())
+        .flatten()
+        .filter_map(|message| match message.message {
+            Message::DigitalRadarData(radar_data) => Some(*radar_data),
+            _ => None,
+        })
+        .flat_map(|radar_data| {
+            [
+                radar_data.reflectivity_data_block,
+                radar_data.velocity_data_block,
+                radar_data.spectrum_width_data_block,
+                radar_data.differential_reflectivity_data_block,
+                radar_data.differential_phase_data_block,
+                radar_data.correlation_coefficient_data_block,
+            ]
+        })
+        .flatten()
+        .collect()
+}
+
+fn bench_moment_unpacking(c: &mut Criterion) {
+    let volume = synthetic_volume();
+    let records = decompressed_records(&volume);
+    let blocks = generic_data_blocks(&records);
+    let gate_count = blocks
+        .iter()
+        .map(|block| block.header.number_of_data_moment_gates as u64)
+        .sum();
+    let max_gates = blocks
+        .iter()
+        .map(|block| block.header.number_of_data_moment_gates as usize)
+        .max()
+        .unwrap_or_default();
+
+    let mut group = c.benchmark_group("moment_unpacking");
+    group.throughput(Throughput::Elements(gate_count));
+    group.sample_size(10);
+    group.bench_function("decoded_values", |b| {
+        b.iter(|| {
+            for block in black_box(&blocks) {
+                black_box(block.decoded_values());
+            }
+        })
+    });
+    group.bench_function("decode_into", |b| {
+        let mut output = vec![0.0; max_gates];
+        b.iter(|| {
+            for block in black_box(&blocks) {
+                black_box(block.decode_into(&mut output).ok());
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_decompression,
+    bench_decoding,
+    bench_moment_unpacking
+);
+criterion_main!(benches);
@@ -126,7 +126,12 @@ async fn main() -> nexrad_data::result::Result<()> {
                 record = record.decompress().expect("Failed to decompress record");
             }
 
-            messages.extend(record.messages()?.iter().cloned());
+            messages.extend(
+                record
+                    .messages()?
+                    .into_iter()
+                    .map(|message| message.into_owned()),
+            );
         }
 
         let summary = nexrad_decode::summarize::messages(messages.as_slice());
@@ -139,7 +144,7 @@ async fn main() -> nexrad_data::result::Result<()> {
 /// Returns the index of the file with the nearest time to the provided start time.
 #[cfg(feature = "aws")]
 fn get_nearest_file_index(
-    files: &Vec<nexrad_data::aws::archive::Identifier>,
+    files: &[nexrad_data::aws::archive::Identifier],
     start_time: chrono::NaiveTime,
 ) -> usize {
     let first_file = files.first().expect("find at least one file");
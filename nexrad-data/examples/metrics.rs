@@ -0,0 +1,211 @@
+use clap::Parser;
+use log::{info, warn, LevelFilter};
+
+// Example scrape output:
+//
+//     # HELP nexrad_chunks_received_total Real-time chunks downloaded.
+//     # TYPE nexrad_chunks_received_total counter
+//     nexrad_chunks_received_total{site="KDMX"} 42
+//     ...
+//     # HELP nexrad_scan_lag_seconds Delay between the latest sweep's last radial and its receipt.
+//     # TYPE nexrad_scan_lag_seconds gauge
+//     nexrad_scan_lag_seconds{site="KDMX"} 21.384
+
+#[cfg(not(all(
+    feature = "aws",
+    feature = "tokio",
+    feature = "decode",
+    feature = "nexrad-model"
+)))]
+fn main() {
+    println!(
+        "This example requires the \"aws\", \"tokio\", \"decode\", and \"nexrad-model\" features to be enabled."
+    );
+}
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    /// Site identifiers (e.g., KDMX KTLX)
+    #[arg(default_values = ["KDMX"])]
+    sites: Vec<String>,
+
+    /// The address to serve Prometheus metrics from
+    #[arg(long, default_value = "127.0.0.1:9184")]
+    listen: String,
+}
+
+#[cfg(all(
+    feature = "aws",
+    feature = "tokio",
+    feature = "decode",
+    feature = "nexrad-model"
+))]
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    use chrono::Utc;
+    use nexrad_data::aws::realtime::Subscriptions;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
+        .filter_module("reqwest::connect", LevelFilter::Info)
+        .init();
+
+    let cli = Cli::parse();
+
+    let (sweep_tx, mut sweep_rx) = mpsc::channel(16);
+    let subscriptions = Subscriptions::start(cli.sites, sweep_tx);
+
+    // Tracks each site's radials received and the lag from its latest sweep's collection
+    let sweep_stats = Arc::new(Mutex::new(HashMap::<String, SweepStats>::new()));
+
+    let receiver_stats = sweep_stats.clone();
+    tokio::spawn(async move {
+        while let Some(site_sweep) = sweep_rx.recv().await {
+            let latest_collection = site_sweep
+                .sweep
+                .radials()
+                .iter()
+                .map(|radial| radial.collection_timestamp())
+                .max();
+
+            if let Ok(mut stats) = receiver_stats.lock() {
+                let site_stats = stats.entry(site_sweep.site).or_default();
+                site_stats.radials_received += site_sweep.sweep.radials().len();
+                site_stats.scan_lag_seconds = latest_collection.map(|collection_millis| {
+                    (Utc::now().timestamp_millis() - collection_millis) as f64 / 1000.0
+                });
+            }
+        }
+    });
+
+    let listener = TcpListener::bind(&cli.listen).await?;
+    info!("Serving metrics at http://{}/metrics", cli.listen);
+
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+
+        // The request itself is ignored; every path serves the metrics
+        let mut request = [0; 1024];
+        if let Err(err) = stream.read(&mut request).await {
+            warn!("Failed to read scrape request: {err}");
+            continue;
+        }
+
+        let sweep_stats = sweep_stats
+            .lock()
+            .map(|stats| stats.clone())
+            .unwrap_or_default();
+        let body = render_metrics(&subscriptions.health(), &sweep_stats);
+
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        if let Err(err) = stream.write_all(response.as_bytes()).await {
+            warn!("Failed to write scrape response: {err}");
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "aws",
+    feature = "tokio",
+    feature = "decode",
+    feature = "nexrad-model"
+))]
+#[derive(Debug, Clone, Default)]
+struct SweepStats {
+    radials_received: usize,
+    scan_lag_seconds: Option<f64>,
+}
+
+/// Renders each site's subscription health and sweep statistics in the Prometheus text format.
+#[cfg(all(
+    feature = "aws",
+    feature = "tokio",
+    feature = "decode",
+    feature = "nexrad-model"
+))]
+fn render_metrics(
+    health: &[nexrad_data::aws::realtime::SiteHealth],
+    sweep_stats: &std::collections::HashMap<String, SweepStats>,
+) -> String {
+    use nexrad_data::aws::realtime::SiteStatus;
+    use std::fmt::Write;
+
+    let mut body = String::new();
+    let mut family = |name: &str, kind: &str, help: &str, samples: Vec<(&str, f64)>| {
+        let _ = writeln!(body, "# HELP {name} {help}");
+        let _ = writeln!(body, "# TYPE {name} {kind}");
+        for (site, value) in samples {
+            let _ = writeln!(body, "{name}{{site=\"{site}\"}} {value}");
+        }
+    };
+
+    let per_site = |value: &dyn Fn(&nexrad_data::aws::realtime::SiteHealth) -> Option<f64>| {
+        health
+            .iter()
+            .filter_map(|site| Some((site.site.as_str(), value(site)?)))
+            .collect::<Vec<_>>()
+    };
+
+    family(
+        "nexrad_site_up",
+        "gauge",
+        "Whether the site's feed is being polled.",
+        per_site(&|site| Some(f64::from(u8::from(site.status == SiteStatus::Running)))),
+    );
+    family(
+        "nexrad_chunks_received_total",
+        "counter",
+        "Real-time chunks downloaded.",
+        per_site(&|site| Some(site.chunks_received as f64)),
+    );
+    family(
+        "nexrad_decode_errors_total",
+        "counter",
+        "Real-time chunks which failed to decode.",
+        per_site(&|site| Some(site.decode_errors as f64)),
+    );
+    family(
+        "nexrad_sweeps_processed_total",
+        "counter",
+        "Sweeps decoded and dispatched.",
+        per_site(&|site| Some(site.sweeps_dispatched as f64)),
+    );
+    family(
+        "nexrad_radials_received_total",
+        "counter",
+        "Radials received in dispatched sweeps.",
+        per_site(&|site| {
+            let radials_received = sweep_stats
+                .get(&site.site)
+                .map_or(0, |stats| stats.radials_received);
+            Some(radials_received as f64)
+        }),
+    );
+    family(
+        "nexrad_chunk_latency_seconds",
+        "gauge",
+        "Delay between the latest chunk's upload and its download.",
+        per_site(&|site| site.last_chunk_latency.map(|latency| latency.as_secs_f64())),
+    );
+    family(
+        "nexrad_scan_lag_seconds",
+        "gauge",
+        "Delay between the latest sweep's last radial and its receipt.",
+        per_site(&|site| {
+            sweep_stats
+                .get(&site.site)
+                .and_then(|stats| stats.scan_lag_seconds)
+        }),
+    );
+
+    body
+}
@@ -38,9 +38,11 @@ use log::{debug, info, trace, LevelFilter};
 //         ),
 //     }
 
-#[cfg(not(all(feature = "aws", feature = "decode")))]
+#[cfg(not(all(feature = "aws", feature = "tokio", feature = "decode")))]
 fn main() {
-    println!("This example requires the \"aws\" and \"decode\" features to be enabled.");
+    println!(
+        "This example requires the \"aws\", \"tokio\", and \"decode\" features to be enabled."
+    );
 }
 
 #[derive(Parser)]
@@ -55,7 +57,7 @@ struct Cli {
     chunk_count: usize,
 }
 
-#[cfg(all(feature = "aws", feature = "decode"))]
+#[cfg(all(feature = "aws", feature = "tokio", feature = "decode"))]
 #[tokio::main]
 async fn main() -> nexrad_data::result::Result<()> {
     use chrono::Utc;
@@ -151,7 +153,7 @@ async fn main() -> nexrad_data::result::Result<()> {
     Ok(())
 }
 
-#[cfg(all(feature = "aws", feature = "decode"))]
+#[cfg(all(feature = "aws", feature = "tokio", feature = "decode"))]
 fn decode_record(
     mut record: nexrad_data::volume::Record,
     download_time: chrono::DateTime<chrono::Utc>,
@@ -0,0 +1,75 @@
+use clap::Parser;
+use log::{info, LevelFilter};
+
+#[cfg(not(all(
+    feature = "aws",
+    feature = "tokio",
+    feature = "decode",
+    feature = "nexrad-model"
+)))]
+fn main() {
+    println!(
+        "This example requires the \"aws\", \"tokio\", \"decode\", and \"nexrad-model\" features to be enabled."
+    );
+}
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    /// Site identifiers (e.g., KDMX KTLX)
+    #[arg(default_values = ["KDMX"])]
+    sites: Vec<String>,
+
+    /// How long to subscribe for, in seconds
+    #[arg(long, default_value = "300")]
+    duration: u64,
+}
+
+#[cfg(all(
+    feature = "aws",
+    feature = "tokio",
+    feature = "decode",
+    feature = "nexrad-model"
+))]
+#[tokio::main]
+async fn main() {
+    use nexrad_data::aws::realtime::Subscriptions;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+    use tokio::time::{interval, sleep};
+
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
+        .filter_module("reqwest::connect", LevelFilter::Info)
+        .init();
+
+    let cli = Cli::parse();
+
+    let (sweep_tx, mut sweep_rx) = mpsc::channel(16);
+    let subscriptions = Subscriptions::start(cli.sites, sweep_tx);
+
+    let deadline = sleep(Duration::from_secs(cli.duration));
+    tokio::pin!(deadline);
+
+    let mut health_interval = interval(Duration::from_secs(60));
+    loop {
+        tokio::select! {
+            Some(site_sweep) = sweep_rx.recv() => {
+                info!(
+                    "{}: elevation {} with {} radials",
+                    site_sweep.site,
+                    site_sweep.sweep.elevation_number(),
+                    site_sweep.sweep.radials().len()
+                );
+            }
+            _ = health_interval.tick() => {
+                for health in subscriptions.health() {
+                    info!("{health:?}");
+                }
+            }
+            _ = &mut deadline => break,
+        }
+    }
+
+    info!("Shutting down...");
+    subscriptions.shutdown().await;
+}
@@ -10,8 +10,14 @@
 //!
 //! **NEXRAD Level II real-time data**: `arn:aws:s3:::unidata-nexrad-level2-chunks`
 //!
+//! Listing and downloading are runtime-agnostic, while the routines which wait or spawn tasks
+//! depend on the facilities described in [runtime]. Timeouts and retries are configured with the
+//! [network::NetworkOptions] accepted by each function's `_with_options` variant.
+//!
 
 pub mod archive;
+pub mod network;
 pub mod realtime;
+pub mod runtime;
 
 mod s3;
@@ -15,9 +15,20 @@ mod identifier;
 pub use identifier::Identifier;
 
 mod download_file;
-pub use download_file::download_file;
+pub use download_file::{
+    download_file, download_file_with_fallback, download_file_with_fallback_and_options,
+    download_file_with_options,
+};
 
 mod list_files;
-pub use list_files::list_files;
+pub use list_files::{list_files, list_files_with_options};
+
+mod list_volumes;
+pub use list_volumes::{list_volumes, list_volumes_with_options, ArchiveVolume};
+
+#[cfg(all(feature = "decode", feature = "nexrad-model", feature = "tokio"))]
+mod backfill;
+#[cfg(all(feature = "decode", feature = "nexrad-model", feature = "tokio"))]
+pub use backfill::{Backfill, BackfillSummary};
 
 const ARCHIVE_BUCKET: &str = "noaa-nexrad-level2";
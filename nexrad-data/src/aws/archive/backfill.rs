@@ -0,0 +1,315 @@
+use crate::aws::archive::{download_file_with_options, list_files_with_options, Identifier};
+use crate::aws::network::NetworkOptions;
+use crate::aws::runtime::TokioTimer;
+use crate::result::{Error, Result};
+use crate::telemetry::{debug, info, warn};
+use chrono::NaiveDate;
+use nexrad_model::data::Scan;
+use std::collections::HashSet;
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::path::PathBuf;
+use tokio::task::JoinSet;
+
+/// A batch process which lists, downloads, and decodes every archived volume for a set of sites
+/// over a range of dates, passing each decoded volume to a callback. See [Backfill::run].
+///
+/// If a progress file is configured, each volume's name is appended to it once the callback has
+/// processed it successfully, and volumes already listed in it are skipped. An interrupted backfill
+/// therefore resumes where it left off when run again with the same progress file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backfill {
+    sites: Vec<String>,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    concurrency: usize,
+    progress_path: Option<PathBuf>,
+    network_options: NetworkOptions,
+}
+
+/// The outcome of a backfill run.
+#[derive(Debug, Default)]
+pub struct BackfillSummary {
+    /// The number of volumes processed by the callback during this run.
+    pub processed: usize,
+    /// The number of volumes skipped because a previous run had processed them.
+    pub skipped: usize,
+    /// Volumes, or site-dates which could not be listed, that failed along with their errors.
+    /// These are not recorded as complete and will be retried by a subsequent run.
+    pub failed: Vec<(String, Error)>,
+}
+
+impl Backfill {
+    /// Creates a backfill of the given sites' volumes between the start and end dates, inclusive.
+    /// By default, four volumes are downloaded and decoded concurrently, failed requests are retried
+    /// per [NetworkOptions::new], and progress is not recorded.
+    pub fn new(sites: Vec<String>, start_date: NaiveDate, end_date: NaiveDate) -> Self {
+        Self {
+            sites,
+            start_date,
+            end_date,
+            concurrency: 4,
+            progress_path: None,
+            network_options: NetworkOptions::new(),
+        }
+    }
+
+    /// The maximum number of volumes to download and decode concurrently. A limit of zero is
+    /// treated as one.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Records progress to, and resumes progress from, the given file.
+    pub fn with_progress_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.progress_path = Some(path.into());
+        self
+    }
+
+    /// The timeouts and retries with which volumes are listed and downloaded.
+    pub fn with_network_options(mut self, network_options: NetworkOptions) -> Self {
+        self.network_options = network_options;
+        self
+    }
+
+    /// Runs the backfill, calling `process` with each decoded volume in the order downloads
+    /// complete. Failures to list, download, decode, or process a volume are collected in the
+    /// returned summary rather than stopping the backfill; errors reading or writing the progress
+    /// file are returned immediately.
+    pub async fn run(
+        &self,
+        mut process: impl FnMut(&Identifier, Scan) -> Result<()>,
+    ) -> Result<BackfillSummary> {
+        let mut progress = match &self.progress_path {
+            Some(path) => Some(Progress::load(path.clone())?),
+            None => None,
+        };
+
+        let mut summary = BackfillSummary::default();
+        let mut tasks = JoinSet::new();
+
+        for date in self.start_date.iter_days() {
+            if date > self.end_date {
+                break;
+            }
+
+            for site in &self.sites {
+                let identifiers =
+                    match list_files_with_options(site, &date, &self.network_options, &TokioTimer)
+                        .await
+                    {
+                        Ok(identifiers) => identifiers,
+                        Err(err) => {
+                            warn!("Failed to list {site} volumes for {date}: {err}");
+                            summary.failed.push((format!("{site}/{date}"), err));
+                            continue;
+                        }
+                    };
+
+                debug!("Found {} {site} files for {date}", identifiers.len());
+                for identifier in volume_identifiers(identifiers) {
+                    if progress
+                        .as_ref()
+                        .is_some_and(|progress| progress.contains(identifier.name()))
+                    {
+                        summary.skipped += 1;
+                        continue;
+                    }
+
+                    while tasks.len() >= self.concurrency {
+                        if let Some(result) = tasks.join_next().await {
+                            complete(result, &mut process, &mut progress, &mut summary)?;
+                        }
+                    }
+
+                    tasks.spawn(download_and_decode(identifier, self.network_options));
+                }
+            }
+        }
+
+        while let Some(result) = tasks.join_next().await {
+            complete(result, &mut process, &mut progress, &mut summary)?;
+        }
+
+        info!(
+            "Backfill processed {} volumes, skipped {}, and failed {}",
+            summary.processed,
+            summary.skipped,
+            summary.failed.len()
+        );
+
+        Ok(summary)
+    }
+}
+
+/// Filters listed archive files to volumes, excluding auxiliary files such as metadata ("_MDM").
+fn volume_identifiers(identifiers: Vec<Identifier>) -> impl Iterator<Item = Identifier> {
+    identifiers
+        .into_iter()
+        .filter(|identifier| !identifier.name().ends_with("_MDM"))
+}
+
+/// Downloads a volume and decodes it on a blocking task.
+async fn download_and_decode(
+    identifier: Identifier,
+    network_options: NetworkOptions,
+) -> (Identifier, Result<Scan>) {
+    let file =
+        match download_file_with_options(identifier.clone(), &network_options, &TokioTimer).await {
+            Ok(file) => file,
+            Err(err) => return (identifier, Err(err)),
+        };
+
+    let scan = tokio::task::spawn_blocking(move || file.scan())
+        .await
+        .map_err(Error::TaskError)
+        .and_then(|scan| scan);
+
+    (identifier, scan)
+}
+
+/// Handles a completed download, passing its volume to the callback and recording progress. A
+/// download task which panicked or was cancelled fails the backfill since its volume is unknown.
+fn complete(
+    result: std::result::Result<(Identifier, Result<Scan>), tokio::task::JoinError>,
+    process: &mut impl FnMut(&Identifier, Scan) -> Result<()>,
+    progress: &mut Option<Progress>,
+    summary: &mut BackfillSummary,
+) -> Result<()> {
+    let (identifier, scan) = result?;
+
+    match scan.and_then(|scan| process(&identifier, scan)) {
+        Ok(()) => {
+            if let Some(progress) = progress {
+                progress.record(identifier.name())?;
+            }
+            summary.processed += 1;
+        }
+        Err(err) => {
+            warn!("Failed to process {}: {err}", identifier.name());
+            summary.failed.push((identifier.name().to_string(), err));
+        }
+    }
+
+    Ok(())
+}
+
+/// The names of completed volumes, persisted one per line.
+struct Progress {
+    path: PathBuf,
+    completed: HashSet<String>,
+}
+
+impl Progress {
+    /// Loads progress from the given file, which need not exist yet.
+    fn load(path: PathBuf) -> Result<Self> {
+        let completed = match std::fs::File::open(&path) {
+            Ok(file) => BufReader::new(file)
+                .lines()
+                .map(|line| line.map(|line| line.trim().to_string()))
+                .filter(|line| !line.as_ref().is_ok_and(|line| line.is_empty()))
+                .collect::<std::io::Result<_>>()?,
+            Err(err) if err.kind() == ErrorKind::NotFound => HashSet::new(),
+            Err(err) => return Err(err.into()),
+        };
+
+        Ok(Self { path, completed })
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        self.completed.contains(name)
+    }
+
+    /// Durably records a volume as completed.
+    fn record(&mut self, name: &str) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{name}")?;
+        file.sync_data()?;
+
+        self.completed.insert(name.to_string());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_resumes() -> Result<()> {
+        let path = std::env::temp_dir().join(format!(
+            "nexrad-backfill-progress-{}.txt",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let mut progress = Progress::load(path.clone())?;
+        assert!(!progress.contains("KDMX20240101_000000_V06"));
+        progress.record("KDMX20240101_000000_V06")?;
+        progress.record("KDMX20240101_000500_V06")?;
+
+        let resumed = Progress::load(path.clone())?;
+        assert!(resumed.contains("KDMX20240101_000000_V06"));
+        assert!(resumed.contains("KDMX20240101_000500_V06"));
+        assert!(!resumed.contains("KDMX20240101_001000_V06"));
+
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_volume_identifiers_exclude_metadata() {
+        let identifiers = vec![
+            Identifier::new("KDMX20240101_000000_V06".to_string()),
+            Identifier::new("KDMX20240101_000000_V06_MDM".to_string()),
+        ];
+
+        let names: Vec<String> = volume_identifiers(identifiers)
+            .map(|identifier| identifier.name().to_string())
+            .collect();
+        assert_eq!(names, vec!["KDMX20240101_000000_V06".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_complete_propagates_errors() -> Result<()> {
+        let identifier = Identifier::new("KDMX20240101_000000_V06".to_string());
+        let mut summary = BackfillSummary::default();
+        let mut process = |_: &Identifier, _: Scan| Ok(());
+
+        // A volume which failed to download or decode is recorded and the backfill continues
+        let failed_volume = Ok((identifier.clone(), Err(Error::MissingCoveragePattern)));
+        complete(failed_volume, &mut process, &mut None, &mut summary)?;
+        assert_eq!(summary.processed, 0);
+        assert_eq!(summary.failed.len(), 1);
+        assert_eq!(summary.failed[0].0, identifier.name());
+        assert!(matches!(summary.failed[0].1, Error::MissingCoveragePattern));
+
+        // A download task which panicked or was cancelled fails the backfill
+        let mut tasks = JoinSet::new();
+        tasks.spawn(async { panic!("decoder panicked") });
+        let Some(panicked) = tasks.join_next().await else {
+            panic!("expected a completed task");
+        };
+        assert!(matches!(
+            complete(panicked, &mut process, &mut None, &mut summary),
+            Err(Error::TaskError(err)) if err.is_panic()
+        ));
+
+        let handle = tasks.spawn(std::future::pending());
+        handle.abort();
+        let Some(cancelled) = tasks.join_next().await else {
+            panic!("expected a completed task");
+        };
+        assert!(matches!(
+            complete(cancelled, &mut process, &mut None, &mut summary),
+            Err(Error::TaskError(err)) if err.is_cancelled()
+        ));
+
+        assert_eq!(summary.failed.len(), 1);
+        Ok(())
+    }
+}
@@ -1,12 +1,32 @@
 use crate::aws::archive::identifier::Identifier;
+use crate::aws::archive::list_files::list_file_objects;
+use crate::aws::archive::list_volumes::METADATA_SUFFIX;
 use crate::aws::archive::ARCHIVE_BUCKET;
+use crate::aws::network::{is_transient, single_attempt, NetworkOptions, NoDelay};
+use crate::aws::runtime::Timer;
 use crate::aws::s3::download_object;
-use crate::result::aws::AWSError::{DateTimeError, InvalidSiteIdentifier};
+use crate::result::aws::AWSError::{DateTimeError, InvalidSiteIdentifier, S3ObjectNotFoundError};
+use crate::result::Error;
+use crate::telemetry::debug;
 use crate::volume::File;
+use chrono::Utc;
+use std::cmp::Reverse;
 
 /// Download a data file specified by its metadata. Returns the downloaded file's encoded contents
-/// which may then need to be decompressed and decoded.
+/// which may then need to be decompressed and decoded, with its S3 location and retrieval time
+/// recorded as its source.
 pub async fn download_file(identifier: Identifier) -> crate::result::Result<File> {
+    download_file_with_options(identifier, &single_attempt(), &NoDelay).await
+}
+
+/// Download a data file like [download_file], retrying failed requests as configured by the given
+/// options and waiting between retries with the given timer. A file which is not found is not
+/// retried.
+pub async fn download_file_with_options(
+    identifier: Identifier,
+    options: &NetworkOptions,
+    timer: &impl Timer,
+) -> crate::result::Result<File> {
     let date = identifier
         .date_time()
         .ok_or_else(|| DateTimeError(identifier.name().to_string()))?;
@@ -16,7 +36,119 @@ pub async fn download_file(identifier: Identifier) -> crate::result::Result<File
         .ok_or_else(|| InvalidSiteIdentifier(identifier.name().to_string()))?;
 
     let key = format!("{}/{}/{}", date.format("%Y/%m/%d"), site, identifier.name());
-    let downloaded_object = download_object(ARCHIVE_BUCKET, &key).await?;
+    let (_, downloaded_object) = options
+        .retry(timer, is_transient, || {
+            download_object(ARCHIVE_BUCKET, &key, options)
+        })
+        .await;
+
+    let downloaded_object = downloaded_object?;
+
+    Ok(File::new(downloaded_object.data)
+        .with_source(format!("s3://{}/{}", ARCHIVE_BUCKET, key), Utc::now()))
+}
+
+/// Download a data file like [download_file], but if it is not found, retry with the other files
+/// uploaded for the same site and scan time under different naming variants, such as a `_V03`,
+/// `_V04`, or `_V06` suffix or a `.gz` extension. Variants are tried from the newest format
+/// version, with compressed variants last. Returns the identifier of the file that was found
+/// along with its contents.
+pub async fn download_file_with_fallback(
+    identifier: Identifier,
+) -> crate::result::Result<(Identifier, File)> {
+    download_file_with_fallback_and_options(identifier, &single_attempt(), &NoDelay).await
+}
+
+/// Download a data file like [download_file_with_fallback], retrying failed requests as configured
+/// by the given options and waiting between retries with the given timer.
+pub async fn download_file_with_fallback_and_options(
+    identifier: Identifier,
+    options: &NetworkOptions,
+    timer: &impl Timer,
+) -> crate::result::Result<(Identifier, File)> {
+    match download_file_with_options(identifier.clone(), options, timer).await {
+        Err(Error::AWS(S3ObjectNotFoundError)) => {}
+        result => return result.map(|file| (identifier, file)),
+    }
+
+    let date = identifier
+        .date_time()
+        .ok_or_else(|| DateTimeError(identifier.name().to_string()))?;
+
+    let site = identifier
+        .site()
+        .ok_or_else(|| InvalidSiteIdentifier(identifier.name().to_string()))?;
+
+    let files = list_file_objects(site, &date.date_naive(), options, timer).await?;
+    for variant in naming_variants(&identifier, files.into_iter().map(|(file, _)| file)) {
+        debug!(
+            "File {} not found, trying variant {}",
+            identifier.name(),
+            variant.name()
+        );
+
+        match download_file_with_options(variant.clone(), options, timer).await {
+            Err(Error::AWS(S3ObjectNotFoundError)) => continue,
+            result => return result.map(|file| (variant, file)),
+        }
+    }
+
+    Err(Error::AWS(S3ObjectNotFoundError))
+}
+
+/// The data files among those given for the same site and scan time as the identifier, other than
+/// the identifier itself, in the order they should be tried.
+fn naming_variants(
+    identifier: &Identifier,
+    files: impl IntoIterator<Item = Identifier>,
+) -> Vec<Identifier> {
+    let mut variants: Vec<Identifier> = files
+        .into_iter()
+        .filter(|file| {
+            file != identifier
+                && file.site() == identifier.site()
+                && file.date_time().is_some()
+                && file.date_time() == identifier.date_time()
+                && !file.name().ends_with(METADATA_SUFFIX)
+        })
+        .collect();
+
+    variants.sort_by_key(|file| (file.name().ends_with(".gz"), Reverse(file.clone())));
+    variants
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identifier(name: &str) -> Identifier {
+        Identifier::new(name.to_string())
+    }
+
+    #[test]
+    fn test_naming_variants() {
+        let variants = naming_variants(
+            &identifier("KDMX20240101_001012_V06"),
+            vec![
+                identifier("KDMX20240101_000512_V06"),
+                identifier("KDMX20240101_001012_V06.gz"),
+                identifier("KDMX20240101_001012"),
+                identifier("KDMX20240101_001012_V06_MDM"),
+                identifier("KDMX20240101_001012_V03"),
+                identifier("KDMX20240101_001012_V06"),
+                identifier("KDMX20240101_001012_V04"),
+            ],
+        );
 
-    Ok(File::new(downloaded_object.data))
+        let names: Vec<&str> = variants.iter().map(Identifier::name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "KDMX20240101_001012_V04",
+                "KDMX20240101_001012_V03",
+                "KDMX20240101_001012",
+                "KDMX20240101_001012_V06.gz",
+            ]
+        );
+    }
 }
@@ -1,5 +1,7 @@
 use crate::aws::archive::identifier::Identifier;
 use crate::aws::archive::ARCHIVE_BUCKET;
+use crate::aws::network::{is_transient, single_attempt, NetworkOptions, NoDelay};
+use crate::aws::runtime::Timer;
 use crate::aws::s3::list_objects;
 use crate::result::aws::AWSError::TruncatedListObjectsResponse;
 use crate::result::Error::AWS;
@@ -8,22 +10,53 @@ use chrono::NaiveDate;
 /// List data files for the specified site and date. This effectively returns an index of data files
 /// which can then be individually downloaded.
 pub async fn list_files(site: &str, date: &NaiveDate) -> crate::result::Result<Vec<Identifier>> {
+    list_files_with_options(site, date, &single_attempt(), &NoDelay).await
+}
+
+/// List data files like [list_files], retrying failed requests as configured by the given options
+/// and waiting between retries with the given timer.
+pub async fn list_files_with_options(
+    site: &str,
+    date: &NaiveDate,
+    options: &NetworkOptions,
+    timer: &impl Timer,
+) -> crate::result::Result<Vec<Identifier>> {
+    let objects = list_file_objects(site, date, options, timer).await?;
+    Ok(objects
+        .into_iter()
+        .map(|(identifier, _)| identifier)
+        .collect())
+}
+
+/// List data files for the specified site and date along with their sizes in bytes.
+pub(crate) async fn list_file_objects(
+    site: &str,
+    date: &NaiveDate,
+    options: &NetworkOptions,
+    timer: &impl Timer,
+) -> crate::result::Result<Vec<(Identifier, u64)>> {
     let prefix = format!("{}/{}", date.format("%Y/%m/%d"), site);
-    let list_result = list_objects(ARCHIVE_BUCKET, &prefix, None).await?;
+    let (_, list_result) = options
+        .retry(timer, is_transient, || {
+            list_objects(ARCHIVE_BUCKET, &prefix, None, options)
+        })
+        .await;
+
+    let list_result = list_result?;
     if list_result.truncated {
         return Err(AWS(TruncatedListObjectsResponse));
     }
 
-    let metas = list_result
+    let objects = list_result
         .objects
-        .iter()
+        .into_iter()
         .map(|object| {
             let key_parts = object.key.split('/');
             let name = key_parts.skip(4).collect::<String>();
 
-            Identifier::new(name)
+            (Identifier::new(name), object.size)
         })
         .collect();
 
-    Ok(metas)
+    Ok(objects)
 }
@@ -0,0 +1,201 @@
+use crate::aws::archive::identifier::Identifier;
+use crate::aws::archive::list_files::list_file_objects;
+use crate::aws::network::{single_attempt, NetworkOptions, NoDelay};
+use crate::aws::runtime::Timer;
+use crate::telemetry::debug;
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+
+/// The suffix of a volume's metadata companion file.
+pub(super) const METADATA_SUFFIX: &str = "_MDM";
+
+/// A logical volume scan in the archive, grouping the files uploaded for it. See [list_volumes].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ArchiveVolume {
+    scan_time: DateTime<Utc>,
+    identifier: Identifier,
+    size: u64,
+    metadata: Option<Identifier>,
+    duplicates: Vec<Identifier>,
+    possibly_incomplete: bool,
+}
+
+impl ArchiveVolume {
+    /// The time the volume scan started.
+    pub fn scan_time(&self) -> DateTime<Utc> {
+        self.scan_time
+    }
+
+    /// The volume's data file. Where several data files were uploaded for the same scan, this is
+    /// the largest of them.
+    pub fn identifier(&self) -> &Identifier {
+        &self.identifier
+    }
+
+    /// The size of the volume's data file in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// The volume's metadata ("_MDM") companion file, if one was uploaded.
+    pub fn metadata(&self) -> Option<&Identifier> {
+        self.metadata.as_ref()
+    }
+
+    /// Other data files uploaded for the same scan, such as re-uploads.
+    pub fn duplicates(&self) -> &[Identifier] {
+        &self.duplicates
+    }
+
+    /// Whether the volume's data file is less than half the median size of the day's volumes,
+    /// which suggests the scan was cut short or only partially uploaded. This is a heuristic; a
+    /// short volume may also be a legitimately small scan, such as one in clear air.
+    pub fn possibly_incomplete(&self) -> bool {
+        self.possibly_incomplete
+    }
+}
+
+/// Lists the volume scans for the specified site which started within `[start, end)`. Files are
+/// grouped by their scan start time, so each volume appears once with its metadata companion file
+/// and any duplicate uploads attached. Volumes are ordered by scan time.
+pub async fn list_volumes(
+    site: &str,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> crate::result::Result<Vec<ArchiveVolume>> {
+    list_volumes_with_options(site, start, end, &single_attempt(), &NoDelay).await
+}
+
+/// Lists volume scans like [list_volumes], retrying failed requests as configured by the given
+/// options and waiting between retries with the given timer.
+pub async fn list_volumes_with_options(
+    site: &str,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    options: &NetworkOptions,
+    timer: &impl Timer,
+) -> crate::result::Result<Vec<ArchiveVolume>> {
+    let mut volumes = Vec::new();
+    for date in start.date_naive().iter_days() {
+        if date > end.date_naive() {
+            break;
+        }
+
+        let files = list_file_objects(site, &date, options, timer).await?;
+        volumes.extend(
+            group_volumes(files)
+                .into_iter()
+                .filter(|volume| start <= volume.scan_time && volume.scan_time < end),
+        );
+    }
+
+    Ok(volumes)
+}
+
+/// Groups a day's files and their sizes into volumes by scan start time.
+fn group_volumes(files: Vec<(Identifier, u64)>) -> Vec<ArchiveVolume> {
+    #[derive(Default)]
+    struct Group {
+        data_files: Vec<(Identifier, u64)>,
+        metadata: Option<Identifier>,
+    }
+
+    let mut groups: BTreeMap<DateTime<Utc>, Group> = BTreeMap::new();
+    for (identifier, size) in files {
+        let Some(scan_time) = identifier.date_time() else {
+            debug!("Skipping unrecognized archive file {}", identifier.name());
+            continue;
+        };
+
+        let group = groups.entry(scan_time).or_default();
+        if identifier.name().ends_with(METADATA_SUFFIX) {
+            group.metadata = Some(identifier);
+        } else {
+            group.data_files.push((identifier, size));
+        }
+    }
+
+    let mut volumes: Vec<ArchiveVolume> = groups
+        .into_iter()
+        .filter_map(|(scan_time, mut group)| {
+            group
+                .data_files
+                .sort_by(|(a_id, a_size), (b_id, b_size)| b_size.cmp(a_size).then(a_id.cmp(b_id)));
+
+            let mut data_files = group.data_files.into_iter();
+            let (identifier, size) = data_files.next()?;
+
+            Some(ArchiveVolume {
+                scan_time,
+                identifier,
+                size,
+                metadata: group.metadata,
+                duplicates: data_files.map(|(identifier, _)| identifier).collect(),
+                possibly_incomplete: false,
+            })
+        })
+        .collect();
+
+    let mut sizes: Vec<u64> = volumes.iter().map(|volume| volume.size).collect();
+    sizes.sort_unstable();
+    if let Some(&median_size) = sizes.get(sizes.len() / 2) {
+        for volume in &mut volumes {
+            volume.possibly_incomplete = volume.size * 2 < median_size;
+        }
+    }
+
+    volumes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, size: u64) -> (Identifier, u64) {
+        (Identifier::new(name.to_string()), size)
+    }
+
+    #[test]
+    fn test_group_volumes() {
+        let volumes = group_volumes(vec![
+            file("KDMX20240101_000512_V06", 9_000_000),
+            file("KDMX20240101_000512_V06_MDM", 20_000),
+            file("KDMX20240101_001012_V06", 8_000_000),
+            file("KDMX20240101_001012_V06.gz", 7_000_000),
+            file("KDMX20240101_001512_V06", 1_000_000),
+            file("KDMX20240101_002012_V06_MDM", 20_000),
+            file("NOTAVOLUME", 100),
+        ]);
+
+        let names: Vec<&str> = volumes
+            .iter()
+            .map(|volume| volume.identifier().name())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "KDMX20240101_000512_V06",
+                "KDMX20240101_001012_V06",
+                "KDMX20240101_001512_V06",
+            ]
+        );
+
+        assert_eq!(
+            volumes[0].metadata().map(Identifier::name),
+            Some("KDMX20240101_000512_V06_MDM")
+        );
+        assert!(volumes[0].duplicates().is_empty());
+
+        assert!(volumes[1].metadata().is_none());
+        assert_eq!(
+            volumes[1].duplicates(),
+            &[Identifier::new("KDMX20240101_001012_V06.gz".to_string())]
+        );
+
+        let incomplete: Vec<bool> = volumes
+            .iter()
+            .map(ArchiveVolume::possibly_incomplete)
+            .collect();
+        assert_eq!(incomplete, vec![false, false, true]);
+    }
+}
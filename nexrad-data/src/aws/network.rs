@@ -0,0 +1,252 @@
+//!
+//! Configures how requests to AWS are made: how long connecting and reading may take, and how
+//! failed requests are retried. Each listing and downloading function has a `_with_options` variant
+//! accepting [NetworkOptions] and a [Timer] with which to wait between retries, while the plain
+//! functions make a single attempt with the default timeouts.
+//!
+
+use crate::aws::runtime::Timer;
+use crate::result::aws::AWSError;
+use crate::result::{Error, Result};
+use crate::telemetry::debug;
+use std::future::{ready, Future, Ready};
+use std::time::Duration;
+
+/// Timeouts and retry behavior for requests to AWS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NetworkOptions {
+    connect_timeout: Duration,
+    read_timeout: Duration,
+    retries: usize,
+    backoff: Duration,
+}
+
+impl NetworkOptions {
+    /// Creates options which allow ten seconds to connect and thirty seconds between reads,
+    /// retrying a failed request up to four times after waiting 500ms, doubling the wait after
+    /// each retry.
+    pub fn new() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(10),
+            read_timeout: Duration::from_secs(30),
+            retries: 4,
+            backoff: Duration::from_millis(500),
+        }
+    }
+
+    /// The longest a request may take to connect before it fails.
+    pub fn with_connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = connect_timeout;
+        self
+    }
+
+    /// The longest a request may wait for data from the connection before it fails.
+    pub fn with_read_timeout(mut self, read_timeout: Duration) -> Self {
+        self.read_timeout = read_timeout;
+        self
+    }
+
+    /// The number of times a failed request is retried. Zero makes a single attempt.
+    pub fn with_retries(mut self, retries: usize) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// The wait before the first retry, which doubles after each subsequent retry.
+    pub fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// The longest a request may take to connect before it fails.
+    pub fn connect_timeout(&self) -> Duration {
+        self.connect_timeout
+    }
+
+    /// The longest a request may wait for data from the connection before it fails.
+    pub fn read_timeout(&self) -> Duration {
+        self.read_timeout
+    }
+
+    /// The number of times a failed request is retried.
+    pub fn retries(&self) -> usize {
+        self.retries
+    }
+
+    /// The wait before the first retry.
+    pub fn backoff(&self) -> Duration {
+        self.backoff
+    }
+
+    /// The wait before the given retry, counting from zero.
+    pub fn retry_delay(&self, retry: usize) -> Duration {
+        let factor = 2u32.checked_pow(retry as u32).unwrap_or(u32::MAX);
+        self.backoff.saturating_mul(factor)
+    }
+
+    /// Builds an HTTP client which applies these options' timeouts.
+    pub(crate) fn client(&self) -> Result<reqwest::Client> {
+        reqwest::Client::builder()
+            .connect_timeout(self.connect_timeout)
+            .read_timeout(self.read_timeout)
+            .build()
+            .map_err(|err| Error::AWS(AWSError::HttpClientError(err)))
+    }
+
+    /// Attempts an action, retrying it with exponential backoff while it fails with an error for
+    /// which `retryable` is true. Returns the number of attempts made along with the last result.
+    pub(crate) async fn retry<F, R>(
+        &self,
+        timer: &impl Timer,
+        retryable: impl Fn(&Error) -> bool,
+        action: impl Fn() -> F,
+    ) -> (usize, Result<R>)
+    where
+        F: Future<Output = Result<R>>,
+    {
+        let mut retry = 0;
+        loop {
+            match action().await {
+                Err(err) if retry < self.retries && retryable(&err) => {
+                    let delay = self.retry_delay(retry);
+                    debug!("Retrying failed request in {:?}: {}", delay, err);
+
+                    timer.sleep(delay).await;
+                    retry += 1;
+                }
+                result => return (retry + 1, result),
+            }
+        }
+    }
+}
+
+impl Default for NetworkOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether an error may be resolved by retrying the request, such as a timeout, dropped connection,
+/// or error response from S3. A missing object or a malformed listing is not retried.
+pub(crate) fn is_transient(error: &Error) -> bool {
+    matches!(
+        error,
+        Error::AWS(
+            AWSError::S3ListObjectsError(_)
+                | AWSError::S3GetObjectRequestError(_)
+                | AWSError::S3GetObjectError(_)
+                | AWSError::S3StreamingError(_)
+        )
+    )
+}
+
+/// Options for the plain listing and downloading functions, which make a single attempt.
+pub(crate) fn single_attempt() -> NetworkOptions {
+    NetworkOptions::new().with_retries(0)
+}
+
+/// A [Timer] for requests made with [single_attempt], which never wait between retries.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct NoDelay;
+
+impl Timer for NoDelay {
+    type Sleep = Ready<()>;
+
+    fn sleep(&self, _duration: Duration) -> Self::Sleep {
+        ready(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+
+    /// A [Timer] which records the waits requested of it without waiting.
+    #[derive(Default)]
+    struct RecordingTimer {
+        waits: RefCell<Vec<Duration>>,
+    }
+
+    impl Timer for RecordingTimer {
+        type Sleep = Ready<()>;
+
+        fn sleep(&self, duration: Duration) -> Self::Sleep {
+            self.waits.borrow_mut().push(duration);
+            ready(())
+        }
+    }
+
+    fn server_error() -> Error {
+        Error::AWS(AWSError::S3GetObjectError(None))
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let options = NetworkOptions::new().with_backoff(Duration::from_millis(100));
+        assert_eq!(options.retry_delay(0), Duration::from_millis(100));
+        assert_eq!(options.retry_delay(3), Duration::from_millis(800));
+        assert!(options.retry_delay(64) > options.retry_delay(31));
+    }
+
+    #[tokio::test]
+    async fn test_retry_until_success() {
+        let timer = RecordingTimer::default();
+        let failures = Cell::new(2usize);
+
+        let (attempts, result) = NetworkOptions::new()
+            .retry(&timer, is_transient, || {
+                let failed = failures.get() > 0;
+                failures.set(failures.get().saturating_sub(1));
+                async move {
+                    if failed {
+                        Err(server_error())
+                    } else {
+                        Ok(())
+                    }
+                }
+            })
+            .await;
+
+        assert!(result.is_ok());
+        assert_eq!(attempts, 3);
+        assert_eq!(
+            *timer.waits.borrow(),
+            vec![Duration::from_millis(500), Duration::from_millis(1000)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_exhausted() {
+        let timer = RecordingTimer::default();
+        let options = NetworkOptions::new().with_retries(2);
+
+        let (attempts, result) = options
+            .retry(&timer, is_transient, || async {
+                Err::<(), _>(server_error())
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts, 3);
+        assert_eq!(timer.waits.borrow().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_not_retryable() {
+        let timer = RecordingTimer::default();
+
+        let (attempts, result) = NetworkOptions::new()
+            .retry(&timer, is_transient, || async {
+                Err::<(), _>(Error::AWS(AWSError::S3ObjectNotFoundError))
+            })
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(Error::AWS(AWSError::S3ObjectNotFoundError))
+        ));
+        assert_eq!(attempts, 1);
+        assert!(timer.waits.borrow().is_empty());
+    }
+}
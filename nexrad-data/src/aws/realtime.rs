@@ -39,6 +39,9 @@ pub use chunk_type::*;
 mod chunk_identifier;
 pub use chunk_identifier::*;
 
+mod chunk_sequence;
+pub use chunk_sequence::*;
+
 mod download_chunk;
 pub use download_chunk::*;
 
@@ -57,6 +60,26 @@ pub use poll_chunks::*;
 mod poll_stats;
 pub use poll_stats::*;
 
+#[cfg(feature = "decode")]
+mod latency;
+#[cfg(feature = "decode")]
+pub use latency::*;
+
+#[cfg(all(feature = "decode", feature = "nexrad-model", feature = "tokio"))]
+mod subscription;
+#[cfg(all(feature = "decode", feature = "nexrad-model", feature = "tokio"))]
+pub use subscription::*;
+
+#[cfg(all(feature = "decode", feature = "nexrad-model", feature = "tokio"))]
+mod supervisor;
+#[cfg(all(feature = "decode", feature = "nexrad-model", feature = "tokio"))]
+pub use supervisor::*;
+
+#[cfg(all(feature = "decode", feature = "nexrad-model"))]
+mod volume_events;
+#[cfg(all(feature = "decode", feature = "nexrad-model"))]
+pub use volume_events::*;
+
 mod search;
 
 const REALTIME_BUCKET: &str = "unidata-nexrad-level2-chunks";
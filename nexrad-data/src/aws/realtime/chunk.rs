@@ -1,6 +1,7 @@
 use crate::result::aws::AWSError::UnrecognizedChunkFormat;
 use crate::result::Error::AWS;
 use crate::volume;
+use crate::volume::FormatKind;
 
 /// A chunk of real-time data within a volume. Chunks are ordered and when concatenated together
 /// form a complete volume of radar data. All chunks contain an LDM record with radar data messages.
@@ -22,19 +23,11 @@ impl Chunk<'_> {
     ///
     /// The chunk type is determined by the data's format.
     pub fn new(data: Vec<u8>) -> crate::result::Result<Self> {
-        // Check if the data begins with an Archive II volume header, indicating a "start" chunk
-        if data[0..3].as_ref() == b"AR2" {
-            let file = volume::File::new(data);
-            return Ok(Self::Start(file));
+        match volume::detect_format(&data) {
+            FormatKind::ArchiveII => Ok(Self::Start(volume::File::new(data))),
+            FormatKind::ChunkRecord => Ok(Self::IntermediateOrEnd(volume::Record::new(data))),
+            _ => Err(AWS(UnrecognizedChunkFormat)),
         }
-
-        // Check if the data begins with a BZ compressed record, indicating an "intermediate" or "end" chunk
-        if data[4..6].as_ref() == b"BZ" {
-            let record = volume::Record::new(data);
-            return Ok(Self::IntermediateOrEnd(record));
-        }
-
-        Err(AWS(UnrecognizedChunkFormat))
     }
 
     /// The data contained within this chunk.
@@ -184,9 +184,8 @@ mod tests {
             Some(date_time),
         );
 
-        let next_chunk = chunk.next_chunk().expect("Expected next chunk");
-        match next_chunk {
-            NextChunk::Sequence(next_chunk) => {
+        match chunk.next_chunk() {
+            Some(NextChunk::Sequence(next_chunk)) => {
                 assert_eq!(next_chunk.site(), site);
                 assert_eq!(next_chunk.volume().as_number(), 50);
                 assert_eq!(next_chunk.name(), "20240813-123330-002-I");
@@ -213,9 +212,8 @@ mod tests {
             Some(date_time),
         );
 
-        let next_chunk = chunk.next_chunk().expect("Expected next chunk");
-        match next_chunk {
-            NextChunk::Sequence(next_chunk) => {
+        match chunk.next_chunk() {
+            Some(NextChunk::Sequence(next_chunk)) => {
                 assert_eq!(next_chunk.site(), site);
                 assert_eq!(next_chunk.volume().as_number(), 999);
                 assert_eq!(next_chunk.name(), "20240813-123330-015-I");
@@ -242,9 +240,8 @@ mod tests {
             Some(date_time),
         );
 
-        let next_chunk = chunk.next_chunk().expect("Expected next chunk");
-        match next_chunk {
-            NextChunk::Volume(next_volume) => {
+        match chunk.next_chunk() {
+            Some(NextChunk::Volume(next_volume)) => {
                 assert_eq!(next_volume.as_number(), 51);
             }
             _ => panic!("Expected volume"),
@@ -265,9 +262,8 @@ mod tests {
             Some(date_time),
         );
 
-        let next_chunk = chunk.next_chunk().expect("Expected next chunk");
-        match next_chunk {
-            NextChunk::Volume(next_volume) => {
+        match chunk.next_chunk() {
+            Some(NextChunk::Volume(next_volume)) => {
                 assert_eq!(next_volume.as_number(), 1);
             }
             _ => panic!("Expected volume"),
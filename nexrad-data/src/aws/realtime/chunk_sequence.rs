@@ -0,0 +1,188 @@
+use crate::aws::realtime::{ChunkIdentifier, VolumeIndex};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The default number of most recent volumes whose chunk sequences are tracked.
+const DEFAULT_VOLUME_HISTORY: usize = 10;
+
+/// How a chunk relates to the chunks previously observed for its volume. See
+/// [ChunkSequenceTracker::observe].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChunkObservation {
+    /// The chunk follows every chunk previously observed for its volume, possibly skipping some.
+    New,
+    /// The chunk was missing and has arrived after a later chunk in its volume.
+    Late,
+    /// The chunk was already observed and should be ignored.
+    Duplicate,
+    /// The chunk's name has no sequence number, so it cannot be tracked.
+    Unsequenced,
+}
+
+/// Chunks missing from a volume, preceding the latest chunk observed for it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChunkGap {
+    /// The volume's rotating index in the real-time bucket.
+    pub volume: VolumeIndex,
+    /// The chunk name prefix identifying the volume, its start date and time.
+    pub name_prefix: String,
+    /// The sequence numbers of the missing chunks in increasing order.
+    pub missing_sequences: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct VolumeSequence {
+    volume: VolumeIndex,
+    sequences: BTreeSet<usize>,
+}
+
+/// Tracks the sequence numbers of the real-time chunks received for a site, tolerating the
+/// duplicate and out-of-order chunk keys that the bucket's eventual consistency can produce.
+/// Re-delivered chunks are identified so they can be ignored, and missing chunks are reported as
+/// gaps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkSequenceTracker {
+    volumes: BTreeMap<String, VolumeSequence>,
+    volume_history: usize,
+}
+
+impl ChunkSequenceTracker {
+    /// Creates a new tracker which remembers the chunks of the ten most recent volumes.
+    pub fn new() -> Self {
+        Self {
+            volumes: BTreeMap::new(),
+            volume_history: DEFAULT_VOLUME_HISTORY,
+        }
+    }
+
+    /// Sets how many of the most recent volumes' chunks are remembered. Chunks re-delivered for
+    /// older volumes are treated as new.
+    pub fn with_volume_history(mut self, volume_history: usize) -> Self {
+        self.volume_history = volume_history.max(1);
+        self
+    }
+
+    /// Records that the given chunk was received and classifies it relative to the chunks
+    /// previously received for its volume.
+    pub fn observe(&mut self, chunk: &ChunkIdentifier) -> ChunkObservation {
+        let Some(sequence) = chunk.sequence() else {
+            return ChunkObservation::Unsequenced;
+        };
+
+        let volume = self
+            .volumes
+            .entry(chunk.name_prefix().to_string())
+            .or_insert_with(|| VolumeSequence {
+                volume: *chunk.volume(),
+                sequences: BTreeSet::new(),
+            });
+
+        let latest_sequence = volume.sequences.last().copied();
+        let observation = if !volume.sequences.insert(sequence) {
+            ChunkObservation::Duplicate
+        } else if latest_sequence.is_some_and(|latest_sequence| sequence < latest_sequence) {
+            ChunkObservation::Late
+        } else {
+            ChunkObservation::New
+        };
+
+        while self.volumes.len() > self.volume_history {
+            self.volumes.pop_first();
+        }
+
+        observation
+    }
+
+    /// The chunks missing from each tracked volume, in chronological order of the volumes. Chunks
+    /// after the latest observed chunk of a volume are not considered missing.
+    pub fn gaps(&self) -> Vec<ChunkGap> {
+        self.volumes
+            .iter()
+            .filter_map(|(name_prefix, volume)| {
+                let latest_sequence = *volume.sequences.last()?;
+                let missing_sequences: Vec<usize> = (1..latest_sequence)
+                    .filter(|sequence| !volume.sequences.contains(sequence))
+                    .collect();
+
+                (!missing_sequences.is_empty()).then(|| ChunkGap {
+                    volume: volume.volume,
+                    name_prefix: name_prefix.clone(),
+                    missing_sequences,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Default for ChunkSequenceTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(volume: usize, name: &str) -> ChunkIdentifier {
+        ChunkIdentifier::new(
+            "KDMX".to_string(),
+            VolumeIndex::new(volume),
+            name.to_string(),
+            None,
+        )
+    }
+
+    #[test]
+    fn test_observe_duplicates_and_late_chunks() {
+        let mut tracker = ChunkSequenceTracker::new();
+
+        assert_eq!(
+            tracker.observe(&chunk(1, "20240813-123330-001-S")),
+            ChunkObservation::New
+        );
+        assert_eq!(
+            tracker.observe(&chunk(1, "20240813-123330-004-I")),
+            ChunkObservation::New
+        );
+        assert_eq!(
+            tracker.observe(&chunk(1, "20240813-123330-004-I")),
+            ChunkObservation::Duplicate
+        );
+        assert_eq!(
+            tracker.observe(&chunk(1, "20240813-123330-002-I")),
+            ChunkObservation::Late
+        );
+        assert_eq!(
+            tracker.observe(&chunk(1, "20240813-123330-002-I")),
+            ChunkObservation::Duplicate
+        );
+
+        assert_eq!(
+            tracker.gaps(),
+            vec![ChunkGap {
+                volume: VolumeIndex::new(1),
+                name_prefix: "20240813-123330".to_string(),
+                missing_sequences: vec![3],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_volume_history() {
+        let mut tracker = ChunkSequenceTracker::new().with_volume_history(2);
+
+        tracker.observe(&chunk(1, "20240813-123330-003-I"));
+        tracker.observe(&chunk(2, "20240813-123830-002-I"));
+        tracker.observe(&chunk(3, "20240813-124330-001-S"));
+
+        let gaps = tracker.gaps();
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0].volume, VolumeIndex::new(2));
+        assert_eq!(gaps[0].missing_sequences, vec![1]);
+
+        assert_eq!(
+            tracker.observe(&chunk(1, "20240813-123330-003-I")),
+            ChunkObservation::New
+        );
+    }
+}
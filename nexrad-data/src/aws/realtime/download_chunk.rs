@@ -1,10 +1,24 @@
+use crate::aws::network::{is_transient, single_attempt, NetworkOptions, NoDelay};
 use crate::aws::realtime::{Chunk, ChunkIdentifier, REALTIME_BUCKET};
+use crate::aws::runtime::Timer;
 use crate::aws::s3::download_object;
 
 /// Downloads the specified chunk from the real-time NEXRAD data bucket.
 pub async fn download_chunk<'a>(
     site: &str,
     chunk_id: &ChunkIdentifier,
+) -> crate::result::Result<(ChunkIdentifier, Chunk<'a>)> {
+    download_chunk_with_options(site, chunk_id, &single_attempt(), &NoDelay).await
+}
+
+/// Downloads the specified chunk like [download_chunk], retrying failed requests as configured by
+/// the given options and waiting between retries with the given timer. A chunk which is not found
+/// is not retried.
+pub async fn download_chunk_with_options<'a>(
+    site: &str,
+    chunk_id: &ChunkIdentifier,
+    options: &NetworkOptions,
+    timer: &impl Timer,
 ) -> crate::result::Result<(ChunkIdentifier, Chunk<'a>)> {
     let key = format!(
         "{}/{}/{}",
@@ -13,7 +27,13 @@ pub async fn download_chunk<'a>(
         chunk_id.name()
     );
 
-    let downloaded_object = download_object(REALTIME_BUCKET, &key).await?;
+    let (_, downloaded_object) = options
+        .retry(timer, is_transient, || {
+            download_object(REALTIME_BUCKET, &key, options)
+        })
+        .await;
+
+    let downloaded_object = downloaded_object?;
 
     Ok((
         ChunkIdentifier::new(
@@ -1,6 +1,8 @@
-use crate::aws::realtime::list_chunks_in_volume::list_chunks_in_volume;
+use crate::aws::network::{single_attempt, NetworkOptions, NoDelay};
+use crate::aws::realtime::list_chunks_in_volume::list_chunks_in_volume_with_options;
 use crate::aws::realtime::search::search;
 use crate::aws::realtime::VolumeIndex;
+use crate::aws::runtime::Timer;
 use chrono::{DateTime, Utc};
 use std::sync::atomic::AtomicI32;
 use std::sync::atomic::Ordering::Relaxed;
@@ -10,11 +12,29 @@ use std::sync::Arc;
 /// NEXRAD data is uploaded to a series of rotating volumes 0..=999, each containing ~55 chunks.
 /// This function performs a binary search to find the most recent volume with data.
 pub async fn get_latest_volume(site: &str) -> crate::result::Result<LatestVolumeResult> {
+    get_latest_volume_with_options(site, &single_attempt(), &NoDelay).await
+}
+
+/// Identifies the volume index with the most recent data like [get_latest_volume], retrying failed
+/// requests as configured by the given options and waiting between retries with the given timer.
+/// The calls reported in the result count each volume searched once, regardless of retries.
+pub async fn get_latest_volume_with_options(
+    site: &str,
+    options: &NetworkOptions,
+    timer: &impl Timer,
+) -> crate::result::Result<LatestVolumeResult> {
     let calls = Arc::new(AtomicI32::new(0));
     let latest_volume = search(998, DateTime::<Utc>::MAX_UTC, |volume| {
         calls.fetch_add(1, Relaxed);
         async move {
-            let chunks = list_chunks_in_volume(site, VolumeIndex::new(volume + 1), 1).await?;
+            let chunks = list_chunks_in_volume_with_options(
+                site,
+                VolumeIndex::new(volume + 1),
+                1,
+                options,
+                timer,
+            )
+            .await?;
             Ok(chunks.first().and_then(|chunk| chunk.date_time()))
         }
     })
@@ -0,0 +1,261 @@
+use crate::aws::realtime::{Chunk, ChunkIdentifier, VolumeIndex};
+use crate::result::Result;
+use chrono::{DateTime, TimeDelta, Utc};
+use nexrad_decode::limits::DecodeLimits;
+
+/// The end-to-end latency of a real-time chunk: when its radials were collected, when it was
+/// uploaded to the real-time bucket, and when it finished downloading. See [ChunkLatency::measure].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChunkLatency {
+    /// The chunk that was measured.
+    pub chunk: ChunkIdentifier,
+    /// The collection time of the chunk's earliest radial, if it contained any.
+    pub first_collection_time: Option<DateTime<Utc>>,
+    /// The collection time of the chunk's latest radial, if it contained any.
+    pub last_collection_time: Option<DateTime<Utc>>,
+    /// When the chunk was uploaded to the real-time bucket, if known.
+    pub upload_time: Option<DateTime<Utc>>,
+    /// When the chunk finished downloading.
+    pub download_time: DateTime<Utc>,
+}
+
+impl ChunkLatency {
+    /// Measures a downloaded chunk's latency by decoding its radials' collection times.
+    pub fn measure(
+        chunk_id: &ChunkIdentifier,
+        chunk: &Chunk,
+        download_time: DateTime<Utc>,
+    ) -> Result<Self> {
+        Self::measure_with_limits(chunk_id, chunk, download_time, &DecodeLimits::new())
+    }
+
+    /// Measures a downloaded chunk's latency by decoding its radials' collection times within the
+    /// given limits.
+    pub fn measure_with_limits(
+        chunk_id: &ChunkIdentifier,
+        chunk: &Chunk,
+        download_time: DateTime<Utc>,
+        limits: &DecodeLimits,
+    ) -> Result<Self> {
+        let records = match chunk {
+            Chunk::Start(file) => file.records(),
+            Chunk::IntermediateOrEnd(record) => vec![record.clone()],
+        };
+
+        let mut first_collection_time = None;
+        let mut last_collection_time = None;
+        for record in records {
+            let record = if record.compressed() {
+                record.decompress_with_limits(limits)?
+            } else {
+                record
+            };
+
+            let summary = nexrad_decode::summarize::messages(&record.messages_with_limits(limits)?);
+            first_collection_time = first_collection_time
+                .into_iter()
+                .chain(summary.earliest_collection_time)
+                .min();
+            last_collection_time = last_collection_time
+                .into_iter()
+                .chain(summary.latest_collection_time)
+                .max();
+        }
+
+        Ok(Self {
+            chunk: chunk_id.clone(),
+            first_collection_time,
+            last_collection_time,
+            upload_time: chunk_id.date_time(),
+            download_time,
+        })
+    }
+
+    /// The delay between the chunk's latest radial being collected and the chunk being uploaded.
+    pub fn availability_latency(&self) -> Option<TimeDelta> {
+        Some(self.upload_time? - self.last_collection_time?)
+    }
+
+    /// The delay between the chunk's latest radial being collected and the chunk finishing
+    /// downloading.
+    pub fn download_latency(&self) -> Option<TimeDelta> {
+        Some(self.download_time - self.last_collection_time?)
+    }
+}
+
+/// The latency of the chunks received for a single volume. See [LatencyReport::volumes].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VolumeLatency {
+    /// The volume's rotating index in the real-time bucket.
+    pub volume: VolumeIndex,
+    /// The number of chunks measured for the volume.
+    pub chunk_count: usize,
+    /// The collection time of the volume's earliest radial.
+    pub first_collection_time: Option<DateTime<Utc>>,
+    /// The collection time of the volume's latest radial.
+    pub last_collection_time: Option<DateTime<Utc>>,
+    /// When the volume's last chunk finished downloading.
+    pub last_download_time: DateTime<Utc>,
+    /// The mean of the volume's chunks' download latencies.
+    pub mean_download_latency: Option<TimeDelta>,
+    /// The largest of the volume's chunks' download latencies.
+    pub max_download_latency: Option<TimeDelta>,
+}
+
+impl VolumeLatency {
+    /// The delay between the volume's earliest radial being collected and its last chunk finishing
+    /// downloading.
+    pub fn total_latency(&self) -> Option<TimeDelta> {
+        Some(self.last_download_time - self.first_collection_time?)
+    }
+}
+
+/// A report of the latency of real-time chunks as they are received, summarized per chunk and per
+/// volume so that services can monitor end-to-end data latency.
+///
+/// ```no_run
+/// # fn example(chunk_id: nexrad_data::aws::realtime::ChunkIdentifier,
+/// #            chunk: nexrad_data::aws::realtime::Chunk) -> nexrad_data::result::Result<()> {
+/// use nexrad_data::aws::realtime::{ChunkLatency, LatencyReport};
+///
+/// let mut report = LatencyReport::new();
+/// report.push(ChunkLatency::measure(&chunk_id, &chunk, chrono::Utc::now())?);
+///
+/// for volume in report.volumes() {
+///     println!("{:?}: {:?}", volume.volume, volume.max_download_latency);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LatencyReport {
+    chunks: Vec<ChunkLatency>,
+}
+
+impl LatencyReport {
+    /// Creates a new, empty latency report.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a chunk's latency to the report.
+    pub fn push(&mut self, latency: ChunkLatency) {
+        self.chunks.push(latency);
+    }
+
+    /// The latency of each chunk in the order they were added.
+    pub fn chunks(&self) -> &[ChunkLatency] {
+        &self.chunks
+    }
+
+    /// The latency of each volume with chunks in the report, in the order each volume's first chunk
+    /// was added.
+    pub fn volumes(&self) -> Vec<VolumeLatency> {
+        let mut volume_keys: Vec<(VolumeIndex, &str)> = Vec::new();
+        for latency in &self.chunks {
+            let key = (*latency.chunk.volume(), latency.chunk.name_prefix());
+            if !volume_keys.contains(&key) {
+                volume_keys.push(key);
+            }
+        }
+
+        volume_keys
+            .into_iter()
+            .filter_map(|(volume, name_prefix)| {
+                let chunks: Vec<&ChunkLatency> = self
+                    .chunks
+                    .iter()
+                    .filter(|latency| {
+                        *latency.chunk.volume() == volume
+                            && latency.chunk.name_prefix() == name_prefix
+                    })
+                    .collect();
+
+                let download_latencies: Vec<TimeDelta> = chunks
+                    .iter()
+                    .filter_map(|latency| latency.download_latency())
+                    .collect();
+
+                let mean_download_latency = (!download_latencies.is_empty()).then(|| {
+                    download_latencies.iter().copied().sum::<TimeDelta>()
+                        / download_latencies.len() as i32
+                });
+
+                Some(VolumeLatency {
+                    volume,
+                    chunk_count: chunks.len(),
+                    first_collection_time: chunks
+                        .iter()
+                        .filter_map(|latency| latency.first_collection_time)
+                        .min(),
+                    last_collection_time: chunks
+                        .iter()
+                        .filter_map(|latency| latency.last_collection_time)
+                        .max(),
+                    last_download_time: chunks.iter().map(|latency| latency.download_time).max()?,
+                    mean_download_latency,
+                    max_download_latency: download_latencies.into_iter().max(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(seconds: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000 + seconds, 0).unwrap_or_default()
+    }
+
+    fn latency(volume: usize, sequence: usize, collected: i64, downloaded: i64) -> ChunkLatency {
+        let chunk = ChunkIdentifier::new(
+            "KDMX".to_string(),
+            VolumeIndex::new(volume),
+            format!("20231114-221320-{:03}-I", sequence),
+            Some(time(downloaded - 2)),
+        );
+
+        ChunkLatency {
+            chunk,
+            first_collection_time: Some(time(collected - 10)),
+            last_collection_time: Some(time(collected)),
+            upload_time: Some(time(downloaded - 2)),
+            download_time: time(downloaded),
+        }
+    }
+
+    #[test]
+    fn test_chunk_latency() {
+        let latency = latency(1, 2, 0, 15);
+        assert_eq!(latency.availability_latency(), Some(TimeDelta::seconds(13)));
+        assert_eq!(latency.download_latency(), Some(TimeDelta::seconds(15)));
+    }
+
+    #[test]
+    fn test_volume_latency() {
+        let mut report = LatencyReport::new();
+        report.push(latency(1, 2, 0, 10));
+        report.push(latency(2, 2, 300, 320));
+        report.push(latency(1, 3, 20, 40));
+
+        let volumes = report.volumes();
+        assert_eq!(volumes.len(), 2);
+
+        let first_volume = &volumes[0];
+        assert_eq!(first_volume.volume, VolumeIndex::new(1));
+        assert_eq!(first_volume.chunk_count, 2);
+        assert_eq!(first_volume.first_collection_time, Some(time(-10)));
+        assert_eq!(first_volume.last_download_time, time(40));
+        assert_eq!(
+            first_volume.mean_download_latency,
+            Some(TimeDelta::seconds(15))
+        );
+        assert_eq!(
+            first_volume.max_download_latency,
+            Some(TimeDelta::seconds(20))
+        );
+        assert_eq!(first_volume.total_latency(), Some(TimeDelta::seconds(50)));
+    }
+}
@@ -1,4 +1,6 @@
+use crate::aws::network::{is_transient, single_attempt, NetworkOptions, NoDelay};
 use crate::aws::realtime::{ChunkIdentifier, VolumeIndex, REALTIME_BUCKET};
+use crate::aws::runtime::Timer;
 use crate::aws::s3::list_objects;
 
 /// Lists the chunks for the specified radar site and volume. The `max_keys` parameter can be used
@@ -7,15 +9,34 @@ pub async fn list_chunks_in_volume(
     site: &str,
     volume: VolumeIndex,
     max_keys: usize,
+) -> crate::result::Result<Vec<ChunkIdentifier>> {
+    list_chunks_in_volume_with_options(site, volume, max_keys, &single_attempt(), &NoDelay).await
+}
+
+/// Lists the chunks for the specified radar site and volume like [list_chunks_in_volume], retrying
+/// failed requests as configured by the given options and waiting between retries with the given
+/// timer.
+pub async fn list_chunks_in_volume_with_options(
+    site: &str,
+    volume: VolumeIndex,
+    max_keys: usize,
+    options: &NetworkOptions,
+    timer: &impl Timer,
 ) -> crate::result::Result<Vec<ChunkIdentifier>> {
     let prefix = format!("{}/{}/", site, volume.as_number());
-    let list_result = list_objects(REALTIME_BUCKET, &prefix, Some(max_keys)).await?;
+    let (_, list_result) = options
+        .retry(timer, is_transient, || {
+            list_objects(REALTIME_BUCKET, &prefix, Some(max_keys), options)
+        })
+        .await;
+
+    let list_result = list_result?;
 
     let metas = list_result
         .objects
         .iter()
         .map(|object| {
-            let identifier_segment = object.key.split('/').last();
+            let identifier_segment = object.key.split('/').next_back();
             let identifier = identifier_segment
                 .unwrap_or_else(|| object.key.as_ref())
                 .to_string();
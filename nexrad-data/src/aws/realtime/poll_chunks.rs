@@ -1,26 +1,58 @@
+use crate::aws::network::NetworkOptions;
 use crate::aws::realtime::poll_stats::PollStats;
 use crate::aws::realtime::{
-    download_chunk, estimate_next_chunk_time, get_latest_volume, list_chunks_in_volume, Chunk,
-    ChunkIdentifier, NewChunkStats, NextChunk, VolumeIndex,
+    download_chunk_with_options, estimate_next_chunk_time, get_latest_volume_with_options,
+    list_chunks_in_volume_with_options, Chunk, ChunkGap, ChunkIdentifier, ChunkObservation,
+    ChunkSequenceTracker, NewChunkStats, NextChunk, VolumeIndex,
 };
+use crate::aws::runtime::Timer;
 use crate::result::{aws::AWSError, Result};
+use crate::telemetry::debug;
 use chrono::Utc;
-use std::future::Future;
 use std::sync::mpsc::{Receiver, Sender};
-use std::time::Duration;
-use tokio::time::{sleep, sleep_until, Instant};
+
+#[cfg(feature = "tokio")]
+use crate::aws::runtime::TokioTimer;
 
 /// Polls for the latest real-time chunks from the AWS S3 bucket. When new chunks are identified,
 /// they will be downloaded and sent to the provided `Sender`. If a statistics `Sender` is provided,
 /// statistics from the polling process such as how many requests are being sent will be sent to it.
-/// The polling process will stop when a message is received on the provided `Receiver`.
+/// The polling process will stop when a message is received on the provided `Receiver`. Chunks
+/// which are listed again after they were already sent, for example due to the bucket's eventual
+/// consistency, are not sent twice, and chunks which are skipped are reported as
+/// [PollStats::ChunkGap].
+#[cfg(feature = "tokio")]
 pub async fn poll_chunks<'a>(
     site: &str,
     tx: Sender<(ChunkIdentifier, Chunk<'a>)>,
     stats_tx: Option<Sender<PollStats>>,
     stop_rx: Receiver<bool>,
 ) -> Result<()> {
-    let latest_volume_result = get_latest_volume(site).await?;
+    poll_chunks_with_options(
+        site,
+        tx,
+        stats_tx,
+        stop_rx,
+        &NetworkOptions::new(),
+        &TokioTimer,
+    )
+    .await
+}
+
+/// Polls for the latest real-time chunks like [poll_chunks] with the given network options,
+/// waiting between requests with the given timer so that polling may run on any async runtime.
+/// Since a chunk may be requested before it has been uploaded, requests for the next chunk are
+/// retried after any failure, while those which locate the latest volume are only retried after
+/// transient failures.
+pub async fn poll_chunks_with_options<'a>(
+    site: &str,
+    tx: Sender<(ChunkIdentifier, Chunk<'a>)>,
+    stats_tx: Option<Sender<PollStats>>,
+    stop_rx: Receiver<bool>,
+    options: &NetworkOptions,
+    timer: &impl Timer,
+) -> Result<()> {
+    let latest_volume_result = get_latest_volume_with_options(site, options, timer).await?;
     if let Some(stats_tx) = &stats_tx {
         stats_tx
             .send(PollStats::LatestVolumeCalls(latest_volume_result.calls))
@@ -31,11 +63,19 @@ pub async fn poll_chunks<'a>(
         .volume
         .ok_or(AWSError::LatestVolumeNotFound)?;
 
-    let latest_chunk_id = get_latest_chunk(site, latest_volume)
+    let latest_chunk_id = get_latest_chunk(site, latest_volume, options, timer)
         .await?
         .ok_or(AWSError::ExpectedChunkNotFound)?;
 
-    let (latest_chunk_id, latest_chunk) = download_chunk(site, &latest_chunk_id).await?;
+    let (latest_chunk_id, latest_chunk) =
+        download_chunk_with_options(site, &latest_chunk_id, options, timer).await?;
+
+    // Requests for the next chunk are retried here rather than by each request
+    let single_attempt = options.with_retries(0);
+
+    let mut sequence_tracker = ChunkSequenceTracker::new();
+    sequence_tracker.observe(&latest_chunk_id);
+
     tx.send((latest_chunk_id.clone(), latest_chunk))
         .map_err(|_| AWSError::PollingAsyncError)?;
 
@@ -52,7 +92,7 @@ pub async fn poll_chunks<'a>(
                 .to_std()
                 .ok();
             if let Some(time_until) = time_until {
-                sleep_until(Instant::now() + time_until).await;
+                timer.sleep(time_until).await;
             }
         }
 
@@ -62,8 +102,13 @@ pub async fn poll_chunks<'a>(
         {
             NextChunk::Sequence(next_chunk_id) => next_chunk_id,
             NextChunk::Volume(next_volume) => {
-                let (attempts, chunk_id) =
-                    try_resiliently(|| get_latest_chunk(site, next_volume), 500, 5).await;
+                let (attempts, chunk_id) = options
+                    .retry(
+                        timer,
+                        |_| true,
+                        || get_latest_chunk(site, next_volume, &single_attempt, timer),
+                    )
+                    .await;
 
                 if let Some(stats_tx) = &stats_tx {
                     stats_tx
@@ -71,60 +116,179 @@ pub async fn poll_chunks<'a>(
                         .map_err(|_| AWSError::PollingAsyncError)?;
                 }
 
-                chunk_id.flatten().ok_or(AWSError::ExpectedChunkNotFound)?
+                chunk_id
+                    .ok()
+                    .flatten()
+                    .ok_or(AWSError::ExpectedChunkNotFound)?
             }
         };
 
-        let (attempts, next_chunk) =
-            try_resiliently(|| download_chunk(site, &next_chunk_id), 500, 5).await;
+        let (attempts, next_chunk) = options
+            .retry(
+                timer,
+                |_| true,
+                || download_chunk_with_options(site, &next_chunk_id, &single_attempt, timer),
+            )
+            .await;
 
-        let (next_chunk_id, next_chunk) = next_chunk.ok_or(AWSError::ExpectedChunkNotFound)?;
+        let (next_chunk_id, next_chunk) = next_chunk.ok().ok_or(AWSError::ExpectedChunkNotFound)?;
 
-        if let Some(stats_tx) = &stats_tx {
-            let latency = next_chunk_id
-                .date_time()
-                .and_then(|date_time| Utc::now().signed_duration_since(date_time).to_std().ok());
+        let observation = receive_chunk(
+            &mut sequence_tracker,
+            next_chunk_id.clone(),
+            next_chunk,
+            attempts,
+            &tx,
+            stats_tx.as_ref(),
+        )?;
+
+        if observation == ChunkObservation::Duplicate {
+            // A stale listing may return the same chunk again, so wait before requesting it anew
+            timer.sleep(options.backoff()).await;
+            continue;
+        }
+
+        previous_chunk_id = next_chunk_id;
+    }
+
+    Ok(())
+}
+
+/// Records a downloaded chunk's sequence and sends it along with its statistics, including any
+/// chunks which its arrival shows are missing. Re-delivered chunks are ignored.
+fn receive_chunk<'a>(
+    sequence_tracker: &mut ChunkSequenceTracker,
+    chunk_id: ChunkIdentifier,
+    chunk: Chunk<'a>,
+    calls: usize,
+    tx: &Sender<(ChunkIdentifier, Chunk<'a>)>,
+    stats_tx: Option<&Sender<PollStats>>,
+) -> Result<ChunkObservation> {
+    let previously_missing = missing_sequences(sequence_tracker, &chunk_id);
+
+    let observation = sequence_tracker.observe(&chunk_id);
+    if observation == ChunkObservation::Duplicate {
+        debug!("Ignoring re-delivered chunk {}", chunk_id.name());
+        return Ok(observation);
+    }
+
+    if let Some(stats_tx) = stats_tx {
+        let latency = chunk_id
+            .date_time()
+            .and_then(|date_time| Utc::now().signed_duration_since(date_time).to_std().ok());
+
+        stats_tx
+            .send(PollStats::NewChunk(NewChunkStats { calls, latency }))
+            .map_err(|_| AWSError::PollingAsyncError)?;
+
+        let newly_missing: Vec<usize> = missing_sequences(sequence_tracker, &chunk_id)
+            .into_iter()
+            .filter(|sequence| !previously_missing.contains(sequence))
+            .collect();
+
+        if !newly_missing.is_empty() {
+            debug!(
+                "Chunks {:?} missing before {}",
+                newly_missing,
+                chunk_id.name()
+            );
 
             stats_tx
-                .send(PollStats::NewChunk(NewChunkStats {
-                    calls: attempts,
-                    latency,
+                .send(PollStats::ChunkGap(ChunkGap {
+                    volume: *chunk_id.volume(),
+                    name_prefix: chunk_id.name_prefix().to_string(),
+                    missing_sequences: newly_missing,
                 }))
                 .map_err(|_| AWSError::PollingAsyncError)?;
         }
+    }
 
-        tx.send((next_chunk_id.clone(), next_chunk))
-            .map_err(|_| AWSError::PollingAsyncError)?;
+    tx.send((chunk_id, chunk))
+        .map_err(|_| AWSError::PollingAsyncError)?;
 
-        previous_chunk_id = next_chunk_id;
-    }
+    Ok(observation)
+}
 
-    Ok(())
+/// The sequences of the chunks missing from the given chunk's volume.
+fn missing_sequences(
+    sequence_tracker: &ChunkSequenceTracker,
+    chunk_id: &ChunkIdentifier,
+) -> Vec<usize> {
+    sequence_tracker
+        .gaps()
+        .into_iter()
+        .find(|gap| gap.name_prefix == chunk_id.name_prefix())
+        .map(|gap| gap.missing_sequences)
+        .unwrap_or_default()
 }
 
 /// Queries for the latest chunk in the specified volume.
-async fn get_latest_chunk(site: &str, volume: VolumeIndex) -> Result<Option<ChunkIdentifier>> {
-    let chunks = list_chunks_in_volume(site, volume, 100).await?;
+async fn get_latest_chunk(
+    site: &str,
+    volume: VolumeIndex,
+    options: &NetworkOptions,
+    timer: &impl Timer,
+) -> Result<Option<ChunkIdentifier>> {
+    let chunks = list_chunks_in_volume_with_options(site, volume, 100, options, timer).await?;
     Ok(chunks.last().cloned())
 }
 
-/// Attempts an action with retries on an exponential backoff.
-async fn try_resiliently<F, R>(
-    action: impl Fn() -> F,
-    wait_millis: u64,
-    attempts: usize,
-) -> (usize, Option<R>)
-where
-    F: Future<Output = Result<R>>,
-{
-    for attempt in 0..attempts {
-        if let Ok(result) = action().await {
-            return (attempt + 1, Some(result));
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::volume::Record;
+    use std::sync::mpsc::channel;
 
-        let wait = wait_millis * 2u64.pow(attempt as u32);
-        sleep(Duration::from_millis(wait)).await;
+    fn chunk_id(sequence: usize) -> ChunkIdentifier {
+        let name = format!("20240813-123330-{sequence:03}-I");
+        ChunkIdentifier::new("KDMX".to_string(), VolumeIndex::new(1), name, None)
     }
 
-    (attempts, None)
+    #[test]
+    fn test_receive_chunk_reports_gaps() -> Result<()> {
+        let (tx, rx) = channel();
+        let (stats_tx, stats_rx) = channel();
+
+        let mut sequence_tracker = ChunkSequenceTracker::new();
+        for sequence in [1, 2, 5, 5, 3] {
+            let chunk = Chunk::IntermediateOrEnd(Record::new(Vec::new()));
+            receive_chunk(
+                &mut sequence_tracker,
+                chunk_id(sequence),
+                chunk,
+                1,
+                &tx,
+                Some(&stats_tx),
+            )?;
+        }
+
+        let sent: Vec<String> = rx.try_iter().map(|(id, _)| id.name().to_string()).collect();
+        assert_eq!(
+            sent,
+            vec![
+                "20240813-123330-001-I",
+                "20240813-123330-002-I",
+                "20240813-123330-005-I",
+                "20240813-123330-003-I",
+            ]
+        );
+
+        let gaps: Vec<ChunkGap> = stats_rx
+            .try_iter()
+            .filter_map(|stats| match stats {
+                PollStats::ChunkGap(gap) => Some(gap),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            gaps,
+            vec![ChunkGap {
+                volume: VolumeIndex::new(1),
+                name_prefix: "20240813-123330".to_string(),
+                missing_sequences: vec![3, 4],
+            }]
+        );
+
+        Ok(())
+    }
 }
@@ -1,7 +1,8 @@
+use crate::aws::realtime::ChunkGap;
 use std::time::Duration;
 
 /// Statistics from the polling process.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum PollStats {
     /// The number of network calls made to find the most recent volume.
     LatestVolumeCalls(usize),
@@ -9,6 +10,9 @@ pub enum PollStats {
     NewVolumeCalls(usize),
     /// Statistics for a new chunk.
     NewChunk(NewChunkStats),
+    /// Chunks found to be missing when a later chunk in their volume arrived. Only the chunks
+    /// newly missing as of that arrival are included.
+    ChunkGap(ChunkGap),
 }
 
 /// Statistics for a new chunk.
@@ -0,0 +1,340 @@
+use crate::aws::network::NetworkOptions;
+use crate::aws::realtime::{poll_chunks_with_options, Chunk, ChunkIdentifier, PollStats};
+use crate::aws::runtime::TokioTimer;
+use crate::telemetry::{debug, warn};
+use crate::volume::{StreamOptions, SweepBuffer};
+use chrono::{DateTime, Utc};
+use nexrad_decode::messages::rda_status_data::{OperabilityStatus, RDAStatus};
+use nexrad_model::data::Sweep;
+use std::collections::HashMap;
+use std::sync::{mpsc as std_mpsc, Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+use tokio::task::{self, JoinHandle};
+
+/// A sweep decoded from a site's real-time feed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SiteSweep {
+    /// The radar site the sweep was collected by, e.g. "KDMX".
+    pub site: String,
+    /// The decoded sweep.
+    pub sweep: Sweep,
+}
+
+/// The state of a site's subscription.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SiteStatus {
+    /// The site's feed is being polled.
+    Running,
+    /// The subscription was shut down.
+    Stopped,
+    /// Polling the site's feed failed with the given error and was not retried.
+    Failed(String),
+}
+
+/// Health of a single site's subscription.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SiteHealth {
+    /// The radar site, e.g. "KDMX".
+    pub site: String,
+    /// The subscription's current state.
+    pub status: SiteStatus,
+    /// The number of chunks downloaded.
+    pub chunks_received: usize,
+    /// The number of chunks which failed to decode.
+    pub decode_errors: usize,
+    /// The number of sweeps sent to the subscriber.
+    pub sweeps_dispatched: usize,
+    /// The upload time of the most recently downloaded chunk.
+    pub last_chunk_time: Option<DateTime<Utc>>,
+    /// The delay between the most recent chunk's upload and its download.
+    pub last_chunk_latency: Option<Duration>,
+    /// The RDA status from the site's most recent RDA status message.
+    pub rda_status: Option<RDAStatus>,
+    /// The RDA operability status from the site's most recent RDA status message.
+    pub operability_status: Option<OperabilityStatus>,
+}
+
+impl SiteHealth {
+    pub(crate) fn new(site: String) -> Self {
+        Self {
+            site,
+            status: SiteStatus::Running,
+            chunks_received: 0,
+            decode_errors: 0,
+            sweeps_dispatched: 0,
+            last_chunk_time: None,
+            last_chunk_latency: None,
+            rda_status: None,
+            operability_status: None,
+        }
+    }
+}
+
+type SharedHealth = Arc<Mutex<HashMap<String, SiteHealth>>>;
+
+/// Subscriptions to multiple sites' real-time feeds. Each site is polled by its own task and its
+/// chunks are decoded as they arrive, with each completed sweep sent to the subscriber's channel.
+///
+/// ```no_run
+/// # async fn example() {
+/// use nexrad_data::aws::realtime::Subscriptions;
+/// use tokio::sync::mpsc;
+///
+/// let (sweep_tx, mut sweep_rx) = mpsc::channel(16);
+/// let subscriptions = Subscriptions::start(["KDMX", "KTLX"], sweep_tx);
+///
+/// while let Some(site_sweep) = sweep_rx.recv().await {
+///     println!("{}: elevation {}", site_sweep.site, site_sweep.sweep.elevation_number());
+/// }
+///
+/// subscriptions.shutdown().await;
+/// # }
+/// ```
+pub struct Subscriptions {
+    health: SharedHealth,
+    shutdown_tx: watch::Sender<bool>,
+    handles: Vec<JoinHandle<()>>,
+}
+
+impl Subscriptions {
+    /// Starts polling each of the given sites, sending decoded sweeps to `sweep_tx`. Must be called
+    /// from within a Tokio runtime. A site's task ends if its subscriber channel is closed.
+    pub fn start<S: Into<String>>(
+        sites: impl IntoIterator<Item = S>,
+        sweep_tx: mpsc::Sender<SiteSweep>,
+    ) -> Self {
+        Self::start_with_options(sites, NetworkOptions::new(), sweep_tx)
+    }
+
+    /// Starts polling each of the given sites like [Subscriptions::start], making requests with
+    /// the given timeouts and retries.
+    pub fn start_with_options<S: Into<String>>(
+        sites: impl IntoIterator<Item = S>,
+        network_options: NetworkOptions,
+        sweep_tx: mpsc::Sender<SiteSweep>,
+    ) -> Self {
+        let health = SharedHealth::default();
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
+        let mut handles = Vec::new();
+        for site in sites {
+            let site = site.into();
+            update_health_map(&health, |health| {
+                health.insert(site.clone(), SiteHealth::new(site.clone()));
+            });
+
+            handles.push(task::spawn(run_site(
+                site,
+                network_options,
+                sweep_tx.clone(),
+                health.clone(),
+                shutdown_rx.clone(),
+            )));
+        }
+
+        Self {
+            health,
+            shutdown_tx,
+            handles,
+        }
+    }
+
+    /// The current health of each site's subscription, ordered by site.
+    pub fn health(&self) -> Vec<SiteHealth> {
+        let mut health: Vec<SiteHealth> = self
+            .health
+            .lock()
+            .map(|health| health.values().cloned().collect())
+            .unwrap_or_default();
+        health.sort_by(|a, b| a.site.cmp(&b.site));
+        health
+    }
+
+    /// Stops polling all sites and waits for their tasks to finish. Sweeps which were partially
+    /// received are discarded.
+    pub async fn shutdown(self) {
+        let _ = self.shutdown_tx.send(true);
+        for handle in self.handles {
+            let _ = handle.await;
+        }
+    }
+}
+
+/// Polls a site's feed until shutdown, decoding its chunks on a blocking task.
+async fn run_site(
+    site: String,
+    network_options: NetworkOptions,
+    sweep_tx: mpsc::Sender<SiteSweep>,
+    health: SharedHealth,
+    mut shutdown_rx: watch::Receiver<bool>,
+) {
+    let (chunk_tx, chunk_rx) = std_mpsc::channel();
+    let (stats_tx, stats_rx) = std_mpsc::channel();
+    let (_stop_tx, stop_rx) = std_mpsc::channel();
+
+    let decoder = task::spawn_blocking({
+        let site = site.clone();
+        let health = health.clone();
+        move || decode_chunks(&site, chunk_rx, stats_rx, sweep_tx, &health)
+    });
+
+    debug!("Subscribing to {site}");
+    let result = tokio::select! {
+        result = poll_chunks_with_options(
+            &site,
+            chunk_tx,
+            Some(stats_tx),
+            stop_rx,
+            &network_options,
+            &TokioTimer,
+        ) => result,
+        _ = shutdown_rx.wait_for(|shutdown| *shutdown) => Ok(()),
+    };
+
+    // The polling future and its chunk sender have been dropped, so the decoder will finish
+    let _ = decoder.await;
+
+    let status = match result {
+        Ok(()) => SiteStatus::Stopped,
+        Err(err) => {
+            warn!("Subscription to {site} failed: {err}");
+            SiteStatus::Failed(err.to_string())
+        }
+    };
+    update_health(&health, &site, |health| health.status = status);
+}
+
+/// Decodes chunks as they are received, sending each completed sweep to `sweep_tx`. Returns when
+/// the chunk sender is dropped or the subscriber's channel is closed.
+fn decode_chunks(
+    site: &str,
+    chunk_rx: std_mpsc::Receiver<(ChunkIdentifier, Chunk<'static>)>,
+    stats_rx: std_mpsc::Receiver<PollStats>,
+    sweep_tx: mpsc::Sender<SiteSweep>,
+    health: &SharedHealth,
+) {
+    let mut buffer = SweepBuffer::new(StreamOptions::new());
+    while let Ok((chunk_id, chunk)) = chunk_rx.recv() {
+        let latency = stats_rx
+            .try_iter()
+            .fold(None, |latency, stats| match stats {
+                PollStats::NewChunk(stats) => stats.latency,
+                _ => latency,
+            });
+
+        let mut sweeps = Vec::new();
+        let mut on_sweep = |sweep| sweeps.push(sweep);
+        let result = match chunk {
+            Chunk::Start(file) => file
+                .records()
+                .into_iter()
+                .try_for_each(|record| buffer.push_record(record, &mut on_sweep)),
+            Chunk::IntermediateOrEnd(record) => buffer.push_record(record, &mut on_sweep),
+        };
+
+        if let Err(err) = &result {
+            warn!(
+                "Failed to decode chunk {} for {site}: {err}",
+                chunk_id.name()
+            );
+        }
+
+        update_health(health, site, |health| {
+            health.chunks_received += 1;
+            health.decode_errors += result.is_err() as usize;
+            health.last_chunk_time = chunk_id.date_time();
+            health.last_chunk_latency = latency.or(health.last_chunk_latency);
+            if let Some((rda_status, operability_status)) = buffer.rda_status() {
+                health.rda_status = Some(rda_status);
+                health.operability_status = Some(operability_status);
+            }
+        });
+
+        for sweep in sweeps {
+            let site_sweep = SiteSweep {
+                site: site.to_string(),
+                sweep,
+            };
+            if sweep_tx.blocking_send(site_sweep).is_err() {
+                debug!("Subscriber for {site} closed its channel");
+                return;
+            }
+
+            update_health(health, site, |health| health.sweeps_dispatched += 1);
+        }
+    }
+}
+
+fn update_health(health: &SharedHealth, site: &str, update: impl FnOnce(&mut SiteHealth)) {
+    update_health_map(health, |health| {
+        if let Some(site_health) = health.get_mut(site) {
+            update(site_health);
+        }
+    });
+}
+
+fn update_health_map(health: &SharedHealth, update: impl FnOnce(&mut HashMap<String, SiteHealth>)) {
+    if let Ok(mut health) = health.lock() {
+        update(&mut health);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aws::realtime::VolumeIndex;
+    use crate::volume::{File, Header, Record, SyntheticVolume};
+
+    #[test]
+    fn test_decode_chunks_dispatches_sweeps() -> Result<(), Box<dyn std::error::Error>> {
+        let volume = SyntheticVolume::new()
+            .with_elevation_count(2)
+            .with_radials_per_sweep(240)
+            .with_gate_count(20)
+            .generate()?;
+
+        // Split the volume into chunks as it would be uploaded to the real-time bucket
+        let records = volume.records();
+        let mut start_data = volume.data()[..size_of::<Header>()].to_vec();
+        start_data.extend_from_slice(records[0].data());
+
+        let chunk_id = |sequence| {
+            let name = format!("20240101-000000-{sequence:03}-I");
+            ChunkIdentifier::new("KSYN".to_string(), VolumeIndex::new(1), name, None)
+        };
+
+        let (chunk_tx, chunk_rx) = std_mpsc::channel();
+        chunk_tx.send((chunk_id(1), Chunk::Start(File::new(start_data))))?;
+        for (index, record) in records.iter().enumerate().skip(1) {
+            let record = Record::new(record.data().to_vec());
+            chunk_tx.send((chunk_id(index + 1), Chunk::IntermediateOrEnd(record)))?;
+        }
+        drop(chunk_tx);
+
+        let (_stats_tx, stats_rx) = std_mpsc::channel();
+        let (sweep_tx, mut sweep_rx) = mpsc::channel(8);
+        let health = SharedHealth::default();
+        update_health_map(&health, |health| {
+            health.insert("KSYN".to_string(), SiteHealth::new("KSYN".to_string()));
+        });
+
+        decode_chunks("KSYN", chunk_rx, stats_rx, sweep_tx, &health);
+
+        let mut sweeps = Vec::new();
+        while let Ok(site_sweep) = sweep_rx.try_recv() {
+            assert_eq!(site_sweep.site, "KSYN");
+            sweeps.push(site_sweep.sweep);
+        }
+        assert_eq!(&sweeps, volume.scan()?.sweeps());
+
+        let mut site_health = None;
+        update_health(&health, "KSYN", |health| site_health = Some(health.clone()));
+        let site_health = site_health.ok_or("missing site health")?;
+        assert_eq!(site_health.chunks_received, records.len());
+        assert_eq!(site_health.decode_errors, 0);
+        assert_eq!(site_health.sweeps_dispatched, 2);
+
+        Ok(())
+    }
+}
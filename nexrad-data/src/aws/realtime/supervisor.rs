@@ -0,0 +1,641 @@
+use crate::aws::network::NetworkOptions;
+use crate::aws::realtime::{SiteHealth, SiteStatus, SiteSweep, Subscriptions};
+use crate::telemetry::{debug, info};
+use nexrad_decode::messages::rda_status_data::{OperabilityStatus, RDAStatus};
+use nexrad_model::meta::{nearest_sites, Site};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+use tokio::task::{self, JoinHandle};
+use tokio::time::{interval, Instant, MissedTickBehavior};
+
+/// Options for supervising a site's real-time feed. See [Supervisor].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SupervisorOptions {
+    max_data_gap: Duration,
+    check_interval: Duration,
+    max_neighbors: usize,
+    network_options: NetworkOptions,
+}
+
+impl SupervisorOptions {
+    /// Creates options which fail over after ten minutes without a sweep, checking every thirty
+    /// seconds and considering the three nearest neighbor sites, with the default
+    /// [NetworkOptions].
+    pub fn new() -> Self {
+        Self {
+            max_data_gap: Duration::from_secs(600),
+            check_interval: Duration::from_secs(30),
+            max_neighbors: 3,
+            network_options: NetworkOptions::new(),
+        }
+    }
+
+    /// The longest a site may go without sending a sweep before it is considered down.
+    pub fn with_max_data_gap(mut self, max_data_gap: Duration) -> Self {
+        self.max_data_gap = max_data_gap;
+        self
+    }
+
+    /// How often the active site's health is checked.
+    pub fn with_check_interval(mut self, check_interval: Duration) -> Self {
+        self.check_interval = check_interval;
+        self
+    }
+
+    /// The number of neighbor sites, nearest first, which may be failed over to.
+    pub fn with_max_neighbors(mut self, max_neighbors: usize) -> Self {
+        self.max_neighbors = max_neighbors;
+        self
+    }
+
+    /// The timeouts and retries with which each site's feed is polled.
+    pub fn with_network_options(mut self, network_options: NetworkOptions) -> Self {
+        self.network_options = network_options;
+        self
+    }
+}
+
+impl Default for SupervisorOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Why a site was considered down.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FailoverReason {
+    /// The site has not sent a sweep within the maximum data gap.
+    DataGap,
+    /// Polling the site's feed failed with the given error.
+    SubscriptionFailed(String),
+    /// The site's most recent RDA status message reports that the radar is not operating, for
+    /// example in standby or shut down for maintenance.
+    RadarDown {
+        rda_status: RDAStatus,
+        operability_status: OperabilityStatus,
+    },
+}
+
+/// A transition between the sites a [Supervisor] forwards sweeps from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SupervisorEvent {
+    /// The active site went down and sweeps are now forwarded from a neighbor.
+    FailedOver {
+        from: String,
+        to: String,
+        reason: FailoverReason,
+    },
+    /// The primary site is sending data again and sweeps are forwarded from it once more.
+    Restored { from: String, to: String },
+    /// The active site went down and no operational neighbor remains to fail over to. Sweeps
+    /// continue to be forwarded from the active site should it recover.
+    NoOperationalNeighbor {
+        site: String,
+        reason: FailoverReason,
+    },
+}
+
+/// Supervises a primary site's real-time feed, automatically switching to the nearest operational
+/// neighbor site when the primary stops sending data, reports in an RDA status message that its
+/// radar is down, or its subscription fails, and back once the primary recovers. Sweeps from the
+/// active site are forwarded to the subscriber and each transition is reported as a
+/// [SupervisorEvent], keeping dashboards populated during outages.
+///
+/// While failed over, the primary site continues to be polled so that its recovery is detected. A
+/// polled site whose subscription fails is resubscribed after the check interval, waiting twice as
+/// long after each further failure up to the maximum data gap.
+pub struct Supervisor {
+    shutdown_tx: watch::Sender<bool>,
+    handle: JoinHandle<()>,
+}
+
+impl Supervisor {
+    /// Starts supervising the primary site, with neighbors chosen from the given sites by distance.
+    /// Must be called from within a Tokio runtime.
+    pub fn start(
+        primary: &Site,
+        sites: &[Site],
+        options: SupervisorOptions,
+        sweep_tx: mpsc::Sender<SiteSweep>,
+        event_tx: mpsc::Sender<SupervisorEvent>,
+    ) -> Self {
+        let neighbors = nearest_sites(
+            sites
+                .iter()
+                .filter(|site| site.identifier() != primary.identifier()),
+            &primary.location(),
+            options.max_neighbors,
+        )
+        .into_iter()
+        .map(Site::identifier_string)
+        .collect();
+
+        let state = FailoverState::new(
+            primary.identifier_string(),
+            neighbors,
+            options.max_data_gap,
+            options.check_interval,
+            Instant::now(),
+        );
+
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let handle = task::spawn(supervise(
+            state,
+            options.check_interval,
+            options.network_options,
+            sweep_tx,
+            event_tx,
+            shutdown_rx,
+        ));
+
+        Self {
+            shutdown_tx,
+            handle,
+        }
+    }
+
+    /// Stops supervising and polling all sites, waiting for their tasks to finish.
+    pub async fn shutdown(self) {
+        let _ = self.shutdown_tx.send(true);
+        let _ = self.handle.await;
+    }
+}
+
+async fn supervise(
+    mut state: FailoverState,
+    check_interval: Duration,
+    network_options: NetworkOptions,
+    sweep_tx: mpsc::Sender<SiteSweep>,
+    event_tx: mpsc::Sender<SupervisorEvent>,
+    mut shutdown_rx: watch::Receiver<bool>,
+) {
+    let (site_sweep_tx, mut site_sweep_rx) = mpsc::channel(16);
+    let mut subscriptions = SiteSubscriptions::new(network_options, site_sweep_tx);
+    subscriptions.poll(&state.polled_sites()).await;
+
+    let mut check = interval(check_interval);
+    check.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            Some(site_sweep) = site_sweep_rx.recv() => {
+                state.record_data(&site_sweep.site, Instant::now());
+                if site_sweep.site == state.active
+                    && sweep_tx.send(site_sweep).await.is_err()
+                {
+                    debug!("Supervisor subscriber closed its channel");
+                    break;
+                }
+            }
+            _ = check.tick() => {
+                let now = Instant::now();
+                let health = subscriptions.health();
+                if let Some(event) = state.evaluate(&health, now) {
+                    info!("Supervisor transition: {event:?}");
+                    subscriptions.poll(&state.polled_sites()).await;
+
+                    if event_tx.send(event).await.is_err() {
+                        debug!("Supervisor event subscriber closed its channel");
+                    }
+                }
+
+                for site in state.due_restarts(&health, now) {
+                    info!("Resubscribing to {site} after its subscription failed");
+                    subscriptions.restart(&site).await;
+                }
+            }
+            // The borrowed value is not sendable, so it must not outlive this branch
+            _ = async { shutdown_rx.wait_for(|shutdown| *shutdown).await.is_ok() } => break,
+        }
+    }
+
+    subscriptions.shutdown().await;
+}
+
+/// A subscription for each polled site, so that sites can be started, stopped and restarted
+/// independently of one another.
+struct SiteSubscriptions {
+    subscriptions: HashMap<String, Subscriptions>,
+    network_options: NetworkOptions,
+    sweep_tx: mpsc::Sender<SiteSweep>,
+}
+
+impl SiteSubscriptions {
+    fn new(network_options: NetworkOptions, sweep_tx: mpsc::Sender<SiteSweep>) -> Self {
+        Self {
+            subscriptions: HashMap::new(),
+            network_options,
+            sweep_tx,
+        }
+    }
+
+    fn health(&self) -> Vec<SiteHealth> {
+        self.subscriptions
+            .values()
+            .flat_map(Subscriptions::health)
+            .collect()
+    }
+
+    /// Starts polling the given sites which are not already polled, and stops polling any others.
+    async fn poll(&mut self, sites: &[String]) {
+        let stopped: Vec<String> = self
+            .subscriptions
+            .keys()
+            .filter(|site| !sites.contains(site))
+            .cloned()
+            .collect();
+        for site in stopped {
+            self.stop(&site).await;
+        }
+
+        for site in sites {
+            if !self.subscriptions.contains_key(site) {
+                self.start(site);
+            }
+        }
+    }
+
+    async fn restart(&mut self, site: &str) {
+        self.stop(site).await;
+        self.start(site);
+    }
+
+    fn start(&mut self, site: &str) {
+        let subscription =
+            Subscriptions::start_with_options([site], self.network_options, self.sweep_tx.clone());
+        self.subscriptions.insert(site.to_string(), subscription);
+    }
+
+    async fn stop(&mut self, site: &str) {
+        if let Some(subscription) = self.subscriptions.remove(site) {
+            subscription.shutdown().await;
+        }
+    }
+
+    async fn shutdown(self) {
+        for subscription in self.subscriptions.into_values() {
+            subscription.shutdown().await;
+        }
+    }
+}
+
+/// Decides which site sweeps are forwarded from based on each site's most recent data and
+/// subscription health.
+#[derive(Debug, Clone)]
+struct FailoverState {
+    primary: String,
+    neighbors: Vec<String>,
+    active: String,
+    failed_neighbors: HashSet<String>,
+    last_data: HashMap<String, Instant>,
+    max_data_gap: Duration,
+    exhausted: bool,
+    restart_backoff: Duration,
+    restart_attempts: HashMap<String, u32>,
+    scheduled_restarts: HashMap<String, Instant>,
+}
+
+impl FailoverState {
+    fn new(
+        primary: String,
+        neighbors: Vec<String>,
+        max_data_gap: Duration,
+        restart_backoff: Duration,
+        now: Instant,
+    ) -> Self {
+        // The primary is given a full data gap to send its first sweep
+        let last_data = HashMap::from([(primary.clone(), now)]);
+
+        Self {
+            active: primary.clone(),
+            primary,
+            neighbors,
+            failed_neighbors: HashSet::new(),
+            last_data,
+            max_data_gap,
+            exhausted: false,
+            restart_backoff,
+            restart_attempts: HashMap::new(),
+            scheduled_restarts: HashMap::new(),
+        }
+    }
+
+    /// The sites to poll: the active site and, while failed over, the primary.
+    fn polled_sites(&self) -> Vec<String> {
+        if self.active == self.primary {
+            vec![self.primary.clone()]
+        } else {
+            vec![self.primary.clone(), self.active.clone()]
+        }
+    }
+
+    fn record_data(&mut self, site: &str, now: Instant) {
+        self.last_data.insert(site.to_string(), now);
+        self.restart_attempts.remove(site);
+    }
+
+    /// The polled sites whose subscriptions failed and are due to be restarted. A failed site is
+    /// restarted after the restart backoff, which doubles with each restart until the site sends
+    /// data again, up to the maximum data gap.
+    fn due_restarts(&mut self, health: &[SiteHealth], now: Instant) -> Vec<String> {
+        let polled_sites = self.polled_sites();
+        self.scheduled_restarts
+            .retain(|site, _| polled_sites.contains(site));
+
+        let mut due = Vec::new();
+        for site in polled_sites {
+            let failed = health.iter().any(|site_health| {
+                site_health.site == site && matches!(site_health.status, SiteStatus::Failed(_))
+            });
+            if !failed {
+                continue;
+            }
+
+            let attempts = self.restart_attempts.get(&site).copied().unwrap_or(0);
+            let scheduled = *self
+                .scheduled_restarts
+                .entry(site.clone())
+                .or_insert_with(|| {
+                    let factor = 2u32.checked_pow(attempts).unwrap_or(u32::MAX);
+                    now + self
+                        .restart_backoff
+                        .saturating_mul(factor)
+                        .min(self.max_data_gap)
+                });
+
+            if now >= scheduled {
+                self.scheduled_restarts.remove(&site);
+                self.restart_attempts.insert(site.clone(), attempts + 1);
+                due.push(site);
+            }
+        }
+
+        due
+    }
+
+    /// Why the given site is considered down, if it is.
+    fn down_reason(
+        &self,
+        site: &str,
+        health: &[SiteHealth],
+        now: Instant,
+    ) -> Option<FailoverReason> {
+        let site_health = health.iter().find(|site_health| site_health.site == site);
+        if let Some(SiteStatus::Failed(err)) = site_health.map(|site_health| &site_health.status) {
+            return Some(FailoverReason::SubscriptionFailed(err.clone()));
+        }
+
+        if let Some((rda_status, operability_status)) = site_health
+            .and_then(|site_health| site_health.rda_status.zip(site_health.operability_status))
+            .filter(|(rda_status, operability_status)| radar_down(*rda_status, *operability_status))
+        {
+            return Some(FailoverReason::RadarDown {
+                rda_status,
+                operability_status,
+            });
+        }
+
+        let receiving = self
+            .last_data
+            .get(site)
+            .is_some_and(|last_data| now.duration_since(*last_data) <= self.max_data_gap);
+        (!receiving).then_some(FailoverReason::DataGap)
+    }
+
+    /// Checks the active and primary sites' health, switching sites if needed.
+    fn evaluate(&mut self, health: &[SiteHealth], now: Instant) -> Option<SupervisorEvent> {
+        if self.active != self.primary && self.down_reason(&self.primary, health, now).is_none() {
+            let from = std::mem::replace(&mut self.active, self.primary.clone());
+            self.failed_neighbors.clear();
+            self.exhausted = false;
+            return Some(SupervisorEvent::Restored {
+                from,
+                to: self.primary.clone(),
+            });
+        }
+
+        let reason = self.down_reason(&self.active, health, now)?;
+        if self.active != self.primary {
+            self.failed_neighbors.insert(self.active.clone());
+        }
+
+        let next = self
+            .neighbors
+            .iter()
+            .find(|neighbor| {
+                **neighbor != self.active && !self.failed_neighbors.contains(*neighbor)
+            })
+            .cloned();
+
+        match next {
+            Some(next) => {
+                // The down site must send new data to be considered recovered
+                self.last_data.remove(&self.active);
+                self.last_data.insert(next.clone(), now);
+
+                let from = std::mem::replace(&mut self.active, next.clone());
+                Some(SupervisorEvent::FailedOver {
+                    from,
+                    to: next,
+                    reason,
+                })
+            }
+            None if !self.exhausted => {
+                self.exhausted = true;
+                Some(SupervisorEvent::NoOperationalNeighbor {
+                    site: self.active.clone(),
+                    reason,
+                })
+            }
+            None => None,
+        }
+    }
+}
+
+/// Whether an RDA reporting the given statuses is not collecting data.
+fn radar_down(rda_status: RDAStatus, operability_status: OperabilityStatus) -> bool {
+    matches!(
+        rda_status,
+        RDAStatus::StartUp | RDAStatus::Standby | RDAStatus::Restart
+    ) || matches!(
+        operability_status,
+        OperabilityStatus::CommandedShutDown | OperabilityStatus::Inoperable
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_DATA_GAP: Duration = Duration::from_secs(600);
+    const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+    fn state(now: Instant) -> FailoverState {
+        FailoverState::new(
+            "KDMX".to_string(),
+            vec!["KDVN".to_string(), "KOAX".to_string()],
+            MAX_DATA_GAP,
+            CHECK_INTERVAL,
+            now,
+        )
+    }
+
+    fn failed(site: &str) -> SiteHealth {
+        let mut health = SiteHealth::new(site.to_string());
+        health.status = SiteStatus::Failed("not found".to_string());
+        health
+    }
+
+    #[test]
+    fn test_fails_over_on_data_gap_and_restores() {
+        let start = Instant::now();
+        let mut state = state(start);
+
+        state.record_data("KDMX", start + Duration::from_secs(60));
+        assert_eq!(state.evaluate(&[], start + Duration::from_secs(600)), None);
+
+        let event = state.evaluate(&[], start + Duration::from_secs(700));
+        assert_eq!(
+            event,
+            Some(SupervisorEvent::FailedOver {
+                from: "KDMX".to_string(),
+                to: "KDVN".to_string(),
+                reason: FailoverReason::DataGap,
+            })
+        );
+        assert_eq!(state.polled_sites(), vec!["KDMX", "KDVN"]);
+
+        state.record_data("KDVN", start + Duration::from_secs(760));
+        assert_eq!(state.evaluate(&[], start + Duration::from_secs(800)), None);
+
+        state.record_data("KDMX", start + Duration::from_secs(850));
+        let event = state.evaluate(&[], start + Duration::from_secs(900));
+        assert_eq!(
+            event,
+            Some(SupervisorEvent::Restored {
+                from: "KDVN".to_string(),
+                to: "KDMX".to_string(),
+            })
+        );
+        assert_eq!(state.polled_sites(), vec!["KDMX"]);
+    }
+
+    #[test]
+    fn test_fails_over_when_radar_down() {
+        let start = Instant::now();
+        let mut state = state(start);
+
+        let mut health = SiteHealth::new("KDMX".to_string());
+        health.rda_status = Some(RDAStatus::Operate);
+        health.operability_status = Some(OperabilityStatus::MaintenanceActionRequired);
+        assert_eq!(state.evaluate(&[health.clone()], start), None);
+
+        health.rda_status = Some(RDAStatus::Standby);
+        let event = state.evaluate(&[health.clone()], start);
+        assert_eq!(
+            event,
+            Some(SupervisorEvent::FailedOver {
+                from: "KDMX".to_string(),
+                to: "KDVN".to_string(),
+                reason: FailoverReason::RadarDown {
+                    rda_status: RDAStatus::Standby,
+                    operability_status: OperabilityStatus::MaintenanceActionRequired,
+                },
+            })
+        );
+
+        // The primary is restored once it is operating and sending data again
+        state.record_data("KDMX", start + Duration::from_secs(60));
+        assert_eq!(
+            state.evaluate(&[health.clone()], start + Duration::from_secs(60)),
+            None
+        );
+
+        health.rda_status = Some(RDAStatus::Operate);
+        assert!(matches!(
+            state.evaluate(&[health], start + Duration::from_secs(60)),
+            Some(SupervisorEvent::Restored { .. })
+        ));
+    }
+
+    #[test]
+    fn test_fails_over_through_neighbors() {
+        let start = Instant::now();
+        let mut state = state(start);
+
+        let event = state.evaluate(&[failed("KDMX")], start);
+        assert!(matches!(
+            event,
+            Some(SupervisorEvent::FailedOver {
+                reason: FailoverReason::SubscriptionFailed(_),
+                ..
+            })
+        ));
+
+        let event = state.evaluate(&[failed("KDVN")], start);
+        assert!(matches!(event, Some(SupervisorEvent::FailedOver { to, .. }) if to == "KOAX"));
+
+        let event = state.evaluate(&[failed("KOAX")], start);
+        assert!(matches!(
+            event,
+            Some(SupervisorEvent::NoOperationalNeighbor { site, .. }) if site == "KOAX"
+        ));
+        assert_eq!(state.evaluate(&[failed("KOAX")], start), None);
+    }
+
+    #[test]
+    fn test_restarts_failed_primary_and_restores() {
+        let start = Instant::now();
+        let mut state = state(start);
+
+        let health = [failed("KDMX")];
+        assert!(matches!(
+            state.evaluate(&health, start),
+            Some(SupervisorEvent::FailedOver { to, .. }) if to == "KDVN"
+        ));
+
+        // The failed primary is resubscribed after the check interval
+        assert!(state.due_restarts(&health, start).is_empty());
+        assert_eq!(
+            state.due_restarts(&health, start + CHECK_INTERVAL),
+            vec!["KDMX"]
+        );
+
+        // Each further failure doubles the wait
+        let failed_again = start + CHECK_INTERVAL * 2;
+        assert!(state.due_restarts(&health, failed_again).is_empty());
+        assert!(state
+            .due_restarts(&health, failed_again + CHECK_INTERVAL)
+            .is_empty());
+        assert_eq!(
+            state.due_restarts(&health, failed_again + CHECK_INTERVAL * 2),
+            vec!["KDMX"]
+        );
+
+        // The resubscribed primary sends data and is restored
+        let recovered = failed_again + CHECK_INTERVAL * 3;
+        let health = [SiteHealth::new("KDMX".to_string())];
+        assert_eq!(state.evaluate(&health, recovered), None);
+        assert!(state.due_restarts(&health, recovered).is_empty());
+
+        state.record_data("KDMX", recovered);
+        let event = state.evaluate(&health, recovered + CHECK_INTERVAL);
+        assert_eq!(
+            event,
+            Some(SupervisorEvent::Restored {
+                from: "KDVN".to_string(),
+                to: "KDMX".to_string(),
+            })
+        );
+
+        // Having sent data, the primary's next failure is again resubscribed after one interval
+        let health = [failed("KDMX")];
+        let failed_later = recovered + CHECK_INTERVAL * 2;
+        assert!(state.due_restarts(&health, failed_later).is_empty());
+        assert_eq!(
+            state.due_restarts(&health, failed_later + CHECK_INTERVAL),
+            vec!["KDMX"]
+        );
+    }
+}
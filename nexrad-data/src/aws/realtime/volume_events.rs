@@ -0,0 +1,247 @@
+use crate::aws::realtime::Chunk;
+use crate::result::Result;
+use crate::volume::{Record, StreamOptions, SweepBuffer};
+use nexrad_model::data::{RadialStatus, Sweep};
+
+/// A volume which has finished, either because its final radial was received or because the next
+/// volume started before it was complete. See [VolumeAssembler::on_volume_complete].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CompletedVolume {
+    /// The volume's coverage pattern number, if a volume data block was received.
+    pub coverage_pattern_number: Option<u16>,
+    /// The number of sweeps completed in the volume.
+    pub sweep_count: usize,
+    /// Whether the volume's final radial was received, rather than the volume being cut short.
+    pub complete: bool,
+}
+
+/// Assembles real-time chunks into sweeps and volumes, notifying callbacks of semantic milestones
+/// rather than raw chunk arrival: each completed sweep, each completed volume, and changes of
+/// volume coverage pattern between volumes.
+///
+/// ```no_run
+/// # fn example(chunks: Vec<nexrad_data::aws::realtime::Chunk>) -> nexrad_data::result::Result<()> {
+/// use nexrad_data::aws::realtime::VolumeAssembler;
+/// use nexrad_data::volume::StreamOptions;
+///
+/// let mut assembler = VolumeAssembler::new(StreamOptions::new())
+///     .on_sweep_complete(|sweep| println!("elevation {}", sweep.elevation_number()))
+///     .on_volume_complete(|volume| println!("{} sweeps", volume.sweep_count))
+///     .on_vcp_change(|previous, current| println!("VCP {previous} -> {current}"));
+///
+/// for chunk in chunks {
+///     assembler.push_chunk(chunk)?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct VolumeAssembler<'a> {
+    options: StreamOptions,
+    buffer: SweepBuffer,
+    sweep_count: usize,
+    coverage_pattern_number: Option<u16>,
+    on_sweep_complete: Option<Box<dyn FnMut(Sweep) + 'a>>,
+    on_volume_complete: Option<Box<dyn FnMut(CompletedVolume) + 'a>>,
+    on_vcp_change: Option<Box<dyn FnMut(u16, u16) + 'a>>,
+}
+
+impl<'a> VolumeAssembler<'a> {
+    /// Creates a new assembler which buffers sweeps with the given options.
+    pub fn new(options: StreamOptions) -> Self {
+        Self {
+            buffer: SweepBuffer::new(options.clone()),
+            options,
+            sweep_count: 0,
+            coverage_pattern_number: None,
+            on_sweep_complete: None,
+            on_volume_complete: None,
+            on_vcp_change: None,
+        }
+    }
+
+    /// Sets a callback receiving each sweep as soon as it is complete.
+    pub fn on_sweep_complete(mut self, callback: impl FnMut(Sweep) + 'a) -> Self {
+        self.on_sweep_complete = Some(Box::new(callback));
+        self
+    }
+
+    /// Sets a callback notified when a volume's final radial is received, or when the next volume
+    /// starts before it was complete.
+    pub fn on_volume_complete(mut self, callback: impl FnMut(CompletedVolume) + 'a) -> Self {
+        self.on_volume_complete = Some(Box::new(callback));
+        self
+    }
+
+    /// Sets a callback notified with the previous and current coverage pattern numbers when a
+    /// volume's coverage pattern differs from the preceding volume's.
+    pub fn on_vcp_change(mut self, callback: impl FnMut(u16, u16) + 'a) -> Self {
+        self.on_vcp_change = Some(Box::new(callback));
+        self
+    }
+
+    /// Decodes a chunk, notifying callbacks of any milestones it completes. A start chunk first
+    /// completes any volume still in progress.
+    pub fn push_chunk(&mut self, chunk: Chunk) -> Result<()> {
+        match chunk {
+            Chunk::Start(file) => {
+                self.finish();
+                for record in file.records() {
+                    self.push_record(record)?;
+                }
+            }
+            Chunk::IntermediateOrEnd(record) => self.push_record(record)?,
+        }
+
+        Ok(())
+    }
+
+    /// Completes the volume in progress, if any, emitting its partial sweep. This should be called
+    /// when no more chunks will be pushed, for example when a subscription is stopped.
+    pub fn finish(&mut self) {
+        let mut sweeps = Vec::new();
+        self.buffer.flush(&mut |sweep| sweeps.push(sweep));
+        self.complete_sweeps(sweeps);
+
+        self.complete_volume(false);
+    }
+
+    fn push_record(&mut self, record: Record) -> Result<()> {
+        let mut sweeps = Vec::new();
+        let result = self
+            .buffer
+            .push_record(record, &mut |sweep| sweeps.push(sweep));
+
+        self.check_coverage_pattern();
+        self.complete_sweeps(sweeps);
+
+        result
+    }
+
+    /// Notifies the VCP change callback once the volume's coverage pattern is known.
+    fn check_coverage_pattern(&mut self) {
+        let Some(current) = self.buffer.coverage_pattern_number() else {
+            return;
+        };
+
+        if let Some(previous) = self.coverage_pattern_number.replace(current) {
+            if previous != current {
+                if let Some(on_vcp_change) = &mut self.on_vcp_change {
+                    on_vcp_change(previous, current);
+                }
+            }
+        }
+    }
+
+    fn complete_sweeps(&mut self, sweeps: Vec<Sweep>) {
+        for sweep in sweeps {
+            let volume_ended = sweep
+                .radials()
+                .last()
+                .is_some_and(|radial| radial.radial_status() == RadialStatus::VolumeScanEnd);
+
+            self.sweep_count += 1;
+            if let Some(on_sweep_complete) = &mut self.on_sweep_complete {
+                on_sweep_complete(sweep);
+            }
+
+            if volume_ended {
+                self.complete_volume(true);
+            }
+        }
+    }
+
+    /// Notifies the volume callback if any sweeps were completed, and resets for the next volume.
+    fn complete_volume(&mut self, complete: bool) {
+        if self.sweep_count > 0 {
+            let completed_volume = CompletedVolume {
+                coverage_pattern_number: self.buffer.coverage_pattern_number(),
+                sweep_count: self.sweep_count,
+                complete,
+            };
+
+            if let Some(on_volume_complete) = &mut self.on_volume_complete {
+                on_volume_complete(completed_volume);
+            }
+        }
+
+        self.buffer = SweepBuffer::new(self.options.clone());
+        self.sweep_count = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::volume::{File, Header, SyntheticVolume};
+    use std::cell::RefCell;
+
+    /// Splits a volume into chunks as it would be uploaded to the real-time bucket.
+    fn chunks(volume: &File) -> Vec<Chunk<'static>> {
+        let records = volume.records();
+        let mut start_data = volume.data()[..size_of::<Header>()].to_vec();
+        start_data.extend_from_slice(records[0].data());
+
+        let mut chunks = vec![Chunk::Start(File::new(start_data))];
+        for record in records.iter().skip(1) {
+            chunks.push(Chunk::IntermediateOrEnd(Record::new(
+                record.data().to_vec(),
+            )));
+        }
+
+        chunks
+    }
+
+    #[test]
+    fn test_volume_events() -> Result<()> {
+        let volume = |coverage_pattern_number| {
+            SyntheticVolume::new()
+                .with_elevation_count(2)
+                .with_radials_per_sweep(90)
+                .with_gate_count(20)
+                .with_coverage_pattern_number(coverage_pattern_number)
+                .generate()
+        };
+
+        let sweeps = RefCell::new(Vec::new());
+        let volumes = RefCell::new(Vec::new());
+        let vcp_changes = RefCell::new(Vec::new());
+
+        let mut assembler = VolumeAssembler::new(StreamOptions::new())
+            .on_sweep_complete(|sweep| sweeps.borrow_mut().push(sweep.elevation_number()))
+            .on_volume_complete(|volume| volumes.borrow_mut().push(volume))
+            .on_vcp_change(|previous, current| vcp_changes.borrow_mut().push((previous, current)));
+
+        for chunk in chunks(&volume(212)?) {
+            assembler.push_chunk(chunk)?;
+        }
+
+        // The second volume is cut short before its final sweep
+        let mut truncated_chunks = chunks(&volume(215)?);
+        truncated_chunks.pop();
+        for chunk in truncated_chunks {
+            assembler.push_chunk(chunk)?;
+        }
+        assembler.finish();
+        drop(assembler);
+
+        assert_eq!(sweeps.into_inner(), vec![1, 2, 1, 2]);
+        assert_eq!(
+            volumes.into_inner(),
+            vec![
+                CompletedVolume {
+                    coverage_pattern_number: Some(212),
+                    sweep_count: 2,
+                    complete: true,
+                },
+                CompletedVolume {
+                    coverage_pattern_number: Some(215),
+                    sweep_count: 2,
+                    complete: false,
+                },
+            ]
+        );
+        assert_eq!(vcp_changes.into_inner(), vec![(212, 215)]);
+
+        Ok(())
+    }
+}
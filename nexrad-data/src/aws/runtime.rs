@@ -0,0 +1,38 @@
+//!
+//! Abstracts the async runtime facilities used by long-running AWS routines, so they may run on
+//! executors other than `tokio`. Routines which wait between requests, such as
+//! [crate::aws::realtime::poll_chunks_with_options], accept a [Timer] rather than calling a runtime's
+//! timer directly.
+//!
+//! With the `tokio` feature, [TokioTimer] is provided and the routines which spawn tasks, such as
+//! real-time subscriptions and archive backfills, are available. Without it, callers on async-std,
+//! smol, or a custom executor supply their own [Timer]. Requests are made with `reqwest`, whose
+//! connections require a `tokio` reactor, so those callers should run them within a compatibility
+//! layer such as `async-compat`.
+//!
+
+use std::future::Future;
+use std::time::Duration;
+
+/// Provides delays for routines which wait between requests.
+pub trait Timer {
+    /// The future returned by [Timer::sleep].
+    type Sleep: Future<Output = ()>;
+
+    /// Returns a future which completes after the given duration.
+    fn sleep(&self, duration: Duration) -> Self::Sleep;
+}
+
+/// A [Timer] backed by the `tokio` runtime's timer.
+#[cfg(feature = "tokio")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TokioTimer;
+
+#[cfg(feature = "tokio")]
+impl Timer for TokioTimer {
+    type Sleep = tokio::time::Sleep;
+
+    fn sleep(&self, duration: Duration) -> Self::Sleep {
+        tokio::time::sleep(duration)
+    }
+}
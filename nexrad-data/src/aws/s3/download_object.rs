@@ -1,17 +1,20 @@
+use crate::aws::network::NetworkOptions;
 use crate::aws::s3::bucket_object::BucketObject;
 use crate::aws::s3::downloaded_bucket_object::DownloadedBucketObject;
 use crate::result::aws::AWSError;
 use crate::result::aws::AWSError::{S3GetObjectError, S3GetObjectRequestError, S3StreamingError};
 use crate::result::Error;
+use crate::telemetry::{debug, trace};
 use chrono::{DateTime, Utc};
-use log::{debug, trace};
 use reqwest::header::HeaderMap;
 use reqwest::StatusCode;
 
-/// Downloads an object from S3 and returns its contents.
+/// Downloads an object from S3 and returns its contents. The request is attempted once with the
+/// given options' timeouts.
 pub async fn download_object(
     bucket: &str,
     key: &str,
+    options: &NetworkOptions,
 ) -> crate::result::Result<DownloadedBucketObject> {
     debug!(
         "Downloading object key \"{}\" from bucket \"{}\"",
@@ -19,7 +22,12 @@ pub async fn download_object(
     );
     let path = format!("https://{bucket}.s3.amazonaws.com/{key}");
 
-    let response = reqwest::get(path).await.map_err(S3GetObjectRequestError)?;
+    let response = options
+        .client()?
+        .get(path)
+        .send()
+        .await
+        .map_err(S3GetObjectRequestError)?;
     trace!(
         "  Object \"{}\" download response status: {}",
         key,
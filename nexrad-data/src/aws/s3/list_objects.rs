@@ -1,19 +1,22 @@
+use crate::aws::network::NetworkOptions;
 use crate::aws::s3::bucket_list_result::BucketListResult;
 use crate::aws::s3::bucket_object::BucketObject;
 use crate::aws::s3::bucket_object_field::BucketObjectField;
 use crate::result::aws::AWSError;
 use crate::result::aws::AWSError::S3ListObjectsError;
+use crate::telemetry::{debug, trace, warn};
 use chrono::{DateTime, Utc};
-use log::{debug, trace, warn};
 use xml::reader::XmlEvent;
 use xml::EventReader;
 
 /// Lists objects from a S3 bucket with the specified prefix. A maximum number of keys can be
 /// specified to limit the number of objects returned, otherwise it will use AWS's default (1000).
+/// The request is attempted once with the given options' timeouts.
 pub async fn list_objects(
     bucket: &str,
     prefix: &str,
     max_keys: Option<usize>,
+    options: &NetworkOptions,
 ) -> crate::result::Result<BucketListResult> {
     let mut path = format!("https://{bucket}.s3.amazonaws.com?list-type=2&prefix={prefix}");
     if let Some(max_keys) = max_keys {
@@ -24,7 +27,12 @@ pub async fn list_objects(
         bucket, prefix
     );
 
-    let response = reqwest::get(path).await.map_err(S3ListObjectsError)?;
+    let response = options
+        .client()?
+        .get(path)
+        .send()
+        .await
+        .map_err(S3ListObjectsError)?;
     trace!("  List objects response status: {}", response.status());
 
     let body = response.text().await.map_err(S3ListObjectsError)?;
@@ -84,11 +92,9 @@ pub async fn list_objects(
                     }
                 }
             }
-            Ok(XmlEvent::EndElement { name }) => {
-                if name.local_name.as_str() == "Contents" {
-                    if let Some(item) = object.take() {
-                        objects.push(item);
-                    }
+            Ok(XmlEvent::EndElement { name }) if name.local_name.as_str() == "Contents" => {
+                if let Some(item) = object.take() {
+                    objects.push(item);
                 }
             }
             _ => {}
@@ -4,6 +4,13 @@
 //! with functions for downloading both archival and real-time data from open cloud providers like
 //! AWS OpenData.
 //!
+//! The `tokio` feature, enabled by default, provides the AWS routines which spawn tasks, such as
+//! real-time subscriptions and archive backfills, and a `tokio`-backed timer for polling. Without
+//! it, the AWS functions run on any async runtime. See [aws::runtime].
+//!
+//! The `tracing` feature emits diagnostics as `tracing` events within spans identifying the record,
+//! message, or volume being processed, rather than as `log` records.
+//!
 
 #![forbid(unsafe_code)]
 #![deny(clippy::unwrap_used)]
@@ -15,4 +22,9 @@ pub mod aws;
 
 pub mod volume;
 
+#[cfg(feature = "nexrad-model")]
+pub mod uf;
+
 pub mod result;
+
+mod telemetry;
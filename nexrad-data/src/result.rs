@@ -25,15 +25,30 @@ pub enum Error {
     #[cfg(feature = "nexrad-model")]
     #[error("error in common model")]
     Model(#[from] nexrad_model::result::Error),
+    #[cfg(feature = "nexrad-model")]
+    #[error("universal format error: {0}")]
+    UniversalFormatError(String),
     #[cfg(feature = "decode")]
     #[error("compressed data cannot be decoded")]
     CompressedDataError,
     #[cfg(feature = "decode")]
     #[error("volume missing coverage pattern number")]
     MissingCoveragePattern,
+    #[cfg(feature = "decode")]
+    #[error("volume index is invalid or has an unsupported version")]
+    InvalidIndex,
+    #[cfg(feature = "decode")]
+    #[error("volume index does not match the volume file")]
+    IndexMismatch,
+    #[cfg(feature = "bzip2")]
+    #[error("decompressed ldm record exceeds the limit of {0} bytes")]
+    DecompressedRecordTooLarge(usize),
     #[cfg(feature = "bzip2")]
     #[error("ldm record decompression error")]
     DecompressionError(#[from] bzip2::Error),
+    #[cfg(feature = "tokio")]
+    #[error("background task panicked or was cancelled")]
+    TaskError(#[from] tokio::task::JoinError),
 }
 
 #[cfg(feature = "aws")]
@@ -70,5 +85,7 @@ pub mod aws {
         FailedToDetermineNextChunk,
         #[error("error decoding S3 list objects response")]
         S3ListObjectsDecodingError,
+        #[error("error building HTTP client")]
+        HttpClientError(reqwest::Error),
     }
 }
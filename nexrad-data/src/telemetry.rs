@@ -0,0 +1,40 @@
+//!
+//! Diagnostics which are emitted as `tracing` events within structured spans when the `tracing`
+//! feature is enabled, and as `log` records otherwise.
+//!
+
+#[cfg(not(feature = "tracing"))]
+#[allow(unused_imports)]
+pub(crate) use log::{debug, info, trace, warn};
+#[cfg(feature = "tracing")]
+#[allow(unused_imports)]
+pub(crate) use tracing::{debug, info, trace, warn};
+
+/// Enters a debug-level span with the given name and fields for the rest of the enclosing block if
+/// the `tracing` feature is enabled. Otherwise, the fields' values are evaluated and discarded so
+/// that variables used only by the span are not reported as unused.
+#[cfg(feature = "decode")]
+macro_rules! enter_span {
+    ($name:literal $(, $($fields:tt)*)?) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!($name $(, $($fields)*)?).entered();
+        #[cfg(not(feature = "tracing"))]
+        $crate::telemetry::enter_span!(@discard $($($fields)*)?);
+    };
+    (@discard) => {};
+    (@discard $field:ident = ? $value:expr $(, $($rest:tt)*)?) => {
+        let _ = &$value;
+        $crate::telemetry::enter_span!(@discard $($($rest)*)?);
+    };
+    (@discard $field:ident = % $value:expr $(, $($rest:tt)*)?) => {
+        let _ = &$value;
+        $crate::telemetry::enter_span!(@discard $($($rest)*)?);
+    };
+    (@discard $field:ident = $value:expr $(, $($rest:tt)*)?) => {
+        let _ = &$value;
+        $crate::telemetry::enter_span!(@discard $($($rest)*)?);
+    };
+}
+
+#[cfg(feature = "decode")]
+pub(crate) use enter_span;
@@ -0,0 +1,675 @@
+//!
+//! Reading and writing of the radar Universal Format (UF) used by research radars and older
+//! archives, allowing polar data to be exchanged with tools beyond the NEXRAD ecosystem.
+//!
+//! A UF file is a series of records of big-endian 16-bit words, one record per ray. Each record
+//! holds a mandatory header describing the radar and the ray's position and time, a data header
+//! listing the ray's fields, and a field header and gate values for each field. Records may be
+//! stored bare or, as written by FORTRAN programs, wrapped in four-byte length markers; both are
+//! read, and records are written bare.
+//!
+//! UF fields are mapped to the common model's moments by name: `DZ` reflectivity, `VR` or `VE`
+//! velocity, `SW` spectrum width, `DR` or `ZD` differential reflectivity, `PH` differential phase,
+//! `RH` correlation coefficient, and `KD` specific differential phase. Other fields are read as
+//! extension moments. The common model stores 8-bit gate values, so each field's values are
+//! requantized to 253 levels spanning the ray's range of values for that field.
+//!
+
+use crate::result::{Error, Result};
+use chrono::{DateTime, Datelike, NaiveDate, Timelike, Utc};
+use nexrad_model::data::{ExtensionMoment, MomentData, MomentValue, Radial, RadialStatus, Sweep};
+use nexrad_model::meta::Site;
+
+/// The value marking a deleted or missing word or gate.
+const MISSING: i16 = -32768;
+
+/// The number of words in a record's mandatory header.
+const MANDATORY_HEADER_WORDS: usize = 45;
+
+/// The number of words in a field header, excluding any field-specific words.
+const FIELD_HEADER_WORDS: usize = 19;
+
+/// The UF field names written for each of the common model's moments with their scale factors,
+/// along with alternate names accepted when reading.
+const MOMENT_FIELDS: [MomentField; 7] = [
+    MomentField {
+        names: &[*b"DZ"],
+        scale: 100,
+        moment: Radial::reflectivity,
+    },
+    MomentField {
+        names: &[*b"VR", *b"VE"],
+        scale: 100,
+        moment: Radial::velocity,
+    },
+    MomentField {
+        names: &[*b"SW"],
+        scale: 100,
+        moment: Radial::spectrum_width,
+    },
+    MomentField {
+        names: &[*b"DR", *b"ZD"],
+        scale: 100,
+        moment: Radial::differential_reflectivity,
+    },
+    MomentField {
+        names: &[*b"PH"],
+        scale: 10,
+        moment: Radial::differential_phase,
+    },
+    MomentField {
+        names: &[*b"RH"],
+        scale: 100,
+        moment: Radial::correlation_coefficient,
+    },
+    MomentField {
+        names: &[*b"KD"],
+        scale: 100,
+        moment: Radial::specific_differential_phase,
+    },
+];
+
+/// The scale factor written for extension moments.
+const EXTENSION_SCALE: i16 = 100;
+
+/// A UF field corresponding to one of the common model's moments.
+struct MomentField {
+    names: &'static [[u8; 2]],
+    scale: i16,
+    moment: fn(&Radial) -> Option<&MomentData>,
+}
+
+/// A volume read from a UF file: the radar site described by its records and its sweeps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Volume {
+    /// The radar site, identified by the first four characters of the records' radar name. UF does
+    /// not distinguish the feedhorn's height, so the antenna's height is the site's height.
+    pub site: Site,
+    /// The volume's sweeps in order of their sweep numbers' appearance.
+    pub sweeps: Vec<Sweep>,
+}
+
+/// Decodes a UF file's records into the common model, grouping consecutive rays with the same
+/// sweep number into sweeps.
+pub fn decode_volume(data: &[u8]) -> Result<Volume> {
+    let mut site = None;
+    let mut sweeps: Vec<(i16, Vec<Ray>)> = Vec::new();
+    for record in split_records(data)? {
+        let record = Record(record);
+        if site.is_none() {
+            site = Some(record.site()?);
+        }
+
+        let ray = record.ray()?;
+        match sweeps.last_mut() {
+            Some((sweep_number, rays)) if *sweep_number == ray.sweep_number => rays.push(ray),
+            _ => sweeps.push((ray.sweep_number, vec![ray])),
+        }
+    }
+
+    let site = site.ok_or_else(|| uf_error("file contains no records"))?;
+    let sweeps = sweeps
+        .into_iter()
+        .map(|(sweep_number, rays)| {
+            let elevation_number = u8::try_from(sweep_number)
+                .map_err(|_| uf_error(format!("invalid sweep number {}", sweep_number)))?;
+            Ok(Sweep::new(
+                elevation_number,
+                radials(elevation_number, rays),
+            ))
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(Volume { site, sweeps })
+}
+
+/// Encodes the given sweeps as a UF file of bare records, one per radial, describing the given
+/// radar site. Gates which are below threshold, range folded, or missing are written as missing.
+pub fn encode_volume(site: &Site, sweeps: &[Sweep]) -> Result<Vec<u8>> {
+    let mut data = Vec::new();
+    let mut ray_number = 0;
+    for sweep in sweeps {
+        let fixed_angle = sweep
+            .cut()
+            .map(|cut| cut.elevation_angle_degrees())
+            .or_else(|| sweep.radials().first().map(Radial::elevation_angle_degrees))
+            .unwrap_or_default();
+
+        for radial in sweep.radials() {
+            ray_number += 1;
+            let words = encode_ray(
+                site,
+                sweep.elevation_number(),
+                fixed_angle,
+                ray_number,
+                radial,
+            )?;
+            data.extend(words.iter().flat_map(|word| word.to_be_bytes()));
+        }
+    }
+
+    Ok(data)
+}
+
+/// A ray decoded from a record, before its position within its sweep is known.
+struct Ray {
+    sweep_number: i16,
+    collection_timestamp: i64,
+    azimuth_angle_degrees: f32,
+    elevation_angle_degrees: f32,
+    fields: Vec<([u8; 2], MomentData)>,
+}
+
+/// Converts a sweep's rays to radials, numbering them and marking the sweep's first and last.
+fn radials(elevation_number: u8, rays: Vec<Ray>) -> Vec<Radial> {
+    let ray_count = rays.len();
+    let azimuth_spacing_degrees = 360.0 / ray_count.max(1) as f32;
+
+    rays.into_iter()
+        .enumerate()
+        .map(|(index, mut ray)| {
+            let radial_status = if index == 0 {
+                RadialStatus::ElevationStart
+            } else if index + 1 == ray_count {
+                RadialStatus::ElevationEnd
+            } else {
+                RadialStatus::IntermediateRadialData
+            };
+
+            let mut take = |field: &MomentField| {
+                let index = ray
+                    .fields
+                    .iter()
+                    .position(|(name, _)| field.names.contains(name))?;
+                Some(ray.fields.remove(index).1)
+            };
+            let [
+                reflectivity,
+                velocity,
+                spectrum_width,
+                differential_reflectivity,
+                differential_phase,
+                correlation_coefficient,
+                specific_differential_phase,
+            ] = MOMENT_FIELDS.each_ref().map(&mut take);
+
+            let extension_moments = ray
+                .fields
+                .into_iter()
+                .map(|(name, data)| {
+                    ExtensionMoment::new(String::from_utf8_lossy(&name).trim().to_string(), data)
+                })
+                .collect();
+
+            Radial::new(
+                ray.collection_timestamp,
+                index as u16 + 1,
+                ray.azimuth_angle_degrees,
+                azimuth_spacing_degrees,
+                radial_status,
+                elevation_number,
+                ray.elevation_angle_degrees,
+                reflectivity,
+                velocity,
+                spectrum_width,
+                differential_reflectivity,
+                differential_phase,
+                correlation_coefficient,
+                specific_differential_phase,
+            )
+            .with_extension_moments(extension_moments)
+        })
+        .collect()
+}
+
+/// Splits a UF file into its records, which may be bare or wrapped in four-byte length markers.
+fn split_records(data: &[u8]) -> Result<Vec<&[u8]>> {
+    let mut records = Vec::new();
+    let mut position = 0;
+    while position < data.len() {
+        let remaining = &data[position..];
+        if remaining.starts_with(b"UF") {
+            let length = be_word(remaining, 1)? as u16 as usize * 2;
+            let record = remaining
+                .get(..length)
+                .filter(|record| record.len() >= MANDATORY_HEADER_WORDS * 2)
+                .ok_or_else(|| uf_error(format!("truncated record at offset {}", position)))?;
+            records.push(record);
+            position += length;
+        } else if remaining.get(4..6) == Some(b"UF") {
+            let mut length = [0; 4];
+            length.copy_from_slice(&remaining[..4]);
+            let length = u32::from_be_bytes(length) as usize;
+            let record = remaining
+                .get(4..4 + length)
+                .filter(|record| record.len() >= MANDATORY_HEADER_WORDS * 2)
+                .ok_or_else(|| uf_error(format!("truncated record at offset {}", position)))?;
+            records.push(record);
+            position += length + 8;
+        } else {
+            return Err(uf_error(format!("no record found at offset {}", position)));
+        }
+    }
+
+    Ok(records)
+}
+
+/// A single UF record's data.
+struct Record<'a>(&'a [u8]);
+
+impl Record<'_> {
+    /// The word at the given 1-based position within the record.
+    fn word(&self, position: usize) -> Result<i16> {
+        position
+            .checked_sub(1)
+            .ok_or_else(|| uf_error("invalid word position 0"))
+            .and_then(|index| be_word(self.0, index))
+    }
+
+    /// The unsigned word at the given 1-based position within the record, such as a count or the
+    /// position of another word.
+    fn unsigned_word(&self, position: usize) -> Result<usize> {
+        Ok(self.word(position)? as u16 as usize)
+    }
+
+    /// The characters of the given number of words starting at the given 1-based position.
+    fn characters(&self, position: usize, word_count: usize) -> Result<Vec<u8>> {
+        let mut characters = Vec::with_capacity(word_count * 2);
+        for offset in 0..word_count {
+            characters.extend(self.word(position + offset)?.to_be_bytes());
+        }
+
+        Ok(characters)
+    }
+
+    /// An angle encoded as degrees, minutes, and 64ths of seconds at the given position.
+    fn degrees_minutes_seconds(&self, position: usize) -> Result<f64> {
+        Ok(self.word(position)? as f64
+            + self.word(position + 1)? as f64 / 60.0
+            + self.word(position + 2)? as f64 / 64.0 / 3600.0)
+    }
+
+    /// The radar site described by the record's mandatory header.
+    fn site(&self) -> Result<Site> {
+        let mut identifier = *b"    ";
+        for (byte, character) in identifier.iter_mut().zip(self.characters(11, 2)?) {
+            *byte = character;
+        }
+
+        Ok(Site::new(
+            identifier,
+            self.degrees_minutes_seconds(19)? as f32,
+            self.degrees_minutes_seconds(22)? as f32,
+            self.word(25)?,
+            0,
+        ))
+    }
+
+    /// The ray described by the record's mandatory header, data header, and fields.
+    fn ray(&self) -> Result<Ray> {
+        let data_header = self.unsigned_word(5)?;
+        let field_count = self.unsigned_word(data_header + 2)?;
+
+        let mut fields = Vec::with_capacity(field_count);
+        for field_index in 0..field_count {
+            let name_position = data_header + 3 + field_index * 2;
+            let mut name = [0; 2];
+            name.copy_from_slice(&self.characters(name_position, 1)?);
+
+            let field_header = self.unsigned_word(name_position + 1)?;
+            fields.push((name, self.field(field_header)?));
+        }
+
+        Ok(Ray {
+            sweep_number: self.word(10)?,
+            collection_timestamp: self.collection_timestamp()?,
+            azimuth_angle_degrees: self.word(33)? as f32 / 64.0,
+            elevation_angle_degrees: self.word(34)? as f32 / 64.0,
+            fields,
+        })
+    }
+
+    /// The ray's collection time in milliseconds since the epoch. Two-digit years before 70 are
+    /// taken to be in the 21st century.
+    fn collection_timestamp(&self) -> Result<i64> {
+        let year = match self.word(26)? as i32 {
+            year @ 0..=69 => year + 2000,
+            year @ 70..=99 => year + 1900,
+            year => year,
+        };
+
+        let [month, day, hour, minute, second] = [
+            self.word(27)?,
+            self.word(28)?,
+            self.word(29)?,
+            self.word(30)?,
+            self.word(31)?,
+        ]
+        .map(|word| word as u32);
+
+        NaiveDate::from_ymd_opt(year, month, day)
+            .and_then(|date| date.and_hms_opt(hour, minute, second))
+            .map(|time| DateTime::<Utc>::from_naive_utc_and_offset(time, Utc).timestamp_millis())
+            .ok_or_else(|| uf_error("invalid ray date and time"))
+    }
+
+    /// Decodes the field whose header is at the given position into moment data.
+    fn field(&self, field_header: usize) -> Result<MomentData> {
+        let data_position = self.unsigned_word(field_header)?;
+        let scale = self.word(field_header + 1)?;
+        let first_gate_meters =
+            self.word(field_header + 2)? as f32 * 1000.0 + self.word(field_header + 3)? as f32;
+        let gate_spacing_meters = self.word(field_header + 4)?;
+        let gate_count = self.unsigned_word(field_header + 5)?;
+
+        let scale = if scale > 0 { scale as f32 } else { 1.0 };
+        let missing = self.word(MANDATORY_HEADER_WORDS)?;
+        let values = (0..gate_count)
+            .map(|gate| {
+                let word = self.word(data_position + gate)?;
+                Ok((word != missing && word != MISSING).then_some(word as f32 / scale))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let moment = quantize(&values);
+        Ok(if gate_spacing_meters > 0 {
+            moment.with_gate_range(
+                first_gate_meters / 1000.0,
+                gate_spacing_meters as f32 / 1000.0,
+            )
+        } else {
+            moment
+        })
+    }
+}
+
+/// Encodes values as 8-bit moment data with 253 levels spanning their range, reserving the raw
+/// values for below threshold and range folded. Gates without a value are marked missing.
+fn quantize(values: &[Option<f32>]) -> MomentData {
+    let (min, max) = values
+        .iter()
+        .flatten()
+        .fold((f32::MAX, f32::MIN), |(min, max), value| {
+            (min.min(*value), max.max(*value))
+        });
+
+    let scale = if max > min { 253.0 / (max - min) } else { 1.0 };
+    let offset = 2.0 - min * scale;
+    let raw_values = values
+        .iter()
+        .map(|value| match value {
+            Some(value) => (value * scale + offset).round().clamp(2.0, 255.0) as u8,
+            None => 0,
+        })
+        .collect();
+    let missing = values
+        .iter()
+        .enumerate()
+        .filter_map(|(index, value)| value.is_none().then_some(index));
+
+    MomentData::from_fixed_point(scale, offset, raw_values).with_missing_gates(missing)
+}
+
+/// Encodes a radial as a UF record's words.
+fn encode_ray(
+    site: &Site,
+    sweep_number: u8,
+    fixed_angle_degrees: f32,
+    ray_number: usize,
+    radial: &Radial,
+) -> Result<Vec<i16>> {
+    let time = DateTime::from_timestamp_millis(radial.collection_timestamp())
+        .ok_or_else(|| uf_error("radial collection time is out of range"))?;
+
+    let mut fields: Vec<([u8; 2], i16, &MomentData)> = MOMENT_FIELDS
+        .iter()
+        .filter_map(|field| Some((field.names[0], field.scale, (field.moment)(radial)?)))
+        .collect();
+    for extension in radial.extension_moments() {
+        let mut name = *b"  ";
+        for (byte, character) in name.iter_mut().zip(extension.name().bytes()) {
+            *byte = character;
+        }
+        fields.push((name, EXTENSION_SCALE, extension.data()));
+    }
+
+    let mut words = vec![MISSING; MANDATORY_HEADER_WORDS];
+    let data_header = MANDATORY_HEADER_WORDS + 1;
+
+    words[0] = i16::from_be_bytes(*b"UF");
+    // With no optional or local use headers, each points to the data header
+    words[2] = data_header as i16;
+    words[3] = data_header as i16;
+    words[4] = data_header as i16;
+    words[5] = ray_number as i16;
+    words[6] = 1;
+    words[7] = ray_number as i16;
+    words[8] = 1;
+    words[9] = sweep_number as i16;
+    set_characters(&mut words, 11, site.identifier());
+    set_characters(&mut words, 13, b"    ");
+    set_characters(&mut words, 15, site.identifier());
+    set_characters(&mut words, 17, b"    ");
+    set_degrees_minutes_seconds(&mut words, 19, site.latitude() as f64);
+    set_degrees_minutes_seconds(&mut words, 22, site.longitude() as f64);
+    words[24] = site
+        .height_meters()
+        .saturating_add(site.feedhorn_height_meters() as i16);
+    words[25] = (time.year() % 100) as i16;
+    words[26] = time.month() as i16;
+    words[27] = time.day() as i16;
+    words[28] = time.hour() as i16;
+    words[29] = time.minute() as i16;
+    words[30] = time.second() as i16;
+    set_characters(&mut words, 32, b"UT");
+    words[32] = encode_degrees(radial.azimuth_angle_degrees());
+    words[33] = encode_degrees(radial.elevation_angle_degrees());
+    words[34] = 1; // Plan position indicator (PPI) sweep mode
+    words[35] = encode_degrees(fixed_angle_degrees);
+    words[44] = MISSING;
+
+    words.push(fields.len() as i16);
+    words.push(1);
+    words.push(fields.len() as i16);
+    let field_directory = words.len();
+    words.resize(field_directory + fields.len() * 2, 0);
+
+    for (field_index, (name, scale, moment)) in fields.into_iter().enumerate() {
+        let field_header = words.len() + 1;
+        set_characters(&mut words, field_directory + field_index * 2 + 1, &name);
+        words[field_directory + field_index * 2 + 1] = field_header as i16;
+
+        let first_gate_meters = (moment.first_gate_range_km().unwrap_or_default() * 1000.0).round();
+        let gate_spacing_meters = (moment.gate_interval_km().unwrap_or_default() * 1000.0).round();
+        let values = moment.values();
+
+        let mut header = vec![MISSING; FIELD_HEADER_WORDS];
+        header[0] = (field_header + FIELD_HEADER_WORDS) as i16;
+        header[1] = scale;
+        header[2] = (first_gate_meters / 1000.0).trunc() as i16;
+        header[3] = (first_gate_meters % 1000.0) as i16;
+        header[4] = gate_spacing_meters as i16;
+        header[5] = values.len() as i16;
+        header[18] = 16;
+        words.extend(header);
+
+        words.extend(values.into_iter().map(|value| {
+            match value {
+                MomentValue::Value(value) => (value * scale as f32)
+                    .round()
+                    .clamp(-(i16::MAX as f32), i16::MAX as f32)
+                    as i16,
+                MomentValue::BelowThreshold | MomentValue::RangeFolded | MomentValue::Missing => {
+                    MISSING
+                }
+            }
+        }));
+    }
+
+    let record_length = u16::try_from(words.len())
+        .map_err(|_| uf_error("radial exceeds the maximum record length"))?;
+    words[1] = record_length as i16;
+
+    Ok(words)
+}
+
+/// Writes characters into consecutive words starting at the given 1-based position.
+fn set_characters(words: &mut [i16], position: usize, characters: &[u8]) {
+    for (index, pair) in characters.chunks(2).enumerate() {
+        let high = pair[0];
+        let low = pair.get(1).copied().unwrap_or(b' ');
+        if let Some(word) = words.get_mut(position - 1 + index) {
+            *word = i16::from_be_bytes([high, low]);
+        }
+    }
+}
+
+/// Writes an angle as degrees, minutes, and 64ths of seconds starting at the given 1-based
+/// position. Each component carries the angle's sign.
+fn set_degrees_minutes_seconds(words: &mut [i16], position: usize, degrees: f64) {
+    let total_seconds = (degrees * 3600.0 * 64.0).round() / 64.0;
+    let whole_degrees = (total_seconds / 3600.0).trunc();
+    let minutes = ((total_seconds - whole_degrees * 3600.0) / 60.0).trunc();
+    let seconds = total_seconds - whole_degrees * 3600.0 - minutes * 60.0;
+
+    words[position - 1] = whole_degrees as i16;
+    words[position] = minutes as i16;
+    words[position + 1] = (seconds * 64.0).round() as i16;
+}
+
+/// Encodes an angle in 64ths of a degree.
+fn encode_degrees(degrees: f32) -> i16 {
+    (degrees * 64.0).round() as i16
+}
+
+/// The big-endian word at the given 0-based word index.
+fn be_word(data: &[u8], index: usize) -> Result<i16> {
+    data.get(index * 2..index * 2 + 2)
+        .map(|bytes| i16::from_be_bytes([bytes[0], bytes[1]]))
+        .ok_or_else(|| uf_error(format!("record ends before word {}", index + 1)))
+}
+
+fn uf_error(message: impl Into<String>) -> Error {
+    Error::UniversalFormatError(message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn site() -> Site {
+        Site::new(*b"KTLX", 35.3331, -97.2778, 370, 20)
+    }
+
+    fn sweep(elevation_number: u8) -> Sweep {
+        let radials = (0..4)
+            .map(|index| {
+                let reflectivity = MomentData::from_fixed_point(2.0, 66.0, vec![0, 70, 80, 130])
+                    .with_gate_range(2.125, 0.25);
+                let velocity = MomentData::from_fixed_point(2.0, 129.0, vec![1, 109, 129, 149])
+                    .with_gate_range(2.125, 0.25);
+
+                Radial::new(
+                    1_700_000_000_000 + index as i64 * 1_000,
+                    index + 1,
+                    index as f32 * 90.0,
+                    90.0,
+                    RadialStatus::IntermediateRadialData,
+                    elevation_number,
+                    0.5,
+                    Some(reflectivity),
+                    Some(velocity),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+            })
+            .collect();
+
+        Sweep::new(elevation_number, radials)
+    }
+
+    fn assert_close(actual: Option<MomentValue>, expected: f32) {
+        match actual {
+            Some(MomentValue::Value(actual)) => assert!(
+                (actual - expected).abs() < 0.2,
+                "{} is not close to {}",
+                actual,
+                expected
+            ),
+            _ => panic!("{:?} is not a value", actual),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() -> Result<()> {
+        let data = encode_volume(&site(), &[sweep(1), sweep(2)])?;
+        let volume = decode_volume(&data)?;
+
+        assert_eq!(volume.site.identifier(), b"KTLX");
+        assert!((volume.site.latitude() - 35.3331).abs() < 0.001);
+        assert!((volume.site.longitude() + 97.2778).abs() < 0.001);
+        assert_eq!(volume.site.height_meters(), 390);
+
+        assert_eq!(volume.sweeps.len(), 2);
+        let sweep = &volume.sweeps[1];
+        assert_eq!(sweep.elevation_number(), 2);
+        assert_eq!(sweep.radials().len(), 4);
+
+        let radial = &sweep.radials()[1];
+        assert_eq!(radial.collection_timestamp(), 1_700_000_001_000);
+        assert_eq!(radial.azimuth_angle_degrees(), 90.0);
+        assert_eq!(radial.elevation_angle_degrees(), 0.5);
+        assert_eq!(radial.radial_status(), RadialStatus::IntermediateRadialData);
+        assert_eq!(
+            sweep.radials()[3].radial_status(),
+            RadialStatus::ElevationEnd
+        );
+
+        let reflectivity = radial.reflectivity();
+        assert_eq!(
+            reflectivity.and_then(|moment| moment.value(0)),
+            Some(MomentValue::Missing)
+        );
+        assert_close(reflectivity.and_then(|moment| moment.value(1)), 2.0);
+        assert_close(reflectivity.and_then(|moment| moment.value(3)), 32.0);
+        assert_eq!(
+            reflectivity.and_then(MomentData::first_gate_range_km),
+            Some(2.125)
+        );
+        assert_eq!(
+            reflectivity.and_then(MomentData::gate_interval_km),
+            Some(0.25)
+        );
+
+        let velocity = radial.velocity();
+        assert_eq!(
+            velocity.and_then(|moment| moment.value(0)),
+            Some(MomentValue::Missing)
+        );
+        assert_close(velocity.and_then(|moment| moment.value(1)), -10.0);
+        assert_close(velocity.and_then(|moment| moment.value(3)), 10.0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fortran_blocked_records() -> Result<()> {
+        let bare = encode_volume(&site(), &[sweep(1)])?;
+
+        let mut blocked = Vec::new();
+        for record in split_records(&bare)? {
+            let length = (record.len() as u32).to_be_bytes();
+            blocked.extend_from_slice(&length);
+            blocked.extend_from_slice(record);
+            blocked.extend_from_slice(&length);
+        }
+
+        assert_eq!(decode_volume(&blocked)?, decode_volume(&bare)?);
+        assert!(decode_volume(&blocked[..blocked.len() - 20]).is_err());
+
+        Ok(())
+    }
+}
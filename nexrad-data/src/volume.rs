@@ -16,10 +16,66 @@
 mod file;
 pub use file::*;
 
+mod format;
+pub use format::*;
+
+mod reader;
+pub use reader::*;
+
 mod header;
 pub use header::*;
 
 mod record;
 pub use record::*;
 
+#[cfg(feature = "decode")]
+mod console_feed;
+#[cfg(feature = "decode")]
+pub use console_feed::*;
+
+#[cfg(feature = "decode")]
+mod index;
+#[cfg(feature = "decode")]
+pub use index::*;
+
+#[cfg(feature = "decode")]
+mod locate;
+#[cfg(feature = "decode")]
+pub use locate::*;
+
+#[cfg(all(feature = "nexrad-model", feature = "decode"))]
+mod salvage;
+#[cfg(all(feature = "nexrad-model", feature = "decode"))]
+pub use salvage::*;
+
+#[cfg(all(feature = "nexrad-model", feature = "decode"))]
+mod stream;
+#[cfg(all(feature = "nexrad-model", feature = "decode"))]
+pub use stream::*;
+
+#[cfg(all(feature = "nexrad-model", feature = "decode"))]
+mod site_check;
+#[cfg(all(feature = "nexrad-model", feature = "decode"))]
+pub use site_check::*;
+
+#[cfg(feature = "decode")]
+mod status_timeline;
+#[cfg(feature = "decode")]
+pub use status_timeline::*;
+
+#[cfg(feature = "decode")]
+mod subset;
+#[cfg(feature = "decode")]
+pub use subset::*;
+
+#[cfg(feature = "decode")]
+mod synthetic;
+#[cfg(feature = "decode")]
+pub use synthetic::*;
+
+#[cfg(all(feature = "nexrad-model", feature = "decode"))]
+mod timestamps;
+#[cfg(all(feature = "nexrad-model", feature = "decode"))]
+pub use timestamps::*;
+
 mod util;
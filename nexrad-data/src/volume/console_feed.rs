@@ -0,0 +1,116 @@
+use crate::result::Result;
+use crate::volume::File;
+use nexrad_decode::limits::DecodeLimits;
+use nexrad_decode::messages::console_message::{console_entries, ConsoleEntry};
+use nexrad_decode::messages::MessageWithHeader;
+
+/// A chronological feed of the console messages exchanged between the RDA and RPG operators,
+/// gathered across many volumes or real-time chunks for display in operational monitoring tools.
+/// Each entry is tagged with whether it originated at the RDA or the RPG.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConsoleFeed {
+    entries: Vec<ConsoleEntry>,
+}
+
+impl ConsoleFeed {
+    /// Creates a new, empty console feed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the console messages from each of the given volume files to the feed.
+    pub fn from_files<'a>(files: impl IntoIterator<Item = &'a File>) -> Result<Self> {
+        let mut feed = Self::new();
+        for file in files {
+            feed.push_file(file)?;
+        }
+
+        Ok(feed)
+    }
+
+    /// Adds the console messages from the given volume file to the feed.
+    pub fn push_file(&mut self, file: &File) -> Result<()> {
+        self.push_file_with_limits(file, &DecodeLimits::new())
+    }
+
+    /// Adds the console messages from the given volume file to the feed, decoding its records
+    /// within the given limits.
+    pub fn push_file_with_limits(&mut self, file: &File, limits: &DecodeLimits) -> Result<()> {
+        for record in file.records() {
+            let record = if record.compressed() {
+                record.decompress_with_limits(limits)?
+            } else {
+                record
+            };
+
+            self.push_messages(&record.messages_with_limits(limits)?);
+        }
+
+        Ok(())
+    }
+
+    /// Adds the console messages among the given messages to the feed, for example those decoded
+    /// from a real-time chunk.
+    pub fn push_messages(&mut self, messages: &[MessageWithHeader]) {
+        for entry in console_entries(messages) {
+            self.push(entry);
+        }
+    }
+
+    /// Adds an entry to the feed, keeping the feed ordered by time. Entries without a valid time
+    /// are ordered first.
+    pub fn push(&mut self, entry: ConsoleEntry) {
+        let index = self
+            .entries
+            .partition_point(|existing| existing.date_time <= entry.date_time);
+        self.entries.insert(index, entry);
+    }
+
+    /// The feed's entries in chronological order.
+    pub fn entries(&self) -> &[ConsoleEntry] {
+        &self.entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nexrad_decode::messages::console_message::{self, Origin};
+    use nexrad_decode::messages::message_header::MessageHeader;
+    use nexrad_decode::messages::{Message, MessageType};
+
+    fn console_message(
+        message_type: MessageType,
+        time: u32,
+        text: &str,
+    ) -> MessageWithHeader<'static> {
+        MessageWithHeader {
+            header: MessageHeader::new(message_type, 0, 0, 19724, time, 0, 1, 1),
+            message: Message::ConsoleMessage(Box::new(console_message::Message {
+                text: text.to_string(),
+            })),
+            byte_range: None,
+        }
+    }
+
+    #[test]
+    fn test_feed_orders_and_tags_origin() {
+        let mut feed = ConsoleFeed::new();
+        feed.push_messages(&[console_message(
+            MessageType::RPGConsoleMessage,
+            120_000,
+            "RESTARTING",
+        )]);
+        feed.push_messages(&[console_message(
+            MessageType::RDAConsoleMessage,
+            60_000,
+            "TRANSMITTER DOWN",
+        )]);
+
+        let entries = feed.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].origin, Origin::RDA);
+        assert_eq!(entries[0].text, "TRANSMITTER DOWN");
+        assert_eq!(entries[1].origin, Origin::RPG);
+    }
+}
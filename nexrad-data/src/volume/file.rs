@@ -1,67 +1,95 @@
+#[cfg(all(feature = "serde", feature = "bincode"))]
 use crate::result::Result;
 use crate::volume::{split_compressed_records, Header, Record};
+use chrono::{DateTime, Utc};
 use std::fmt::Debug;
 
+/// The name and version of this crate, recorded as the decoder in scans' provenance.
+#[cfg(all(feature = "nexrad-model", feature = "decode"))]
+const DECODER: &str = concat!(env!("CARGO_PKG_NAME"), " ", env!("CARGO_PKG_VERSION"));
+
 /// A NEXRAD Archive II volume data file.
 #[derive(Clone, PartialEq, Eq, Hash)]
-pub struct File(Vec<u8>);
+pub struct File {
+    data: Vec<u8>,
+    source: Option<String>,
+    retrieved_time: Option<DateTime<Utc>>,
+}
 
 impl File {
     /// Creates a new Archive II volume file with the provided data.
     pub fn new(data: Vec<u8>) -> Self {
-        Self(data)
+        Self {
+            data,
+            source: None,
+            retrieved_time: None,
+        }
+    }
+
+    /// Sets the location this file was retrieved from, e.g. an S3 URI, and the time it was
+    /// retrieved, which are recorded in the provenance of scans decoded from it.
+    pub fn with_source(mut self, source: impl Into<String>, retrieved_time: DateTime<Utc>) -> Self {
+        self.source = Some(source.into());
+        self.retrieved_time = Some(retrieved_time);
+        self
     }
 
     /// The file's encoded and compressed data.
     pub fn data(&self) -> &Vec<u8> {
-        &self.0
+        &self.data
+    }
+
+    /// The location this file was retrieved from, if known.
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+
+    /// The time this file was retrieved, if known.
+    pub fn retrieved_time(&self) -> Option<DateTime<Utc>> {
+        self.retrieved_time
     }
 
     /// The file's decoded Archive II volume header.
     #[cfg(all(feature = "serde", feature = "bincode"))]
     pub fn header(&self) -> Result<Header> {
-        Header::deserialize(&mut self.0.as_slice())
+        Header::deserialize(&mut self.data.as_slice())
     }
 
-    /// The file's LDM records.
-    pub fn records(&self) -> Vec<Record> {
-        split_compressed_records(&self.0[size_of::<Header>()..])
+    /// The file's LDM records in the order they appear in the file.
+    pub fn records(&self) -> Vec<Record<'_>> {
+        split_compressed_records(self.data.get(size_of::<Header>()..).unwrap_or_default())
     }
 
     /// Decodes this volume file into a common model scan containing sweeps and radials with moment
-    /// data.
+    /// data. The scan's provenance records this file's source and this crate as its decoder.
+    ///
+    /// Sweeps and radials are kept in the order they appear in the file, which is the order they
+    /// were collected, and are indexed by their positions. See [nexrad_model::data::Sweep::index]
+    /// and [nexrad_model::data::Radial::index].
     #[cfg(all(feature = "nexrad-model", feature = "decode"))]
     pub fn scan(&self) -> Result<nexrad_model::data::Scan> {
-        use crate::result::Error;
-        use nexrad_decode::messages::Message;
-        use nexrad_model::data::{Scan, Sweep};
+        use crate::volume::StreamOptions;
+        use nexrad_model::data::Scan;
 
-        let mut coverage_pattern_number = None;
-        let mut radials = Vec::new();
-        for mut record in self.records() {
-            if record.compressed() {
-                record = record.decompress()?;
-            }
+        let mut sweeps = Vec::new();
+        let coverage_pattern_number =
+            self.stream_sweeps(&StreamOptions::new(), |sweep| sweeps.push(sweep))?;
 
-            let messages = record.messages()?;
-            for message in messages {
-                if let Message::DigitalRadarData(radar_data_message) = message.message {
-                    if coverage_pattern_number.is_none() {
-                        if let Some(volume_block) = &radar_data_message.volume_data_block {
-                            coverage_pattern_number =
-                                Some(volume_block.volume_coverage_pattern_number);
-                        }
-                    }
-
-                    radials.push(radar_data_message.into_radial()?);
-                }
-            }
+        Ok(Scan::new(coverage_pattern_number, sweeps).with_provenance(self.provenance()))
+    }
+
+    /// The provenance of data decoded from this file.
+    #[cfg(all(feature = "nexrad-model", feature = "decode"))]
+    pub fn provenance(&self) -> nexrad_model::provenance::Provenance {
+        let mut provenance = nexrad_model::provenance::Provenance::new().with_decoder(DECODER);
+        if let Some(source) = &self.source {
+            provenance = provenance.with_source(source.clone());
+        }
+        if let Some(retrieved_time) = self.retrieved_time {
+            provenance = provenance.with_retrieved_timestamp(retrieved_time.timestamp_millis());
         }
 
-        Ok(Scan::new(
-            coverage_pattern_number.ok_or(Error::MissingCoveragePattern)?,
-            Sweep::from_radials(radials),
-        ))
+        provenance
     }
 }
 
@@ -69,6 +97,8 @@ impl Debug for File {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut debug = f.debug_struct("File");
         debug.field("data.len()", &self.data().len());
+        debug.field("source", &self.source());
+        debug.field("retrieved_time", &self.retrieved_time());
 
         #[cfg(all(feature = "serde", feature = "bincode"))]
         debug.field("header", &self.header());
@@ -79,3 +109,79 @@ impl Debug for File {
         debug.finish()
     }
 }
+
+#[cfg(all(test, feature = "nexrad-model", feature = "decode"))]
+mod tests {
+    use super::*;
+    use crate::volume::SyntheticVolume;
+
+    #[test]
+    fn test_scan_provenance() -> Result<()> {
+        let retrieved_time = DateTime::from_timestamp_millis(1_715_000_000_000).unwrap_or_default();
+        let file = SyntheticVolume::new()
+            .with_elevation_count(1)
+            .generate()?
+            .with_source("s3://bucket/key", retrieved_time);
+
+        let scan = file.scan()?;
+        let provenance = scan.provenance();
+        assert_eq!(
+            provenance.and_then(|provenance| provenance.source()),
+            Some("s3://bucket/key")
+        );
+        assert_eq!(
+            provenance.and_then(|provenance| provenance.retrieved_timestamp()),
+            Some(1_715_000_000_000)
+        );
+        assert_eq!(
+            provenance.and_then(|provenance| provenance.decoder()),
+            Some(DECODER)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_scan_ordering() -> Result<()> {
+        use nexrad_decode::messages::Message;
+
+        let file = SyntheticVolume::new()
+            .with_elevation_count(3)
+            .with_radials_per_sweep(90)
+            .with_gate_count(10)
+            .generate()?;
+
+        // Radar data messages in record and stream order
+        let mut message_radials = Vec::new();
+        for record in file.records() {
+            let record = if record.compressed() {
+                record.decompress()?
+            } else {
+                record
+            };
+            for message in record.messages()? {
+                if let Message::DigitalRadarData(message) = message.message {
+                    message_radials.push((
+                        message.header.elevation_number,
+                        message.header.azimuth_number,
+                    ));
+                }
+            }
+        }
+
+        let scan = file.scan()?;
+        let mut scan_radials = Vec::new();
+        for (sweep_position, sweep) in scan.sweeps().iter().enumerate() {
+            assert_eq!(sweep.index(), sweep_position);
+            for (radial_position, radial) in sweep.radials().iter().enumerate() {
+                assert_eq!(radial.index(), radial_position);
+                scan_radials.push((radial.elevation_number(), radial.azimuth_number()));
+            }
+        }
+
+        assert_eq!(scan_radials.len(), 270);
+        assert_eq!(scan_radials, message_radials);
+
+        Ok(())
+    }
+}
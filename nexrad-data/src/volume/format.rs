@@ -0,0 +1,89 @@
+/// The kind of content recognized by [detect_format], for routing files to the appropriate decoder
+/// before attempting a full decode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FormatKind {
+    /// An Archive II volume file beginning with a volume header, such as an archival volume or a
+    /// real-time "start" chunk. See [crate::volume::File].
+    ArchiveII,
+    /// A standalone bzip2-compressed LDM record, such as a real-time "intermediate" or "end" chunk.
+    /// See [crate::volume::Record].
+    ChunkRecord,
+    /// Gzip-compressed content, as some legacy archival volumes are distributed. The content must
+    /// be decompressed and detected again.
+    Gzip,
+    /// A Level III (NIDS) product, either beginning with its WMO header or with its message header.
+    LevelIII,
+    /// Content in an unrecognized format.
+    Unknown,
+}
+
+/// Detects the format of the given data from its leading bytes. Only the data's first few dozen
+/// bytes are inspected, so this does not guarantee the data will decode successfully.
+pub fn detect_format(data: &[u8]) -> FormatKind {
+    if data.starts_with(b"AR2") || data.starts_with(b"ARCHIVE2") {
+        return FormatKind::ArchiveII;
+    }
+
+    if data.get(4..6) == Some(b"BZ") {
+        return FormatKind::ChunkRecord;
+    }
+
+    if data.starts_with(&[0x1f, 0x8b]) {
+        return FormatKind::Gzip;
+    }
+
+    if has_level_iii_wmo_header(data) || has_level_iii_message_header(data) {
+        return FormatKind::LevelIII;
+    }
+
+    FormatKind::Unknown
+}
+
+/// Whether the data begins with a WMO header for a radar product, optionally preceded by the start
+/// of message marker and sequence number added by NOAAPort.
+fn has_level_iii_wmo_header(data: &[u8]) -> bool {
+    let header_start = data
+        .iter()
+        .take(16)
+        .position(|byte| !matches!(byte, 0x01 | b'\r' | b'\n' | b' ' | b'0'..=b'9'))
+        .unwrap_or(0);
+
+    data[header_start..].starts_with(b"SDUS")
+}
+
+/// Whether the data begins with a Level III message header followed by a product description block,
+/// identified by a product message code and the block's divider.
+fn has_level_iii_message_header(data: &[u8]) -> bool {
+    let (Some(code), Some(divider)) = (data.get(0..2), data.get(18..20)) else {
+        return false;
+    };
+
+    let code = i16::from_be_bytes([code[0], code[1]]);
+    (16..=211).contains(&code) && divider == [0xff, 0xff]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_format() {
+        let mut record = 100i32.to_be_bytes().to_vec();
+        record.extend_from_slice(b"BZh91AY&SY");
+
+        let mut message = vec![0; 30];
+        message[0..2].copy_from_slice(&94i16.to_be_bytes());
+        message[18..20].copy_from_slice(&[0xff, 0xff]);
+
+        assert_eq!(detect_format(b"AR2V0006.001"), FormatKind::ArchiveII);
+        assert_eq!(detect_format(&record), FormatKind::ChunkRecord);
+        assert_eq!(detect_format(&[0x1f, 0x8b, 0x08, 0x00]), FormatKind::Gzip);
+        assert_eq!(
+            detect_format(b"\x01\r\r\n123 \r\r\nSDUS54 KOUN 011200\r\r\nN0QTLX\r\r\n"),
+            FormatKind::LevelIII
+        );
+        assert_eq!(detect_format(&message), FormatKind::LevelIII);
+        assert_eq!(detect_format(b"<html>"), FormatKind::Unknown);
+        assert_eq!(detect_format(&[]), FormatKind::Unknown);
+    }
+}
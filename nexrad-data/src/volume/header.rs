@@ -1,8 +1,10 @@
+#[cfg(all(feature = "serde", feature = "bincode"))]
 use crate::result::Result;
 use crate::volume::util::get_datetime;
 use chrono::{DateTime, Duration, Utc};
 use std::fmt;
 use std::fmt::{Debug, Formatter};
+#[cfg(all(feature = "serde", feature = "bincode"))]
 use std::io::Read;
 
 /// Header for an Archive II volume file containing metadata about the radar data. This header is
@@ -0,0 +1,460 @@
+use crate::result::{Error, Result};
+use crate::telemetry::enter_span;
+use crate::volume::{record_from_range, split_compressed_record_ranges, File, Header, Record};
+use nexrad_decode::limits::DecodeLimits;
+use nexrad_decode::messages::{Message, MessageType};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// The magic bytes identifying a volume index sidecar.
+const INDEX_MAGIC: &[u8; 5] = b"NXIDX";
+
+/// The current version of the volume index sidecar format.
+const INDEX_VERSION: u8 = 1;
+
+/// The largest volume index sidecar that will be read in bytes, far larger than the index of any
+/// operational volume, so that a corrupt length cannot cause an unbounded allocation.
+const MAX_INDEX_BYTES: u64 = 16 * 1024 * 1024;
+
+/// An index of an Archive II volume file's LDM records. The index describes where each record is
+/// located in the file along with a summary of the messages it contains, allowing the records
+/// containing a desired elevation or message type to be located and decoded without decompressing
+/// the entire volume.
+///
+/// An index can be written to a compact "sidecar" file alongside the volume using
+/// [Index::write] and later read back with [Index::read].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Index {
+    volume_size: u64,
+    records: Vec<RecordIndexEntry>,
+}
+
+/// An index entry describing a single LDM record within a volume file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordIndexEntry {
+    /// The byte offset of this record, including its four-byte size prefix, from the start of the
+    /// volume file.
+    pub offset: u64,
+
+    /// The size of this record in bytes, including its four-byte size prefix.
+    pub size: u64,
+
+    /// Whether this record's data is compressed.
+    pub compressed: bool,
+
+    /// The number of messages of each type in this record, keyed by the ICD message type code.
+    pub message_type_counts: Vec<(u8, u32)>,
+
+    /// The lowest and highest elevation numbers of the digital radar data in this record, if any.
+    pub elevation_number_range: Option<(u8, u8)>,
+
+    /// The lowest and highest azimuth angles in degrees of the digital radar data in this record,
+    /// if any.
+    pub azimuth_angle_range: Option<(f32, f32)>,
+}
+
+impl Index {
+    /// Builds an index for the given volume file. This requires decompressing and decoding each of
+    /// the volume's records once.
+    pub fn new(file: &File) -> Result<Self> {
+        Self::new_with_limits(file, &DecodeLimits::new())
+    }
+
+    /// Builds an index for the given volume file, decoding its records within the given limits.
+    pub fn new_with_limits(file: &File, limits: &DecodeLimits) -> Result<Self> {
+        let header_size = size_of::<Header>();
+        let records_data = file.data().get(header_size..).unwrap_or_default();
+        let ranges = split_compressed_record_ranges(records_data);
+
+        let mut records = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            enter_span!(
+                "record",
+                index = records.len(),
+                offset = header_size + range.start,
+                size = range.len(),
+            );
+
+            let mut record = record_from_range(records_data, range.clone());
+
+            let compressed = record.compressed();
+            if compressed {
+                record = record.decompress_with_limits(limits)?;
+            }
+
+            let mut entry = RecordIndexEntry {
+                offset: (header_size + range.start) as u64,
+                size: range.len() as u64,
+                compressed,
+                message_type_counts: Vec::new(),
+                elevation_number_range: None,
+                azimuth_angle_range: None,
+            };
+
+            for message in record.messages_with_limits(limits)? {
+                entry.add_message_type(message.header.message_type);
+
+                if let Message::DigitalRadarData(radar_data_message) = &message.message {
+                    entry.add_radial(
+                        radar_data_message.header.elevation_number,
+                        radar_data_message.header.azimuth_angle,
+                    );
+                }
+            }
+
+            records.push(entry);
+        }
+
+        Ok(Self {
+            volume_size: file.data().len() as u64,
+            records,
+        })
+    }
+
+    /// The path of the sidecar index file for a volume file at the given path.
+    pub fn sidecar_path(volume_path: &Path) -> PathBuf {
+        let mut path = volume_path.as_os_str().to_owned();
+        path.push(".idx");
+        PathBuf::from(path)
+    }
+
+    /// Reads an index previously written with [Index::write] from the provided reader.
+    pub fn read<R: Read>(reader: &mut R) -> Result<Self> {
+        use bincode::{DefaultOptions, Options};
+
+        let mut magic = [0; INDEX_MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+
+        let mut version = [0; 1];
+        reader.read_exact(&mut version)?;
+
+        if &magic != INDEX_MAGIC || version[0] != INDEX_VERSION {
+            return Err(Error::InvalidIndex);
+        }
+
+        Ok(DefaultOptions::new()
+            .with_fixint_encoding()
+            .with_big_endian()
+            .with_limit(MAX_INDEX_BYTES)
+            .deserialize_from(reader)?)
+    }
+
+    /// Writes this index in a compact binary form to the provided writer.
+    pub fn write<W: Write>(&self, writer: &mut W) -> Result<()> {
+        use bincode::{DefaultOptions, Options};
+
+        writer.write_all(INDEX_MAGIC)?;
+        writer.write_all(&[INDEX_VERSION])?;
+
+        Ok(DefaultOptions::new()
+            .with_fixint_encoding()
+            .with_big_endian()
+            .serialize_into(writer, self)?)
+    }
+
+    /// The size in bytes of the volume file this index was built from.
+    pub fn volume_size(&self) -> u64 {
+        self.volume_size
+    }
+
+    /// The index entries for each of the volume's LDM records in file order.
+    pub fn records(&self) -> &[RecordIndexEntry] {
+        &self.records
+    }
+
+    /// The index entries for records containing digital radar data for the given elevation number.
+    pub fn records_with_elevation(
+        &self,
+        elevation_number: u8,
+    ) -> impl Iterator<Item = &RecordIndexEntry> {
+        self.records
+            .iter()
+            .filter(move |entry| entry.contains_elevation(elevation_number))
+    }
+
+    /// The index entries for records containing messages of the given type.
+    pub fn records_with_message_type(
+        &self,
+        message_type: MessageType,
+    ) -> impl Iterator<Item = &RecordIndexEntry> {
+        self.records
+            .iter()
+            .filter(move |entry| entry.message_count(message_type) > 0)
+    }
+}
+
+impl RecordIndexEntry {
+    /// The number of messages of the given type in this record.
+    pub fn message_count(&self, message_type: MessageType) -> u32 {
+        if message_type == MessageType::Unknown {
+            return 0;
+        }
+
+        self.message_type_counts
+            .iter()
+            .find(|(code, _)| *code == message_type as u8)
+            .map(|(_, count)| *count)
+            .unwrap_or(0)
+    }
+
+    /// Whether this record contains digital radar data for the given elevation number.
+    pub fn contains_elevation(&self, elevation_number: u8) -> bool {
+        self.elevation_number_range
+            .is_some_and(|(low, high)| low <= elevation_number && elevation_number <= high)
+    }
+
+    /// Counts a message of the given type code in this entry.
+    fn add_message_type(&mut self, code: u8) {
+        match self
+            .message_type_counts
+            .iter_mut()
+            .find(|(existing_code, _)| *existing_code == code)
+        {
+            Some((_, count)) => *count += 1,
+            None => self.message_type_counts.push((code, 1)),
+        }
+    }
+
+    /// Extends this entry's elevation and azimuth ranges to include the given radial.
+    fn add_radial(&mut self, elevation_number: u8, azimuth_angle: f32) {
+        self.elevation_number_range = Some(match self.elevation_number_range {
+            Some((low, high)) => (low.min(elevation_number), high.max(elevation_number)),
+            None => (elevation_number, elevation_number),
+        });
+
+        self.azimuth_angle_range = Some(match self.azimuth_angle_range {
+            Some((low, high)) => (low.min(azimuth_angle), high.max(azimuth_angle)),
+            None => (azimuth_angle, azimuth_angle),
+        });
+    }
+}
+
+impl File {
+    /// Builds an index of this volume file's LDM records. See [Index] for details.
+    pub fn index(&self) -> Result<Index> {
+        Index::new(self)
+    }
+
+    /// The LDM record described by the given index entry. The index must have been built from this
+    /// volume file.
+    pub fn indexed_record(&self, entry: &RecordIndexEntry) -> Result<Record<'_>> {
+        let end = entry
+            .offset
+            .checked_add(entry.size)
+            .filter(|end| *end <= self.data().len() as u64)
+            .ok_or(Error::IndexMismatch)?;
+        let start = entry.offset as usize;
+        let end = end as usize;
+
+        Ok(record_from_range(self.data(), start..end))
+    }
+
+    /// The LDM records containing digital radar data for the given elevation number, located using
+    /// the provided index so that other records need not be decompressed or decoded.
+    pub fn records_with_elevation(
+        &self,
+        index: &Index,
+        elevation_number: u8,
+    ) -> Result<Vec<Record<'_>>> {
+        self.check_index(index)?;
+        index
+            .records_with_elevation(elevation_number)
+            .map(|entry| self.indexed_record(entry))
+            .collect()
+    }
+
+    /// The LDM records containing messages of the given type, located using the provided index so
+    /// that other records need not be decompressed or decoded.
+    pub fn records_with_message_type(
+        &self,
+        index: &Index,
+        message_type: MessageType,
+    ) -> Result<Vec<Record<'_>>> {
+        self.check_index(index)?;
+        index
+            .records_with_message_type(message_type)
+            .map(|entry| self.indexed_record(entry))
+            .collect()
+    }
+
+    /// Ensures the given index plausibly describes this volume file.
+    fn check_index(&self, index: &Index) -> Result<()> {
+        if index.volume_size() != self.data().len() as u64 {
+            return Err(Error::IndexMismatch);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::volume::SyntheticVolume;
+
+    fn test_index() -> Index {
+        Index {
+            volume_size: 1024,
+            records: vec![
+                RecordIndexEntry {
+                    offset: 24,
+                    size: 500,
+                    compressed: true,
+                    message_type_counts: vec![(2, 1), (5, 1), (15, 77)],
+                    elevation_number_range: None,
+                    azimuth_angle_range: None,
+                },
+                RecordIndexEntry {
+                    offset: 524,
+                    size: 500,
+                    compressed: true,
+                    message_type_counts: vec![(31, 120)],
+                    elevation_number_range: Some((1, 2)),
+                    azimuth_angle_range: Some((0.25, 359.75)),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_index_round_trip() -> Result<()> {
+        let index = test_index();
+
+        let mut sidecar = Vec::new();
+        index.write(&mut sidecar)?;
+
+        let read_index = Index::read(&mut sidecar.as_slice())?;
+        assert_eq!(read_index, index);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_invalid_magic() {
+        let mut sidecar = b"NOTANINDEX".to_vec();
+        sidecar.extend_from_slice(&[0; 32]);
+
+        assert!(matches!(
+            Index::read(&mut sidecar.as_slice()),
+            Err(Error::InvalidIndex)
+        ));
+    }
+
+    #[test]
+    fn test_index_queries() {
+        let index = test_index();
+
+        let elevation_offsets = index
+            .records_with_elevation(2)
+            .map(|entry| entry.offset)
+            .collect::<Vec<_>>();
+        assert_eq!(elevation_offsets, vec![524]);
+        assert_eq!(index.records_with_elevation(3).count(), 0);
+
+        let metadata_offsets = index
+            .records_with_message_type(MessageType::RDAClutterFilterMap)
+            .map(|entry| entry.offset)
+            .collect::<Vec<_>>();
+        assert_eq!(metadata_offsets, vec![24]);
+        assert_eq!(
+            index.records[0].message_count(MessageType::RDAStatusData),
+            1
+        );
+        assert_eq!(
+            index.records[1].message_count(MessageType::RDAStatusData),
+            0
+        );
+    }
+
+    #[test]
+    fn test_index_read_limit() {
+        let mut sidecar = INDEX_MAGIC.to_vec();
+        sidecar.push(INDEX_VERSION);
+        sidecar.extend_from_slice(&1024u64.to_be_bytes());
+        sidecar.extend_from_slice(&u64::MAX.to_be_bytes());
+
+        // Without a limit, an endless stream of empty entries would be read until memory ran out
+        let mut reader = sidecar.as_slice().chain(std::io::repeat(0));
+        assert!(matches!(
+            Index::read(&mut reader),
+            Err(Error::DeserializationError(error))
+                if matches!(*error, bincode::ErrorKind::SizeLimit)
+        ));
+    }
+
+    #[test]
+    fn test_indexed_record_overflow() -> Result<()> {
+        let file = SyntheticVolume::new()
+            .with_elevation_count(1)
+            .with_radials_per_sweep(10)
+            .with_gate_count(10)
+            .generate()?;
+
+        let entry = RecordIndexEntry {
+            offset: u64::MAX - 10,
+            size: 20,
+            compressed: true,
+            message_type_counts: Vec::new(),
+            elevation_number_range: None,
+            azimuth_angle_range: None,
+        };
+        assert!(matches!(
+            file.indexed_record(&entry),
+            Err(Error::IndexMismatch)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_synthetic_volume() -> Result<()> {
+        let file = SyntheticVolume::new()
+            .with_elevation_count(3)
+            .with_radials_per_sweep(120)
+            .with_gate_count(10)
+            .generate()?;
+
+        let index = Index::new(&file)?;
+        assert_eq!(index.volume_size(), file.data().len() as u64);
+        assert_eq!(index.records().len(), file.records().len());
+
+        for elevation_number in 1..=3 {
+            let records = file.records_with_elevation(&index, elevation_number)?;
+            assert!(!records.is_empty());
+
+            for record in records {
+                let record = record.decompress()?;
+                for message in record.messages()? {
+                    if let Message::DigitalRadarData(message) = message.message {
+                        assert_eq!(message.header.elevation_number, elevation_number);
+                    }
+                }
+            }
+        }
+        assert!(file.records_with_elevation(&index, 4)?.is_empty());
+
+        let coverage_pattern_records =
+            file.records_with_message_type(&index, MessageType::RDAVolumeCoveragePattern)?;
+        assert_eq!(coverage_pattern_records.len(), 1);
+        let record = coverage_pattern_records[0].decompress()?;
+        assert!(record
+            .messages()?
+            .iter()
+            .any(|message| matches!(message.message, Message::VolumeCoveragePattern(_))));
+
+        let other_file = SyntheticVolume::new().with_elevation_count(1).generate()?;
+        assert!(matches!(
+            other_file.records_with_elevation(&index, 1),
+            Err(Error::IndexMismatch)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sidecar_path() {
+        let path = Index::sidecar_path(Path::new("downloads/KDMX20220305_232324_V06"));
+        assert_eq!(path, PathBuf::from("downloads/KDMX20220305_232324_V06.idx"));
+    }
+}
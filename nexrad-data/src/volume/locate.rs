@@ -0,0 +1,158 @@
+use crate::result::Result;
+use crate::telemetry::enter_span;
+use crate::volume::{record_from_range, split_compressed_record_ranges, File, Header};
+use nexrad_decode::limits::DecodeLimits;
+use nexrad_decode::messages::MessageWithHeader;
+use std::ops::Range;
+
+/// Where a decoded message is located within an Archive II volume file, allowing decode findings to
+/// be mapped back to the file for forensic work or targeted re-reads.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MessageLocation {
+    /// The index of the LDM record containing the message.
+    pub record_index: usize,
+
+    /// The byte offset of the record containing the message, including its four-byte size prefix,
+    /// from the start of the volume file.
+    pub record_offset: u64,
+
+    /// Whether the record containing the message is compressed. If so, the message's range within
+    /// the record refers to the record's decompressed data.
+    pub record_compressed: bool,
+
+    /// The range of the message's bytes within its record's data, excluding the record's size
+    /// prefix.
+    pub record_range: Range<u64>,
+}
+
+impl MessageLocation {
+    /// The range of the message's bytes from the start of the volume file, or `None` if its record
+    /// is compressed and so the message does not appear in the file verbatim.
+    pub fn file_range(&self) -> Option<Range<u64>> {
+        if self.record_compressed {
+            return None;
+        }
+
+        let record_data_offset = self.record_offset + 4;
+        Some(
+            record_data_offset + self.record_range.start
+                ..record_data_offset + self.record_range.end,
+        )
+    }
+}
+
+/// A message decoded from a volume file along with its location within the file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocatedMessage {
+    /// Where the message is located within the volume file.
+    pub location: MessageLocation,
+
+    /// The decoded message.
+    pub message: MessageWithHeader<'static>,
+}
+
+impl File {
+    /// Decodes this volume's messages along with each message's location within the file,
+    /// accounting for the volume header and each record's size prefix and compression.
+    pub fn locate_messages(&self) -> Result<Vec<LocatedMessage>> {
+        self.locate_messages_with_limits(&DecodeLimits::new())
+    }
+
+    /// Decodes and locates this volume's messages as [File::locate_messages] does, decoding its
+    /// records within the given limits.
+    pub fn locate_messages_with_limits(
+        &self,
+        limits: &DecodeLimits,
+    ) -> Result<Vec<LocatedMessage>> {
+        let header_size = size_of::<Header>();
+        let records_data = self.data().get(header_size..).unwrap_or_default();
+
+        let mut located_messages = Vec::new();
+        for (record_index, range) in split_compressed_record_ranges(records_data)
+            .into_iter()
+            .enumerate()
+        {
+            let record_offset = (header_size + range.start) as u64;
+            enter_span!("record", index = record_index, offset = record_offset);
+
+            let mut record = record_from_range(records_data, range);
+            let record_compressed = record.compressed();
+            if record_compressed {
+                record = record.decompress_with_limits(limits)?;
+            }
+
+            for message in record.messages_with_limits(limits)? {
+                let record_range = message
+                    .byte_range
+                    .clone()
+                    .map_or(0..0, |range| range.start as u64..range.end as u64);
+
+                located_messages.push(LocatedMessage {
+                    location: MessageLocation {
+                        record_index,
+                        record_offset,
+                        record_compressed,
+                        record_range,
+                    },
+                    message: message.into_owned(),
+                });
+            }
+        }
+
+        Ok(located_messages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::volume::SyntheticVolume;
+    use nexrad_decode::messages::decode_message_header;
+
+    fn volume(compressed: bool) -> Result<File> {
+        SyntheticVolume::new()
+            .with_elevation_count(1)
+            .with_radials_per_sweep(90)
+            .with_gate_count(20)
+            .with_compression(compressed)
+            .generate()
+    }
+
+    #[test]
+    fn test_locate_uncompressed_messages() -> Result<()> {
+        let volume = volume(false)?;
+        let located_messages = volume.locate_messages()?;
+        assert!(!located_messages.is_empty());
+
+        for located in &located_messages {
+            let file_range = located.location.file_range();
+            assert!(file_range.is_some());
+
+            if let Some(file_range) = file_range {
+                let data = &volume.data()[file_range.start as usize..file_range.end as usize];
+                assert_eq!(
+                    decode_message_header(&mut &data[..])?,
+                    located.message.header
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_locate_compressed_messages() -> Result<()> {
+        let located_messages = volume(true)?.locate_messages()?;
+
+        assert!(located_messages
+            .iter()
+            .all(|located| located.location.record_compressed
+                && located.location.file_range().is_none()));
+        assert!(located_messages
+            .windows(2)
+            .filter(|pair| pair[0].location.record_index == pair[1].location.record_index)
+            .all(|pair| pair[0].location.record_range.end == pair[1].location.record_range.start));
+
+        Ok(())
+    }
+}
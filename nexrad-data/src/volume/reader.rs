@@ -0,0 +1,135 @@
+use crate::result::Result;
+use crate::volume::{Header, Record};
+use std::io::{Read, Seek, SeekFrom};
+use std::ops::Range;
+
+/// Reads an Archive II volume file from any seekable source, such as a file handle or a seekable
+/// object stream, without loading the file into memory. The volume header is read when requested,
+/// the record index is built by seeking from one record's size prefix to the next when first
+/// needed, and each record's data is read only when that record is requested.
+#[derive(Debug)]
+pub struct FileReader<R> {
+    reader: R,
+    record_ranges: Option<Vec<Range<u64>>>,
+}
+
+impl<R: Read + Seek> FileReader<R> {
+    /// Creates a new reader for the Archive II volume file in the provided source. No data is read
+    /// until it is requested.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            record_ranges: None,
+        }
+    }
+
+    /// Consumes this reader, returning its underlying source.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// The file's decoded Archive II volume header.
+    #[cfg(all(feature = "serde", feature = "bincode"))]
+    pub fn header(&mut self) -> Result<Header> {
+        self.reader.seek(SeekFrom::Start(0))?;
+        Header::deserialize(&mut self.reader)
+    }
+
+    /// The number of LDM records in the file.
+    pub fn record_count(&mut self) -> Result<usize> {
+        Ok(self.record_ranges()?.len())
+    }
+
+    /// The byte range of the LDM record at the given index from the start of the file, including
+    /// its four-byte size prefix, or `None` if there is no such record.
+    pub fn record_range(&mut self, index: usize) -> Result<Option<Range<u64>>> {
+        Ok(self.record_ranges()?.get(index).cloned())
+    }
+
+    /// Reads the LDM record at the given index, or `None` if there is no such record. As with
+    /// [crate::volume::File::records], the size prefix is omitted from uncompressed records.
+    pub fn record(&mut self, index: usize) -> Result<Option<Record<'static>>> {
+        let Some(range) = self.record_range(index)? else {
+            return Ok(None);
+        };
+
+        self.reader.seek(SeekFrom::Start(range.start))?;
+        let mut data = vec![0; (range.end - range.start) as usize];
+        self.reader.read_exact(&mut data)?;
+
+        if !Record::from_slice(&data).compressed() {
+            data.drain(..data.len().min(4));
+        }
+
+        Ok(Some(Record::new(data)))
+    }
+
+    /// Reads the file's LDM records in order, one at a time.
+    pub fn records(&mut self) -> Result<impl Iterator<Item = Result<Record<'static>>> + '_> {
+        let record_count = self.record_count()?;
+        Ok((0..record_count).filter_map(move |index| self.record(index).transpose()))
+    }
+
+    /// The byte ranges of the file's LDM records, building the record index if it has not been.
+    fn record_ranges(&mut self) -> Result<&[Range<u64>]> {
+        if self.record_ranges.is_none() {
+            self.record_ranges = Some(self.index_records()?);
+        }
+
+        Ok(self.record_ranges.as_deref().unwrap_or_default())
+    }
+
+    /// Finds the byte ranges of the file's LDM records by reading each record's size prefix and
+    /// seeking past its data. A truncated final record is clamped to the end of the file.
+    fn index_records(&mut self) -> Result<Vec<Range<u64>>> {
+        let length = self.reader.seek(SeekFrom::End(0))?;
+
+        let mut ranges = Vec::new();
+        let mut position = size_of::<Header>() as u64;
+        while position + 4 <= length {
+            self.reader.seek(SeekFrom::Start(position))?;
+
+            let mut record_size = [0; 4];
+            self.reader.read_exact(&mut record_size)?;
+            let record_size = i32::from_be_bytes(record_size).unsigned_abs() as u64;
+
+            let end = (position + record_size + 4).min(length);
+            ranges.push(position..end);
+            position = end;
+        }
+
+        Ok(ranges)
+    }
+}
+
+#[cfg(all(test, feature = "decode"))]
+mod tests {
+    use super::*;
+    use crate::volume::SyntheticVolume;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_reader_matches_file() -> Result<()> {
+        for compressed in [true, false] {
+            let volume = SyntheticVolume::new()
+                .with_elevation_count(2)
+                .with_radials_per_sweep(90)
+                .with_gate_count(20)
+                .with_compression(compressed)
+                .generate()?;
+
+            let mut reader = FileReader::new(Cursor::new(volume.data().clone()));
+            assert_eq!(reader.header()?, volume.header()?);
+
+            let records = volume.records();
+            assert_eq!(reader.record_count()?, records.len());
+            for (read, record) in reader.records()?.zip(&records) {
+                assert_eq!(read?.data(), record.data());
+            }
+
+            assert!(reader.record(records.len())?.is_none());
+        }
+
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use std::fmt::Debug;
+use std::ops::Range;
 
 #[derive(Clone, PartialEq, Eq, Hash)]
 enum RecordData<'a> {
@@ -48,9 +49,25 @@ impl<'a> Record<'a> {
         self.data().len() >= 6 && self.data()[4..6].as_ref() == b"BZ"
     }
 
-    /// Decompresses this LDM record's data.
+    /// Decompresses this LDM record's data. Untrusted records should instead be decompressed with
+    /// [Record::decompress_with_limits] to guard against decompression bombs.
     #[cfg(feature = "bzip2")]
     pub fn decompress<'b>(&self) -> crate::result::Result<Record<'b>> {
+        self.decompress_at_most(usize::MAX)
+    }
+
+    /// Decompresses this LDM record's data, failing rather than decompressing more than the limits'
+    /// maximum decompressed record size.
+    #[cfg(feature = "decode")]
+    pub fn decompress_with_limits<'b>(
+        &self,
+        limits: &nexrad_decode::limits::DecodeLimits,
+    ) -> crate::result::Result<Record<'b>> {
+        self.decompress_at_most(limits.max_decompressed_record_bytes())
+    }
+
+    #[cfg(feature = "bzip2")]
+    fn decompress_at_most<'b>(&self, max_bytes: usize) -> crate::result::Result<Record<'b>> {
         use crate::result::Error;
         use bzip2::read::BzDecoder;
         use std::io::Read;
@@ -62,19 +79,38 @@ impl<'a> Record<'a> {
         // Skip the four-byte record size prefix
         let data = self.data().split_at(4).1;
 
+        // Read one byte past the limit to distinguish data which fills it from data exceeding it
         let mut decompressed_data = Vec::new();
-        BzDecoder::new(data).read_to_end(&mut decompressed_data)?;
+        BzDecoder::new(data)
+            .take((max_bytes as u64).saturating_add(1))
+            .read_to_end(&mut decompressed_data)?;
+
+        if decompressed_data.len() > max_bytes {
+            return Err(Error::DecompressedRecordTooLarge(max_bytes));
+        }
 
         Ok(Record::new(decompressed_data))
     }
 
-    /// Decodes the NEXRAD level II messages contained in this LDM record.
+    /// Decodes the NEXRAD level II messages contained in this LDM record in the order they appear in
+    /// it. Moment data in the returned messages is borrowed from this record's data rather than
+    /// copied.
     #[cfg(feature = "decode")]
     pub fn messages(
         &self,
-    ) -> crate::result::Result<Vec<nexrad_decode::messages::MessageWithHeader>> {
+    ) -> crate::result::Result<Vec<nexrad_decode::messages::MessageWithHeader<'_>>> {
+        self.messages_with_limits(&nexrad_decode::limits::DecodeLimits::new())
+    }
+
+    /// Decodes the NEXRAD level II messages contained in this LDM record within the given limits.
+    /// Moment data in the returned messages is borrowed from this record's data rather than copied.
+    #[cfg(feature = "decode")]
+    pub fn messages_with_limits(
+        &self,
+        limits: &nexrad_decode::limits::DecodeLimits,
+    ) -> crate::result::Result<Vec<nexrad_decode::messages::MessageWithHeader<'_>>> {
         use crate::result::Error;
-        use nexrad_decode::messages::decode_messages;
+        use nexrad_decode::messages::decode_messages_borrowed_with_limits;
         use std::io::Cursor;
 
         if self.compressed() {
@@ -82,7 +118,7 @@ impl<'a> Record<'a> {
         }
 
         let mut reader = Cursor::new(self.data());
-        Ok(decode_messages(&mut reader)?)
+        Ok(decode_messages_borrowed_with_limits(&mut reader, limits)?)
     }
 }
 
@@ -110,25 +146,69 @@ impl Debug for Record<'_> {
 }
 
 /// Splits compressed LDM record data into individual records. Will omit the record size prefix from
-/// each record.
-pub fn split_compressed_records(data: &[u8]) -> Vec<Record> {
-    let mut records = Vec::new();
+/// each uncompressed record.
+pub fn split_compressed_records(data: &[u8]) -> Vec<Record<'_>> {
+    split_compressed_record_ranges(data)
+        .into_iter()
+        .map(|range| record_from_range(data, range))
+        .collect()
+}
+
+/// The LDM record in the given range of record data, where the range includes the record's size
+/// prefix. Compressed records retain their prefix, while it is omitted from uncompressed records so
+/// that their messages can be decoded directly.
+pub(crate) fn record_from_range(data: &[u8], range: Range<usize>) -> Record<'_> {
+    let record = Record::from_slice(&data[range.clone()]);
+    if record.compressed() {
+        return record;
+    }
+
+    Record::from_slice(data.get(range.start + 4..range.end).unwrap_or_default())
+}
+
+/// Splits compressed LDM record data into the byte ranges of individual records, including each
+/// record's size prefix.
+pub(crate) fn split_compressed_record_ranges(data: &[u8]) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
 
     let mut position = 0;
-    loop {
-        if position >= data.len() {
-            break;
-        }
+    while let Some(record_size) = data.get(position..position + 4) {
+        let mut record_size_bytes = [0; 4];
+        record_size_bytes.copy_from_slice(record_size);
+        let record_size = i32::from_be_bytes(record_size_bytes).unsigned_abs() as usize;
+
+        // A truncated final record is clamped to the end of the data so that decompressing it
+        // surfaces an error rather than its bytes being silently dropped
+        let end = (position + record_size + 4).min(data.len());
+        ranges.push(position..end);
+        position = end;
+    }
+
+    ranges
+}
 
-        let mut record_size = [0; 4];
-        record_size.copy_from_slice(&data[position..position + 4]);
-        let record_size = i32::from_be_bytes(record_size).unsigned_abs() as usize;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        records.push(Record::from_slice(
-            &data[position..position + record_size + 4],
-        ));
-        position += record_size + 4;
+    #[test]
+    fn test_split_compressed_record_ranges() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&(-3i32).to_be_bytes());
+        data.extend_from_slice(b"abc");
+        data.extend_from_slice(&2i32.to_be_bytes());
+        data.extend_from_slice(b"de");
+
+        assert_eq!(split_compressed_record_ranges(&data), vec![0..7, 7..13]);
     }
 
-    records
+    #[test]
+    fn test_split_compressed_record_ranges_truncated() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&100i32.to_be_bytes());
+        data.extend_from_slice(b"abc");
+
+        assert_eq!(split_compressed_record_ranges(&data), vec![0..7]);
+        assert!(split_compressed_record_ranges(&[0, 0]).is_empty());
+    }
 }
@@ -0,0 +1,200 @@
+use crate::result::{Error, Result};
+use crate::telemetry::{enter_span, warn};
+use crate::volume::{File, StreamOptions, SweepBuffer};
+use bzip2::read::BzDecoder;
+use nexrad_decode::limits::DecodeLimits;
+use nexrad_decode::messages::Message;
+use nexrad_decode::salvage::salvage_messages;
+use nexrad_model::data::Scan;
+use std::io::Read;
+use std::ops::Range;
+
+/// A scan recovered from a truncated or corrupted volume, along with what could not be recovered.
+/// See [File::salvage_scan].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SalvagedScan {
+    /// The scan composed of every radial which could be recovered.
+    pub scan: Scan,
+    /// Problems encountered while recovering the scan, in the order they were found.
+    pub issues: Vec<SalvageIssue>,
+}
+
+/// A problem encountered while salvaging a volume. Records are identified by their index in
+/// [File::records].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SalvageIssue {
+    /// The record's compressed data was corrupt or truncated, or decompressed beyond the
+    /// [DecodeLimits::max_decompressed_record_bytes] limit, so only its first `decompressed_bytes`
+    /// bytes could be recovered.
+    Decompression {
+        record_index: usize,
+        decompressed_bytes: usize,
+    },
+    /// A range of the record's decompressed data did not contain decodable messages.
+    SkippedBytes {
+        record_index: usize,
+        range: Range<usize>,
+    },
+    /// A message's radial could not be converted to the common model.
+    InvalidRadial { record_index: usize },
+}
+
+impl File {
+    /// Decodes as much of this volume as possible rather than failing on the first error. Corrupt
+    /// or truncated records are decompressed up to the point of corruption, and undecodable data
+    /// within records is skipped by resynchronizing on the next plausible message. Records are
+    /// decompressed no further than [DecodeLimits::new] allows. Fails only if no coverage pattern
+    /// number could be recovered.
+    pub fn salvage_scan(&self) -> Result<SalvagedScan> {
+        let mut issues = Vec::new();
+        let mut sweeps = Vec::new();
+        let mut on_sweep = |sweep| sweeps.push(sweep);
+        let mut buffer = SweepBuffer::new(StreamOptions::new());
+        let max_bytes = DecodeLimits::new().max_decompressed_record_bytes();
+
+        for (record_index, record) in self.records().into_iter().enumerate() {
+            enter_span!("record", index = record_index, size = record.data().len());
+
+            let data = if record.compressed() {
+                let mut decompressed_data = Vec::new();
+                let compressed_data = record.data().get(4..).unwrap_or_default();
+                let decompressed = BzDecoder::new(compressed_data)
+                    .take(max_bytes as u64)
+                    .read_to_end(&mut decompressed_data);
+                if decompressed.is_err() || decompressed_data.len() >= max_bytes {
+                    warn!(
+                        "Record {} decompression failed after {} bytes",
+                        record_index,
+                        decompressed_data.len()
+                    );
+                    issues.push(SalvageIssue::Decompression {
+                        record_index,
+                        decompressed_bytes: decompressed_data.len(),
+                    });
+                }
+                decompressed_data
+            } else {
+                record.data().to_vec()
+            };
+
+            let salvaged = salvage_messages(&data);
+            issues.extend(salvaged.stats.warnings.iter().map(|warning| {
+                SalvageIssue::SkippedBytes {
+                    record_index,
+                    range: warning.range().clone(),
+                }
+            }));
+
+            for message in salvaged.messages {
+                match message.message {
+                    Message::DigitalRadarData(radar_data_message) => {
+                        let result = buffer.push_radar_data(*radar_data_message, &mut on_sweep);
+                        if result.is_err() {
+                            issues.push(SalvageIssue::InvalidRadial { record_index });
+                        }
+                    }
+                    Message::VolumeCoveragePattern(coverage_pattern_message) => {
+                        buffer.push_coverage_pattern(&coverage_pattern_message)
+                    }
+                    _ => {}
+                }
+            }
+        }
+        buffer.flush(&mut on_sweep);
+
+        let coverage_pattern_number = buffer
+            .coverage_pattern_number()
+            .ok_or(Error::MissingCoveragePattern)?;
+
+        Ok(SalvagedScan {
+            scan: Scan::new(coverage_pattern_number, sweeps),
+            issues,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::volume::SyntheticVolume;
+
+    fn volume(compressed: bool) -> Result<File> {
+        SyntheticVolume::new()
+            .with_elevation_count(2)
+            .with_radials_per_sweep(240)
+            .with_gate_count(20)
+            .with_compression(compressed)
+            .generate()
+    }
+
+    fn radial_count(scan: &Scan) -> usize {
+        scan.sweeps()
+            .iter()
+            .map(|sweep| sweep.radials().len())
+            .sum()
+    }
+
+    #[test]
+    fn test_salvage_intact_volume() -> Result<()> {
+        let volume = volume(true)?;
+
+        let salvaged = volume.salvage_scan()?;
+        assert!(salvaged.issues.is_empty());
+        assert_eq!(salvaged.scan, volume.scan()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_salvage_corrupted_record() -> Result<()> {
+        let volume = volume(false)?;
+
+        // Corrupt the message header of a radial partway through the second record
+        let records = volume.records();
+        let record_offset = records[1].data().as_ptr() as usize - volume.data().as_ptr() as usize;
+        let record_length = records[1].data().len();
+        let corrupt_offset = record_offset + record_length / 2;
+
+        let mut data = volume.data().clone();
+        data[corrupt_offset..corrupt_offset + 64].fill(0xFF);
+        let corrupted = File::new(data);
+
+        assert!(corrupted.scan().is_err());
+
+        let salvaged = corrupted.salvage_scan()?;
+        let recovered = radial_count(&salvaged.scan);
+        assert!(recovered < 480);
+        assert!(recovered >= 470);
+        assert!(salvaged.issues.iter().all(|issue| matches!(
+            issue,
+            SalvageIssue::SkippedBytes {
+                record_index: 1,
+                ..
+            }
+        )));
+        assert!(!salvaged.issues.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_salvage_truncated_volume() -> Result<()> {
+        let volume = volume(true)?;
+
+        let mut data = volume.data().clone();
+        data.truncate(data.len() - 2000);
+        let truncated = File::new(data);
+
+        let salvaged = truncated.salvage_scan()?;
+        let last_record_index = truncated.records().len() - 1;
+
+        // Radials in the records preceding the truncated one are recovered
+        assert_eq!(radial_count(&salvaged.scan), 360);
+        assert!(matches!(
+            salvaged.issues.as_slice(),
+            [SalvageIssue::Decompression { record_index, .. }] if *record_index == last_record_index
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,145 @@
+use crate::result::Result;
+use crate::volume::File;
+use nexrad_decode::limits::DecodeLimits;
+use nexrad_decode::messages::Message;
+use nexrad_model::geo::GeoPoint;
+use nexrad_model::meta::Site;
+
+/// A discrepancy between a volume's station identifier or location and a registry of known radar
+/// sites, suggesting the volume is mislabeled.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SiteFinding {
+    /// The volume header has no valid station identifier.
+    MissingIdentifier,
+    /// The volume header's station identifier is not in the registry.
+    UnknownSite { identifier: String },
+    /// The volume's radial data does not report the radar's location.
+    MissingLocation,
+    /// The radar location reported in the volume's radial data is too far from the registry's
+    /// location for its station identifier.
+    LocationMismatch {
+        identifier: String,
+        registry_location: GeoPoint,
+        reported_location: GeoPoint,
+        distance_meters: f64,
+    },
+}
+
+impl File {
+    /// Checks this volume's header station identifier against the given registry of known radar
+    /// sites, and the radar location reported in its radial data against that site's location.
+    /// Returns any discrepancies, where a location more than `max_distance_meters` from the
+    /// registry's is a mismatch. Mislabeled volumes should be excluded from multi-site products.
+    pub fn check_site(&self, sites: &[Site], max_distance_meters: f64) -> Result<Vec<SiteFinding>> {
+        self.check_site_with_limits(sites, max_distance_meters, &DecodeLimits::new())
+    }
+
+    /// Checks this volume's station identifier and reported location as [File::check_site] does,
+    /// decoding its records within the given limits.
+    pub fn check_site_with_limits(
+        &self,
+        sites: &[Site],
+        max_distance_meters: f64,
+        limits: &DecodeLimits,
+    ) -> Result<Vec<SiteFinding>> {
+        let mut findings = Vec::new();
+
+        let identifier = self.header()?.icao_of_radar();
+        let site = match &identifier {
+            Some(identifier) => {
+                let site = sites
+                    .iter()
+                    .find(|site| site.identifier().as_slice() == identifier.as_bytes());
+                if site.is_none() {
+                    findings.push(SiteFinding::UnknownSite {
+                        identifier: identifier.clone(),
+                    });
+                }
+                site
+            }
+            None => {
+                findings.push(SiteFinding::MissingIdentifier);
+                None
+            }
+        };
+
+        let Some(reported_location) = self.reported_location(limits)? else {
+            findings.push(SiteFinding::MissingLocation);
+            return Ok(findings);
+        };
+
+        if let (Some(identifier), Some(site)) = (identifier, site) {
+            let distance_meters = site.distance_meters(&reported_location);
+            if distance_meters > max_distance_meters {
+                findings.push(SiteFinding::LocationMismatch {
+                    identifier,
+                    registry_location: site.location(),
+                    reported_location,
+                    distance_meters,
+                });
+            }
+        }
+
+        Ok(findings)
+    }
+
+    /// The radar location reported by the first radial with a volume data block, if any.
+    fn reported_location(&self, limits: &DecodeLimits) -> Result<Option<GeoPoint>> {
+        for record in self.records() {
+            let record = if record.compressed() {
+                record.decompress_with_limits(limits)?
+            } else {
+                record
+            };
+
+            for message in record.messages_with_limits(limits)? {
+                if let Message::DigitalRadarData(radar_data_message) = &message.message {
+                    if let Some(volume_data_block) = &radar_data_message.volume_data_block {
+                        return Ok(Some(GeoPoint::new(
+                            volume_data_block.latitude as f64,
+                            volume_data_block.longitude as f64,
+                        )));
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::volume::SyntheticVolume;
+
+    #[test]
+    fn test_check_site() -> Result<()> {
+        let volume = SyntheticVolume::new()
+            .with_elevation_count(1)
+            .with_radials_per_sweep(10)
+            .with_gate_count(10)
+            .generate()?;
+
+        let site = Site::new(*b"KSYN", 41.7311, -93.7228, 299, 20);
+        assert!(volume.check_site(&[site], 1_000.0)?.is_empty());
+
+        let findings = volume.check_site(&[], 1_000.0)?;
+        assert_eq!(
+            findings,
+            vec![SiteFinding::UnknownSite {
+                identifier: "KSYN".to_string()
+            }]
+        );
+
+        let moved_site = Site::new(*b"KSYN", 41.9, -93.7228, 299, 20);
+        let findings = volume.check_site(&[moved_site], 1_000.0)?;
+        assert!(matches!(
+            findings.as_slice(),
+            [SiteFinding::LocationMismatch { distance_meters, .. }]
+                if (*distance_meters - 18_800.0).abs() < 200.0
+        ));
+
+        Ok(())
+    }
+}
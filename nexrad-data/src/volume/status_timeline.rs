@@ -0,0 +1,177 @@
+use crate::result::Result;
+use crate::volume::File;
+use chrono::{DateTime, Utc};
+use nexrad_decode::limits::DecodeLimits;
+use nexrad_decode::messages::{rda_status_data, Message, MessageWithHeader};
+use serde::{Deserialize, Serialize};
+
+/// An RDA status message along with the time it was sent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusEntry {
+    /// The time the status message was sent in milliseconds since the UNIX epoch, if its header
+    /// had a valid date and time.
+    pub timestamp_millis: Option<i64>,
+
+    /// The RDA status message.
+    pub status: rda_status_data::Message,
+}
+
+impl StatusEntry {
+    /// The time the status message was sent, if its header had a valid date and time.
+    pub fn date_time(&self) -> Option<DateTime<Utc>> {
+        self.timestamp_millis
+            .and_then(DateTime::<Utc>::from_timestamp_millis)
+    }
+
+    /// Whether the RDA's state differs from the given earlier entry in its status, operability,
+    /// volume coverage pattern, build number, or active alarms.
+    pub fn changed_from(&self, previous: &StatusEntry) -> bool {
+        let (status, previous_status) = (&self.status, &previous.status);
+
+        status.rda_status() != previous_status.rda_status()
+            || status.operability_status() != previous_status.operability_status()
+            || status.volume_coverage_pattern() != previous_status.volume_coverage_pattern()
+            || status.rda_build_number() != previous_status.rda_build_number()
+            || status.alarm_messages() != previous_status.alarm_messages()
+    }
+}
+
+/// A chronological series of RDA status messages gathered across many volumes or real-time chunks,
+/// for example a day of a site's data when studying an outage. Status messages are sent at the
+/// beginning of each volume scan and whenever the RDA's state changes.
+///
+/// The status messages themselves are retained so that any of their fields, such as average
+/// transmitter power or alarm codes, can be charted over time. The timeline may be serialized to
+/// persist or share it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StatusTimeline {
+    entries: Vec<StatusEntry>,
+}
+
+impl StatusTimeline {
+    /// Creates a new, empty status timeline.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the status messages from each of the given volume files to the timeline.
+    pub fn from_files<'a>(files: impl IntoIterator<Item = &'a File>) -> Result<Self> {
+        let mut timeline = Self::new();
+        for file in files {
+            timeline.push_file(file)?;
+        }
+
+        Ok(timeline)
+    }
+
+    /// Adds the status messages from the given volume file to the timeline.
+    pub fn push_file(&mut self, file: &File) -> Result<()> {
+        self.push_file_with_limits(file, &DecodeLimits::new())
+    }
+
+    /// Adds the status messages from the given volume file to the timeline, decoding its records
+    /// within the given limits.
+    pub fn push_file_with_limits(&mut self, file: &File, limits: &DecodeLimits) -> Result<()> {
+        for record in file.records() {
+            let record = if record.compressed() {
+                record.decompress_with_limits(limits)?
+            } else {
+                record
+            };
+
+            self.push_messages(&record.messages_with_limits(limits)?);
+        }
+
+        Ok(())
+    }
+
+    /// Adds the status messages among the given messages to the timeline, for example those
+    /// decoded from a real-time chunk.
+    pub fn push_messages(&mut self, messages: &[MessageWithHeader]) {
+        for message in messages {
+            if let Message::RDAStatusData(status) = &message.message {
+                self.push(StatusEntry {
+                    timestamp_millis: message
+                        .header
+                        .date_time()
+                        .map(|date_time| date_time.timestamp_millis()),
+                    status: status.as_ref().clone(),
+                });
+            }
+        }
+    }
+
+    /// Adds an entry to the timeline, keeping the timeline ordered by time. Entries without a valid
+    /// time are ordered first.
+    pub fn push(&mut self, entry: StatusEntry) {
+        let index = self
+            .entries
+            .partition_point(|existing| existing.timestamp_millis <= entry.timestamp_millis);
+        self.entries.insert(index, entry);
+    }
+
+    /// The timeline's entries in chronological order.
+    pub fn entries(&self) -> &[StatusEntry] {
+        &self.entries
+    }
+
+    /// The entries at which the RDA's state changed from the preceding entry, along with the first
+    /// entry. See [StatusEntry::changed_from].
+    pub fn changes(&self) -> Vec<&StatusEntry> {
+        let mut changes = Vec::new();
+        let mut previous: Option<&StatusEntry> = None;
+
+        for entry in &self.entries {
+            if previous.is_none_or(|previous| entry.changed_from(previous)) {
+                changes.push(entry);
+            }
+            previous = Some(entry);
+        }
+
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nexrad_decode::messages::message_header::MessageHeader;
+    use nexrad_decode::messages::rda_status_data::{decode_rda_status_message, OperabilityStatus};
+    use nexrad_decode::messages::MessageType;
+
+    fn status_message(time: u32, operability_status: u16) -> Result<MessageWithHeader<'static>> {
+        let data = vec![0; size_of::<rda_status_data::Message>()];
+        let mut status = decode_rda_status_message(&mut data.as_slice())?;
+        status.operability_status = operability_status;
+
+        Ok(MessageWithHeader {
+            header: MessageHeader::new(MessageType::RDAStatusData, 0, 0, 19724, time, 0, 1, 1),
+            message: Message::RDAStatusData(Box::new(status)),
+            byte_range: None,
+        })
+    }
+
+    #[test]
+    fn test_timeline_orders_and_detects_changes() -> Result<()> {
+        let mut timeline = StatusTimeline::new();
+        timeline.push_messages(&[status_message(600_000, 2)?, status_message(0, 2)?]);
+        timeline.push_messages(&[status_message(900_000, 4)?, status_message(300_000, 2)?]);
+
+        let times = timeline
+            .entries()
+            .iter()
+            .map(|entry| entry.timestamp_millis)
+            .collect::<Vec<_>>();
+        assert!(times.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        let changes = timeline.changes();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(
+            changes[1].status.operability_status(),
+            OperabilityStatus::MaintenanceActionRequired
+        );
+        assert_eq!(changes[1].date_time(), timeline.entries()[3].date_time());
+
+        Ok(())
+    }
+}
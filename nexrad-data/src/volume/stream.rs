@@ -0,0 +1,316 @@
+use crate::result::{Error, Result};
+use crate::telemetry::enter_span;
+use crate::volume::{File, Record};
+use nexrad_decode::limits::DecodeLimits;
+use nexrad_decode::messages::digital_radar_data::Message as RadarDataMessage;
+#[cfg(feature = "tokio")]
+use nexrad_decode::messages::rda_status_data::{OperabilityStatus, RDAStatus};
+use nexrad_decode::messages::volume_coverage_pattern::Message as CoveragePatternMessage;
+use nexrad_decode::messages::Message;
+use nexrad_model::data::{ElevationCut, Radial, RadialStatus, Sweep};
+
+/// Options for decoding a volume sweep-by-sweep with bounded memory use, for example on small edge
+/// devices colocated with ingest. See [File::stream_sweeps].
+///
+/// LDM records are decompressed and decoded one at a time, and each record's raw data is dropped
+/// once its radials have been converted to the common model. Sweeps are handed to a callback as
+/// soon as they are complete rather than collected into a [nexrad_model::data::Scan].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StreamOptions {
+    max_buffered_radials: Option<usize>,
+    limits: DecodeLimits,
+}
+
+impl StreamOptions {
+    /// Creates new options which buffer each sweep in full before emitting it.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Caps the number of radials buffered before a sweep is emitted. Sweeps larger than this are
+    /// emitted in several parts sharing an elevation number, which may be recombined with
+    /// [Sweep::merge] if needed. A cap of zero is treated as one.
+    pub fn with_max_buffered_radials(mut self, max_buffered_radials: usize) -> Self {
+        self.max_buffered_radials = Some(max_buffered_radials.max(1));
+        self
+    }
+
+    /// Sets the limits within which each record is decompressed and decoded. By default,
+    /// [DecodeLimits::new] is applied.
+    pub fn with_limits(mut self, limits: DecodeLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// The limits within which each record is decompressed and decoded.
+    pub fn limits(&self) -> &DecodeLimits {
+        &self.limits
+    }
+}
+
+impl File {
+    /// Decodes this volume's sweeps in order, passing each to `on_sweep` as soon as it is complete
+    /// rather than building a full scan in memory. Returns the volume's coverage pattern number.
+    ///
+    /// Each emitted sweep's radials are indexed from zero in the order they were collected, while
+    /// the sweeps' own indices are zero as they are not part of a scan.
+    pub fn stream_sweeps(
+        &self,
+        options: &StreamOptions,
+        mut on_sweep: impl FnMut(Sweep),
+    ) -> Result<u16> {
+        let mut buffer = SweepBuffer::new(options.clone());
+        for (record_index, record) in self.records().into_iter().enumerate() {
+            enter_span!("record", index = record_index, size = record.data().len());
+            buffer.push_record(record, &mut on_sweep)?;
+        }
+        buffer.flush(&mut on_sweep);
+
+        buffer
+            .coverage_pattern_number()
+            .ok_or(Error::MissingCoveragePattern)
+    }
+}
+
+/// Accumulates radials decoded from successive LDM records into sweeps, emitting each sweep once
+/// its final radial is seen, its elevation changes, or the buffered radial limit is reached. Sweeps
+/// are identified with their elevation cut from the most recent volume coverage pattern message.
+pub(crate) struct SweepBuffer {
+    options: StreamOptions,
+    coverage_pattern_number: Option<u16>,
+    cuts: Vec<ElevationCut>,
+    #[cfg(feature = "tokio")]
+    rda_status: Option<(RDAStatus, OperabilityStatus)>,
+    radials: Vec<Radial>,
+}
+
+impl SweepBuffer {
+    pub(crate) fn new(options: StreamOptions) -> Self {
+        Self {
+            options,
+            coverage_pattern_number: None,
+            cuts: Vec::new(),
+            #[cfg(feature = "tokio")]
+            rda_status: None,
+            radials: Vec::new(),
+        }
+    }
+
+    /// The coverage pattern number from the first volume data block seen, if any.
+    pub(crate) fn coverage_pattern_number(&self) -> Option<u16> {
+        self.coverage_pattern_number
+    }
+
+    /// The RDA and operability statuses from the most recent RDA status message seen, if any. Only
+    /// real-time subscriptions report these, so they are not tracked without the `tokio` feature.
+    #[cfg(feature = "tokio")]
+    pub(crate) fn rda_status(&self) -> Option<(RDAStatus, OperabilityStatus)> {
+        self.rda_status
+    }
+
+    /// Decompresses and decodes a record within the options' limits, adding its radials to the
+    /// buffer. The record's data is dropped once its radials have been converted.
+    pub(crate) fn push_record(
+        &mut self,
+        record: Record,
+        on_sweep: &mut impl FnMut(Sweep),
+    ) -> Result<()> {
+        let record = if record.compressed() {
+            record.decompress_with_limits(&self.options.limits)?
+        } else {
+            record
+        };
+
+        for message in record.messages_with_limits(&self.options.limits)? {
+            match message.message {
+                Message::DigitalRadarData(radar_data_message) => {
+                    self.push_radar_data(*radar_data_message, on_sweep)?
+                }
+                Message::VolumeCoveragePattern(coverage_pattern_message) => {
+                    self.push_coverage_pattern(&coverage_pattern_message)
+                }
+                #[cfg(feature = "tokio")]
+                Message::RDAStatusData(status_message) => {
+                    self.rda_status = Some((
+                        status_message.rda_status(),
+                        status_message.operability_status(),
+                    ))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records the elevation cuts of a volume coverage pattern message for identifying the sweeps
+    /// which follow it.
+    pub(crate) fn push_coverage_pattern(&mut self, message: &CoveragePatternMessage) {
+        self.cuts = message.elevation_cuts();
+    }
+
+    /// Converts a digital radar data message to a radial and adds it to the buffer.
+    pub(crate) fn push_radar_data(
+        &mut self,
+        radar_data_message: RadarDataMessage,
+        on_sweep: &mut impl FnMut(Sweep),
+    ) -> Result<()> {
+        if self.coverage_pattern_number.is_none() {
+            if let Some(volume_block) = &radar_data_message.volume_data_block {
+                self.coverage_pattern_number = Some(volume_block.volume_coverage_pattern_number);
+            }
+        }
+
+        self.push_radial(radar_data_message.into_radial()?, on_sweep);
+        Ok(())
+    }
+
+    fn push_radial(&mut self, radial: Radial, on_sweep: &mut impl FnMut(Sweep)) {
+        let elevation_changed = self
+            .radials
+            .first()
+            .is_some_and(|first| first.elevation_number() != radial.elevation_number());
+        let buffer_full = self
+            .options
+            .max_buffered_radials
+            .is_some_and(|max_buffered_radials| self.radials.len() >= max_buffered_radials);
+
+        if elevation_changed || buffer_full {
+            self.flush(on_sweep);
+        }
+
+        let sweep_ended = matches!(
+            radial.radial_status(),
+            RadialStatus::ElevationEnd | RadialStatus::VolumeScanEnd
+        );
+
+        self.radials.push(radial);
+
+        if sweep_ended {
+            self.flush(on_sweep);
+        }
+    }
+
+    /// Emits the buffered radials, if any, as a sweep.
+    pub(crate) fn flush(&mut self, on_sweep: &mut impl FnMut(Sweep)) {
+        if let Some(first) = self.radials.first() {
+            let elevation_number = first.elevation_number();
+            let mut sweep = Sweep::new(elevation_number, std::mem::take(&mut self.radials));
+            if let Some(cut) = self
+                .cuts
+                .iter()
+                .find(|cut| cut.elevation_number() == elevation_number)
+            {
+                sweep = sweep.with_cut(*cut);
+            }
+
+            on_sweep(sweep);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::volume::SyntheticVolume;
+    use nexrad_model::data::Waveform;
+
+    fn volume() -> Result<File> {
+        SyntheticVolume::new()
+            .with_elevation_count(3)
+            .with_radials_per_sweep(90)
+            .with_gate_count(40)
+            .generate()
+    }
+
+    #[test]
+    fn test_stream_sweeps_emits_whole_sweeps() -> Result<()> {
+        let mut sweeps = Vec::new();
+        let coverage_pattern_number =
+            volume()?.stream_sweeps(&StreamOptions::new(), |sweep| sweeps.push(sweep))?;
+
+        assert_eq!(coverage_pattern_number, 212);
+        assert_eq!(
+            sweeps
+                .iter()
+                .map(|sweep| (sweep.elevation_number(), sweep.radials().len()))
+                .collect::<Vec<_>>(),
+            vec![(1, 90), (2, 90), (3, 90)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stream_sweeps_identifies_cuts() -> Result<()> {
+        let mut sweeps = Vec::new();
+        volume()?.stream_sweeps(&StreamOptions::new(), |sweep| sweeps.push(sweep))?;
+
+        let cuts = sweeps
+            .iter()
+            .map(|sweep| {
+                sweep
+                    .cut()
+                    .map(|cut| (cut.elevation_number(), cut.waveform()))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            cuts,
+            vec![
+                Some((1, Some(Waveform::ContiguousSurveillance))),
+                Some((2, Some(Waveform::ContiguousSurveillance))),
+                Some((3, Some(Waveform::Batch))),
+            ]
+        );
+        assert!(sweeps
+            .iter()
+            .filter_map(Sweep::cut)
+            .all(|cut| cut.split_cut_role().is_none() && !cut.is_revisit()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stream_sweeps_caps_buffered_radials() -> Result<()> {
+        let volume = volume()?;
+        let scan = volume.scan()?;
+
+        let mut parts = Vec::new();
+        let options = StreamOptions::new().with_max_buffered_radials(40);
+        volume.stream_sweeps(&options, |sweep| parts.push(sweep))?;
+
+        assert_eq!(parts.len(), 9);
+        assert!(parts.iter().all(|part| part.radials().len() <= 40));
+
+        let mut merged: Vec<Sweep> = Vec::new();
+        for part in parts {
+            match merged.pop() {
+                Some(last) if last.elevation_number() == part.elevation_number() => {
+                    merged.push(last.merge(part)?)
+                }
+                Some(last) => merged.extend([last, part]),
+                None => merged.push(part),
+            }
+        }
+        assert_eq!(&merged, scan.sweeps());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stream_sweeps_enforces_limits() -> Result<()> {
+        let volume = volume()?;
+
+        let options = StreamOptions::new().with_limits(DecodeLimits::new().with_max_gate_count(20));
+        assert!(volume.stream_sweeps(&options, |_| {}).is_err());
+
+        let options = StreamOptions::new()
+            .with_limits(DecodeLimits::new().with_max_decompressed_record_bytes(1024));
+        assert!(matches!(
+            volume.stream_sweeps(&options, |_| {}),
+            Err(Error::DecompressedRecordTooLarge(1024))
+        ));
+
+        Ok(())
+    }
+}
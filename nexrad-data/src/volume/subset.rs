@@ -0,0 +1,306 @@
+use crate::result::Result;
+use crate::volume::{File, Header};
+use nexrad_decode::ctm::{CTM_HEADER_SIZE, FIXED_FRAME_SIZE};
+use nexrad_decode::limits::DecodeLimits;
+use nexrad_decode::messages::digital_radar_data::{
+    decode_digital_radar_data_with_limits, encode_digital_radar_data, GenericDataBlock,
+    Message as DigitalRadarDataMessage,
+};
+use nexrad_decode::messages::message_header::MessageHeader;
+use nexrad_decode::messages::{decode_message_header, encode_message_header, MessageType};
+use std::io::{Cursor, ErrorKind, Write};
+
+/// Criteria for reducing a volume to a subset of its data, for example to produce a small,
+/// shareable reproduction of an issue or a compact test fixture. See [File::subset].
+///
+/// By default, nothing is removed from the volume.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VolumeSubset {
+    elevation_numbers: Option<Vec<u8>>,
+    max_range_meters: Option<f64>,
+    message_types: Option<Vec<MessageType>>,
+    limits: DecodeLimits,
+}
+
+impl VolumeSubset {
+    /// Creates a new subset which retains all of a volume's data.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Retain digital radar data only for the given elevation numbers.
+    pub fn with_elevation_numbers(mut self, elevation_numbers: Vec<u8>) -> Self {
+        self.elevation_numbers = Some(elevation_numbers);
+        self
+    }
+
+    /// Truncate moment data to gates whose center is within the given range of the radar.
+    pub fn with_max_range_meters(mut self, max_range_meters: f64) -> Self {
+        self.max_range_meters = Some(max_range_meters);
+        self
+    }
+
+    /// Retain only messages of the given types.
+    pub fn with_message_types(mut self, message_types: Vec<MessageType>) -> Self {
+        self.message_types = Some(message_types);
+        self
+    }
+
+    /// Decode the volume's records within the given limits. By default, [DecodeLimits::new] is
+    /// applied.
+    pub fn with_limits(mut self, limits: DecodeLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// The limits the volume's records are decoded within.
+    pub fn limits(&self) -> &DecodeLimits {
+        &self.limits
+    }
+
+    /// Whether messages of the given type are retained.
+    fn retains_message_type(&self, message_type: MessageType) -> bool {
+        self.message_types
+            .as_ref()
+            .is_none_or(|message_types| message_types.contains(&message_type))
+    }
+
+    /// Whether digital radar data for the given elevation number is retained.
+    fn retains_elevation(&self, elevation_number: u8) -> bool {
+        self.elevation_numbers
+            .as_ref()
+            .is_none_or(|elevation_numbers| elevation_numbers.contains(&elevation_number))
+    }
+
+    /// Reduces a decompressed LDM record's messages, returning the record's new message data.
+    /// Messages other than digital radar data are copied as-is, while digital radar data messages
+    /// are re-encoded with their moment data truncated.
+    fn subset_record(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let mut output = Vec::new();
+
+        let mut reader = Cursor::new(data);
+        while let Ok(mut header) = decode_message_header(&mut reader) {
+            let message_start = reader.position() as usize - size_of::<MessageHeader>();
+            let message_type = header.message_type();
+
+            if message_type == MessageType::RDADigitalRadarDataGenericFormat {
+                let mut message = decode_digital_radar_data_with_limits(&mut reader, &self.limits)?;
+                if !self.retains_message_type(message_type)
+                    || !self.retains_elevation(message.header.elevation_number)
+                {
+                    continue;
+                }
+
+                if let Some(max_range_meters) = self.max_range_meters {
+                    truncate_gates(&mut message, max_range_meters);
+                }
+
+                let mut encoded_message = Vec::new();
+                encode_digital_radar_data(&mut encoded_message, &message)?;
+
+                message.header.radial_length = encoded_message.len() as u16;
+                encoded_message.clear();
+                encode_digital_radar_data(&mut encoded_message, &message)?;
+
+                // Message sizes are given in halfwords, so an odd-length message is padded
+                let mut message_size =
+                    size_of::<MessageHeader>() - CTM_HEADER_SIZE + encoded_message.len();
+                if !message_size.is_multiple_of(2) {
+                    encoded_message.push(0);
+                    message_size += 1;
+                }
+                header.segment_size = (message_size / 2) as u16;
+
+                encode_message_header(&mut output, &header)?;
+                output.write_all(&encoded_message)?;
+                continue;
+            }
+
+            let message_end = message_start + FIXED_FRAME_SIZE;
+            let frame = data
+                .get(message_start..message_end)
+                .ok_or(std::io::Error::from(ErrorKind::UnexpectedEof))?;
+            reader.set_position(message_end as u64);
+
+            if self.retains_message_type(message_type) {
+                output.write_all(frame)?;
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+impl File {
+    /// Produces a new volume file containing a subset of this volume's data. The volume header is
+    /// retained and each LDM record is decompressed, reduced, and recompressed. Records left with no
+    /// messages are omitted.
+    pub fn subset(&self, subset: &VolumeSubset) -> Result<File> {
+        use bzip2::write::BzEncoder;
+        use bzip2::Compression;
+
+        let header_size = size_of::<Header>();
+        let mut data = self.data().get(..header_size).unwrap_or_default().to_vec();
+
+        for record in self.records() {
+            let record = if record.compressed() {
+                record.decompress_with_limits(subset.limits())?
+            } else {
+                record
+            };
+
+            let record_data = subset.subset_record(record.data())?;
+            if record_data.is_empty() {
+                continue;
+            }
+
+            let mut encoder = BzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(&record_data)?;
+            let compressed_data = encoder.finish()?;
+
+            data.extend_from_slice(&(compressed_data.len() as i32).to_be_bytes());
+            data.extend_from_slice(&compressed_data);
+        }
+
+        Ok(File::new(data))
+    }
+}
+
+/// Truncates a digital radar data message's moment data to gates within the given range.
+fn truncate_gates(message: &mut DigitalRadarDataMessage, max_range_meters: f64) {
+    for block in [
+        &mut message.reflectivity_data_block,
+        &mut message.velocity_data_block,
+        &mut message.spectrum_width_data_block,
+        &mut message.differential_reflectivity_data_block,
+        &mut message.differential_phase_data_block,
+        &mut message.correlation_coefficient_data_block,
+        &mut message.specific_diff_phase_data_block,
+    ]
+    .into_iter()
+    .flatten()
+    .chain(&mut message.extension_data_blocks)
+    {
+        truncate_block_gates(block, max_range_meters);
+    }
+}
+
+/// Truncates a generic data block's gates to those within the given range.
+fn truncate_block_gates(block: &mut GenericDataBlock, max_range_meters: f64) {
+    // Both the first gate's range and the gate interval are encoded in meters
+    let first_gate_meters = block.header.data_moment_range as f64;
+    let interval_meters = block.header.data_moment_range_sample_interval as f64;
+
+    let gate_count = if max_range_meters < first_gate_meters {
+        0
+    } else if interval_meters == 0.0 {
+        block.header.number_of_data_moment_gates
+    } else {
+        let gates_in_range = ((max_range_meters - first_gate_meters) / interval_meters) as u64 + 1;
+        gates_in_range.min(block.header.number_of_data_moment_gates as u64) as u16
+    };
+
+    let word_size_bytes = block.header.data_word_size as usize / 8;
+    block.header.number_of_data_moment_gates = gate_count;
+    block
+        .encoded_data
+        .to_mut()
+        .truncate(gate_count as usize * word_size_bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subset_retains_header_and_drops_empty_records() -> Result<()> {
+        let mut data = b"AR2V0006.001".to_vec();
+        data.extend_from_slice(&[0; 12]);
+
+        let file = File::new(data.clone());
+        let subset = file.subset(&VolumeSubset::new().with_elevation_numbers(vec![1]))?;
+        assert_eq!(subset.data(), &data);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_subset_message_types() -> Result<()> {
+        let mut record = Vec::new();
+        for message_type in [
+            MessageType::RDAStatusData,
+            MessageType::RDAPerformanceMaintenanceData,
+        ] {
+            let mut frame = vec![0; FIXED_FRAME_SIZE];
+            frame[CTM_HEADER_SIZE + 3] = message_type as u8;
+            record.extend_from_slice(&frame);
+        }
+
+        let subset = VolumeSubset::new().with_message_types(vec![MessageType::RDAStatusData]);
+        let subset_record = subset.subset_record(&record)?;
+
+        assert_eq!(subset_record, record[..FIXED_FRAME_SIZE]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_subset_enforces_limits() -> Result<()> {
+        use crate::result::Error;
+        use crate::volume::SyntheticVolume;
+
+        let volume = SyntheticVolume::new()
+            .with_elevation_count(1)
+            .with_radials_per_sweep(10)
+            .with_gate_count(40)
+            .generate()?;
+
+        let subset = VolumeSubset::new().with_limits(DecodeLimits::new().with_max_gate_count(20));
+        assert!(volume.subset(&subset).is_err());
+
+        let subset = VolumeSubset::new()
+            .with_limits(DecodeLimits::new().with_max_decompressed_record_bytes(1024));
+        assert!(matches!(
+            volume.subset(&subset),
+            Err(Error::DecompressedRecordTooLarge(1024))
+        ));
+
+        assert!(volume.subset(&VolumeSubset::new()).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_subset_pads_odd_length_messages() -> std::result::Result<(), Box<dyn std::error::Error>>
+    {
+        use crate::volume::SyntheticVolume;
+
+        let volume = SyntheticVolume::new()
+            .with_elevation_count(1)
+            .with_radials_per_sweep(10)
+            .with_gate_count(40)
+            .generate()?;
+
+        // Truncating to an odd number of 8-bit gates leaves each radar data message an odd length
+        let subset = volume.subset(&VolumeSubset::new().with_max_range_meters(4_625.0))?;
+
+        let mut radial_count = 0;
+        for record in subset.records() {
+            let record = record.decompress()?;
+            for message in record.messages()? {
+                if message.header.message_type() != MessageType::RDADigitalRadarDataGenericFormat {
+                    continue;
+                }
+
+                let byte_range = message.byte_range.ok_or("missing byte range")?;
+                let message_size = byte_range.len() - CTM_HEADER_SIZE;
+                assert!(message_size.is_multiple_of(2));
+                assert_eq!(message.header.segment_size as usize * 2, message_size);
+                radial_count += 1;
+            }
+        }
+        assert_eq!(radial_count, 10);
+
+        Ok(())
+    }
+}
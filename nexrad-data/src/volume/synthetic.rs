@@ -0,0 +1,618 @@
+use crate::result::Result;
+use crate::volume::File;
+use nexrad_decode::ctm::CTM_HEADER_SIZE;
+use nexrad_decode::messages::digital_radar_data::{
+    encode_digital_radar_data, DataBlockId, ElevationDataBlock, GenericDataBlock,
+    GenericDataBlockHeader, Header as RadarDataHeader, Message as RadarDataMessage,
+    RadialDataBlock, VolumeDataBlock,
+};
+use nexrad_decode::messages::message_header::MessageHeader;
+use nexrad_decode::messages::volume_coverage_pattern::{
+    ElevationDataBlock as CoveragePatternElevation, Header as CoveragePatternHeader,
+    Message as CoveragePatternMessage,
+};
+use nexrad_decode::messages::{encode_message, encode_message_header, Message, MessageType};
+use std::io::Write;
+
+/// The number of digital radar data messages in each LDM record, matching operational volumes.
+const RADIALS_PER_RECORD: usize = 120;
+
+/// The modified Julian date of the synthetic volume's collection, 1 January 2024.
+const COLLECTION_DATE: u16 = 19724;
+
+/// The time each synthetic sweep takes to collect in milliseconds.
+const SWEEP_DURATION_MILLISECONDS: u32 = 20_000;
+
+/// The synthetic volume's radar site identifier.
+const SITE_IDENTIFIER: &[u8; 4] = b"KSYN";
+
+/// The synthetic volume's volume coverage pattern number.
+const COVERAGE_PATTERN_NUMBER: u16 = 212;
+
+/// Elevation angles in degrees for the synthetic volume's sweeps, following operational VCPs.
+const ELEVATION_ANGLES: [f32; 14] = [
+    0.5, 0.9, 1.3, 1.8, 2.4, 3.1, 4.0, 5.1, 6.4, 8.0, 10.0, 12.5, 15.6, 19.5,
+];
+
+/// The range to the center of the first gate in meters.
+const FIRST_GATE_RANGE_METERS: u16 = 2125;
+
+/// The gate interval in meters.
+const GATE_INTERVAL_METERS: u16 = 250;
+
+/// A moment included in synthetic radials with its encoding parameters.
+#[derive(Debug, Clone, Copy)]
+struct MomentDefinition {
+    name: [u8; 3],
+    word_size: u8,
+    scale: f32,
+    offset: f32,
+}
+
+/// The moments included in every synthetic radial.
+const MOMENTS: [MomentDefinition; 3] = [
+    MomentDefinition {
+        name: *b"REF",
+        word_size: 8,
+        scale: 2.0,
+        offset: 66.0,
+    },
+    MomentDefinition {
+        name: *b"VEL",
+        word_size: 8,
+        scale: 2.0,
+        offset: 129.0,
+    },
+    MomentDefinition {
+        name: *b"SW ",
+        word_size: 8,
+        scale: 2.0,
+        offset: 129.0,
+    },
+];
+
+/// The moments included in synthetic radials with dual polarization.
+const DUAL_POLARIZATION_MOMENTS: [MomentDefinition; 3] = [
+    MomentDefinition {
+        name: *b"ZDR",
+        word_size: 8,
+        scale: 16.0,
+        offset: 128.0,
+    },
+    MomentDefinition {
+        name: *b"PHI",
+        word_size: 16,
+        scale: 2.8361,
+        offset: 2.0,
+    },
+    MomentDefinition {
+        name: *b"RHO",
+        word_size: 8,
+        scale: 300.0,
+        offset: -60.5,
+    },
+];
+
+/// A generator for synthetic but structurally-valid Archive II volumes, allowing benchmarks and
+/// tests to exercise decoding without depending on downloaded data. Volumes are generated
+/// deterministically for a given configuration and seed.
+///
+/// A generated volume contains a metadata record with a volume coverage pattern message followed by
+/// records of digital radar data messages, one per radial, for each configured elevation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SyntheticVolume {
+    elevation_count: u8,
+    radials_per_sweep: u16,
+    gate_count: u16,
+    dual_polarization: bool,
+    compressed: bool,
+    seed: u64,
+    coverage_pattern_number: u16,
+}
+
+impl Default for SyntheticVolume {
+    fn default() -> Self {
+        Self {
+            elevation_count: 4,
+            radials_per_sweep: 360,
+            gate_count: 460,
+            dual_polarization: true,
+            compressed: true,
+            seed: 0,
+            coverage_pattern_number: COVERAGE_PATTERN_NUMBER,
+        }
+    }
+}
+
+impl SyntheticVolume {
+    /// Creates a new synthetic volume generator with a small default configuration of 4
+    /// elevations, 360 radials per sweep, 460 gates per radial, dual polarization, and compression.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of elevation sweeps in the volume.
+    pub fn with_elevation_count(mut self, elevation_count: u8) -> Self {
+        self.elevation_count = elevation_count;
+        self
+    }
+
+    /// Sets the number of radials in each sweep, which are evenly spaced in azimuth.
+    pub fn with_radials_per_sweep(mut self, radials_per_sweep: u16) -> Self {
+        self.radials_per_sweep = radials_per_sweep;
+        self
+    }
+
+    /// Sets the number of gates in each radial's moment data.
+    pub fn with_gate_count(mut self, gate_count: u16) -> Self {
+        self.gate_count = gate_count;
+        self
+    }
+
+    /// Sets whether radials include the dual polarization moments: differential reflectivity,
+    /// differential phase, and correlation coefficient.
+    pub fn with_dual_polarization(mut self, dual_polarization: bool) -> Self {
+        self.dual_polarization = dual_polarization;
+        self
+    }
+
+    /// Sets whether the volume's LDM records are bzip2-compressed as they are operationally.
+    pub fn with_compression(mut self, compressed: bool) -> Self {
+        self.compressed = compressed;
+        self
+    }
+
+    /// Sets the seed used to generate the volume's moment data.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the volume coverage pattern number reported by the volume, which does not otherwise
+    /// affect its elevations.
+    pub fn with_coverage_pattern_number(mut self, coverage_pattern_number: u16) -> Self {
+        self.coverage_pattern_number = coverage_pattern_number;
+        self
+    }
+
+    /// Generates the synthetic volume file.
+    pub fn generate(&self) -> Result<File> {
+        let mut data = Vec::new();
+        data.extend_from_slice(b"AR2V0006.");
+        data.extend_from_slice(b"001");
+        data.extend_from_slice(&(COLLECTION_DATE as u32).to_be_bytes());
+        data.extend_from_slice(&0u32.to_be_bytes());
+        data.extend_from_slice(SITE_IDENTIFIER);
+
+        let mut sequence_number = 0;
+        let mut next_header = |message_type: MessageType, time: u32, message_size: usize| {
+            sequence_number += 1;
+            MessageHeader::new(
+                message_type,
+                8,
+                sequence_number,
+                COLLECTION_DATE,
+                time,
+                (size_of::<MessageHeader>() - CTM_HEADER_SIZE + message_size).div_ceil(2) as u16,
+                1,
+                1,
+            )
+        };
+
+        let mut metadata_record = Vec::new();
+        let coverage_pattern = Message::VolumeCoveragePattern(Box::new(self.coverage_pattern()));
+        let mut coverage_pattern_data = Vec::new();
+        encode_message(&mut coverage_pattern_data, &coverage_pattern)?;
+        let header = next_header(
+            MessageType::RDAVolumeCoveragePattern,
+            0,
+            coverage_pattern_data.len(),
+        );
+        encode_message_header(&mut metadata_record, &header)?;
+        metadata_record.extend_from_slice(&coverage_pattern_data);
+        self.write_record(&mut data, &metadata_record)?;
+
+        let mut random = SplitMix64(self.seed);
+        let mut record = Vec::new();
+        let mut record_radials = 0;
+        for elevation_index in 0..self.elevation_count {
+            for azimuth_index in 0..self.radials_per_sweep {
+                let mut message = self.radial(elevation_index, azimuth_index, &mut random);
+
+                let mut message_data = Vec::new();
+                encode_digital_radar_data(&mut message_data, &message)?;
+                message.header.radial_length = message_data.len() as u16;
+                message_data.clear();
+                encode_digital_radar_data(&mut message_data, &message)?;
+
+                let header = next_header(
+                    MessageType::RDADigitalRadarDataGenericFormat,
+                    message.header.time,
+                    message_data.len(),
+                );
+                encode_message_header(&mut record, &header)?;
+                record.extend_from_slice(&message_data);
+
+                record_radials += 1;
+                if record_radials == RADIALS_PER_RECORD {
+                    self.write_record(&mut data, &record)?;
+                    record.clear();
+                    record_radials = 0;
+                }
+            }
+        }
+
+        if !record.is_empty() {
+            self.write_record(&mut data, &record)?;
+        }
+
+        Ok(File::new(data))
+    }
+
+    /// Appends an LDM record containing the given messages to the volume data.
+    fn write_record(&self, data: &mut Vec<u8>, messages: &[u8]) -> Result<()> {
+        if !self.compressed {
+            data.extend_from_slice(&(messages.len() as i32).to_be_bytes());
+            data.extend_from_slice(messages);
+            return Ok(());
+        }
+
+        use bzip2::write::BzEncoder;
+        use bzip2::Compression;
+
+        let mut encoder = BzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(messages)?;
+        let compressed_messages = encoder.finish()?;
+
+        data.extend_from_slice(&(compressed_messages.len() as i32).to_be_bytes());
+        data.extend_from_slice(&compressed_messages);
+
+        Ok(())
+    }
+
+    /// The elevation angle in degrees of the given elevation sweep.
+    fn elevation_angle(elevation_index: u8) -> f32 {
+        let last_index = ELEVATION_ANGLES.len() - 1;
+        match ELEVATION_ANGLES.get(elevation_index as usize) {
+            Some(angle) => *angle,
+            None => {
+                let additional_elevations = (elevation_index as usize - last_index) as f32;
+                (ELEVATION_ANGLES[last_index] + additional_elevations * 2.0).min(60.0)
+            }
+        }
+    }
+
+    /// The volume coverage pattern message describing the synthetic volume's elevations.
+    fn coverage_pattern(&self) -> CoveragePatternMessage {
+        let elevations = (0..self.elevation_count)
+            .map(|elevation_index| CoveragePatternElevation {
+                elevation_angle: encode_angle(Self::elevation_angle(elevation_index)),
+                channel_configuration: 2,
+                waveform_type: if elevation_index < 2 { 1 } else { 4 },
+                super_resolution_control: 0,
+                surveillance_prf_number: 1,
+                surveillance_prf_pulse_count_radial: 28,
+                azimuth_rate: encode_angular_velocity(18.0),
+                reflectivity_threshold: 16,
+                velocity_threshold: 28,
+                spectrum_width_threshold: 28,
+                differential_reflectivity_threshold: 28,
+                differential_phase_threshold: 28,
+                correlation_coefficient_threshold: 28,
+                sector_1_edge_angle: 0,
+                sector_1_doppler_prf_number: 0,
+                sector_1_doppler_prf_pulse_count_radial: 0,
+                supplemental_data: 0,
+                sector_2_edge_angle: 0,
+                sector_2_doppler_prf_number: 0,
+                sector_2_doppler_prf_pulse_count_radial: 0,
+                ebc_angle: 0,
+                sector_3_edge_angle: 0,
+                sector_3_doppler_prf_number: 0,
+                sector_3_doppler_prf_pulse_count_radial: 0,
+                reserved: 0,
+            })
+            .collect::<Vec<_>>();
+
+        let mut header = CoveragePatternHeader {
+            message_size: 0,
+            pattern_type: 2,
+            pattern_number: self.coverage_pattern_number,
+            number_of_elevation_cuts: elevations.len() as u16,
+            version: 1,
+            clutter_map_group_number: 1,
+            doppler_velocity_resolution: 2,
+            pulse_width: 2,
+            reserved_1: 0,
+            vcp_sequencing: 0,
+            vcp_supplemental_data: 0,
+            reserved_2: 0,
+        };
+
+        let header_size = size_of_serialized(&header);
+        let elevation_size = elevations.first().map(size_of_serialized).unwrap_or(0);
+        header.message_size = ((header_size + elevation_size * elevations.len()) / 2) as u16;
+
+        CoveragePatternMessage { header, elevations }
+    }
+
+    /// A digital radar data message for the given elevation sweep and azimuth.
+    fn radial(
+        &self,
+        elevation_index: u8,
+        azimuth_index: u16,
+        random: &mut SplitMix64,
+    ) -> RadarDataMessage<'static> {
+        let azimuth_spacing = 360.0 / self.radials_per_sweep.max(1) as f32;
+
+        let first_radial = azimuth_index == 0;
+        let last_radial = azimuth_index + 1 == self.radials_per_sweep;
+        let radial_status = match (first_radial, last_radial) {
+            (true, _) if elevation_index == 0 => 3,
+            (_, true) if elevation_index + 1 == self.elevation_count => 4,
+            (true, _) => 0,
+            (_, true) => 2,
+            _ => 1,
+        };
+
+        let sweep_time = elevation_index as u32 * SWEEP_DURATION_MILLISECONDS;
+        let radial_time = (SWEEP_DURATION_MILLISECONDS as u64 * azimuth_index as u64
+            / self.radials_per_sweep.max(1) as u64) as u32;
+
+        let mut moments = MOMENTS.to_vec();
+        if self.dual_polarization {
+            moments.extend(DUAL_POLARIZATION_MOMENTS);
+        }
+
+        let mut generic_blocks = moments
+            .iter()
+            .map(|moment| self.moment(moment, elevation_index, azimuth_index, random));
+
+        let mut message = RadarDataMessage {
+            header: RadarDataHeader {
+                radar_identifier: *SITE_IDENTIFIER,
+                time: sweep_time + radial_time,
+                date: COLLECTION_DATE,
+                azimuth_number: azimuth_index + 1,
+                azimuth_angle: (azimuth_index as f32 + 0.5) * azimuth_spacing,
+                compression_indicator: 0,
+                spare: 0,
+                radial_length: 0,
+                azimuth_resolution_spacing: (azimuth_spacing / 0.5).round() as u8,
+                radial_status,
+                elevation_number: elevation_index + 1,
+                cut_sector_number: 1,
+                elevation_angle: Self::elevation_angle(elevation_index),
+                radial_spot_blanking_status: 0,
+                azimuth_indexing_mode: 0,
+                data_block_count: 0,
+            },
+            volume_data_block: Some(VolumeDataBlock {
+                data_block_id: block_id(b'R', b"VOL"),
+                lrtup: 44,
+                major_version_number: 2,
+                minor_version_number: 0,
+                latitude: 41.7311,
+                longitude: -93.7228,
+                site_height: 299,
+                feedhorn_height: 20,
+                calibration_constant: -44.625,
+                horizontal_shv_tx_power: 330.5,
+                vertical_shv_tx_power: 325.5,
+                system_differential_reflectivity: 0.3,
+                initial_system_differential_phase: 60.0,
+                volume_coverage_pattern_number: self.coverage_pattern_number,
+                processing_status: 0,
+                zdr_bias_estimate_weighted_mean: 0,
+                spare: [0; 6],
+            }),
+            elevation_data_block: Some(ElevationDataBlock {
+                data_block_id: block_id(b'R', b"ELV"),
+                lrtup: 12,
+                atmos: -12,
+                calibration_constant: -44.625,
+            }),
+            radial_data_block: Some(RadialDataBlock {
+                data_block_id: block_id(b'R', b"RAD"),
+                lrtup: 28,
+                unambiguous_range: 4660,
+                horizontal_channel_noise_level: -81.5,
+                vertical_channel_noise_level: -81.2,
+                nyquist_velocity: 2650,
+                radial_flags: 0,
+                horizontal_channel_calibration_constant: -44.625,
+                vertical_channel_calibration_constant: -44.625,
+            }),
+            reflectivity_data_block: generic_blocks.next(),
+            velocity_data_block: generic_blocks.next(),
+            spectrum_width_data_block: generic_blocks.next(),
+            differential_reflectivity_data_block: generic_blocks.next(),
+            differential_phase_data_block: generic_blocks.next(),
+            correlation_coefficient_data_block: generic_blocks.next(),
+            specific_diff_phase_data_block: None,
+            extension_data_blocks: Vec::new(),
+            unknown_data_blocks: Vec::new(),
+        };
+
+        message.header.data_block_count = 3 + if self.dual_polarization { 6 } else { 3 };
+        message
+    }
+
+    /// A moment's data block for the given radial. Values follow a banded pattern with pockets of
+    /// below-threshold gates and a small amount of noise so that the data compresses comparably to
+    /// operational data.
+    fn moment(
+        &self,
+        moment: &MomentDefinition,
+        elevation_index: u8,
+        azimuth_index: u16,
+        random: &mut SplitMix64,
+    ) -> GenericDataBlock<'static> {
+        let word_size_bytes = moment.word_size as usize / 8;
+        let maximum_value = if moment.word_size == 8 { 255 } else { 1023 };
+
+        let mut encoded_data = Vec::with_capacity(self.gate_count as usize * word_size_bytes);
+        for gate_index in 0..self.gate_count as u64 {
+            let below_threshold = (gate_index / 32 + azimuth_index as u64 / 16).is_multiple_of(5);
+            let value = if below_threshold {
+                0
+            } else {
+                let banded =
+                    azimuth_index as u64 * 7 + gate_index * 3 + elevation_index as u64 * 11;
+                2 + (banded + random.next() % 8) % (maximum_value - 1)
+            };
+
+            match word_size_bytes {
+                1 => encoded_data.push(value as u8),
+                _ => encoded_data.extend_from_slice(&(value as u16).to_be_bytes()),
+            }
+        }
+
+        GenericDataBlock {
+            header: GenericDataBlockHeader {
+                data_block_id: block_id(b'D', &moment.name),
+                reserved: 0,
+                number_of_data_moment_gates: self.gate_count,
+                data_moment_range: FIRST_GATE_RANGE_METERS,
+                data_moment_range_sample_interval: GATE_INTERVAL_METERS,
+                tover: 50,
+                snr_threshold: 16,
+                control_flags: 0,
+                data_word_size: moment.word_size,
+                scale: moment.scale,
+                offset: moment.offset,
+            },
+            encoded_data: encoded_data.into(),
+        }
+    }
+}
+
+/// A data block identifier with the given type and name.
+fn block_id(data_block_type: u8, data_name: &[u8; 3]) -> DataBlockId {
+    DataBlockId {
+        data_block_type,
+        data_name: *data_name,
+    }
+}
+
+/// Encodes an angle in degrees as defined in table III-A of ICD 2620002W.
+fn encode_angle(degrees: f32) -> u16 {
+    ((degrees as f64 / 180.0 * 32768.0) as u16) & !0b111
+}
+
+/// Encodes an angular velocity in degrees per second as defined in table XI-D of ICD 2620002W.
+fn encode_angular_velocity(degrees_per_second: f32) -> u16 {
+    ((degrees_per_second as f64 / 22.5 * 16384.0) as u16) & 0x7FF8
+}
+
+/// The size of a fixed-size message structure when encoded.
+fn size_of_serialized<S: serde::Serialize>(value: &S) -> usize {
+    bincode::serialized_size(value).unwrap_or(0) as usize
+}
+
+/// A small, deterministic pseudo-random number generator.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut value = self.0;
+        value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        value ^ (value >> 31)
+    }
+}
+
+#[cfg(all(test, feature = "nexrad-model"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_synthetic_volume_scan() -> Result<()> {
+        let volume = SyntheticVolume::new()
+            .with_elevation_count(3)
+            .with_radials_per_sweep(720)
+            .with_gate_count(100)
+            .generate()?;
+
+        assert_eq!(volume.header()?.icao_of_radar(), Some("KSYN".to_string()));
+
+        let scan = volume.scan()?;
+        assert_eq!(scan.coverage_pattern_number(), COVERAGE_PATTERN_NUMBER);
+        assert_eq!(scan.sweeps().len(), 3);
+        for sweep in scan.sweeps() {
+            assert_eq!(sweep.radials().len(), 720);
+
+            let radial = &sweep.radials()[0];
+            assert_eq!(radial.azimuth_spacing_degrees(), 0.5);
+            assert!(radial.correlation_coefficient().is_some());
+            assert_eq!(
+                radial.reflectivity().map(|moment| moment.values().len()),
+                Some(100)
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_synthetic_volume_uncompressed() -> Result<()> {
+        let configuration = SyntheticVolume::new()
+            .with_elevation_count(1)
+            .with_gate_count(10)
+            .with_dual_polarization(false);
+
+        let compressed = configuration.generate()?;
+        let uncompressed = configuration.with_compression(false).generate()?;
+        assert!(uncompressed.data().len() > compressed.data().len());
+
+        let radials = uncompressed.scan()?.sweeps()[0].radials().clone();
+        assert_eq!(radials, compressed.scan()?.sweeps()[0].radials().clone());
+        assert!(radials[0].differential_reflectivity().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_synthetic_volume_deterministic() -> Result<()> {
+        let configuration = SyntheticVolume::new().with_elevation_count(1).with_seed(7);
+        assert_eq!(configuration.generate()?, configuration.generate()?);
+        assert_ne!(
+            configuration.generate()?,
+            configuration.with_seed(8).generate()?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_synthetic_volume_subset() -> Result<()> {
+        use crate::volume::VolumeSubset;
+
+        let volume = SyntheticVolume::new().with_elevation_count(3).generate()?;
+        let subset = volume.subset(
+            &VolumeSubset::new()
+                .with_elevation_numbers(vec![2])
+                .with_max_range_meters(10_000.0),
+        )?;
+        assert!(subset.data().len() < volume.data().len());
+
+        let scan = subset.scan()?;
+        assert_eq!(scan.sweeps().len(), 1);
+        assert_eq!(scan.sweeps()[0].elevation_number(), 2);
+        assert_eq!(
+            scan.sweeps()[0].radials()[0]
+                .reflectivity()
+                .map(|moment| moment.values().len()),
+            Some(32)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_angle() {
+        assert_eq!(encode_angle(0.5), 88);
+        assert_eq!(encode_angular_velocity(22.5), 16384);
+    }
+}
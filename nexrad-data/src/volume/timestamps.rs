@@ -0,0 +1,249 @@
+use crate::result::Result;
+use crate::volume::File;
+use chrono::{DateTime, TimeDelta, Utc};
+use nexrad_model::data::Scan;
+
+/// Limits for checking a volume's timestamps. See [check_timestamps].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimestampCheckOptions {
+    max_drift: TimeDelta,
+    max_duration: TimeDelta,
+}
+
+impl TimestampCheckOptions {
+    /// Creates options which allow the first radial to be collected within one minute of the
+    /// volume's nominal time and the volume to span at most twenty minutes, which accommodates
+    /// every operational coverage pattern.
+    pub fn new() -> Self {
+        Self {
+            max_drift: TimeDelta::minutes(1),
+            max_duration: TimeDelta::minutes(20),
+        }
+    }
+
+    /// The maximum difference between the first radial's collection time and the volume's nominal
+    /// time, in either direction.
+    pub fn with_max_drift(mut self, max_drift: TimeDelta) -> Self {
+        self.max_drift = max_drift;
+        self
+    }
+
+    /// The maximum time between the volume's earliest and latest radials.
+    pub fn with_max_duration(mut self, max_duration: TimeDelta) -> Self {
+        self.max_duration = max_duration;
+        self
+    }
+}
+
+impl Default for TimestampCheckOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An implausible timestamp found in a volume, suggesting the radar site's clock was wrong.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TimestampFinding {
+    /// The volume has no nominal time to compare its radials against.
+    MissingNominalTime,
+    /// The first radial was collected too long before or after the volume's nominal time.
+    Drift {
+        nominal_time: DateTime<Utc>,
+        first_radial_time: DateTime<Utc>,
+    },
+    /// A radial was collected before the radial preceding it.
+    NonMonotonic {
+        elevation_number: u8,
+        azimuth_number: u16,
+        previous_time: DateTime<Utc>,
+        time: DateTime<Utc>,
+    },
+    /// The volume's radials span longer than any coverage pattern takes to complete.
+    ExcessiveDuration {
+        earliest_radial_time: DateTime<Utc>,
+        latest_radial_time: DateTime<Utc>,
+    },
+    /// A radial's collection timestamp is outside the representable range.
+    InvalidRadialTime {
+        elevation_number: u8,
+        azimuth_number: u16,
+    },
+}
+
+impl File {
+    /// Decodes this volume and checks its radials' collection times against the nominal time in
+    /// its volume header. See [check_timestamps].
+    pub fn check_timestamps(
+        &self,
+        options: &TimestampCheckOptions,
+    ) -> Result<Vec<TimestampFinding>> {
+        let scan = self.scan()?;
+        Ok(match self.header()?.date_time() {
+            Some(nominal_time) => check_timestamps(&scan, nominal_time, options),
+            None => vec![TimestampFinding::MissingNominalTime],
+        })
+    }
+}
+
+/// Checks a scan's radial collection times against the volume's nominal time, such as the time
+/// in its archive key or volume header, returning any implausible timestamps in the order they
+/// were found. Radials are expected to be in collection order, as decoded.
+pub fn check_timestamps(
+    scan: &Scan,
+    nominal_time: DateTime<Utc>,
+    options: &TimestampCheckOptions,
+) -> Vec<TimestampFinding> {
+    let mut findings = Vec::new();
+    let mut first_time = None;
+    let mut previous_time = None;
+    let mut time_range: Option<(DateTime<Utc>, DateTime<Utc>)> = None;
+
+    for radial in scan.sweeps().iter().flat_map(|sweep| sweep.radials()) {
+        let Some(time) = DateTime::from_timestamp_millis(radial.collection_timestamp()) else {
+            findings.push(TimestampFinding::InvalidRadialTime {
+                elevation_number: radial.elevation_number(),
+                azimuth_number: radial.azimuth_number(),
+            });
+            continue;
+        };
+
+        if first_time.is_none() {
+            first_time = Some(time);
+            if (time - nominal_time).abs() > options.max_drift {
+                findings.push(TimestampFinding::Drift {
+                    nominal_time,
+                    first_radial_time: time,
+                });
+            }
+        }
+
+        if let Some(previous_time) = previous_time.filter(|previous_time| time < *previous_time) {
+            findings.push(TimestampFinding::NonMonotonic {
+                elevation_number: radial.elevation_number(),
+                azimuth_number: radial.azimuth_number(),
+                previous_time,
+                time,
+            });
+        }
+        previous_time = Some(time);
+
+        time_range = Some(match time_range {
+            Some((earliest, latest)) => (earliest.min(time), latest.max(time)),
+            None => (time, time),
+        });
+    }
+
+    if let Some((earliest_radial_time, latest_radial_time)) = time_range {
+        if latest_radial_time - earliest_radial_time > options.max_duration {
+            findings.push(TimestampFinding::ExcessiveDuration {
+                earliest_radial_time,
+                latest_radial_time,
+            });
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nexrad_model::data::{Radial, RadialStatus, Sweep};
+
+    fn radial(timestamp: i64, azimuth_number: u16) -> Radial {
+        Radial::new(
+            timestamp,
+            azimuth_number,
+            azimuth_number as f32,
+            1.0,
+            RadialStatus::IntermediateRadialData,
+            1,
+            0.5,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_check_timestamps() -> Result<()> {
+        let nominal_time = DateTime::from_timestamp_millis(1_700_000_000_000).unwrap_or_default();
+        let start = nominal_time.timestamp_millis();
+        let options = TimestampCheckOptions::new();
+
+        let scan = Scan::new(
+            212,
+            vec![Sweep::new(
+                1,
+                vec![radial(start + 2_000, 1), radial(start + 2_100, 2)],
+            )],
+        );
+        assert!(check_timestamps(&scan, nominal_time, &options).is_empty());
+
+        let scan = Scan::new(
+            212,
+            vec![Sweep::new(
+                1,
+                vec![
+                    radial(start + 120_000, 1),
+                    radial(start + 110_000, 2),
+                    radial(start + 1_500_000, 3),
+                ],
+            )],
+        );
+        let findings = check_timestamps(&scan, nominal_time, &options);
+        assert!(matches!(
+            findings.as_slice(),
+            [
+                TimestampFinding::Drift { .. },
+                TimestampFinding::NonMonotonic {
+                    azimuth_number: 2,
+                    ..
+                },
+                TimestampFinding::ExcessiveDuration { .. },
+            ]
+        ));
+
+        // An out-of-order radial extends the volume's span even when the first and last are close
+        let scan = Scan::new(
+            212,
+            vec![Sweep::new(
+                1,
+                vec![
+                    radial(start + 2_000, 1),
+                    radial(start - 1_500_000, 2),
+                    radial(start + 3_000, 3),
+                ],
+            )],
+        );
+        let findings = check_timestamps(&scan, nominal_time, &options);
+        assert_eq!(
+            findings.last(),
+            Some(&TimestampFinding::ExcessiveDuration {
+                earliest_radial_time: nominal_time - TimeDelta::milliseconds(1_500_000),
+                latest_radial_time: nominal_time + TimeDelta::milliseconds(3_000),
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_synthetic_volume_timestamps() -> Result<()> {
+        let volume = crate::volume::SyntheticVolume::new()
+            .with_elevation_count(2)
+            .with_radials_per_sweep(240)
+            .with_gate_count(20)
+            .generate()?;
+
+        assert!(volume
+            .check_timestamps(&TimestampCheckOptions::new())?
+            .is_empty());
+
+        Ok(())
+    }
+}
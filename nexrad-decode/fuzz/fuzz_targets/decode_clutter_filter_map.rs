@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nexrad_decode::messages::clutter_filter_map::decode_clutter_filter_map;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(message) = decode_clutter_filter_map(&mut &data[..]) {
+        let _ = format!("{:?}", message);
+    }
+});
@@ -0,0 +1,24 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nexrad_decode::messages::digital_radar_data::{
+    decode_digital_radar_data, decode_digital_radar_data_borrowed,
+};
+use std::io::Cursor;
+
+fuzz_target!(|data: &[u8]| {
+    let owned = decode_digital_radar_data(&mut Cursor::new(data));
+    let borrowed = decode_digital_radar_data_borrowed(&mut Cursor::new(data));
+    assert_eq!(owned.is_ok(), borrowed.is_ok());
+
+    if let (Ok(message), Ok(borrowed)) = (owned, borrowed) {
+        assert_eq!(message, borrowed.into_owned());
+        let _ = format!("{:?}", message);
+
+        if let Some(block) = &message.reflectivity_data_block {
+            let _ = block.decoded_values();
+        }
+
+        let _ = message.into_radial();
+    }
+});
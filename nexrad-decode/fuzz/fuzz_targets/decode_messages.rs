@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nexrad_decode::messages::decode_messages;
+use std::io::Cursor;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(messages) = decode_messages(&mut Cursor::new(data)) {
+        for message in messages {
+            // Formatting exercises each of the messages' field accessors
+            let _ = format!("{:?}", message);
+        }
+    }
+});
@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nexrad_decode::messages::rda_status_data::decode_rda_status_message;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(message) = decode_rda_status_message(&mut &data[..]) {
+        let _ = format!("{:?}", message);
+    }
+});
@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nexrad_decode::messages::volume_coverage_pattern::decode_volume_coverage_pattern;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(message) = decode_volume_coverage_pattern(&mut &data[..]) {
+        let _ = format!("{:?}", message);
+    }
+});
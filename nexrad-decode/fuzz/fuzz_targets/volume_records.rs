@@ -0,0 +1,24 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nexrad_data::volume::File;
+use nexrad_decode::limits::DecodeLimits;
+
+fuzz_target!(|data: &[u8]| {
+    let file = File::new(data.to_vec());
+    let _ = file.header();
+    let limits = DecodeLimits::new();
+
+    for mut record in file.records() {
+        if record.compressed() {
+            match record.decompress_with_limits(&limits) {
+                Ok(decompressed) => record = decompressed,
+                Err(_) => continue,
+            }
+        }
+
+        if let Ok(messages) = record.messages_with_limits(&limits) {
+            let _ = format!("{:?}", messages);
+        }
+    }
+});
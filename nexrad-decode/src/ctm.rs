@@ -0,0 +1,309 @@
+//!
+//! Low-level access to the framing of NEXRAD Level II messages. Each message is preceded by a
+//! 12-byte Channel Terminal Manager (CTM) header, a holdover from legacy RDA hardware which
+//! carries no meaningful data in modern archives. Fixed-length messages occupy a 2432-byte frame
+//! including this header, while digital radar data (message type 31) frames are sized by their
+//! message header's segment size.
+//!
+//! [decode_messages](crate::messages::decode_messages) fails on the first malformed message.
+//! [frames] instead splits data into frames without decoding their contents and reports malformed
+//! frames individually, so data can be recovered from nonstandard sources such as legacy tape
+//! dumps where the strict path rejects a record.
+//!
+
+use crate::messages::message_header::MessageHeader;
+use crate::messages::{decode_message_borrowed, Message, MessageType};
+use crate::result::Result;
+use crate::util::deserialize;
+use std::io::Cursor;
+
+/// The size of the CTM header preceding each message header.
+pub const CTM_HEADER_SIZE: usize = 12;
+
+/// The size of a fixed-length message's frame, including its CTM and message headers.
+pub const FIXED_FRAME_SIZE: usize = 2432;
+
+/// A single message frame, borrowed from the data it was split from.
+#[derive(Clone, PartialEq)]
+pub struct Frame<'a> {
+    offset: usize,
+    data: &'a [u8],
+    header: MessageHeader,
+}
+
+impl<'a> Frame<'a> {
+    /// The frame's offset within the data it was split from.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The frame's full data, including its CTM and message headers.
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// The frame's raw CTM header.
+    pub fn ctm_header(&self) -> &'a [u8] {
+        &self.data[..CTM_HEADER_SIZE]
+    }
+
+    /// The frame's decoded message header.
+    pub fn header(&self) -> &MessageHeader {
+        &self.header
+    }
+
+    /// The frame's message data following its message header. Fixed-length frames include any
+    /// padding to the fixed frame size.
+    pub fn body(&self) -> &'a [u8] {
+        &self.data[size_of::<MessageHeader>()..]
+    }
+
+    /// Decodes the frame's message, borrowing moment data from the frame.
+    pub fn message(&self) -> Result<Message<'a>> {
+        decode_message_borrowed(&mut Cursor::new(self.body()), self.header.message_type())
+    }
+}
+
+impl std::fmt::Debug for Frame<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Frame")
+            .field("offset", &self.offset)
+            .field("data.len()", &self.data.len())
+            .field("header", &self.header)
+            .finish()
+    }
+}
+
+/// A frame which could not be split from the data.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MalformedFrame {
+    /// The offset of the malformed frame within the data.
+    pub offset: usize,
+    /// What was wrong with the frame.
+    pub issue: FrameIssue,
+}
+
+/// Diagnostics for a malformed frame.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FrameIssue {
+    /// Fewer bytes remain than a message header requires. Splitting stops.
+    TruncatedHeader {
+        /// The number of bytes remaining.
+        available: usize,
+    },
+    /// The frame's size extends beyond the end of the data. Splitting stops.
+    TruncatedFrame {
+        /// The frame's size according to its message header.
+        expected: usize,
+        /// The number of bytes remaining.
+        available: usize,
+    },
+    /// A variable-length message's segment size is too small to hold its own message header.
+    /// Splitting resumes after a fixed-size frame.
+    InvalidSegmentSize(u16),
+}
+
+/// Splits data into message frames. Each item is either a frame or a diagnostic for a malformed
+/// frame; iteration continues past malformed frames where their extent can be inferred.
+pub fn frames(data: &[u8]) -> Frames<'_> {
+    Frames {
+        data,
+        position: 0,
+        done: false,
+    }
+}
+
+/// An iterator over the message frames in some data. See [frames].
+#[derive(Debug, Clone)]
+pub struct Frames<'a> {
+    data: &'a [u8],
+    position: usize,
+    done: bool,
+}
+
+impl<'a> Iterator for Frames<'a> {
+    type Item = std::result::Result<Frame<'a>, MalformedFrame>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.position >= self.data.len() {
+            return None;
+        }
+
+        let offset = self.position;
+        match frame_at(self.data, offset) {
+            Ok(frame) => {
+                self.position += frame.data.len();
+                Some(Ok(frame))
+            }
+            Err(issue) => {
+                match issue {
+                    FrameIssue::InvalidSegmentSize(_) => self.position += FIXED_FRAME_SIZE,
+                    _ => self.done = true,
+                }
+                Some(Err(MalformedFrame { offset, issue }))
+            }
+        }
+    }
+}
+
+/// Reads the frame starting at the given offset in the data.
+pub(crate) fn frame_at(data: &[u8], offset: usize) -> std::result::Result<Frame<'_>, FrameIssue> {
+    let remaining = data.get(offset..).unwrap_or_default();
+    let header: MessageHeader = remaining
+        .get(..size_of::<MessageHeader>())
+        .and_then(|mut header_data| deserialize(&mut header_data).ok())
+        .ok_or(FrameIssue::TruncatedHeader {
+            available: remaining.len(),
+        })?;
+
+    let frame_size = if header.message_type() == MessageType::RDADigitalRadarDataGenericFormat {
+        let frame_size = CTM_HEADER_SIZE + header.segment_size as usize * 2;
+        if frame_size < size_of::<MessageHeader>() {
+            return Err(FrameIssue::InvalidSegmentSize(header.segment_size));
+        }
+        frame_size
+    } else {
+        FIXED_FRAME_SIZE
+    };
+
+    let frame_data = remaining
+        .get(..frame_size)
+        .ok_or(FrameIssue::TruncatedFrame {
+            expected: frame_size,
+            available: remaining.len(),
+        })?;
+
+    Ok(Frame {
+        offset,
+        data: frame_data,
+        header,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::digital_radar_data::{self, encode_digital_radar_data};
+    use crate::messages::encode_message_header;
+
+    fn radar_data_frame(segment_size: Option<u16>) -> Result<Vec<u8>> {
+        let message = digital_radar_data::Message::new(digital_radar_data::Header {
+            radar_identifier: *b"KDMX",
+            time: 0,
+            date: 0,
+            azimuth_number: 1,
+            azimuth_angle: 0.0,
+            compression_indicator: 0,
+            spare: 0,
+            radial_length: 0,
+            azimuth_resolution_spacing: 1,
+            radial_status: 0,
+            elevation_number: 1,
+            cut_sector_number: 1,
+            elevation_angle: 0.5,
+            radial_spot_blanking_status: 0,
+            azimuth_indexing_mode: 0,
+            data_block_count: 0,
+        });
+
+        let mut body = Vec::new();
+        encode_digital_radar_data(&mut body, &message)?;
+
+        let segment_size = segment_size
+            .unwrap_or(((size_of::<MessageHeader>() - CTM_HEADER_SIZE + body.len()) / 2) as u16);
+        let header = MessageHeader::new(
+            MessageType::RDADigitalRadarDataGenericFormat,
+            0,
+            0,
+            0,
+            0,
+            segment_size,
+            1,
+            1,
+        );
+
+        let mut frame = Vec::new();
+        encode_message_header(&mut frame, &header)?;
+        frame.extend_from_slice(&body);
+        Ok(frame)
+    }
+
+    fn fixed_frame(message_type: u8) -> Vec<u8> {
+        let mut frame = vec![0; FIXED_FRAME_SIZE];
+        frame[CTM_HEADER_SIZE + 3] = message_type;
+        frame
+    }
+
+    #[test]
+    fn test_frames() -> Result<()> {
+        let mut data = fixed_frame(2);
+        let radar_data = radar_data_frame(None)?;
+        data.extend_from_slice(&radar_data);
+        data.extend_from_slice(&fixed_frame(5));
+
+        let frames = frames(&data).collect::<std::result::Result<Vec<_>, _>>();
+        let Ok(frames) = frames else {
+            panic!("unexpected malformed frame: {frames:?}");
+        };
+
+        let offsets: Vec<usize> = frames.iter().map(Frame::offset).collect();
+        assert_eq!(
+            offsets,
+            vec![0, FIXED_FRAME_SIZE, FIXED_FRAME_SIZE + radar_data.len()]
+        );
+        assert_eq!(frames[1].data(), radar_data.as_slice());
+        assert!(matches!(frames[1].message()?, Message::DigitalRadarData(_)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_frames_invalid_segment_size_resumes() -> Result<()> {
+        let mut data = radar_data_frame(Some(1))?;
+        data.resize(FIXED_FRAME_SIZE, 0);
+        data.extend_from_slice(&fixed_frame(2));
+
+        let items: Vec<_> = frames(&data).collect();
+        assert_eq!(items.len(), 2);
+        assert_eq!(
+            items[0],
+            Err(MalformedFrame {
+                offset: 0,
+                issue: FrameIssue::InvalidSegmentSize(1),
+            })
+        );
+        assert!(items[1]
+            .as_ref()
+            .is_ok_and(|frame| frame.offset() == FIXED_FRAME_SIZE));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_frames_truncated() {
+        let mut data = fixed_frame(2);
+        data.extend_from_slice(&fixed_frame(2)[..100]);
+
+        let items: Vec<_> = frames(&data).collect();
+        assert_eq!(items.len(), 2);
+        assert_eq!(
+            items[1],
+            Err(MalformedFrame {
+                offset: FIXED_FRAME_SIZE,
+                issue: FrameIssue::TruncatedFrame {
+                    expected: FIXED_FRAME_SIZE,
+                    available: 100,
+                },
+            })
+        );
+
+        let items: Vec<_> = frames(&data[..20]).collect();
+        assert_eq!(
+            items,
+            vec![Err(MalformedFrame {
+                offset: 0,
+                issue: FrameIssue::TruncatedHeader { available: 20 },
+            })]
+        );
+    }
+}
@@ -0,0 +1,296 @@
+//!
+//! Bounds-checked readers over in-memory data for writing decoders, including decoders for formats
+//! adjacent to Archive II such as TDWR or Level III products. Data is borrowed from the underlying
+//! buffer rather than copied wherever possible, and reading past the end of the data fails with
+//! [Error::UnexpectedEnd] identifying the offset and amount requested rather than panicking.
+//!
+//! Fixed-layout structures are decoded with [SliceReader::read_value] as big-endian fields, the
+//! same way this crate decodes its own message structures.
+//!
+
+use crate::result::{Error, Result};
+use crate::util::deserialize;
+use serde::de::DeserializeOwned;
+use std::borrow::Cow;
+use std::io::{ErrorKind, Read};
+
+/// A bounds-checked reader over a contiguous slice of data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SliceReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> SliceReader<'a> {
+    /// Creates a new reader positioned at the start of the given data.
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    /// The reader's offset from the start of its data.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// The number of bytes remaining to be read.
+    pub fn remaining(&self) -> usize {
+        self.data.len() - self.position
+    }
+
+    /// Whether all of the data has been read.
+    pub fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Moves the reader to the given offset from the start of its data, which may be at most the
+    /// data's length.
+    pub fn seek_to(&mut self, position: usize) -> Result<()> {
+        if position > self.data.len() {
+            return Err(unexpected_end(position, 0, self.data.len()));
+        }
+
+        self.position = position;
+        Ok(())
+    }
+
+    /// Advances the reader past the given number of bytes.
+    pub fn skip(&mut self, length: usize) -> Result<()> {
+        self.take_ref(length).map(|_| ())
+    }
+
+    /// Reads the given number of bytes, borrowing them from the underlying data.
+    pub fn take_ref(&mut self, length: usize) -> Result<&'a [u8]> {
+        let data = self
+            .data
+            .get(self.position..)
+            .and_then(|remaining| remaining.get(..length))
+            .ok_or_else(|| unexpected_end(self.position, length, self.remaining()))?;
+
+        self.position += length;
+        Ok(data)
+    }
+
+    /// Reads a fixed number of bytes into an array.
+    pub fn take_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take_ref(N)?);
+        Ok(array)
+    }
+
+    /// Decodes a fixed-layout structure of big-endian fields, advancing past it.
+    pub fn read_value<T: DeserializeOwned>(&mut self) -> Result<T> {
+        let position = self.position;
+        let mut remaining = self.data.get(position..).unwrap_or_default();
+        let available = remaining.len();
+
+        let value =
+            deserialize(&mut remaining).map_err(|err| read_error::<T>(err, position, available))?;
+        self.position = position + available - remaining.len();
+        Ok(value)
+    }
+}
+
+impl Read for SliceReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let length = buf.len().min(self.remaining());
+        buf[..length].copy_from_slice(&self.data[self.position..self.position + length]);
+        self.position += length;
+        Ok(length)
+    }
+}
+
+/// A bounds-checked reader over a sequence of slices read as though they were contiguous, such as
+/// the segments of a segmented message. Reads within a single segment borrow from it, while reads
+/// spanning segments are copied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SegmentedSliceReader<'a> {
+    segments: Vec<&'a [u8]>,
+    segment_index: usize,
+    segment_position: usize,
+    position: usize,
+}
+
+impl<'a> SegmentedSliceReader<'a> {
+    /// Creates a new reader positioned at the start of the first of the given segments.
+    pub fn new(segments: Vec<&'a [u8]>) -> Self {
+        Self {
+            segments,
+            segment_index: 0,
+            segment_position: 0,
+            position: 0,
+        }
+    }
+
+    /// The reader's offset from the start of its first segment.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// The number of bytes remaining to be read across all segments.
+    pub fn remaining(&self) -> usize {
+        let total: usize = self.segments.iter().map(|segment| segment.len()).sum();
+        total - self.position
+    }
+
+    /// Whether all of the segments have been read.
+    pub fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Advances the reader past the given number of bytes.
+    pub fn skip(&mut self, length: usize) -> Result<()> {
+        self.take_ref(length).map(|_| ())
+    }
+
+    /// Reads the given number of bytes, borrowing them if they lie within a single segment.
+    pub fn take_ref(&mut self, length: usize) -> Result<Cow<'a, [u8]>> {
+        if length > self.remaining() {
+            return Err(unexpected_end(self.position, length, self.remaining()));
+        }
+
+        self.skip_exhausted_segments();
+        if let Some(data) = self
+            .segments
+            .get(self.segment_index)
+            .copied()
+            .and_then(|segment| segment.get(self.segment_position..self.segment_position + length))
+        {
+            self.segment_position += length;
+            self.position += length;
+            return Ok(Cow::Borrowed(data));
+        }
+
+        let mut data = vec![0; length];
+        self.read_exact(&mut data)?;
+        Ok(Cow::Owned(data))
+    }
+
+    /// Decodes a fixed-layout structure of big-endian fields, advancing past it. The reader's
+    /// position is unchanged if decoding fails.
+    pub fn read_value<T: DeserializeOwned>(&mut self) -> Result<T> {
+        let (segment_index, segment_position, position) =
+            (self.segment_index, self.segment_position, self.position);
+        let remaining = self.remaining();
+
+        deserialize(self).map_err(|err| {
+            self.segment_index = segment_index;
+            self.segment_position = segment_position;
+            self.position = position;
+            read_error::<T>(err, position, remaining)
+        })
+    }
+
+    /// Moves past any segments which have been fully read.
+    fn skip_exhausted_segments(&mut self) {
+        while self
+            .segments
+            .get(self.segment_index)
+            .is_some_and(|segment| self.segment_position >= segment.len())
+        {
+            self.segment_index += 1;
+            self.segment_position = 0;
+        }
+    }
+}
+
+impl Read for SegmentedSliceReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut length = 0;
+        while length < buf.len() {
+            self.skip_exhausted_segments();
+            let Some(segment) = self.segments.get(self.segment_index) else {
+                break;
+            };
+
+            let available = &segment[self.segment_position..];
+            let count = available.len().min(buf.len() - length);
+            buf[length..length + count].copy_from_slice(&available[..count]);
+
+            length += count;
+            self.segment_position += count;
+            self.position += count;
+        }
+
+        Ok(length)
+    }
+}
+
+fn unexpected_end(offset: usize, length: usize, remaining: usize) -> Error {
+    Error::UnexpectedEnd(format!(
+        "{} bytes requested at offset {} with {} remaining",
+        length, offset, remaining
+    ))
+}
+
+/// Identifies a structure which could not be decoded because the data ended, passing through other
+/// decoding errors.
+fn read_error<T>(err: Error, offset: usize, remaining: usize) -> Error {
+    let Error::DeserializationError(deserialization_error) = &err else {
+        return err;
+    };
+    let bincode::ErrorKind::Io(io_error) = &**deserialization_error else {
+        return err;
+    };
+    if io_error.kind() != ErrorKind::UnexpectedEof {
+        return err;
+    }
+
+    Error::UnexpectedEnd(format!(
+        "{} requested at offset {} with {} bytes remaining",
+        std::any::type_name::<T>(),
+        offset,
+        remaining
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slice_reader() -> Result<()> {
+        let data = [0, 1, 0, 0, 0, 2, 9, 9];
+        let mut reader = SliceReader::new(&data);
+
+        assert_eq!(reader.read_value::<u16>()?, 1);
+        assert_eq!(reader.read_value::<u32>()?, 2);
+        assert_eq!(reader.position(), 6);
+        assert_eq!(reader.take_array::<2>()?, [9, 9]);
+        assert!(reader.is_empty());
+
+        assert!(matches!(reader.take_ref(1), Err(Error::UnexpectedEnd(_))));
+        assert!(reader.seek_to(9).is_err());
+
+        reader.seek_to(4)?;
+        assert_eq!(reader.take_ref(2)?, &[0, 2]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_segmented_slice_reader() -> Result<()> {
+        let segments: Vec<&[u8]> = vec![&[0, 1, 2], &[], &[3, 4], &[5]];
+        let mut reader = SegmentedSliceReader::new(segments);
+
+        assert!(matches!(reader.take_ref(2)?, Cow::Borrowed(&[0, 1])));
+
+        // A failed read spanning segments leaves the reader where it was
+        assert!(matches!(
+            reader.read_value::<u64>(),
+            Err(Error::UnexpectedEnd(_))
+        ));
+        assert_eq!(reader.position(), 2);
+
+        assert!(matches!(reader.take_ref(2)?, Cow::Owned(ref data) if data == &[2, 3]));
+        assert_eq!(reader.read_value::<u16>()?, 0x0405);
+        assert_eq!(reader.remaining(), 0);
+
+        assert!(matches!(reader.take_ref(1), Err(Error::UnexpectedEnd(_))));
+        assert!(matches!(
+            reader.read_value::<u8>(),
+            Err(Error::UnexpectedEnd(_))
+        ));
+
+        Ok(())
+    }
+}
@@ -5,7 +5,9 @@
 //! build 19.0.
 //!
 //! Optionally, the `nexrad-model` feature provides mappings to a common model for representing
-//! radar data. The `uom` feature can also be used to provide type-safe units of measure.
+//! radar data. The `uom` feature can also be used to provide type-safe units of measure. The
+//! `tracing` feature emits diagnostics as `tracing` events within spans identifying the message
+//! being decoded, rather than as `log` records.
 //!
 
 #![forbid(unsafe_code)]
@@ -14,8 +16,14 @@
 #![warn(clippy::correctness)]
 #![allow(clippy::too_many_arguments)]
 
+pub mod ctm;
+pub mod io;
+pub mod limits;
 pub mod messages;
+pub mod reassembly;
 pub mod result;
+pub mod salvage;
 pub mod summarize;
 
+mod telemetry;
 mod util;
@@ -0,0 +1,84 @@
+//!
+//! Limits on the resources decoding may consume, protecting services which decode untrusted input
+//! against decompression bombs and absurd header values. Decoding functions without explicit
+//! limits apply [DecodeLimits::new], whose limits are well above anything a WSR-88D produces.
+//!
+//! Exceeding a limit fails decoding with [Error::LimitExceeded](crate::result::Error::LimitExceeded)
+//! rather than allocating or looping without bound.
+//!
+
+/// Limits on the resources decoding may consume.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeLimits {
+    max_messages_per_record: usize,
+    max_gate_count: u16,
+    max_segment_count: u16,
+    max_decompressed_record_bytes: usize,
+}
+
+impl DecodeLimits {
+    /// Creates limits of 10,000 messages per record, 4,000 gates per moment, 128 segments per
+    /// message, and 16 MiB per decompressed record. The largest records the RDA sends hold a few
+    /// hundred messages in well under a megabyte, and its longest moments have 1,840 gates.
+    pub fn new() -> Self {
+        Self {
+            max_messages_per_record: 10_000,
+            max_gate_count: 4_000,
+            max_segment_count: 128,
+            max_decompressed_record_bytes: 16 * 1024 * 1024,
+        }
+    }
+
+    /// Sets the maximum number of messages decoded from a single record.
+    pub fn with_max_messages_per_record(mut self, max_messages_per_record: usize) -> Self {
+        self.max_messages_per_record = max_messages_per_record;
+        self
+    }
+
+    /// Sets the maximum number of gates a digital radar data moment may declare.
+    pub fn with_max_gate_count(mut self, max_gate_count: u16) -> Self {
+        self.max_gate_count = max_gate_count;
+        self
+    }
+
+    /// Sets the maximum number of segments a message header may declare.
+    pub fn with_max_segment_count(mut self, max_segment_count: u16) -> Self {
+        self.max_segment_count = max_segment_count;
+        self
+    }
+
+    /// Sets the maximum size in bytes of a record's decompressed data.
+    pub fn with_max_decompressed_record_bytes(
+        mut self,
+        max_decompressed_record_bytes: usize,
+    ) -> Self {
+        self.max_decompressed_record_bytes = max_decompressed_record_bytes;
+        self
+    }
+
+    /// The maximum number of messages decoded from a single record.
+    pub fn max_messages_per_record(&self) -> usize {
+        self.max_messages_per_record
+    }
+
+    /// The maximum number of gates a digital radar data moment may declare.
+    pub fn max_gate_count(&self) -> u16 {
+        self.max_gate_count
+    }
+
+    /// The maximum number of segments a message header may declare.
+    pub fn max_segment_count(&self) -> u16 {
+        self.max_segment_count
+    }
+
+    /// The maximum size in bytes of a record's decompressed data.
+    pub fn max_decompressed_record_bytes(&self) -> usize {
+        self.max_decompressed_record_bytes
+    }
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
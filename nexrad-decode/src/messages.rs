@@ -1,4 +1,6 @@
+pub mod clutter_censor_zones;
 pub mod clutter_filter_map;
+pub mod console_message;
 pub mod digital_radar_data;
 pub mod message_header;
 pub mod rda_status_data;
@@ -13,28 +15,145 @@ pub use message::{Message, MessageWithHeader};
 mod definitions;
 mod primitive_aliases;
 
-use crate::messages::digital_radar_data::decode_digital_radar_data;
+use crate::ctm::CTM_HEADER_SIZE;
+use crate::limits::DecodeLimits;
+use crate::messages::console_message::{decode_console_message, encode_console_message};
+use crate::messages::digital_radar_data::{
+    decode_digital_radar_data_borrowed_with_limits, decode_digital_radar_data_with_limits,
+    encode_digital_radar_data,
+};
 use crate::messages::message_header::MessageHeader;
-use crate::messages::rda_status_data::decode_rda_status_message;
-use crate::messages::volume_coverage_pattern::decode_volume_coverage_pattern;
-use crate::result::Result;
-use crate::util::deserialize;
-use log::{debug, trace};
-use std::io::{Read, Seek};
+use crate::messages::rda_status_data::{decode_rda_status_message, encode_rda_status_message};
+use crate::messages::volume_coverage_pattern::{
+    decode_volume_coverage_pattern, encode_volume_coverage_pattern,
+};
+use crate::result::{Error, Result};
+use crate::telemetry::{debug, enter_span, trace};
+use crate::util::{deserialize, serialize};
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
 
 /// Decode a NEXRAD Level II message from a reader.
 pub fn decode_message_header<R: Read>(reader: &mut R) -> Result<MessageHeader> {
     deserialize(reader)
 }
 
-/// Decode a series of NEXRAD Level II messages from a reader.
-pub fn decode_messages<R: Read + Seek>(reader: &mut R) -> Result<Vec<MessageWithHeader>> {
+/// Decode a series of NEXRAD Level II messages from a reader. Messages are returned in the order they
+/// appear in the reader. Decoding fails on the first malformed message; use
+/// [salvage_messages](crate::salvage::salvage_messages) to recover the messages around damaged
+/// content along with [DecodeStats](crate::salvage::DecodeStats) describing what was skipped.
+pub fn decode_messages<R: Read + Seek>(reader: &mut R) -> Result<Vec<MessageWithHeader<'static>>> {
+    decode_messages_with_limits(reader, &DecodeLimits::new())
+}
+
+/// Decode a series of NEXRAD Level II messages from a reader within the given limits.
+pub fn decode_messages_with_limits<R: Read + Seek>(
+    reader: &mut R,
+    limits: &DecodeLimits,
+) -> Result<Vec<MessageWithHeader<'static>>> {
+    decode_messages_with(reader, limits, decode_digital_radar_data_with_limits)
+}
+
+/// Decode a series of NEXRAD Level II messages from an in-memory reader, borrowing moment data from
+/// its underlying buffer rather than copying it. Messages are returned in the order they appear in
+/// the reader.
+pub fn decode_messages_borrowed<'a>(
+    reader: &mut Cursor<&'a [u8]>,
+) -> Result<Vec<MessageWithHeader<'a>>> {
+    decode_messages_borrowed_with_limits(reader, &DecodeLimits::new())
+}
+
+/// Decode a series of NEXRAD Level II messages from an in-memory reader within the given limits,
+/// borrowing moment data from its underlying buffer rather than copying it.
+pub fn decode_messages_borrowed_with_limits<'a>(
+    reader: &mut Cursor<&'a [u8]>,
+    limits: &DecodeLimits,
+) -> Result<Vec<MessageWithHeader<'a>>> {
+    decode_messages_with(
+        reader,
+        limits,
+        decode_digital_radar_data_borrowed_with_limits,
+    )
+}
+
+/// Decode a NEXRAD Level II message of the specified type from a reader.
+pub fn decode_message<R: Read + Seek>(
+    reader: &mut R,
+    message_type: MessageType,
+) -> Result<Message<'static>> {
+    decode_message_with(reader, message_type, |reader| {
+        decode_digital_radar_data_with_limits(reader, &DecodeLimits::new())
+    })
+}
+
+/// Decode a NEXRAD Level II message of the specified type from an in-memory reader, borrowing moment
+/// data from its underlying buffer rather than copying it.
+pub fn decode_message_borrowed<'a>(
+    reader: &mut Cursor<&'a [u8]>,
+    message_type: MessageType,
+) -> Result<Message<'a>> {
+    decode_message_with(reader, message_type, |reader| {
+        decode_digital_radar_data_borrowed_with_limits(reader, &DecodeLimits::new())
+    })
+}
+
+fn decode_messages_with<'a, R: Read + Seek>(
+    reader: &mut R,
+    limits: &DecodeLimits,
+    mut decode_radar_data: impl FnMut(&mut R, &DecodeLimits) -> Result<digital_radar_data::Message<'a>>,
+) -> Result<Vec<MessageWithHeader<'a>>> {
     debug!("Decoding messages");
 
     let mut messages = Vec::new();
-    while let Ok(header) = decode_message_header(reader) {
-        let message = decode_message(reader, header.message_type())?;
-        messages.push(MessageWithHeader { header, message });
+    loop {
+        let start = reader.stream_position()?;
+        let Ok(header) = decode_message_header(reader) else {
+            break;
+        };
+
+        enter_span!(
+            "message",
+            index = messages.len(),
+            message_type = ?header.message_type(),
+            offset = start,
+        );
+
+        if messages.len() >= limits.max_messages_per_record() {
+            return Err(Error::LimitExceeded(format!(
+                "more than {} messages in record",
+                limits.max_messages_per_record()
+            )));
+        }
+
+        if let Some(segment_count) = header
+            .segment_count()
+            .filter(|segment_count| *segment_count > limits.max_segment_count())
+        {
+            return Err(Error::LimitExceeded(format!(
+                "{} segments in {:?} message exceeds the limit of {}",
+                segment_count,
+                header.message_type(),
+                limits.max_segment_count()
+            )));
+        }
+
+        let message = decode_message_with(reader, header.message_type(), |reader| {
+            decode_radar_data(reader, limits)
+        })?;
+        let mut end = reader.stream_position()?;
+
+        // Message sizes are given in halfwords, so a digital radar data message of odd length is
+        // followed by a byte of padding
+        if header.message_type() == MessageType::RDADigitalRadarDataGenericFormat {
+            let padded_end = start + (CTM_HEADER_SIZE as u32 + header.message_size_bytes()) as u64;
+            if padded_end == end + 1 {
+                end = reader.seek(SeekFrom::Start(padded_end))?;
+            }
+        }
+        messages.push(MessageWithHeader {
+            header,
+            message,
+            byte_range: Some(start as usize..end as usize),
+        });
     }
 
     debug!(
@@ -46,16 +165,16 @@ pub fn decode_messages<R: Read + Seek>(reader: &mut R) -> Result<Vec<MessageWith
     Ok(messages)
 }
 
-/// Decode a NEXRAD Level II message of the specified type from a reader.
-pub fn decode_message<R: Read + Seek>(
+fn decode_message_with<'a, R: Read + Seek>(
     reader: &mut R,
     message_type: MessageType,
-) -> Result<Message> {
+    decode_radar_data: impl FnOnce(&mut R) -> Result<digital_radar_data::Message<'a>>,
+) -> Result<Message<'a>> {
     let position = reader.stream_position();
     trace!("Decoding message type {:?} at {:?}", message_type, position);
 
     if message_type == MessageType::RDADigitalRadarDataGenericFormat {
-        let decoded_message = decode_digital_radar_data(reader)?;
+        let decoded_message = decode_radar_data(reader)?;
         return Ok(Message::DigitalRadarData(Box::new(decoded_message)));
     }
 
@@ -70,6 +189,9 @@ pub fn decode_message<R: Read + Seek>(
         MessageType::RDAVolumeCoveragePattern => Message::VolumeCoveragePattern(Box::new(
             decode_volume_coverage_pattern(message_reader)?,
         )),
+        MessageType::RDAConsoleMessage | MessageType::RPGConsoleMessage => {
+            Message::ConsoleMessage(Box::new(decode_console_message(message_reader)?))
+        }
         // TODO: this message type is segmented which is not supported well currently
         // MessageType::RDAClutterFilterMap => {
         //     Message::ClutterFilterMap(Box::new(decode_clutter_filter_map(message_reader)?))
@@ -77,3 +199,714 @@ pub fn decode_message<R: Read + Seek>(
         _ => Message::Other,
     })
 }
+
+/// Encode a NEXRAD Level II message header to a writer.
+pub fn encode_message_header<W: Write>(writer: &mut W, header: &MessageHeader) -> Result<()> {
+    serialize(writer, header)
+}
+
+/// Encode a series of NEXRAD Level II messages with their headers to a writer.
+pub fn encode_messages<W: Write>(writer: &mut W, messages: &[MessageWithHeader]) -> Result<()> {
+    debug!("Encoding {} messages", messages.len());
+
+    for message in messages {
+        let header_message_type = message.header.message_type();
+        let matches_header = match message.message {
+            Message::RDAStatusData(_) => header_message_type == MessageType::RDAStatusData,
+            Message::DigitalRadarData(_) => {
+                header_message_type == MessageType::RDADigitalRadarDataGenericFormat
+            }
+            Message::ClutterFilterMap(_) => header_message_type == MessageType::RDAClutterFilterMap,
+            Message::VolumeCoveragePattern(_) => {
+                header_message_type == MessageType::RDAVolumeCoveragePattern
+            }
+            Message::ConsoleMessage(_) => matches!(
+                header_message_type,
+                MessageType::RDAConsoleMessage | MessageType::RPGConsoleMessage
+            ),
+            Message::Other => true,
+        };
+
+        if !matches_header {
+            return Err(Error::EncodingError(format!(
+                "message header type {:?} does not match message",
+                message.header.message_type()
+            )));
+        }
+
+        encode_message_header(writer, &message.header)?;
+        encode_message(writer, &message.message)?;
+    }
+
+    Ok(())
+}
+
+/// Encode a NEXRAD Level II message to a writer. Fixed-length messages are padded to fill their
+/// frame, mirroring [decode_message].
+pub fn encode_message<W: Write>(writer: &mut W, message: &Message) -> Result<()> {
+    let mut message_buffer = Vec::with_capacity(2432 - size_of::<MessageHeader>());
+    match message {
+        Message::DigitalRadarData(message) => return encode_digital_radar_data(writer, message),
+        Message::RDAStatusData(message) => encode_rda_status_message(&mut message_buffer, message)?,
+        Message::VolumeCoveragePattern(message) => {
+            encode_volume_coverage_pattern(&mut message_buffer, message)?
+        }
+        Message::ConsoleMessage(message) => encode_console_message(&mut message_buffer, message)?,
+        // TODO: this message type is segmented which is not supported well currently
+        Message::ClutterFilterMap(_) | Message::Other => {
+            return Err(Error::EncodingError(
+                "message type is not supported for encoding".to_string(),
+            ))
+        }
+    }
+
+    if message_buffer.len() > 2432 - size_of::<MessageHeader>() {
+        return Err(Error::EncodingError(
+            "message exceeds fixed message size".to_string(),
+        ));
+    }
+
+    message_buffer.resize(2432 - size_of::<MessageHeader>(), 0);
+    writer.write_all(&message_buffer)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::clutter_filter_map::{
+        decode_clutter_filter_map, encode_clutter_filter_map, AzimuthSegment, AzimuthSegmentHeader,
+        ElevationSegment, RangeZone,
+    };
+    use crate::messages::digital_radar_data::{
+        decode_digital_radar_data, decode_digital_radar_data_borrowed, DataBlockId,
+        GenericDataBlock, GenericDataBlockHeader,
+    };
+    use crate::messages::message_header::VARIABLE_LENGTH_MESSAGE_SIZE;
+    use proptest::collection::vec;
+    use proptest::option;
+    use proptest::prelude::*;
+    use serde::de::DeserializeOwned;
+    use std::borrow::Cow;
+
+    // Arbitrary bit patterns include NaN floating-point values which never compare equal, so
+    // messages containing floats are compared by their re-encoded bytes rather than by value.
+
+    fn fail(err: Error) -> TestCaseError {
+        TestCaseError::fail(err.to_string())
+    }
+
+    /// Decodes a value from arbitrary bytes, which must be at least as long as its encoding.
+    fn arbitrary<S: DeserializeOwned + std::fmt::Debug>(size: usize) -> impl Strategy<Value = S> {
+        vec(any::<u8>(), size)
+            .prop_filter_map("decodable", |bytes| deserialize(&mut bytes.as_slice()).ok())
+    }
+
+    fn block_id(name: &[u8; 3]) -> DataBlockId {
+        DataBlockId {
+            data_block_type: if name == b"VOL" || name == b"ELV" || name == b"RAD" {
+                b'R'
+            } else {
+                b'D'
+            },
+            data_name: *name,
+        }
+    }
+
+    fn message_header(message_type: MessageType) -> impl Strategy<Value = MessageHeader> {
+        arbitrary::<MessageHeader>(size_of::<MessageHeader>()).prop_map(move |mut header| {
+            header.message_type = message_type as u8;
+            // Keep segmented headers within the default limits so they decode
+            if header.segmented() {
+                header.segment_count %= DecodeLimits::new().max_segment_count() + 1;
+            }
+            header
+        })
+    }
+
+    fn rda_status_message() -> impl Strategy<Value = rda_status_data::Message> {
+        arbitrary(2432 - size_of::<MessageHeader>())
+    }
+
+    fn volume_coverage_pattern_message() -> impl Strategy<Value = volume_coverage_pattern::Message>
+    {
+        (
+            arbitrary::<volume_coverage_pattern::Header>(64),
+            vec(arbitrary(64), 0..8),
+        )
+            .prop_map(|(mut header, elevations)| {
+                header.number_of_elevation_cuts = elevations.len() as u16;
+                volume_coverage_pattern::Message { header, elevations }
+            })
+    }
+
+    fn clutter_filter_map_message() -> impl Strategy<Value = clutter_filter_map::Message> {
+        (
+            arbitrary::<clutter_filter_map::Header>(64),
+            vec(vec(vec(any::<(u16, u16)>(), 0..3), 360), 0..3),
+        )
+            .prop_map(|(mut header, elevations)| {
+                header.elevation_segment_count = elevations.len() as u16;
+                clutter_filter_map::Message {
+                    header,
+                    elevation_segments: elevations
+                        .into_iter()
+                        .enumerate()
+                        .map(|(elevation_number, azimuths)| ElevationSegment {
+                            elevation_segment_number: elevation_number as u8,
+                            azimuth_segments: azimuths
+                                .into_iter()
+                                .enumerate()
+                                .map(|(azimuth_number, zones)| AzimuthSegment {
+                                    header: AzimuthSegmentHeader {
+                                        range_zone_count: zones.len() as u16,
+                                    },
+                                    azimuth_segment: azimuth_number as u16,
+                                    range_zones: zones
+                                        .into_iter()
+                                        .map(|(op_code, end_range)| RangeZone {
+                                            op_code,
+                                            end_range,
+                                        })
+                                        .collect(),
+                                })
+                                .collect(),
+                        })
+                        .collect(),
+                }
+            })
+    }
+
+    fn generic_data_block() -> impl Strategy<Value = GenericDataBlock<'static>> {
+        (
+            arbitrary::<digital_radar_data::GenericDataBlockHeader>(64),
+            prop_oneof![Just(8u8), Just(16u8)],
+            0u16..64,
+        )
+            .prop_flat_map(|(header, word_size, gates)| {
+                let size = gates as usize * word_size as usize / 8;
+                (
+                    Just(header),
+                    Just(word_size),
+                    Just(gates),
+                    vec(any::<u8>(), size),
+                )
+            })
+            .prop_map(|(mut header, word_size, gates, encoded_data)| {
+                header.data_word_size = word_size;
+                header.number_of_data_moment_gates = gates;
+                GenericDataBlock {
+                    header,
+                    encoded_data: encoded_data.into(),
+                }
+            })
+    }
+
+    fn unknown_data_block() -> impl Strategy<Value = digital_radar_data::UnknownDataBlock<'static>>
+    {
+        vec(any::<u8>(), 0..32).prop_map(|data| digital_radar_data::UnknownDataBlock {
+            data_block_id: DataBlockId {
+                data_block_type: b'R',
+                data_name: *b"ABC",
+            },
+            lrtup: (data.len() + 6) as u16,
+            data: data.into(),
+        })
+    }
+
+    fn digital_radar_data_message() -> impl Strategy<Value = digital_radar_data::Message<'static>> {
+        (
+            arbitrary::<digital_radar_data::Header>(64),
+            option::of(arbitrary::<digital_radar_data::VolumeDataBlock>(64)),
+            option::of(arbitrary::<digital_radar_data::ElevationDataBlock>(64)),
+            option::of((
+                arbitrary::<digital_radar_data::RadialDataBlock>(64),
+                any::<bool>(),
+            )),
+            vec(option::of(generic_data_block()), 7),
+            vec(generic_data_block(), 0..3),
+            vec(unknown_data_block(), 0..3),
+        )
+            .prop_map(
+                |(
+                    header,
+                    volume,
+                    elevation,
+                    radial,
+                    generic_blocks,
+                    extension_blocks,
+                    unknown_blocks,
+                )| {
+                    let mut message = digital_radar_data::Message::new(header);
+
+                    message.volume_data_block = volume.map(|mut block| {
+                        block.data_block_id = block_id(b"VOL");
+                        block
+                    });
+                    message.elevation_data_block = elevation.map(|mut block| {
+                        block.data_block_id = block_id(b"ELV");
+                        block
+                    });
+                    message.radial_data_block = radial.map(|(mut block, legacy)| {
+                        block.data_block_id = block_id(b"RAD");
+                        block.lrtup = 28;
+                        if legacy {
+                            block.lrtup = 20;
+                            block.radial_flags = 0;
+                            block.horizontal_channel_calibration_constant = 0.0;
+                            block.vertical_channel_calibration_constant = 0.0;
+                        }
+                        block
+                    });
+
+                    let names = [b"REF", b"VEL", b"SW ", b"ZDR", b"PHI", b"RHO", b"CFP"];
+                    let mut generic_blocks =
+                        generic_blocks.into_iter().zip(names).map(|(block, name)| {
+                            block.map(|mut block| {
+                                block.header.data_block_id = block_id(name);
+                                block
+                            })
+                        });
+                    message.reflectivity_data_block = generic_blocks.next().flatten();
+                    message.velocity_data_block = generic_blocks.next().flatten();
+                    message.spectrum_width_data_block = generic_blocks.next().flatten();
+                    message.differential_reflectivity_data_block = generic_blocks.next().flatten();
+                    message.differential_phase_data_block = generic_blocks.next().flatten();
+                    message.correlation_coefficient_data_block = generic_blocks.next().flatten();
+                    message.specific_diff_phase_data_block = generic_blocks.next().flatten();
+                    message.extension_data_blocks = extension_blocks
+                        .into_iter()
+                        .map(|mut block| {
+                            block.header.data_block_id = block_id(b"XYZ");
+                            block
+                        })
+                        .collect();
+                    message.unknown_data_blocks = unknown_blocks;
+
+                    message.header.data_block_count = [
+                        message.volume_data_block.is_some(),
+                        message.elevation_data_block.is_some(),
+                        message.radial_data_block.is_some(),
+                        message.reflectivity_data_block.is_some(),
+                        message.velocity_data_block.is_some(),
+                        message.spectrum_width_data_block.is_some(),
+                        message.differential_reflectivity_data_block.is_some(),
+                        message.differential_phase_data_block.is_some(),
+                        message.correlation_coefficient_data_block.is_some(),
+                        message.specific_diff_phase_data_block.is_some(),
+                    ]
+                    .into_iter()
+                    .filter(|present| *present)
+                    .count() as u16
+                        + message.extension_data_blocks.len() as u16
+                        + message.unknown_data_blocks.len() as u16;
+
+                    message
+                },
+            )
+    }
+
+    fn console_message() -> impl Strategy<Value = console_message::Message> {
+        "[ -~]{0,200}".prop_map(|text| console_message::Message { text })
+    }
+
+    fn message_with_header() -> impl Strategy<Value = MessageWithHeader<'static>> {
+        prop_oneof![
+            (
+                message_header(MessageType::RDAStatusData),
+                rda_status_message()
+            )
+                .prop_map(|(header, message)| MessageWithHeader {
+                    header,
+                    message: Message::RDAStatusData(Box::new(message)),
+                    byte_range: None,
+                }),
+            (
+                message_header(MessageType::RDAVolumeCoveragePattern),
+                volume_coverage_pattern_message()
+            )
+                .prop_map(|(header, message)| MessageWithHeader {
+                    header,
+                    message: Message::VolumeCoveragePattern(Box::new(message)),
+                    byte_range: None,
+                }),
+            (
+                message_header(MessageType::RDADigitalRadarDataGenericFormat),
+                digital_radar_data_message()
+            )
+                .prop_map(|(header, message)| MessageWithHeader {
+                    header,
+                    message: Message::DigitalRadarData(Box::new(message)),
+                    byte_range: None,
+                }),
+            (
+                message_header(MessageType::RPGConsoleMessage),
+                console_message()
+            )
+                .prop_map(|(header, message)| MessageWithHeader {
+                    header,
+                    message: Message::ConsoleMessage(Box::new(message)),
+                    byte_range: None,
+                }),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_message_header_round_trip(header in message_header(MessageType::RDAStatusData)) {
+            let mut encoded = Vec::new();
+            encode_message_header(&mut encoded, &header).map_err(fail)?;
+            prop_assert_eq!(encoded.len(), size_of::<MessageHeader>());
+
+            let decoded = decode_message_header(&mut encoded.as_slice()).map_err(fail)?;
+            prop_assert_eq!(decoded, header);
+        }
+
+        #[test]
+        fn test_rda_status_round_trip(message in rda_status_message()) {
+            let message = Message::RDAStatusData(Box::new(message));
+
+            let mut encoded = Vec::new();
+            encode_message(&mut encoded, &message).map_err(fail)?;
+            prop_assert_eq!(encoded.len(), 2432 - size_of::<MessageHeader>());
+
+            let decoded = decode_message(&mut Cursor::new(&encoded), MessageType::RDAStatusData)
+                .map_err(fail)?;
+            prop_assert_eq!(decoded, message);
+        }
+
+        #[test]
+        fn test_volume_coverage_pattern_round_trip(message in volume_coverage_pattern_message()) {
+            let message = Message::VolumeCoveragePattern(Box::new(message));
+
+            let mut encoded = Vec::new();
+            encode_message(&mut encoded, &message).map_err(fail)?;
+
+            let decoded = decode_message(
+                &mut Cursor::new(&encoded),
+                MessageType::RDAVolumeCoveragePattern,
+            )
+            .map_err(fail)?;
+
+            let mut reencoded = Vec::new();
+            encode_message(&mut reencoded, &decoded).map_err(fail)?;
+            prop_assert_eq!(reencoded, encoded);
+        }
+
+        #[test]
+        fn test_console_message_round_trip(message in console_message()) {
+            let message = Message::ConsoleMessage(Box::new(message));
+
+            let mut encoded = Vec::new();
+            encode_message(&mut encoded, &message).map_err(fail)?;
+
+            let decoded = decode_message(&mut Cursor::new(&encoded), MessageType::RDAConsoleMessage)
+                .map_err(fail)?;
+            prop_assert_eq!(decoded, message);
+        }
+
+        #[test]
+        fn test_clutter_filter_map_round_trip(message in clutter_filter_map_message()) {
+            let mut encoded = Vec::new();
+            encode_clutter_filter_map(&mut encoded, &message).map_err(fail)?;
+
+            let decoded = decode_clutter_filter_map(&mut encoded.as_slice()).map_err(fail)?;
+            prop_assert_eq!(decoded, message);
+        }
+
+        #[test]
+        fn test_digital_radar_data_round_trip(message in digital_radar_data_message()) {
+            let message = Message::DigitalRadarData(Box::new(message));
+
+            let mut encoded = Vec::new();
+            encode_message(&mut encoded, &message).map_err(fail)?;
+
+            let decoded = decode_message(
+                &mut Cursor::new(&encoded),
+                MessageType::RDADigitalRadarDataGenericFormat,
+            )
+            .map_err(fail)?;
+
+            let mut reencoded = Vec::new();
+            encode_message(&mut reencoded, &decoded).map_err(fail)?;
+            prop_assert_eq!(reencoded, encoded);
+        }
+
+        #[test]
+        fn test_messages_round_trip(messages in vec(message_with_header(), 0..8)) {
+            let mut encoded = Vec::new();
+            encode_messages(&mut encoded, &messages).map_err(fail)?;
+
+            let decoded = decode_messages(&mut Cursor::new(&encoded)).map_err(fail)?;
+            prop_assert_eq!(decoded.len(), messages.len());
+
+            let mut reencoded = Vec::new();
+            encode_messages(&mut reencoded, &decoded).map_err(fail)?;
+            prop_assert_eq!(reencoded, encoded);
+        }
+
+        #[test]
+        fn test_borrowed_messages_match_owned(messages in vec(message_with_header(), 0..8)) {
+            let mut encoded = Vec::new();
+            encode_messages(&mut encoded, &messages).map_err(fail)?;
+
+            let owned = decode_messages(&mut Cursor::new(&encoded)).map_err(fail)?;
+            let borrowed =
+                decode_messages_borrowed(&mut Cursor::new(encoded.as_slice())).map_err(fail)?;
+
+            let mut owned_encoded = Vec::new();
+            encode_messages(&mut owned_encoded, &owned).map_err(fail)?;
+            let mut borrowed_encoded = Vec::new();
+            encode_messages(&mut borrowed_encoded, &borrowed).map_err(fail)?;
+            prop_assert_eq!(&borrowed_encoded, &owned_encoded);
+
+            for message in &borrowed {
+                if let Message::DigitalRadarData(message) = &message.message {
+                    for block in message.reflectivity_data_block.iter() {
+                        prop_assert!(matches!(block.encoded_data, Cow::Borrowed(_)));
+                    }
+                }
+            }
+
+            let detached: Vec<_> = borrowed.into_iter().map(MessageWithHeader::into_owned).collect();
+            drop(encoded);
+            let mut detached_encoded = Vec::new();
+            encode_messages(&mut detached_encoded, &detached).map_err(fail)?;
+            prop_assert_eq!(detached_encoded, owned_encoded);
+        }
+    }
+
+    /// A digital radar data message with a single 16-gate reflectivity moment.
+    fn reflectivity_message() -> digital_radar_data::Message<'static> {
+        let mut message = digital_radar_data::Message::new(digital_radar_data::Header {
+            radar_identifier: *b"KDMX",
+            time: 0,
+            date: 0,
+            azimuth_number: 0,
+            azimuth_angle: 0.0,
+            compression_indicator: 0,
+            spare: 0,
+            radial_length: 0,
+            azimuth_resolution_spacing: 0,
+            radial_status: 0,
+            elevation_number: 0,
+            cut_sector_number: 0,
+            elevation_angle: 0.0,
+            radial_spot_blanking_status: 0,
+            azimuth_indexing_mode: 0,
+            data_block_count: 1,
+        });
+        message.reflectivity_data_block = Some(GenericDataBlock {
+            header: GenericDataBlockHeader {
+                data_block_id: DataBlockId {
+                    data_block_type: b'D',
+                    data_name: *b"REF",
+                },
+                reserved: 0,
+                number_of_data_moment_gates: 16,
+                data_moment_range: 2125,
+                data_moment_range_sample_interval: 250,
+                tover: 0,
+                snr_threshold: 0,
+                control_flags: 0,
+                data_word_size: 8,
+                scale: 2.0,
+                offset: 66.0,
+            },
+            encoded_data: vec![2; 16].into(),
+        });
+
+        message
+    }
+
+    #[test]
+    fn test_borrowed_truncated_moment_data() -> Result<()> {
+        let message = reflectivity_message();
+
+        let mut encoded = Vec::new();
+        encode_digital_radar_data(&mut encoded, &message)?;
+        encoded.truncate(encoded.len() - 1);
+
+        assert!(decode_digital_radar_data_borrowed(&mut Cursor::new(encoded.as_slice())).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_gate_count_limit() -> Result<()> {
+        let mut encoded = Vec::new();
+        encode_digital_radar_data(&mut encoded, &reflectivity_message())?;
+
+        let limits = DecodeLimits::new().with_max_gate_count(8);
+        assert!(matches!(
+            decode_digital_radar_data_with_limits(&mut Cursor::new(encoded.as_slice()), &limits),
+            Err(Error::LimitExceeded(_))
+        ));
+        assert!(decode_digital_radar_data(&mut Cursor::new(encoded.as_slice())).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_segment_count_limit() -> Result<()> {
+        let encode = |segment_size, segment_count| {
+            let header = MessageHeader::new(
+                MessageType::RDAStatusData,
+                0,
+                0,
+                19_000,
+                0,
+                segment_size,
+                segment_count,
+                1,
+            );
+            let mut encoded = Vec::new();
+            encode_message_header(&mut encoded, &header)?;
+            encoded.resize(2432, 0);
+            Ok::<_, Error>(encoded)
+        };
+
+        let limits = DecodeLimits::new().with_max_segment_count(4);
+        let decode = |encoded| decode_messages_with_limits(&mut Cursor::new(encoded), &limits);
+
+        assert!(decode(encode(1_208, 4)?).is_ok());
+        assert!(matches!(
+            decode(encode(1_208, 5)?),
+            Err(Error::LimitExceeded(_))
+        ));
+
+        // Variable-length messages reuse the segment count field for their size
+        assert!(decode(encode(VARIABLE_LENGTH_MESSAGE_SIZE, 5)?).is_ok());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "nexrad-model")]
+    #[test]
+    fn test_extension_moment_in_radial() -> Result<()> {
+        let mut message = digital_radar_data::Message::new(digital_radar_data::Header {
+            radar_identifier: *b"KDMX",
+            time: 0,
+            date: 19_000,
+            azimuth_number: 1,
+            azimuth_angle: 0.0,
+            compression_indicator: 0,
+            spare: 0,
+            radial_length: 0,
+            azimuth_resolution_spacing: 1,
+            radial_status: 0,
+            elevation_number: 1,
+            cut_sector_number: 1,
+            elevation_angle: 0.5,
+            radial_spot_blanking_status: 0,
+            azimuth_indexing_mode: 0,
+            data_block_count: 1,
+        });
+        message.extension_data_blocks.push(GenericDataBlock {
+            header: GenericDataBlockHeader {
+                data_block_id: block_id(b"XYZ"),
+                reserved: 0,
+                number_of_data_moment_gates: 4,
+                data_moment_range: 2125,
+                data_moment_range_sample_interval: 250,
+                tover: 0,
+                snr_threshold: 0,
+                control_flags: 0,
+                data_word_size: 8,
+                scale: 2.0,
+                offset: 66.0,
+            },
+            encoded_data: vec![0, 1, 70, 76].into(),
+        });
+
+        let mut encoded = Vec::new();
+        encode_digital_radar_data(&mut encoded, &message)?;
+        let decoded = decode_digital_radar_data(&mut Cursor::new(encoded.as_slice()))?;
+        assert_eq!(decoded, message);
+
+        let radial = decoded.into_radial()?;
+        assert_eq!(radial.extension_moments().len(), 1);
+        assert_eq!(radial.extension_moments()[0].name(), "XYZ");
+        assert!(radial.extension_moment("XYZ").is_some());
+        assert!(radial.extension_moment("ABC").is_none());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "nexrad-model")]
+    #[test]
+    fn test_legacy_radial_data_block() -> Result<()> {
+        let mut message = digital_radar_data::Message::new(digital_radar_data::Header {
+            radar_identifier: *b"KDMX",
+            time: 0,
+            date: 19_000,
+            azimuth_number: 1,
+            azimuth_angle: 0.0,
+            compression_indicator: 0,
+            spare: 0,
+            radial_length: 0,
+            azimuth_resolution_spacing: 1,
+            radial_status: 0,
+            elevation_number: 1,
+            cut_sector_number: 1,
+            elevation_angle: 0.5,
+            radial_spot_blanking_status: 0,
+            azimuth_indexing_mode: 0,
+            data_block_count: 1,
+        });
+        message.radial_data_block = Some(digital_radar_data::RadialDataBlock {
+            data_block_id: block_id(b"RAD"),
+            lrtup: 20,
+            unambiguous_range: 4660,
+            horizontal_channel_noise_level: -81.5,
+            vertical_channel_noise_level: -81.2,
+            nyquist_velocity: 2650,
+            radial_flags: 0,
+            horizontal_channel_calibration_constant: 0.0,
+            vertical_channel_calibration_constant: 0.0,
+        });
+
+        // The legacy block is the message's last, so decoding must not read past its 20 bytes
+        let mut encoded = Vec::new();
+        encode_digital_radar_data(&mut encoded, &message)?;
+        let decoded = decode_digital_radar_data(&mut Cursor::new(encoded.as_slice()))?;
+        assert_eq!(decoded, message);
+
+        let radial = decoded.into_radial()?;
+        assert_eq!(radial.nyquist_velocity_meters_per_second(), Some(26.5));
+        assert_eq!(radial.unambiguous_range_km(), Some(466.0));
+        assert_eq!(radial.horizontal_calibration_constant_dbz(), None);
+        assert_eq!(radial.vertical_calibration_constant_dbz(), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_mismatched_block_count() {
+        let mut message = digital_radar_data::Message::new(digital_radar_data::Header {
+            radar_identifier: *b"KDMX",
+            time: 0,
+            date: 0,
+            azimuth_number: 0,
+            azimuth_angle: 0.0,
+            compression_indicator: 0,
+            spare: 0,
+            radial_length: 0,
+            azimuth_resolution_spacing: 0,
+            radial_status: 0,
+            elevation_number: 0,
+            cut_sector_number: 0,
+            elevation_angle: 0.0,
+            radial_spot_blanking_status: 0,
+            azimuth_indexing_mode: 0,
+            data_block_count: 0,
+        });
+        message.header.data_block_count = 1;
+
+        let mut encoded = Vec::new();
+        assert!(matches!(
+            encode_message(&mut encoded, &Message::DigitalRadarData(Box::new(message))),
+            Err(Error::EncodingError(_))
+        ));
+    }
+}
@@ -0,0 +1,314 @@
+//!
+//! Message type 8 "Clutter Censor Zones" is sent by the RPG to override the RDA's clutter bypass
+//! map within operator-defined regions. Each region covers a span of range and azimuth within one
+//! of the clutter map's elevation segments and selects how clutter filtering is applied there.
+//!
+//! The message consists of a region count followed by six halfwords for each region. [Message]
+//! validates regions as they are added so that an encoded message is always acceptable to the RDA.
+//!
+
+use crate::result::{Error, Result};
+use std::io::{Read, Write};
+
+/// The maximum number of censor zones in a message.
+pub const MAX_CENSOR_ZONES: usize = 25;
+
+/// The maximum range in kilometers of a censor zone.
+pub const MAX_RANGE_KM: u16 = 511;
+
+/// The maximum azimuth in degrees of a censor zone.
+pub const MAX_AZIMUTH_DEGREES: u16 = 360;
+
+/// The highest clutter map elevation segment number a censor zone may apply to.
+pub const MAX_ELEVATION_SEGMENT_NUMBER: u16 = 5;
+
+/// How clutter filtering is applied within a censor zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OperatorSelectCode {
+    /// The bypass filter is forced, so no clutter filtering is applied.
+    BypassFilterForced,
+    /// The clutter bypass map determines where clutter filtering is applied.
+    BypassMapInControl,
+    /// Clutter filtering is forced.
+    ClutterFilteringForced,
+}
+
+impl OperatorSelectCode {
+    fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0 => Some(OperatorSelectCode::BypassFilterForced),
+            1 => Some(OperatorSelectCode::BypassMapInControl),
+            2 => Some(OperatorSelectCode::ClutterFilteringForced),
+            _ => None,
+        }
+    }
+
+    fn code(&self) -> u16 {
+        match self {
+            OperatorSelectCode::BypassFilterForced => 0,
+            OperatorSelectCode::BypassMapInControl => 1,
+            OperatorSelectCode::ClutterFilteringForced => 2,
+        }
+    }
+}
+
+/// A region in which clutter filtering is overridden. Azimuths are measured clockwise from north,
+/// a zone whose start azimuth is greater than its stop azimuth spans north, and a zone from 0 to
+/// 360 degrees covers every azimuth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CensorZone {
+    start_range_km: u16,
+    stop_range_km: u16,
+    start_azimuth_degrees: u16,
+    stop_azimuth_degrees: u16,
+    elevation_segment_number: u16,
+    operator_select_code: OperatorSelectCode,
+}
+
+impl CensorZone {
+    /// Creates a new censor zone, validating that its ranges are increasing and at most
+    /// [MAX_RANGE_KM], its azimuths are at most [MAX_AZIMUTH_DEGREES] and either distinct or a full
+    /// circle from 0 to 360 degrees, and its elevation segment number is from 1 to
+    /// [MAX_ELEVATION_SEGMENT_NUMBER].
+    pub fn new(
+        start_range_km: u16,
+        stop_range_km: u16,
+        start_azimuth_degrees: u16,
+        stop_azimuth_degrees: u16,
+        elevation_segment_number: u16,
+        operator_select_code: OperatorSelectCode,
+    ) -> Result<Self> {
+        if start_range_km >= stop_range_km || stop_range_km > MAX_RANGE_KM {
+            return Err(Error::InvalidCensorZone(format!(
+                "range {}-{} km must be increasing and at most {} km",
+                start_range_km, stop_range_km, MAX_RANGE_KM
+            )));
+        }
+
+        let full_circle = start_azimuth_degrees == 0 && stop_azimuth_degrees == 360;
+        if start_azimuth_degrees > MAX_AZIMUTH_DEGREES
+            || stop_azimuth_degrees > MAX_AZIMUTH_DEGREES
+            || (start_azimuth_degrees % 360 == stop_azimuth_degrees % 360 && !full_circle)
+        {
+            return Err(Error::InvalidCensorZone(format!(
+                "azimuth {}-{} degrees must be distinct or a full circle and at most {} degrees",
+                start_azimuth_degrees, stop_azimuth_degrees, MAX_AZIMUTH_DEGREES
+            )));
+        }
+
+        if !(1..=MAX_ELEVATION_SEGMENT_NUMBER).contains(&elevation_segment_number) {
+            return Err(Error::InvalidCensorZone(format!(
+                "elevation segment {} must be from 1 to {}",
+                elevation_segment_number, MAX_ELEVATION_SEGMENT_NUMBER
+            )));
+        }
+
+        Ok(Self {
+            start_range_km,
+            stop_range_km,
+            start_azimuth_degrees,
+            stop_azimuth_degrees,
+            elevation_segment_number,
+            operator_select_code,
+        })
+    }
+
+    /// The zone's starting range in kilometers.
+    pub fn start_range_km(&self) -> u16 {
+        self.start_range_km
+    }
+
+    /// The zone's ending range in kilometers.
+    pub fn stop_range_km(&self) -> u16 {
+        self.stop_range_km
+    }
+
+    /// The zone's starting azimuth in degrees clockwise from north.
+    pub fn start_azimuth_degrees(&self) -> u16 {
+        self.start_azimuth_degrees
+    }
+
+    /// The zone's ending azimuth in degrees clockwise from north.
+    pub fn stop_azimuth_degrees(&self) -> u16 {
+        self.stop_azimuth_degrees
+    }
+
+    /// The clutter map elevation segment this zone applies to.
+    pub fn elevation_segment_number(&self) -> u16 {
+        self.elevation_segment_number
+    }
+
+    /// How clutter filtering is applied within this zone.
+    pub fn operator_select_code(&self) -> OperatorSelectCode {
+        self.operator_select_code
+    }
+}
+
+/// A set of clutter censor zones which may be edited and encoded as a message type 8.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Message {
+    zones: Vec<CensorZone>,
+}
+
+impl Message {
+    /// Creates a new message without any censor zones.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The message's censor zones in order.
+    pub fn zones(&self) -> &[CensorZone] {
+        &self.zones
+    }
+
+    /// Adds a censor zone, failing if the message already has [MAX_CENSOR_ZONES] zones.
+    pub fn add_zone(&mut self, zone: CensorZone) -> Result<()> {
+        if self.zones.len() >= MAX_CENSOR_ZONES {
+            return Err(Error::InvalidCensorZone(format!(
+                "at most {} censor zones are allowed",
+                MAX_CENSOR_ZONES
+            )));
+        }
+
+        self.zones.push(zone);
+        Ok(())
+    }
+
+    /// Replaces the censor zone at the given index, returning the zone it replaced or [None] if
+    /// there is no zone at that index.
+    pub fn replace_zone(&mut self, index: usize, zone: CensorZone) -> Option<CensorZone> {
+        self.zones
+            .get_mut(index)
+            .map(|existing| std::mem::replace(existing, zone))
+    }
+
+    /// Removes the censor zone at the given index, returning it or [None] if there is no zone at
+    /// that index.
+    pub fn remove_zone(&mut self, index: usize) -> Option<CensorZone> {
+        (index < self.zones.len()).then(|| self.zones.remove(index))
+    }
+}
+
+/// Decodes a clutter censor zones message type 8 from the provided reader, validating each zone.
+pub fn decode_clutter_censor_zones<R: Read>(reader: &mut R) -> Result<Message> {
+    let zone_count = read_halfword(reader)? as usize;
+
+    let mut message = Message::new();
+    for _ in 0..zone_count {
+        let mut fields = [0; 6];
+        for field in &mut fields {
+            *field = read_halfword(reader)?;
+        }
+
+        let operator_select_code = OperatorSelectCode::from_code(fields[5]).ok_or_else(|| {
+            Error::InvalidCensorZone(format!("unknown operator select code {}", fields[5]))
+        })?;
+
+        message.add_zone(CensorZone::new(
+            fields[0],
+            fields[1],
+            fields[2],
+            fields[3],
+            fields[4],
+            operator_select_code,
+        )?)?;
+    }
+
+    Ok(message)
+}
+
+/// Encodes a clutter censor zones message type 8 to the provided writer.
+pub fn encode_clutter_censor_zones<W: Write>(writer: &mut W, message: &Message) -> Result<()> {
+    writer.write_all(&(message.zones.len() as u16).to_be_bytes())?;
+
+    for zone in &message.zones {
+        for field in [
+            zone.start_range_km,
+            zone.stop_range_km,
+            zone.start_azimuth_degrees,
+            zone.stop_azimuth_degrees,
+            zone.elevation_segment_number,
+            zone.operator_select_code.code(),
+        ] {
+            writer.write_all(&field.to_be_bytes())?;
+        }
+    }
+
+    Ok(())
+}
+
+fn read_halfword<R: Read>(reader: &mut R) -> Result<u16> {
+    let mut halfword = [0; 2];
+    reader.read_exact(&mut halfword)?;
+    Ok(u16::from_be_bytes(halfword))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_censor_zones_round_trip() -> Result<()> {
+        let mut message = Message::new();
+        message.add_zone(CensorZone::new(
+            0,
+            40,
+            350,
+            10,
+            1,
+            OperatorSelectCode::ClutterFilteringForced,
+        )?)?;
+        message.add_zone(CensorZone::new(
+            20,
+            60,
+            90,
+            180,
+            2,
+            OperatorSelectCode::BypassFilterForced,
+        )?)?;
+
+        let mut encoded = Vec::new();
+        encode_clutter_censor_zones(&mut encoded, &message)?;
+        assert_eq!(encoded.len(), 2 + 2 * 6 * 2);
+
+        let decoded = decode_clutter_censor_zones(&mut encoded.as_slice())?;
+        assert_eq!(decoded, message);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_censor_zone_validation() {
+        let code = OperatorSelectCode::BypassMapInControl;
+        assert!(CensorZone::new(40, 20, 0, 90, 1, code).is_err());
+        assert!(CensorZone::new(0, 600, 0, 90, 1, code).is_err());
+        assert!(CensorZone::new(0, 20, 0, 361, 1, code).is_err());
+        assert!(CensorZone::new(0, 20, 90, 90, 1, code).is_err());
+        assert!(CensorZone::new(0, 20, 360, 0, 1, code).is_err());
+        assert!(CensorZone::new(0, 20, 0, 90, 0, code).is_err());
+        assert!(CensorZone::new(0, 20, 0, 90, 6, code).is_err());
+
+        // A zone from 0 to 360 degrees covers every azimuth
+        assert!(CensorZone::new(0, 20, 0, 360, 1, code).is_ok());
+    }
+
+    #[test]
+    fn test_censor_zone_editing() -> Result<()> {
+        let code = OperatorSelectCode::BypassMapInControl;
+        let zone = CensorZone::new(0, 20, 0, 90, 1, code)?;
+        let replacement = CensorZone::new(0, 30, 0, 90, 1, code)?;
+
+        let mut message = Message::new();
+        for _ in 0..MAX_CENSOR_ZONES {
+            message.add_zone(zone)?;
+        }
+        assert!(message.add_zone(zone).is_err());
+
+        assert_eq!(message.replace_zone(0, replacement), Some(zone));
+        assert_eq!(message.remove_zone(0), Some(replacement));
+        assert_eq!(message.remove_zone(MAX_CENSOR_ZONES), None);
+        assert_eq!(message.zones().len(), MAX_CENSOR_ZONES - 1);
+
+        Ok(())
+    }
+}
@@ -22,9 +22,9 @@ pub use range_zone::RangeZone;
 mod definitions;
 pub use definitions::*;
 
-use crate::result::Result;
-use crate::util::deserialize;
-use std::io::Read;
+use crate::result::{Error, Result};
+use crate::util::{deserialize, serialize};
+use std::io::{Read, Write};
 
 /// Decodes a clutter filter map message type 15 from the provided reader.
 pub fn decode_clutter_filter_map<R: Read>(reader: &mut R) -> Result<Message> {
@@ -53,3 +53,37 @@ pub fn decode_clutter_filter_map<R: Read>(reader: &mut R) -> Result<Message> {
 
     Ok(message)
 }
+
+/// Encodes a clutter filter map message type 15 to the provided writer.
+pub fn encode_clutter_filter_map<W: Write>(writer: &mut W, message: &Message) -> Result<()> {
+    if message.elevation_segments.len() != message.header.elevation_segment_count as usize {
+        return Err(Error::EncodingError(
+            "elevation segment count does not match header".to_string(),
+        ));
+    }
+
+    serialize(writer, &message.header)?;
+    for elevation_segment in &message.elevation_segments {
+        if elevation_segment.azimuth_segments.len() != 360 {
+            return Err(Error::EncodingError(
+                "elevation segment must contain 360 azimuth segments".to_string(),
+            ));
+        }
+
+        for azimuth_segment in &elevation_segment.azimuth_segments {
+            if azimuth_segment.range_zones.len() != azimuth_segment.header.range_zone_count as usize
+            {
+                return Err(Error::EncodingError(
+                    "range zone count does not match azimuth segment header".to_string(),
+                ));
+            }
+
+            serialize(writer, &azimuth_segment.header)?;
+            for range_zone in &azimuth_segment.range_zones {
+                serialize(writer, range_zone)?;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -1,9 +1,9 @@
 use crate::messages::clutter_filter_map::range_zone::RangeZone;
 use crate::messages::primitive_aliases::Integer2;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Header information for an azimuth segment to be read directly from the Archive II file.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct AzimuthSegmentHeader {
     /// The number of range zones defined in this azimuth segment, from 1 to 20.
     pub range_zone_count: Integer2,
@@ -7,4 +7,6 @@ pub enum OpCode {
     BypassMapInControl,
     /// The clutter filter is being forced for the range segment.
     ForceFilter,
+    /// A value not defined by the ICD.
+    Other(u16),
 }
@@ -0,0 +1,94 @@
+//!
+//! Message types 4 "RDA Console Message" and 10 "RPG Console Message" carry free-text messages
+//! exchanged between the operators at the RDA and the RPG. Both types share the same structure: a
+//! character count followed by the message's ASCII text.
+//!
+
+use crate::messages::{Message as AnyMessage, MessageType, MessageWithHeader};
+use crate::result::{Error, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+/// A free-text console message.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Message {
+    /// The message's text with any trailing padding removed.
+    pub text: String,
+}
+
+/// Which system's operator sent a console message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Origin {
+    /// The message was sent from the RDA, message type 4.
+    RDA,
+    /// The message was sent from the RPG, message type 10.
+    RPG,
+}
+
+impl Origin {
+    /// The origin of console messages of the given type, if it is a console message type.
+    pub fn from_message_type(message_type: MessageType) -> Option<Self> {
+        match message_type {
+            MessageType::RDAConsoleMessage => Some(Origin::RDA),
+            MessageType::RPGConsoleMessage => Some(Origin::RPG),
+            _ => None,
+        }
+    }
+}
+
+/// A console message along with when and from where it was sent.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ConsoleEntry {
+    /// When the message was sent, if its header had a valid date and time.
+    pub date_time: Option<DateTime<Utc>>,
+    /// Which system's operator sent the message.
+    pub origin: Origin,
+    /// The message's text.
+    pub text: String,
+}
+
+/// The console messages among the given messages, in the order they appear.
+pub fn console_entries(messages: &[MessageWithHeader]) -> Vec<ConsoleEntry> {
+    messages
+        .iter()
+        .filter_map(|message| match &message.message {
+            AnyMessage::ConsoleMessage(console_message) => Some(ConsoleEntry {
+                date_time: message.header.date_time(),
+                origin: Origin::from_message_type(message.header.message_type())?,
+                text: console_message.text.clone(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Decodes a console message type 4 or 10 from the provided reader. A character count exceeding the
+/// available data is tolerated by decoding the text that is present.
+pub fn decode_console_message<R: Read>(reader: &mut R) -> Result<Message> {
+    let mut size = [0; 2];
+    reader.read_exact(&mut size)?;
+
+    let mut text = Vec::new();
+    reader
+        .by_ref()
+        .take(u16::from_be_bytes(size) as u64)
+        .read_to_end(&mut text)?;
+
+    Ok(Message {
+        text: String::from_utf8_lossy(&text)
+            .trim_end_matches('\0')
+            .to_string(),
+    })
+}
+
+/// Encodes a console message type 4 or 10 to the provided writer.
+pub fn encode_console_message<W: Write>(writer: &mut W, message: &Message) -> Result<()> {
+    let size = u16::try_from(message.text.len())
+        .map_err(|_| Error::EncodingError("console message text is too long".to_string()))?;
+
+    writer.write_all(&size.to_be_bytes())?;
+    writer.write_all(message.text.as_bytes())?;
+
+    Ok(())
+}
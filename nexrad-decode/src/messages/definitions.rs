@@ -7,4 +7,6 @@ pub enum RedundantChannel {
     ORDASingleChannel,
     ORDARedundantChannel1,
     ORDARedundantChannel2,
+    /// A value not defined by the ICD.
+    Other(u8),
 }
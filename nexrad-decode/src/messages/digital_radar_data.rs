@@ -27,6 +27,9 @@ pub use elevation_data_block::ElevationDataBlock;
 mod radial_data_block;
 pub use radial_data_block::RadialDataBlock;
 
+mod unknown_data_block;
+pub use unknown_data_block::UnknownDataBlock;
+
 mod definitions;
 pub use definitions::*;
 
@@ -36,12 +39,69 @@ pub use spot_blanking_status::*;
 mod pointers;
 pub use pointers::*;
 
+mod unpack;
+
+use crate::limits::DecodeLimits;
 use crate::result::{Error, Result};
-use crate::util::deserialize;
-use std::io::{Read, Seek, SeekFrom};
+use crate::util::{deserialize, serialize};
+use serde::Serialize;
+use std::borrow::Cow;
+use std::io::{Cursor, ErrorKind, Read, Seek, SeekFrom, Write};
+
+/// Decodes a digital radar data message type 31 from the provided reader. Moment data is copied
+/// into the returned message; see [decode_digital_radar_data_borrowed] to avoid these copies.
+pub fn decode_digital_radar_data<R: Read + Seek>(reader: &mut R) -> Result<Message<'static>> {
+    decode_digital_radar_data_with_limits(reader, &DecodeLimits::new())
+}
+
+/// Decodes a digital radar data message type 31 from the provided reader within the given limits.
+/// Moment data is copied into the returned message.
+pub fn decode_digital_radar_data_with_limits<R: Read + Seek>(
+    reader: &mut R,
+    limits: &DecodeLimits,
+) -> Result<Message<'static>> {
+    decode_digital_radar_data_with(reader, limits, |reader, length| {
+        let mut data = vec![0; length];
+        reader.read_exact(&mut data)?;
+        Ok(Cow::Owned(data))
+    })
+}
 
-/// Decodes a digital radar data message type 31 from the provided reader.
-pub fn decode_digital_radar_data<R: Read + Seek>(reader: &mut R) -> Result<Message> {
+/// Decodes a digital radar data message type 31 from the provided in-memory reader. Moment data is
+/// borrowed from the reader's underlying buffer rather than copied, and may be detached from it
+/// with [Message::into_owned].
+pub fn decode_digital_radar_data_borrowed<'a>(
+    reader: &mut Cursor<&'a [u8]>,
+) -> Result<Message<'a>> {
+    decode_digital_radar_data_borrowed_with_limits(reader, &DecodeLimits::new())
+}
+
+/// Decodes a digital radar data message type 31 from the provided in-memory reader within the given
+/// limits. Moment data is borrowed from the reader's underlying buffer rather than copied.
+pub fn decode_digital_radar_data_borrowed_with_limits<'a>(
+    reader: &mut Cursor<&'a [u8]>,
+    limits: &DecodeLimits,
+) -> Result<Message<'a>> {
+    decode_digital_radar_data_with(reader, limits, |reader, length| {
+        let data: &'a [u8] = reader.get_ref();
+        let start = reader.position() as usize;
+        let end = start.saturating_add(length);
+        let block = data
+            .get(start..end)
+            .ok_or_else(|| std::io::Error::from(ErrorKind::UnexpectedEof))?;
+
+        reader.set_position(end as u64);
+        Ok(Cow::Borrowed(block))
+    })
+}
+
+/// Decodes a digital radar data message, using `read_data` to read each generic data block's
+/// moment data of the given length from the reader.
+fn decode_digital_radar_data_with<'a, R: Read + Seek>(
+    reader: &mut R,
+    limits: &DecodeLimits,
+    mut read_data: impl FnMut(&mut R, usize) -> Result<Cow<'a, [u8]>>,
+) -> Result<Message<'a>> {
     let start_position = reader.stream_position()?;
 
     let header = deserialize(reader)?;
@@ -74,13 +134,42 @@ pub fn decode_digital_radar_data<R: Read + Seek>(reader: &mut R) -> Result<Messa
                 message.elevation_data_block = Some(deserialize(reader)?);
             }
             "RAD" => {
-                message.radial_data_block = Some(deserialize(reader)?);
+                message.radial_data_block = Some(decode_radial_data_block(reader)?);
+            }
+            _ if data_block_id.data_block_type() == 'R' => {
+                let data_block_id: DataBlockId = deserialize(reader)?;
+                let lrtup: u16 = deserialize(reader)?;
+                let data_size = (lrtup as usize)
+                    .checked_sub(UnknownDataBlock::PREFIX_SIZE)
+                    .ok_or_else(|| {
+                        Error::DecodingError(format!(
+                            "data block size {lrtup} too small: {data_block_id:?}"
+                        ))
+                    })?;
+
+                message.unknown_data_blocks.push(UnknownDataBlock {
+                    data: read_data(reader, data_size)?,
+                    data_block_id,
+                    lrtup,
+                });
             }
             _ => {
-                let generic_header: GenericDataBlockHeader = deserialize(reader)?;
+                let header: GenericDataBlockHeader = deserialize(reader)?;
+                if header.number_of_data_moment_gates > limits.max_gate_count() {
+                    return Err(Error::LimitExceeded(format!(
+                        "{} gates in {} block exceeds the limit of {}",
+                        header.number_of_data_moment_gates,
+                        data_block_id.data_block_name(),
+                        limits.max_gate_count()
+                    )));
+                }
 
-                let mut generic_data_block = GenericDataBlock::new(generic_header);
-                reader.read_exact(&mut generic_data_block.encoded_data)?;
+                let word_size_bytes = header.data_word_size as usize / 8;
+                let data_size = header.number_of_data_moment_gates as usize * word_size_bytes;
+                let generic_data_block = GenericDataBlock {
+                    encoded_data: read_data(reader, data_size)?,
+                    header,
+                };
 
                 match data_block_id.data_block_name().as_str() {
                     "REF" => {
@@ -104,7 +193,9 @@ pub fn decode_digital_radar_data<R: Read + Seek>(reader: &mut R) -> Result<Messa
                     "CFP" => {
                         message.specific_diff_phase_data_block = Some(generic_data_block);
                     }
-                    _ => panic!("Unknown generic data block type: {:?}", data_block_id),
+                    _ => {
+                        message.extension_data_blocks.push(generic_data_block);
+                    }
                 }
             }
         }
@@ -112,3 +203,126 @@ pub fn decode_digital_radar_data<R: Read + Seek>(reader: &mut R) -> Result<Messa
 
     Ok(message)
 }
+
+/// Decodes a radial data block, which may be a legacy 20-byte block lacking the trailing fields of
+/// the current 28-byte layout. Missing fields are decoded as zero.
+fn decode_radial_data_block<R: Read>(reader: &mut R) -> Result<RadialDataBlock> {
+    let mut data = [0; RadialDataBlock::SIZE];
+    reader.read_exact(&mut data[..RADIAL_DATA_BLOCK_PREFIX_SIZE])?;
+
+    let lrtup = u16::from_be_bytes([data[4], data[5]]);
+    reader.read_exact(&mut data[RADIAL_DATA_BLOCK_PREFIX_SIZE..radial_data_block_size(lrtup)])?;
+
+    deserialize(&mut data.as_slice())
+}
+
+/// The size of a radial data block's identifier and size fields.
+const RADIAL_DATA_BLOCK_PREFIX_SIZE: usize = size_of::<DataBlockId>() + size_of::<u16>();
+
+/// The number of bytes encoding a radial data block with the given size field.
+fn radial_data_block_size(lrtup: u16) -> usize {
+    (lrtup as usize).clamp(RADIAL_DATA_BLOCK_PREFIX_SIZE, RadialDataBlock::SIZE)
+}
+
+/// Encodes a digital radar data message type 31 to the provided writer. The message's data blocks
+/// are written in ICD order following the header and data block pointers, which are computed from
+/// the encoded blocks. The header's data block count must match the number of blocks present.
+///
+/// Unrecognized non-moment blocks are written after the radial data block and unrecognized moment
+/// blocks after the known moments, each in their relative order. Where a decoded message
+/// interleaved unrecognized blocks with known ones, that interleaving is not preserved.
+pub fn encode_digital_radar_data<W: Write>(writer: &mut W, message: &Message) -> Result<()> {
+    let mut blocks = Vec::new();
+    if let Some(block) = &message.volume_data_block {
+        blocks.push(encode_data_block(block)?);
+    }
+    if let Some(block) = &message.elevation_data_block {
+        blocks.push(encode_data_block(block)?);
+    }
+    if let Some(block) = &message.radial_data_block {
+        let mut data = encode_data_block(block)?;
+        data.truncate(radial_data_block_size(block.lrtup));
+        blocks.push(data);
+    }
+    for block in &message.unknown_data_blocks {
+        blocks.push(encode_unknown_data_block(block)?);
+    }
+
+    for block in [
+        &message.reflectivity_data_block,
+        &message.velocity_data_block,
+        &message.spectrum_width_data_block,
+        &message.differential_reflectivity_data_block,
+        &message.differential_phase_data_block,
+        &message.correlation_coefficient_data_block,
+        &message.specific_diff_phase_data_block,
+    ]
+    .into_iter()
+    .flatten()
+    .chain(&message.extension_data_blocks)
+    {
+        blocks.push(encode_generic_data_block(block)?);
+    }
+
+    if blocks.len() != message.header.data_block_count as usize {
+        return Err(Error::EncodingError(
+            "data block count does not match header".to_string(),
+        ));
+    }
+
+    let header = encode_data_block(&message.header)?;
+    writer.write_all(&header)?;
+
+    let mut pointer = header.len() + blocks.len() * size_of::<u32>();
+    for block in &blocks {
+        let block_pointer = u32::try_from(pointer)
+            .map_err(|_| Error::EncodingError("message pointers".to_string()))?;
+        writer.write_all(&block_pointer.to_be_bytes())?;
+        pointer += block.len();
+    }
+
+    for block in &blocks {
+        writer.write_all(block)?;
+    }
+
+    Ok(())
+}
+
+/// Encodes a fixed-size data block.
+fn encode_data_block<S: Serialize>(block: &S) -> Result<Vec<u8>> {
+    let mut data = Vec::new();
+    serialize(&mut data, block)?;
+    Ok(data)
+}
+
+/// Encodes an unknown data block, ensuring its data matches its size.
+fn encode_unknown_data_block(block: &UnknownDataBlock) -> Result<Vec<u8>> {
+    if block.data.len() + UnknownDataBlock::PREFIX_SIZE != block.lrtup as usize {
+        return Err(Error::EncodingError(format!(
+            "{} data size does not match its size",
+            block.data_block_id.data_block_name()
+        )));
+    }
+
+    let mut data = encode_data_block(&block.data_block_id)?;
+    data.extend_from_slice(&block.lrtup.to_be_bytes());
+    data.extend_from_slice(&block.data);
+    Ok(data)
+}
+
+/// Encodes a generic data moment block, ensuring its data matches its header's gate count and word
+/// size.
+fn encode_generic_data_block(block: &GenericDataBlock) -> Result<Vec<u8>> {
+    let word_size_bytes = block.header.data_word_size as usize / 8;
+    let expected_size = block.header.number_of_data_moment_gates as usize * word_size_bytes;
+    if block.encoded_data.len() != expected_size {
+        return Err(Error::EncodingError(format!(
+            "{} data size does not match header",
+            block.header.data_block_id.data_block_name()
+        )));
+    }
+
+    let mut data = encode_data_block(&block.header)?;
+    data.extend_from_slice(&block.encoded_data);
+    Ok(data)
+}
@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 /// Indicates whether the message is compressed and what type of compression was used.
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub enum CompressionIndicator {
@@ -26,6 +28,8 @@ pub enum ControlFlags {
     RecombinedAzimuthalRadials,
     RecombinedRangeGates,
     RecombinedRadialsAndRangeGatesToLegacyResolution,
+    /// A value not defined by the ICD.
+    Other(u8),
 }
 
 /// Processing status flags.
@@ -37,7 +41,7 @@ pub enum ProcessingStatus {
 }
 
 /// Volume coverage pattern (VCP) definitions.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum VolumeCoveragePattern {
     VCP12,
     VCP31,
@@ -45,6 +49,8 @@ pub enum VolumeCoveragePattern {
     VCP112,
     VCP212,
     VCP215,
+    /// A value not defined by the ICD.
+    Other(u16),
 }
 
 /// The value for a data moment/radial, gate, and product. The value may be a floating-point number
@@ -1,8 +1,10 @@
-use crate::messages::digital_radar_data::{ControlFlags, DataBlockId, ScaledMomentValue};
+use crate::messages::digital_radar_data::{unpack, ControlFlags, DataBlockId, ScaledMomentValue};
 use crate::messages::primitive_aliases::{
     Code1, Integer1, Integer2, Integer4, Real4, ScaledInteger2,
 };
-use serde::Deserialize;
+use crate::result::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::fmt::Debug;
 
 #[cfg(feature = "uom")]
@@ -12,24 +14,23 @@ use uom::si::information::byte;
 #[cfg(feature = "uom")]
 use uom::si::length::kilometer;
 
-/// A generic data moment block.
+/// A generic data moment block. Its encoded moment data may be borrowed from the buffer it was
+/// decoded from, see [GenericDataBlock::into_owned].
 #[derive(Clone, PartialEq)]
-pub struct GenericDataBlock {
+pub struct GenericDataBlock<'a> {
     /// The generic data block's header information.
     pub header: GenericDataBlockHeader,
 
     /// The generic data block's encoded moment data.
-    pub encoded_data: Vec<u8>,
+    pub encoded_data: Cow<'a, [u8]>,
 }
 
-impl GenericDataBlock {
-    /// Creates a new generic data moment block from the decoded header.
-    pub(crate) fn new(header: GenericDataBlockHeader) -> Self {
-        let word_size_bytes = header.data_word_size as usize / 8;
-        let encoded_data_size = header.number_of_data_moment_gates as usize * word_size_bytes;
-        Self {
-            encoded_data: vec![0; encoded_data_size],
-            header,
+impl GenericDataBlock<'_> {
+    /// Converts this block into one which owns its encoded moment data, copying it if borrowed.
+    pub fn into_owned(self) -> GenericDataBlock<'static> {
+        GenericDataBlock {
+            header: self.header,
+            encoded_data: Cow::Owned(self.encoded_data.into_owned()),
         }
     }
 
@@ -44,9 +45,17 @@ impl GenericDataBlock {
     /// their floating point representation. Additionally, identifies special values such as "below
     /// threshold" and "range folded".
     pub fn decoded_values(&self) -> Vec<ScaledMomentValue> {
-        self.encoded_data
-            .iter()
-            .copied()
+        let words: Box<dyn Iterator<Item = u16>> = if self.header.data_word_size == 16 {
+            Box::new(
+                self.encoded_data
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_be_bytes([pair[0], pair[1]])),
+            )
+        } else {
+            Box::new(self.encoded_data.iter().map(|&word| word as u16))
+        };
+
+        words
             .map(|raw_value| {
                 if self.header.scale == 0.0 {
                     return ScaledMomentValue::Value(raw_value as f32);
@@ -63,28 +72,53 @@ impl GenericDataBlock {
             .collect()
     }
 
+    /// Decodes every gate of this block into `output` in a single pass, returning the number of
+    /// gates written. Gates which are below threshold or range folded are written as `f32::NAN`;
+    /// use [GenericDataBlock::decoded_values] to distinguish between them. Both 8-bit and 16-bit
+    /// data words are supported. Fails if `output` has fewer elements than the block has gates.
+    pub fn decode_into(&self, output: &mut [f32]) -> Result<usize> {
+        let word_size_bytes = match self.header.data_word_size {
+            8 => 1,
+            16 => 2,
+            word_size => {
+                return Err(Error::DecodingError(format!(
+                    "unsupported data word size: {word_size}"
+                )))
+            }
+        };
+
+        let gate_count = self.encoded_data.len() / word_size_bytes;
+        let output_len = output.len();
+        let output = output.get_mut(..gate_count).ok_or_else(|| {
+            Error::DecodingError(format!(
+                "output holds {output_len} values but block has {gate_count} gates"
+            ))
+        })?;
+
+        let (scale, offset) = (self.header.scale, self.header.offset);
+        if word_size_bytes == 1 {
+            unpack::unpack_u8(&self.encoded_data, scale, offset, output);
+        } else {
+            unpack::unpack_u16(&self.encoded_data, scale, offset, output);
+        }
+
+        Ok(gate_count)
+    }
+
     /// Get moment data from this generic data block. Note that this will clone the underlying data.
     #[cfg(feature = "nexrad-model")]
     pub fn moment_data(&self) -> nexrad_model::data::MomentData {
-        nexrad_model::data::MomentData::from_fixed_point(
-            self.header.scale,
-            self.header.offset,
-            self.encoded_data.clone(),
-        )
+        self.header.moment_data(self.encoded_data.to_vec())
     }
 
     /// Convert this generic data block into common model moment data, minimizing data copies.
     #[cfg(feature = "nexrad-model")]
     pub fn into_moment_data(self) -> nexrad_model::data::MomentData {
-        nexrad_model::data::MomentData::from_fixed_point(
-            self.header.scale,
-            self.header.offset,
-            self.encoded_data,
-        )
+        self.header.moment_data(self.encoded_data.into_owned())
     }
 }
 
-impl Debug for GenericDataBlock {
+impl Debug for GenericDataBlock<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("GenericDataBlock")
             .field("header", &self.header)
@@ -94,7 +128,7 @@ impl Debug for GenericDataBlock {
 }
 
 /// A generic data moment block's decoded header.
-#[derive(Clone, PartialEq, Deserialize)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct GenericDataBlockHeader {
     /// Data block identifier.
     pub data_block_id: DataBlockId,
@@ -108,7 +142,7 @@ pub struct GenericDataBlockHeader {
     /// Range to center of first range gate in 0.000-scaled kilometers.
     pub data_moment_range: ScaledInteger2,
 
-    /// Size of data moment sample interval in 0.00-scaled kilometers from 0.25 to 4.0.
+    /// Size of data moment sample interval in 0.000-scaled kilometers from 0.25 to 4.0.
     pub data_moment_range_sample_interval: ScaledInteger2,
 
     /// Threshold parameter specifying the minimum difference in echo power between two resolution
@@ -147,7 +181,7 @@ impl GenericDataBlockHeader {
     /// Size of data moment sample interval.
     #[cfg(feature = "uom")]
     pub fn data_moment_range_sample_interval(&self) -> Length {
-        Length::new::<kilometer>(self.data_moment_range_sample_interval as f64 * 0.01)
+        Length::new::<kilometer>(self.data_moment_range_sample_interval as f64 * 0.001)
     }
 
     /// Flags indicating special control features.
@@ -157,7 +191,7 @@ impl GenericDataBlockHeader {
             1 => ControlFlags::RecombinedAzimuthalRadials,
             2 => ControlFlags::RecombinedRangeGates,
             3 => ControlFlags::RecombinedRadialsAndRangeGatesToLegacyResolution,
-            _ => panic!("Invalid control flag value: {}", self.control_flags),
+            _ => ControlFlags::Other(self.control_flags),
         }
     }
 
@@ -168,6 +202,26 @@ impl GenericDataBlockHeader {
             self.number_of_data_moment_gates as f64 * self.data_word_size as f64 / 8.0,
         )
     }
+
+    /// Common model moment data for the given encoded words, using this header's word size,
+    /// fixed-point encoding, and gate ranges.
+    #[cfg(feature = "nexrad-model")]
+    fn moment_data(&self, encoded_data: Vec<u8>) -> nexrad_model::data::MomentData {
+        let moment = if self.data_word_size == 16 {
+            nexrad_model::data::MomentData::from_fixed_point_16(
+                self.scale,
+                self.offset,
+                encoded_data,
+            )
+        } else {
+            nexrad_model::data::MomentData::from_fixed_point(self.scale, self.offset, encoded_data)
+        };
+
+        moment.with_gate_range(
+            self.data_moment_range as f32 * 0.001,
+            self.data_moment_range_sample_interval as f32 * 0.001,
+        )
+    }
 }
 
 #[cfg(not(feature = "uom"))]
@@ -219,3 +273,36 @@ impl Debug for GenericDataBlockHeader {
             .finish()
     }
 }
+
+#[cfg(all(test, feature = "uom"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gate_range_scale() {
+        let header = GenericDataBlockHeader {
+            data_block_id: DataBlockId {
+                data_block_type: b'D',
+                data_name: *b"REF",
+            },
+            reserved: 0,
+            number_of_data_moment_gates: 1832,
+            data_moment_range: 2125,
+            data_moment_range_sample_interval: 250,
+            tover: 50,
+            snr_threshold: 16,
+            control_flags: 0,
+            data_word_size: 8,
+            scale: 2.0,
+            offset: 66.0,
+        };
+
+        assert_eq!(header.data_moment_range().get::<kilometer>(), 2.125);
+        assert_eq!(
+            header
+                .data_moment_range_sample_interval()
+                .get::<kilometer>(),
+            0.25
+        );
+    }
+}
@@ -1,11 +1,12 @@
 use crate::messages::digital_radar_data::{
-    ElevationDataBlock, GenericDataBlock, Header, RadialDataBlock, VolumeDataBlock,
+    ElevationDataBlock, GenericDataBlock, Header, RadialDataBlock, UnknownDataBlock,
+    VolumeDataBlock,
 };
 
 /// The digital radar data message includes base radar data from a single radial for various
 /// products.
 #[derive(Debug, Clone, PartialEq)]
-pub struct Message {
+pub struct Message<'a> {
     /// The decoded digital radar data header.
     pub header: Header,
 
@@ -19,28 +20,36 @@ pub struct Message {
     pub radial_data_block: Option<RadialDataBlock>,
 
     /// Reflectivity data if included in the message.
-    pub reflectivity_data_block: Option<GenericDataBlock>,
+    pub reflectivity_data_block: Option<GenericDataBlock<'a>>,
 
     /// Velocity data if included in the message.
-    pub velocity_data_block: Option<GenericDataBlock>,
+    pub velocity_data_block: Option<GenericDataBlock<'a>>,
 
     /// Spectrum width data if included in the message.
-    pub spectrum_width_data_block: Option<GenericDataBlock>,
+    pub spectrum_width_data_block: Option<GenericDataBlock<'a>>,
 
     /// Differential reflectivity data if included in the message.
-    pub differential_reflectivity_data_block: Option<GenericDataBlock>,
+    pub differential_reflectivity_data_block: Option<GenericDataBlock<'a>>,
 
     /// Differential phase data if included in the message.
-    pub differential_phase_data_block: Option<GenericDataBlock>,
+    pub differential_phase_data_block: Option<GenericDataBlock<'a>>,
 
     /// Correlation coefficient data if included in the message.
-    pub correlation_coefficient_data_block: Option<GenericDataBlock>,
+    pub correlation_coefficient_data_block: Option<GenericDataBlock<'a>>,
 
     /// Specific differential phase data if included in the message.
-    pub specific_diff_phase_data_block: Option<GenericDataBlock>,
+    pub specific_diff_phase_data_block: Option<GenericDataBlock<'a>>,
+
+    /// Data moment blocks with unrecognized names, such as moments introduced by newer RDA builds,
+    /// in the order they were encoded. These are re-encoded after the known moment blocks.
+    pub extension_data_blocks: Vec<GenericDataBlock<'a>>,
+
+    /// Non-moment data blocks with unrecognized names, in the order they were encoded. These are
+    /// re-encoded after the radial data block.
+    pub unknown_data_blocks: Vec<UnknownDataBlock<'a>>,
 }
 
-impl Message {
+impl<'a> Message<'a> {
     /// Create a new digital radar data message with the decoded header.
     pub(crate) fn new(header: Header) -> Self {
         Self {
@@ -55,6 +64,48 @@ impl Message {
             differential_phase_data_block: None,
             correlation_coefficient_data_block: None,
             specific_diff_phase_data_block: None,
+            extension_data_blocks: Vec::new(),
+            unknown_data_blocks: Vec::new(),
+        }
+    }
+
+    /// Converts this message into one which owns its moment data, copying any data borrowed from
+    /// the decoded buffer.
+    pub fn into_owned(self) -> Message<'static> {
+        Message {
+            header: self.header,
+            volume_data_block: self.volume_data_block,
+            elevation_data_block: self.elevation_data_block,
+            radial_data_block: self.radial_data_block,
+            reflectivity_data_block: self
+                .reflectivity_data_block
+                .map(GenericDataBlock::into_owned),
+            velocity_data_block: self.velocity_data_block.map(GenericDataBlock::into_owned),
+            spectrum_width_data_block: self
+                .spectrum_width_data_block
+                .map(GenericDataBlock::into_owned),
+            differential_reflectivity_data_block: self
+                .differential_reflectivity_data_block
+                .map(GenericDataBlock::into_owned),
+            differential_phase_data_block: self
+                .differential_phase_data_block
+                .map(GenericDataBlock::into_owned),
+            correlation_coefficient_data_block: self
+                .correlation_coefficient_data_block
+                .map(GenericDataBlock::into_owned),
+            specific_diff_phase_data_block: self
+                .specific_diff_phase_data_block
+                .map(GenericDataBlock::into_owned),
+            extension_data_blocks: self
+                .extension_data_blocks
+                .into_iter()
+                .map(GenericDataBlock::into_owned)
+                .collect(),
+            unknown_data_blocks: self
+                .unknown_data_blocks
+                .into_iter()
+                .map(UnknownDataBlock::into_owned)
+                .collect(),
         }
     }
 
@@ -63,47 +114,65 @@ impl Message {
     pub fn radial(&self) -> crate::result::Result<nexrad_model::data::Radial> {
         use crate::messages::digital_radar_data::RadialStatus;
         use crate::result::Error;
-        use nexrad_model::data::{Radial, RadialStatus as ModelRadialStatus};
-
-        Ok(Radial::new(
-            self.header
-                .date_time()
-                .ok_or(Error::MessageMissingDateError)?
-                .timestamp_millis(),
-            self.header.azimuth_number,
-            self.header.azimuth_angle,
-            self.header.azimuth_resolution_spacing as f32 * 0.5,
-            match self.header.radial_status() {
-                RadialStatus::ElevationStart => ModelRadialStatus::ElevationStart,
-                RadialStatus::IntermediateRadialData => ModelRadialStatus::IntermediateRadialData,
-                RadialStatus::ElevationEnd => ModelRadialStatus::ElevationEnd,
-                RadialStatus::VolumeScanStart => ModelRadialStatus::VolumeScanStart,
-                RadialStatus::VolumeScanEnd => ModelRadialStatus::VolumeScanEnd,
-                RadialStatus::ElevationStartVCPFinal => ModelRadialStatus::ElevationStartVCPFinal,
-            },
-            self.header.elevation_number,
-            self.header.elevation_angle,
-            self.reflectivity_data_block
-                .as_ref()
-                .map(|block| block.moment_data()),
-            self.velocity_data_block
-                .as_ref()
-                .map(|block| block.moment_data()),
-            self.spectrum_width_data_block
-                .as_ref()
-                .map(|block| block.moment_data()),
-            self.differential_reflectivity_data_block
-                .as_ref()
-                .map(|block| block.moment_data()),
-            self.differential_phase_data_block
-                .as_ref()
-                .map(|block| block.moment_data()),
-            self.correlation_coefficient_data_block
-                .as_ref()
-                .map(|block| block.moment_data()),
-            self.specific_diff_phase_data_block
-                .as_ref()
-                .map(|block| block.moment_data()),
+        use nexrad_model::data::{ExtensionMoment, Radial, RadialStatus as ModelRadialStatus};
+
+        Ok(with_radial_data(
+            Radial::new(
+                self.header
+                    .date_time()
+                    .ok_or(Error::MessageMissingDateError)?
+                    .timestamp_millis(),
+                self.header.azimuth_number,
+                self.header.azimuth_angle,
+                self.header.azimuth_resolution_spacing as f32 * 0.5,
+                match self.header.radial_status() {
+                    RadialStatus::ElevationStart => ModelRadialStatus::ElevationStart,
+                    RadialStatus::IntermediateRadialData => {
+                        ModelRadialStatus::IntermediateRadialData
+                    }
+                    RadialStatus::ElevationEnd => ModelRadialStatus::ElevationEnd,
+                    RadialStatus::VolumeScanStart => ModelRadialStatus::VolumeScanStart,
+                    RadialStatus::VolumeScanEnd => ModelRadialStatus::VolumeScanEnd,
+                    RadialStatus::ElevationStartVCPFinal => {
+                        ModelRadialStatus::ElevationStartVCPFinal
+                    }
+                },
+                self.header.elevation_number,
+                self.header.elevation_angle,
+                self.reflectivity_data_block
+                    .as_ref()
+                    .map(|block| block.moment_data()),
+                self.velocity_data_block
+                    .as_ref()
+                    .map(|block| block.moment_data()),
+                self.spectrum_width_data_block
+                    .as_ref()
+                    .map(|block| block.moment_data()),
+                self.differential_reflectivity_data_block
+                    .as_ref()
+                    .map(|block| block.moment_data()),
+                self.differential_phase_data_block
+                    .as_ref()
+                    .map(|block| block.moment_data()),
+                self.correlation_coefficient_data_block
+                    .as_ref()
+                    .map(|block| block.moment_data()),
+                self.specific_diff_phase_data_block
+                    .as_ref()
+                    .map(|block| block.moment_data()),
+            )
+            .with_extension_moments(
+                self.extension_data_blocks
+                    .iter()
+                    .map(|block| {
+                        ExtensionMoment::new(
+                            block.header.data_block_id.data_block_name(),
+                            block.moment_data(),
+                        )
+                    })
+                    .collect(),
+            ),
+            self.radial_data_block.as_ref(),
         ))
     }
 
@@ -112,40 +181,80 @@ impl Message {
     pub fn into_radial(self) -> crate::result::Result<nexrad_model::data::Radial> {
         use crate::messages::digital_radar_data::RadialStatus;
         use crate::result::Error;
-        use nexrad_model::data::{Radial, RadialStatus as ModelRadialStatus};
-
-        Ok(Radial::new(
-            self.header
-                .date_time()
-                .ok_or(Error::MessageMissingDateError)?
-                .timestamp_millis(),
-            self.header.azimuth_number,
-            self.header.azimuth_angle,
-            self.header.azimuth_resolution_spacing as f32 * 0.5,
-            match self.header.radial_status() {
-                RadialStatus::ElevationStart => ModelRadialStatus::ElevationStart,
-                RadialStatus::IntermediateRadialData => ModelRadialStatus::IntermediateRadialData,
-                RadialStatus::ElevationEnd => ModelRadialStatus::ElevationEnd,
-                RadialStatus::VolumeScanStart => ModelRadialStatus::VolumeScanStart,
-                RadialStatus::VolumeScanEnd => ModelRadialStatus::VolumeScanEnd,
-                RadialStatus::ElevationStartVCPFinal => ModelRadialStatus::ElevationStartVCPFinal,
-            },
-            self.header.elevation_number,
-            self.header.elevation_angle,
-            self.reflectivity_data_block
-                .map(|block| block.into_moment_data()),
-            self.velocity_data_block
-                .map(|block| block.into_moment_data()),
-            self.spectrum_width_data_block
-                .map(|block| block.into_moment_data()),
-            self.differential_reflectivity_data_block
-                .map(|block| block.into_moment_data()),
-            self.differential_phase_data_block
-                .map(|block| block.into_moment_data()),
-            self.correlation_coefficient_data_block
-                .map(|block| block.into_moment_data()),
-            self.specific_diff_phase_data_block
-                .map(|block| block.into_moment_data()),
+        use nexrad_model::data::{ExtensionMoment, Radial, RadialStatus as ModelRadialStatus};
+
+        Ok(with_radial_data(
+            Radial::new(
+                self.header
+                    .date_time()
+                    .ok_or(Error::MessageMissingDateError)?
+                    .timestamp_millis(),
+                self.header.azimuth_number,
+                self.header.azimuth_angle,
+                self.header.azimuth_resolution_spacing as f32 * 0.5,
+                match self.header.radial_status() {
+                    RadialStatus::ElevationStart => ModelRadialStatus::ElevationStart,
+                    RadialStatus::IntermediateRadialData => {
+                        ModelRadialStatus::IntermediateRadialData
+                    }
+                    RadialStatus::ElevationEnd => ModelRadialStatus::ElevationEnd,
+                    RadialStatus::VolumeScanStart => ModelRadialStatus::VolumeScanStart,
+                    RadialStatus::VolumeScanEnd => ModelRadialStatus::VolumeScanEnd,
+                    RadialStatus::ElevationStartVCPFinal => {
+                        ModelRadialStatus::ElevationStartVCPFinal
+                    }
+                },
+                self.header.elevation_number,
+                self.header.elevation_angle,
+                self.reflectivity_data_block
+                    .map(|block| block.into_moment_data()),
+                self.velocity_data_block
+                    .map(|block| block.into_moment_data()),
+                self.spectrum_width_data_block
+                    .map(|block| block.into_moment_data()),
+                self.differential_reflectivity_data_block
+                    .map(|block| block.into_moment_data()),
+                self.differential_phase_data_block
+                    .map(|block| block.into_moment_data()),
+                self.correlation_coefficient_data_block
+                    .map(|block| block.into_moment_data()),
+                self.specific_diff_phase_data_block
+                    .map(|block| block.into_moment_data()),
+            )
+            .with_extension_moments(
+                self.extension_data_blocks
+                    .into_iter()
+                    .map(|block| {
+                        ExtensionMoment::new(
+                            block.header.data_block_id.data_block_name(),
+                            block.into_moment_data(),
+                        )
+                    })
+                    .collect(),
+            ),
+            self.radial_data_block.as_ref(),
         ))
     }
 }
+
+/// Adds the radial data block's parameters to a radial, if present.
+#[cfg(feature = "nexrad-model")]
+fn with_radial_data(
+    mut radial: nexrad_model::data::Radial,
+    radial_data_block: Option<&RadialDataBlock>,
+) -> nexrad_model::data::Radial {
+    if let Some(block) = radial_data_block {
+        radial = radial.with_velocity_limits(
+            block.nyquist_velocity_meters_per_second(),
+            block.unambiguous_range_km(),
+        );
+        if let (Some(horizontal), Some(vertical)) = (
+            block.horizontal_calibration_constant_dbz(),
+            block.vertical_calibration_constant_dbz(),
+        ) {
+            radial = radial.with_calibration_constants(horizontal, vertical);
+        }
+    }
+
+    radial
+}
@@ -1,13 +1,13 @@
 use crate::messages::digital_radar_data::DataBlockId;
 use crate::messages::primitive_aliases::{Integer2, Real4, ScaledInteger2};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
 #[cfg(feature = "uom")]
 use uom::si::f64::{Information, Length, Velocity};
 
 /// A radial data moment block.
-#[derive(Clone, PartialEq, Deserialize)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct RadialDataBlock {
     /// Data block identifier.
     pub data_block_id: DataBlockId,
@@ -38,6 +38,36 @@ pub struct RadialDataBlock {
 }
 
 impl RadialDataBlock {
+    /// The encoded size of a radial data block as of ORDA, which appended the radial flags and
+    /// calibration constants. See [RadialDataBlock::is_legacy].
+    pub(crate) const SIZE: usize = 28;
+
+    /// Whether this is a legacy 20-byte block, which predates the radial flags and calibration
+    /// constants. Those fields are zero when decoded from a legacy block.
+    pub fn is_legacy(&self) -> bool {
+        (self.lrtup as usize) < Self::SIZE
+    }
+
+    /// Unambiguous range, interval size, in km.
+    pub fn unambiguous_range_km(&self) -> f32 {
+        self.unambiguous_range as f32 * 0.1
+    }
+
+    /// Nyquist velocity in m/s.
+    pub fn nyquist_velocity_meters_per_second(&self) -> f32 {
+        self.nyquist_velocity as f32 * 0.01
+    }
+
+    /// Calibration constant for the horizontal channel in dBZ, if this is not a legacy block.
+    pub fn horizontal_calibration_constant_dbz(&self) -> Option<f32> {
+        (!self.is_legacy()).then_some(self.horizontal_channel_calibration_constant)
+    }
+
+    /// Calibration constant for the vertical channel in dBZ, if this is not a legacy block.
+    pub fn vertical_calibration_constant_dbz(&self) -> Option<f32> {
+        (!self.is_legacy()).then_some(self.vertical_channel_calibration_constant)
+    }
+
     /// Size of data block.
     #[cfg(feature = "uom")]
     pub fn lrtup(&self) -> Information {
@@ -47,7 +77,7 @@ impl RadialDataBlock {
     /// Unambiguous range, interval size.
     #[cfg(feature = "uom")]
     pub fn unambiguous_range(&self) -> Length {
-        Length::new::<uom::si::length::kilometer>(self.unambiguous_range as f64)
+        Length::new::<uom::si::length::kilometer>(self.unambiguous_range as f64 * 0.1)
     }
 
     /// Nyquist velocity.
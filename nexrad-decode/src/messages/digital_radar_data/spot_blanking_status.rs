@@ -1,20 +1,32 @@
 use crate::messages::primitive_aliases::Code1;
 use std::fmt::{Debug, Formatter};
 
-#[derive(Clone, PartialEq, Eq, Hash)]
-pub struct SpotBlankingStatus(Code1);
-
+/// The spot blanking status for the current radial, elevation, and volume scan.
+///
 /// Statuses:
 ///   0 = None
 ///   1 = Radial
 ///   2 = Elevation
 ///   4 = Volume
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct SpotBlankingStatus(Code1);
 
 impl SpotBlankingStatus {
     pub(crate) fn new(code: Code1) -> Self {
         Self(code)
     }
 
+    /// The raw status code, including any bits not defined by the ICD.
+    pub fn code(&self) -> Code1 {
+        self.0
+    }
+
+    /// Bits set in the status code which are not defined by the ICD, such as those introduced by a
+    /// newer RDA build. Zero if all set bits are recognized.
+    pub fn unrecognized_bits(&self) -> Code1 {
+        self.0 & !0b0111
+    }
+
     /// Whether no spot blanking is active.
     pub fn none(&self) -> bool {
         self.0 == 0
@@ -43,6 +55,42 @@ impl Debug for SpotBlankingStatus {
             .field("radial", &self.radial())
             .field("elevation", &self.elevation())
             .field("volume", &self.volume())
+            .field("unrecognized_bits", &self.unrecognized_bits())
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_bits() {
+        let status = SpotBlankingStatus::new(0b0101);
+        assert_eq!(status.code(), 0b0101);
+        assert_eq!(status.unrecognized_bits(), 0);
+        assert!(status.radial());
+        assert!(!status.elevation());
+        assert!(status.volume());
+        assert!(!status.none());
+
+        let status = SpotBlankingStatus::new(0);
+        assert_eq!(status.code(), 0);
+        assert_eq!(status.unrecognized_bits(), 0);
+        assert!(status.none());
+    }
+
+    #[test]
+    fn test_unknown_bits() {
+        let status = SpotBlankingStatus::new(0b1000_0010);
+        assert_eq!(status.code(), 0b1000_0010);
+        assert_eq!(status.unrecognized_bits(), 0b1000_0000);
+        assert!(status.elevation());
+        assert!(!status.radial());
+        assert!(!status.none());
+
+        let status = SpotBlankingStatus::new(0b1111_1000);
+        assert_eq!(status.unrecognized_bits(), 0b1111_1000);
+        assert!(!status.radial() && !status.elevation() && !status.volume());
+    }
+}
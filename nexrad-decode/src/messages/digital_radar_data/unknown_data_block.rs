@@ -0,0 +1,42 @@
+use crate::messages::digital_radar_data::DataBlockId;
+use crate::messages::primitive_aliases::Integer2;
+use std::borrow::Cow;
+use std::fmt::Debug;
+
+/// A data block whose name is not recognized, such as one introduced by a newer RDA build than this
+/// crate supports. Its contents are preserved so they are not silently dropped.
+#[derive(Clone, PartialEq)]
+pub struct UnknownDataBlock<'a> {
+    /// Data block identifier.
+    pub data_block_id: DataBlockId,
+
+    /// Size of data block in bytes, including its identifier and this field.
+    pub lrtup: Integer2,
+
+    /// The block's undecoded contents following its identifier and size.
+    pub data: Cow<'a, [u8]>,
+}
+
+impl UnknownDataBlock<'_> {
+    /// The size of the identifier and size fields preceding an unknown block's data.
+    pub(crate) const PREFIX_SIZE: usize = size_of::<DataBlockId>() + size_of::<Integer2>();
+
+    /// Converts this block into one which owns its data, copying it if borrowed.
+    pub fn into_owned(self) -> UnknownDataBlock<'static> {
+        UnknownDataBlock {
+            data_block_id: self.data_block_id,
+            lrtup: self.lrtup,
+            data: Cow::Owned(self.data.into_owned()),
+        }
+    }
+}
+
+impl Debug for UnknownDataBlock<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UnknownDataBlock")
+            .field("data_block_id", &self.data_block_id)
+            .field("lrtup", &self.lrtup)
+            .field("data.len()", &self.data.len())
+            .finish()
+    }
+}
@@ -0,0 +1,138 @@
+//!
+//! Conversion of fixed-point moment words into floating-point gate values. Gates are processed in
+//! fixed-width lanes with branch-free arithmetic so the compiler can vectorize the hot loop, with a
+//! scalar fallback for any trailing gates.
+//!
+
+/// Number of gates processed together in the chunked path.
+const LANES: usize = 16;
+
+/// Raw values at or below this are special codes rather than scaled data.
+const MAX_SPECIAL_CODE: u16 = 1;
+
+/// The value written for gates which are below threshold or range folded.
+pub(crate) const SPECIAL_VALUE: f32 = f32::NAN;
+
+/// Unpacks 8-bit moment words into `output`, which must be at least as long as `words`.
+pub(crate) fn unpack_u8(words: &[u8], scale: f32, offset: f32, output: &mut [f32]) {
+    if scale == 0.0 {
+        for (value, &word) in output.iter_mut().zip(words) {
+            *value = word as f32;
+        }
+        return;
+    }
+
+    // Every 8-bit word maps to one of 256 values, so a lookup table beats per-gate arithmetic.
+    let mut table = [SPECIAL_VALUE; 256];
+    for (word, value) in table
+        .iter_mut()
+        .enumerate()
+        .skip(MAX_SPECIAL_CODE as usize + 1)
+    {
+        *value = scale_word(word as f32, scale, offset);
+    }
+
+    let mut word_chunks = words.chunks_exact(LANES);
+    let mut output_chunks = output.chunks_exact_mut(LANES);
+    for (word_chunk, output_chunk) in (&mut word_chunks).zip(&mut output_chunks) {
+        for (value, &word) in output_chunk.iter_mut().zip(word_chunk) {
+            *value = table[word as usize];
+        }
+    }
+
+    let remainder = word_chunks.remainder();
+    for (value, &word) in output_chunks.into_remainder().iter_mut().zip(remainder) {
+        *value = table[word as usize];
+    }
+}
+
+/// Unpacks big-endian 16-bit moment words from `bytes` into `output`, which must be at least half
+/// as long as `bytes`.
+pub(crate) fn unpack_u16(bytes: &[u8], scale: f32, offset: f32, output: &mut [f32]) {
+    let mut byte_chunks = bytes.chunks_exact(LANES * 2);
+    let mut output_chunks = output.chunks_exact_mut(LANES);
+    for (byte_chunk, output_chunk) in (&mut byte_chunks).zip(&mut output_chunks) {
+        let mut words = [0u16; LANES];
+        for (word, pair) in words.iter_mut().zip(byte_chunk.chunks_exact(2)) {
+            *word = u16::from_be_bytes([pair[0], pair[1]]);
+        }
+
+        for (value, &word) in output_chunk.iter_mut().zip(&words) {
+            *value = unpack_word(word, scale, offset);
+        }
+    }
+
+    let remainder = byte_chunks.remainder().chunks_exact(2);
+    for (value, pair) in output_chunks.into_remainder().iter_mut().zip(remainder) {
+        *value = unpack_word(u16::from_be_bytes([pair[0], pair[1]]), scale, offset);
+    }
+}
+
+/// Converts a single raw word into its floating-point value.
+#[inline(always)]
+fn unpack_word(word: u16, scale: f32, offset: f32) -> f32 {
+    if scale == 0.0 {
+        return word as f32;
+    }
+
+    let value = scale_word(word as f32, scale, offset);
+    if word > MAX_SPECIAL_CODE {
+        value
+    } else {
+        SPECIAL_VALUE
+    }
+}
+
+#[inline(always)]
+fn scale_word(word: f32, scale: f32, offset: f32) -> f32 {
+    (word - offset) / scale
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scalar(words: impl Iterator<Item = u16>, scale: f32, offset: f32) -> Vec<f32> {
+        words
+            .map(|word| match word {
+                _ if scale == 0.0 => word as f32,
+                0 | 1 => SPECIAL_VALUE,
+                _ => (word as f32 - offset) / scale,
+            })
+            .collect()
+    }
+
+    fn assert_same(actual: &[f32], expected: &[f32]) {
+        assert_eq!(actual.len(), expected.len());
+        for (actual, expected) in actual.iter().zip(expected) {
+            assert!(
+                actual == expected || (actual.is_nan() && expected.is_nan()),
+                "{actual} != {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_unpack_u8_matches_scalar() {
+        let words: Vec<u8> = (0..=255).chain(0..=36).collect();
+        for (scale, offset) in [(2.0, 66.0), (0.0, 0.0), (300.0, -60.5)] {
+            let mut output = vec![0.0; words.len()];
+            unpack_u8(&words, scale, offset, &mut output);
+            assert_same(
+                &output,
+                &scalar(words.iter().map(|&word| word as u16), scale, offset),
+            );
+        }
+    }
+
+    #[test]
+    fn test_unpack_u16_matches_scalar() {
+        let words: Vec<u16> = (0..1000u16).map(|word| word.wrapping_mul(67)).collect();
+        let bytes: Vec<u8> = words.iter().flat_map(|word| word.to_be_bytes()).collect();
+        for (scale, offset) in [(2.8361, 2.0), (0.0, 0.0)] {
+            let mut output = vec![0.0; words.len()];
+            unpack_u16(&bytes, scale, offset, &mut output);
+            assert_same(&output, &scalar(words.iter().copied(), scale, offset));
+        }
+    }
+}
@@ -1,13 +1,13 @@
 use crate::messages::digital_radar_data::{DataBlockId, ProcessingStatus, VolumeCoveragePattern};
 use crate::messages::primitive_aliases::{Integer1, Integer2, Real4, SInteger2};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
 #[cfg(feature = "uom")]
 use uom::si::f64::{Angle, Energy, Information, Length};
 
 /// A volume data moment block.
-#[derive(Clone, PartialEq, Deserialize)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct VolumeDataBlock {
     /// Data block identifier.
     pub data_block_id: DataBlockId,
@@ -124,10 +124,7 @@ impl VolumeDataBlock {
             112 => VolumeCoveragePattern::VCP112,
             212 => VolumeCoveragePattern::VCP212,
             215 => VolumeCoveragePattern::VCP215,
-            _ => panic!(
-                "Invalid volume coverage pattern number: {}",
-                self.volume_coverage_pattern_number
-            ),
+            _ => VolumeCoveragePattern::Other(self.volume_coverage_pattern_number),
         }
     }
 
@@ -1,22 +1,57 @@
 use crate::messages::clutter_filter_map;
+use crate::messages::console_message;
 use crate::messages::digital_radar_data;
 use crate::messages::message_header::MessageHeader;
 use crate::messages::rda_status_data;
 use crate::messages::volume_coverage_pattern;
+use std::ops::Range;
 
 /// A decoded NEXRAD Level II message with its metadata header.
 #[derive(Debug, Clone, PartialEq)]
-pub struct MessageWithHeader {
+pub struct MessageWithHeader<'a> {
     pub header: MessageHeader,
-    pub message: Message,
+    pub message: Message<'a>,
+
+    /// The range of bytes this message, including its header, was decoded from, relative to the
+    /// start of the data it was decoded from. This is `None` for messages which were not decoded.
+    pub byte_range: Option<Range<usize>>,
+}
+
+impl MessageWithHeader<'_> {
+    /// Converts this message into one which owns all of its data.
+    pub fn into_owned(self) -> MessageWithHeader<'static> {
+        MessageWithHeader {
+            header: self.header,
+            message: self.message.into_owned(),
+            byte_range: self.byte_range,
+        }
+    }
 }
 
 /// A decoded NEXRAD Level II message.
 #[derive(Debug, Clone, PartialEq)]
-pub enum Message {
+pub enum Message<'a> {
     RDAStatusData(Box<rda_status_data::Message>),
-    DigitalRadarData(Box<digital_radar_data::Message>),
+    DigitalRadarData(Box<digital_radar_data::Message<'a>>),
     ClutterFilterMap(Box<clutter_filter_map::Message>),
     VolumeCoveragePattern(Box<volume_coverage_pattern::Message>),
+    ConsoleMessage(Box<console_message::Message>),
     Other,
 }
+
+impl Message<'_> {
+    /// Converts this message into one which owns all of its data, copying any moment data borrowed
+    /// from the decoded buffer.
+    pub fn into_owned(self) -> Message<'static> {
+        match self {
+            Message::RDAStatusData(message) => Message::RDAStatusData(message),
+            Message::DigitalRadarData(message) => {
+                Message::DigitalRadarData(Box::new(message.into_owned()))
+            }
+            Message::ClutterFilterMap(message) => Message::ClutterFilterMap(message),
+            Message::VolumeCoveragePattern(message) => Message::VolumeCoveragePattern(message),
+            Message::ConsoleMessage(message) => Message::ConsoleMessage(message),
+            Message::Other => Message::Other,
+        }
+    }
+}
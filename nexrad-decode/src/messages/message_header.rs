@@ -3,7 +3,7 @@ use crate::messages::message_type::MessageType;
 use crate::messages::primitive_aliases::{Integer1, Integer2, Integer4};
 use crate::util::get_datetime;
 use chrono::{DateTime, Duration, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
 #[cfg(feature = "uom")]
@@ -21,7 +21,7 @@ pub const VARIABLE_LENGTH_MESSAGE_SIZE: u16 = 65535;
 /// instead variable-length, with the segment count and segment number positions of the header
 /// (bytes 12-15) specifying the size of the full message in bytes.
 #[repr(C)]
-#[derive(Clone, PartialEq, Eq, Hash, Deserialize)]
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct MessageHeader {
     rpg_unknown: [u8; 12],
 
@@ -69,13 +69,39 @@ pub struct MessageHeader {
 }
 
 impl MessageHeader {
+    /// Creates a new message header for a message of the given type. The segment size is in
+    /// half-words and, along with the segment count and number, is interpreted as described by
+    /// [MessageHeader::segment_size].
+    pub fn new(
+        message_type: MessageType,
+        redundant_channel: Integer1,
+        sequence_number: Integer2,
+        date: Integer2,
+        time: Integer4,
+        segment_size: Integer2,
+        segment_count: Integer2,
+        segment_number: Integer2,
+    ) -> Self {
+        Self {
+            rpg_unknown: [0; 12],
+            segment_size,
+            redundant_channel,
+            message_type: message_type as u8,
+            sequence_number,
+            date,
+            time,
+            segment_count,
+            segment_number,
+        }
+    }
+
     /// If this message is [MessageHeader::segmented], this indicates this message segment's size.
     /// Otherwise, this returns [None] and [MessageHeader::message_size] should be used to determine
     /// the message's full size.
     #[cfg(feature = "uom")]
     pub fn segment_size(&self) -> Option<Information> {
         if self.segment_size < VARIABLE_LENGTH_MESSAGE_SIZE {
-            Some(Information::new::<byte>((self.segment_size * 2) as f64))
+            Some(Information::new::<byte>(self.segment_size as f64 * 2.0))
         } else {
             None
         }
@@ -90,7 +116,7 @@ impl MessageHeader {
             8 => RedundantChannel::ORDASingleChannel,
             9 => RedundantChannel::ORDARedundantChannel1,
             10 => RedundantChannel::ORDARedundantChannel2,
-            _ => panic!("Invalid RDA redundant channel: {}", self.redundant_channel),
+            _ => RedundantChannel::Other(self.redundant_channel),
         }
     }
 
@@ -184,7 +210,7 @@ impl MessageHeader {
     pub fn message_size(&self) -> Information {
         match self.segment_count() {
             Some(_) => {
-                let segment_size_bytes = self.segment_size << 1;
+                let segment_size_bytes = self.segment_size as u32 * 2;
                 Information::new::<byte>(segment_size_bytes as f64)
             }
             None => {
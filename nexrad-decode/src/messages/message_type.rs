@@ -1,5 +1,7 @@
+use serde::{Deserialize, Serialize};
+
 /// The types of data messages transferred between the RDA and RPG.
-#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, Ord, PartialOrd)]
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, Ord, PartialOrd, Serialize, Deserialize)]
 pub enum MessageType {
     /// Replaced by message type 31.
     RDADigitalRadarData = 1,
@@ -10,7 +10,7 @@ pub mod alarm;
 mod data_transmission_enabled;
 
 pub use data_transmission_enabled::DataTransmissionEnabled;
-use std::io::Read;
+use std::io::{Read, Write};
 
 mod scan_data_flags;
 pub use scan_data_flags::ScanDataFlags;
@@ -23,10 +23,15 @@ pub use message::Message;
 
 mod volume_coverage_pattern;
 use crate::result::Result;
-use crate::util::deserialize;
+use crate::util::{deserialize, serialize};
 pub use volume_coverage_pattern::VolumeCoveragePatternNumber;
 
 /// Decodes an RDA status message type 2 from the provided reader.
 pub fn decode_rda_status_message<R: Read>(reader: &mut R) -> Result<Message> {
     deserialize(reader)
 }
+
+/// Encodes an RDA status message type 2 to the provided writer.
+pub fn encode_rda_status_message<W: Write>(writer: &mut W, message: &Message) -> Result<()> {
+    serialize(writer, message)
+}
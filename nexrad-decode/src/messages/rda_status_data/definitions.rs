@@ -24,6 +24,8 @@ pub enum AuxiliaryPowerGeneratorState {
     GeneratorOn,
     TransferSwitchSetToManual,
     CommandedSwitchover,
+    /// A value not defined by the ICD.
+    Other(u16),
 }
 
 /// The possible RDA system control authorizations.
@@ -32,6 +34,8 @@ pub enum ControlAuthorization {
     NoAction,
     LocalControlRequested,
     RemoteControlRequested,
+    /// A value not defined by the ICD.
+    Other(u16),
 }
 
 /// The possible RDA system control statuses.
@@ -40,6 +44,8 @@ pub enum ControlStatus {
     LocalControlOnly,
     RemoteControlOnly,
     EitherLocalOrRemoteControl,
+    /// A value not defined by the ICD.
+    Other(u16),
 }
 
 /// The possible RDA system operability statuses.
@@ -50,6 +56,8 @@ pub enum OperabilityStatus {
     MaintenanceActionMandatory,
     CommandedShutDown,
     Inoperable,
+    /// A value not defined by the ICD.
+    Other(u16),
 }
 
 /// The possible RDA system operational modes.
@@ -57,6 +65,8 @@ pub enum OperabilityStatus {
 pub enum OperationalMode {
     Operational,
     Maintenance,
+    /// A value not defined by the ICD.
+    Other(u16),
 }
 
 /// The RDA system's performance check status.
@@ -65,6 +75,8 @@ pub enum PerformanceCheckStatus {
     NoCommandPending,
     ForcePerformanceCheckPending,
     InProgress,
+    /// A value not defined by the ICD.
+    Other(u16),
 }
 
 /// The RDA system's RMS control status.
@@ -73,6 +85,8 @@ pub enum RMSControlStatus {
     NonRMS,
     RMSInControl,
     RDAInControl,
+    /// A value not defined by the ICD.
+    Other(u16),
 }
 
 /// Indicates whether this is the RDA system's controlling channel.
@@ -81,6 +95,8 @@ pub enum SpotBlankingStatus {
     NotInstalled,
     Enabled,
     Disabled,
+    /// A value not defined by the ICD.
+    Other(u16),
 }
 
 /// The possible RDA system statuses.
@@ -91,6 +107,8 @@ pub enum RDAStatus {
     Restart,
     Operate,
     Spare,
+    /// A value not defined by the ICD.
+    Other(u16),
 }
 
 /// Whether the RDA system has super resolution enabled.
@@ -98,6 +116,8 @@ pub enum RDAStatus {
 pub enum SuperResolutionStatus {
     Enabled,
     Disabled,
+    /// A value not defined by the ICD.
+    Other(u16),
 }
 
 /// The possible RDA system transition power source statuses.
@@ -107,4 +127,6 @@ pub enum TransitionPowerSourceStatus {
     Off,
     OK,
     Unknown,
+    /// A value not defined by the ICD.
+    Other(u16),
 }
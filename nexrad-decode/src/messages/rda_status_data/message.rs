@@ -12,13 +12,13 @@ use crate::messages::rda_status_data::scan_data_flags::ScanDataFlags;
 use crate::messages::rda_status_data::volume_coverage_pattern::VolumeCoveragePatternNumber;
 use crate::util::get_datetime;
 use chrono::{DateTime, Duration, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
 /// The RDA status data message includes various information about the current RDA system's state,
 /// including system operating status, performance parameters, and active alarms.
 #[repr(C)]
-#[derive(Clone, PartialEq, Eq, Hash, Deserialize)]
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Message {
     /// The RDA system's status.
     ///
@@ -235,7 +235,7 @@ impl Message {
             8 => RDAStatus::Restart,
             16 => RDAStatus::Operate,
             32 => RDAStatus::Spare,
-            _ => panic!("Invalid RDA status: {}", self.rda_status),
+            _ => RDAStatus::Other(self.rda_status),
         }
     }
 
@@ -247,10 +247,7 @@ impl Message {
             8 => OperabilityStatus::MaintenanceActionMandatory,
             16 => OperabilityStatus::CommandedShutDown,
             32 => OperabilityStatus::Inoperable,
-            _ => panic!(
-                "Invalid RDA operability status: {}",
-                self.operability_status
-            ),
+            _ => OperabilityStatus::Other(self.operability_status),
         }
     }
 
@@ -260,7 +257,7 @@ impl Message {
             2 => ControlStatus::LocalControlOnly,
             4 => ControlStatus::RemoteControlOnly,
             8 => ControlStatus::EitherLocalOrRemoteControl,
-            _ => panic!("Invalid RDA control status: {}", self.control_status),
+            _ => ControlStatus::Other(self.control_status),
         }
     }
 
@@ -272,10 +269,7 @@ impl Message {
             4 => AuxiliaryPowerGeneratorState::GeneratorOn,
             8 => AuxiliaryPowerGeneratorState::TransferSwitchSetToManual,
             16 => AuxiliaryPowerGeneratorState::CommandedSwitchover,
-            _ => panic!(
-                "Invalid RDA auxiliary power generator state: {}",
-                self.auxiliary_power_generator_state
-            ),
+            _ => AuxiliaryPowerGeneratorState::Other(self.auxiliary_power_generator_state),
         }
     }
 
@@ -306,10 +300,7 @@ impl Message {
             0 => ControlAuthorization::NoAction,
             1 => ControlAuthorization::LocalControlRequested,
             2 => ControlAuthorization::RemoteControlRequested,
-            _ => panic!(
-                "Invalid RDA control authorization: {}",
-                self.rda_control_authorization
-            ),
+            _ => ControlAuthorization::Other(self.rda_control_authorization),
         }
     }
 
@@ -328,7 +319,7 @@ impl Message {
         match self.operational_mode {
             4 => OperationalMode::Operational,
             8 => OperationalMode::Maintenance,
-            _ => panic!("Invalid RDA operational mode: {}", self.operational_mode),
+            _ => OperationalMode::Other(self.operational_mode),
         }
     }
 
@@ -337,10 +328,7 @@ impl Message {
         match self.super_resolution_status {
             2 => SuperResolutionStatus::Enabled,
             4 => SuperResolutionStatus::Disabled,
-            _ => panic!(
-                "Invalid RDA super resolution status: {}",
-                self.super_resolution_status
-            ),
+            _ => SuperResolutionStatus::Other(self.super_resolution_status),
         }
     }
 
@@ -394,10 +382,7 @@ impl Message {
             0 => SpotBlankingStatus::NotInstalled,
             1 => SpotBlankingStatus::Enabled,
             4 => SpotBlankingStatus::Disabled,
-            _ => panic!(
-                "Invalid RDA spot blanking status: {}",
-                self.spot_blanking_status
-            ),
+            _ => SpotBlankingStatus::Other(self.spot_blanking_status),
         }
     }
 
@@ -424,10 +409,7 @@ impl Message {
             1 => TransitionPowerSourceStatus::Off,
             3 => TransitionPowerSourceStatus::OK,
             4 => TransitionPowerSourceStatus::Unknown,
-            _ => panic!(
-                "Invalid RDA TPS status: {}",
-                self.transition_power_source_status
-            ),
+            _ => TransitionPowerSourceStatus::Other(self.transition_power_source_status),
         }
     }
 
@@ -437,10 +419,7 @@ impl Message {
             0 => RMSControlStatus::NonRMS,
             2 => RMSControlStatus::RMSInControl,
             4 => RMSControlStatus::RDAInControl,
-            _ => panic!(
-                "Invalid RDA RMS control status: {}",
-                self.rms_control_status
-            ),
+            _ => RMSControlStatus::Other(self.rms_control_status),
         }
     }
 
@@ -450,10 +429,7 @@ impl Message {
             0 => PerformanceCheckStatus::NoCommandPending,
             1 => PerformanceCheckStatus::ForcePerformanceCheckPending,
             2 => PerformanceCheckStatus::InProgress,
-            _ => panic!(
-                "Invalid RDA performance check status: {}",
-                self.performance_check_status
-            ),
+            _ => PerformanceCheckStatus::Other(self.performance_check_status),
         }
     }
 
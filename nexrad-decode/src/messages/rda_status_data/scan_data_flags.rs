@@ -10,15 +10,16 @@ impl ScanDataFlags {
         Self(value)
     }
 
-    /// Whether AVSET is enabled.
-    pub fn avset_enabled(&self) -> bool {
+    /// Whether AVSET is enabled. The RDA reports AVSET as separate enabled and disabled bits, so
+    /// this is `None` if neither or both of them are set.
+    pub fn avset_enabled(&self) -> Option<bool> {
         let enabled_flag = self.0 & 0b0001 != 0;
         let disabled_flag = self.0 & 0b0010 != 0;
-        debug_assert!(
-            enabled_flag ^ disabled_flag,
-            "Unexpected AVSET state (expected: enabled XOR disabled)"
-        );
-        enabled_flag
+        match (enabled_flag, disabled_flag) {
+            (true, false) => Some(true),
+            (false, true) => Some(false),
+            _ => None,
+        }
     }
 
     /// Whether EBC is enabled.
@@ -50,3 +51,17 @@ impl Debug for ScanDataFlags {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_avset_enabled() {
+        assert_eq!(ScanDataFlags::new(0b0001).avset_enabled(), Some(true));
+        assert_eq!(ScanDataFlags::new(0b0010).avset_enabled(), Some(false));
+        assert_eq!(ScanDataFlags::new(0b0000).avset_enabled(), None);
+        assert_eq!(ScanDataFlags::new(0b0011).avset_enabled(), None);
+        assert_eq!(ScanDataFlags::new(0b1101).avset_enabled(), Some(true));
+    }
+}
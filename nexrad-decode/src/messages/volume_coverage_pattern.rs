@@ -6,8 +6,11 @@
 //! collected as well as a block for each elevation cut detailing the radar settings
 //! being used for that cut.
 //!
+//! With the `nexrad-model` feature, a pattern's elevation cuts may be drawn as a beam coverage
+//! diagram using [beam_coverage_svg].
+//!
 
-use std::io::Read;
+use std::io::{Read, Write};
 
 mod definitions;
 pub use definitions::*;
@@ -21,8 +24,13 @@ pub use message::Message;
 mod elevation_data_block;
 pub use elevation_data_block::ElevationDataBlock;
 
-use crate::result::Result;
-use crate::util::deserialize;
+#[cfg(feature = "nexrad-model")]
+mod diagram;
+#[cfg(feature = "nexrad-model")]
+pub use diagram::*;
+
+use crate::result::{Error, Result};
+use crate::util::{deserialize, serialize};
 
 /// Decodes a volume coverage pattern message type 5 from the provided reader.
 pub fn decode_volume_coverage_pattern<R: Read>(reader: &mut R) -> Result<Message> {
@@ -37,3 +45,19 @@ pub fn decode_volume_coverage_pattern<R: Read>(reader: &mut R) -> Result<Message
 
     Ok(message)
 }
+
+/// Encodes a volume coverage pattern message type 5 to the provided writer.
+pub fn encode_volume_coverage_pattern<W: Write>(writer: &mut W, message: &Message) -> Result<()> {
+    if message.elevations.len() != message.header.number_of_elevation_cuts as usize {
+        return Err(Error::EncodingError(
+            "elevation count does not match header's number of elevation cuts".to_string(),
+        ));
+    }
+
+    serialize(writer, &message.header)?;
+    for elevation in &message.elevations {
+        serialize(writer, elevation)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use crate::messages::volume_coverage_pattern::Message;
+use nexrad_model::geo::BeamPropagationModel;
+use std::fmt::Write;
+
+/// The number of points along each beam edge in a beam coverage diagram.
+const BEAM_SAMPLES: usize = 64;
+
+/// Options for drawing a volume coverage pattern's beam coverage diagram. See
+/// [beam_coverage_svg].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BeamDiagramOptions {
+    max_range_meters: f64,
+    max_height_meters: f64,
+    beam_width_degrees: f64,
+    width: u32,
+    height: u32,
+}
+
+impl BeamDiagramOptions {
+    /// Creates options for an 800 by 400 pixel diagram extending 230 km in range and 20 km in
+    /// height, with the WSR-88D's nominal 0.95 degree beam width.
+    pub fn new() -> Self {
+        Self {
+            max_range_meters: 230_000.0,
+            max_height_meters: 20_000.0,
+            beam_width_degrees: 0.95,
+            width: 800,
+            height: 400,
+        }
+    }
+
+    /// Sets the range in meters along the earth's surface at the diagram's right edge.
+    pub fn with_max_range_meters(mut self, max_range_meters: f64) -> Self {
+        self.max_range_meters = max_range_meters;
+        self
+    }
+
+    /// Sets the height in meters above the antenna at the diagram's top edge.
+    pub fn with_max_height_meters(mut self, max_height_meters: f64) -> Self {
+        self.max_height_meters = max_height_meters;
+        self
+    }
+
+    /// Sets the beam width in degrees drawn around each elevation angle.
+    pub fn with_beam_width_degrees(mut self, beam_width_degrees: f64) -> Self {
+        self.beam_width_degrees = beam_width_degrees;
+        self
+    }
+
+    /// Sets the diagram's size in pixels.
+    pub fn with_size(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+}
+
+impl Default for BeamDiagramOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Draws the volume coverage pattern's elevation cuts as a classic beam coverage diagram in SVG,
+/// plotting each distinct elevation angle's beam as height above the antenna against range along
+/// the earth's surface. The given propagation model determines how the beams curve away from the
+/// earth, for example [nexrad_model::geo::EffectiveEarthRadiusModel::four_thirds].
+pub fn beam_coverage_svg(
+    message: &Message,
+    model: &impl BeamPropagationModel,
+    options: &BeamDiagramOptions,
+) -> String {
+    let (width, height) = (options.width as f64, options.height as f64);
+    let x = |ground_range: f64| ground_range / options.max_range_meters * width;
+    let y = |beam_height: f64| height - beam_height / options.max_height_meters * height;
+
+    let mut elevation_angles = message
+        .elevations
+        .iter()
+        .map(|elevation| elevation.elevation_angle_degrees())
+        .collect::<Vec<_>>();
+    elevation_angles.sort_by(f64::total_cmp);
+    elevation_angles.dedup();
+
+    let mut svg = String::new();
+    let _ = write!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    );
+    let _ = write!(
+        svg,
+        r#"<rect width="{width}" height="{height}" fill="white" stroke="black"/>"#
+    );
+
+    for elevation_angle in elevation_angles {
+        // Sample the slant range far enough that the beam reaches the diagram's edge
+        let max_slant_range = options.max_range_meters * 1.5;
+        let edge = |edge_angle: f64| {
+            (0..=BEAM_SAMPLES).map(move |sample| {
+                let range = max_slant_range * sample as f64 / BEAM_SAMPLES as f64;
+                (
+                    x(model.ground_range_meters(range, edge_angle)),
+                    y(model.beam_height_meters(range, edge_angle)),
+                )
+            })
+        };
+
+        let half_width = options.beam_width_degrees / 2.0;
+        let lower_edge = edge(elevation_angle - half_width).collect::<Vec<_>>();
+        let upper_edge = edge(elevation_angle + half_width).collect::<Vec<_>>();
+
+        let points = lower_edge
+            .iter()
+            .chain(upper_edge.iter().rev())
+            .map(|(x, y)| format!("{:.1},{:.1}", x, y))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let _ = write!(
+            svg,
+            r#"<polygon points="{points}" fill="steelblue" fill-opacity="0.3" stroke="steelblue"><title>{elevation_angle:.2}°</title></polygon>"#
+        );
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::volume_coverage_pattern::{ElevationDataBlock, Header};
+    use crate::result::Result;
+    use crate::util::deserialize;
+    use nexrad_model::geo::EffectiveEarthRadiusModel;
+
+    fn vcp(elevation_angles: &[f64]) -> Result<Message> {
+        let header: Header = deserialize(&mut [0; 256].as_slice())?;
+
+        let mut elevations = Vec::new();
+        for elevation_angle in elevation_angles {
+            let mut elevation: ElevationDataBlock = deserialize(&mut [0; 256].as_slice())?;
+            elevation.elevation_angle = ((elevation_angle / 180.0 * 32768.0) as u16) & !0b111;
+            elevations.push(elevation);
+        }
+
+        Ok(Message::new(header, elevations))
+    }
+
+    #[test]
+    fn test_beam_coverage_svg() -> Result<()> {
+        let message = vcp(&[0.5, 0.5, 1.5, 2.4, 19.5])?;
+        let svg = beam_coverage_svg(
+            &message,
+            &EffectiveEarthRadiusModel::four_thirds(),
+            &BeamDiagramOptions::new().with_size(400, 200),
+        );
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>"));
+        assert_eq!(svg.matches("<polygon").count(), 4);
+        assert!(svg.contains(r#"width="400" height="200""#));
+
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
 use crate::messages::primitive_aliases::{Code1, Code2, Integer1, Integer2, ScaledSInteger2};
@@ -12,7 +12,7 @@ use uom::si::{
 };
 
 /// A data block for a single elevation cut.
-#[derive(Clone, PartialEq, Deserialize)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct ElevationDataBlock {
     /// The elevation angle for this cut
     pub elevation_angle: Code2,
@@ -118,7 +118,7 @@ fn decode_angle(raw: Code2) -> f64 {
         }
     }
 
-    return angle;
+    angle
 }
 
 /// Decodes an angular velocity as defined in table XI-D of ICD 2620002W
@@ -135,7 +135,7 @@ fn decode_angular_velocity(raw: Code2) -> f64 {
         angular_velocity = -angular_velocity
     }
 
-    return angular_velocity;
+    angular_velocity
 }
 
 impl ElevationDataBlock {
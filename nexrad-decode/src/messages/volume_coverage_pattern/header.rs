@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
 use crate::messages::primitive_aliases::{Code1, Code2, Integer1, Integer2, Integer4};
@@ -8,7 +8,7 @@ use crate::messages::volume_coverage_pattern::definitions::*;
 use uom::si::{f64::Velocity, velocity::meter_per_second};
 
 /// The volume coverage pattern header block
-#[derive(Clone, PartialEq, Deserialize)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct Header {
     /// Total message size in halfwords, including the header and all elevation blocks
     pub message_size: Integer2,
@@ -1,5 +1,8 @@
 use crate::messages::volume_coverage_pattern::{ElevationDataBlock, Header};
 
+#[cfg(feature = "nexrad-model")]
+use crate::messages::volume_coverage_pattern::WaveformType;
+
 /// The digital radar data message includes base radar data from a single radial for various
 /// products.
 #[derive(Debug, Clone, PartialEq)]
@@ -16,4 +19,128 @@ impl Message {
     pub(crate) fn new(header: Header, elevations: Vec<ElevationDataBlock>) -> Self {
         Self { header, elevations }
     }
+
+    /// The pattern's elevation cuts in order, numbered from 1 to match the elevation numbers of the
+    /// radials collected with this pattern. A surveillance cut immediately followed by a Doppler
+    /// cut at the same angle is identified as a split cut, and a cut repeating an earlier cut's
+    /// angle and waveform, such as a SAILS or MRLE supplemental scan, as a revisit of it.
+    #[cfg(feature = "nexrad-model")]
+    pub fn elevation_cuts(&self) -> Vec<nexrad_model::data::ElevationCut> {
+        use nexrad_model::data::{ElevationCut, SplitCutRole, Waveform};
+
+        let waveform = |elevation: &ElevationDataBlock| match elevation.waveform_type() {
+            WaveformType::CS => Some(Waveform::ContiguousSurveillance),
+            WaveformType::CDW => Some(Waveform::ContiguousDopplerWithAmbiguityResolution),
+            WaveformType::CDWO => Some(Waveform::ContiguousDopplerWithoutAmbiguityResolution),
+            WaveformType::B => Some(Waveform::Batch),
+            WaveformType::SPP => Some(Waveform::StaggeredPulsePair),
+            WaveformType::Unknown => None,
+        };
+
+        let is_split = |surveillance: &ElevationDataBlock, doppler: &ElevationDataBlock| {
+            surveillance.elevation_angle == doppler.elevation_angle
+                && waveform(surveillance) == Some(Waveform::ContiguousSurveillance)
+                && waveform(doppler).is_some_and(|waveform| waveform.is_contiguous_doppler())
+        };
+
+        self.elevations
+            .iter()
+            .enumerate()
+            .map(|(index, elevation)| {
+                let mut cut = ElevationCut::new(
+                    (index + 1) as u8,
+                    elevation.elevation_angle_degrees() as f32,
+                );
+
+                if let Some(waveform) = waveform(elevation) {
+                    cut = cut.with_waveform(waveform);
+                }
+
+                let previous = index
+                    .checked_sub(1)
+                    .map(|previous| &self.elevations[previous]);
+                let next = self.elevations.get(index + 1);
+                if next.is_some_and(|next| is_split(elevation, next)) {
+                    cut = cut.with_split_cut_role(SplitCutRole::Surveillance);
+                } else if previous.is_some_and(|previous| is_split(previous, elevation)) {
+                    cut = cut.with_split_cut_role(SplitCutRole::Doppler);
+                }
+
+                let revisited = self.elevations[..index].iter().position(|earlier| {
+                    earlier.elevation_angle == elevation.elevation_angle
+                        && earlier.waveform_type == elevation.waveform_type
+                });
+                if let Some(revisited) = revisited {
+                    cut = cut.with_revisit_of((revisited + 1) as u8);
+                }
+
+                cut
+            })
+            .collect()
+    }
+}
+
+#[cfg(all(test, feature = "nexrad-model"))]
+mod tests {
+    use super::*;
+    use crate::result::Result;
+    use crate::util::deserialize;
+    use nexrad_model::data::{SplitCutRole, Waveform};
+
+    fn vcp(cuts: &[(u16, u8)]) -> Result<Message> {
+        let header: Header = deserialize(&mut [0; 256].as_slice())?;
+
+        let mut elevations = Vec::new();
+        for &(elevation_angle, waveform_type) in cuts {
+            let mut elevation: ElevationDataBlock = deserialize(&mut [0; 256].as_slice())?;
+            elevation.elevation_angle = elevation_angle;
+            elevation.waveform_type = waveform_type;
+            elevations.push(elevation);
+        }
+
+        Ok(Message::new(header, elevations))
+    }
+
+    #[test]
+    fn test_elevation_cuts() -> Result<()> {
+        // A 0.5 degree split cut, a 0.9 degree batch cut, then a SAILS revisit of the split cut
+        let message = vcp(&[(88, 1), (88, 2), (160, 4), (88, 1), (88, 2)])?;
+        let cuts = message.elevation_cuts();
+
+        assert_eq!(
+            cuts.iter()
+                .map(|cut| (cut.elevation_number(), cut.waveform(), cut.split_cut_role()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    1,
+                    Some(Waveform::ContiguousSurveillance),
+                    Some(SplitCutRole::Surveillance)
+                ),
+                (
+                    2,
+                    Some(Waveform::ContiguousDopplerWithAmbiguityResolution),
+                    Some(SplitCutRole::Doppler)
+                ),
+                (3, Some(Waveform::Batch), None),
+                (
+                    4,
+                    Some(Waveform::ContiguousSurveillance),
+                    Some(SplitCutRole::Surveillance)
+                ),
+                (
+                    5,
+                    Some(Waveform::ContiguousDopplerWithAmbiguityResolution),
+                    Some(SplitCutRole::Doppler)
+                ),
+            ]
+        );
+        assert_eq!(
+            cuts.iter().map(|cut| cut.revisit_of()).collect::<Vec<_>>(),
+            vec![None, None, None, Some(1), Some(2)]
+        );
+        assert!((cuts[0].elevation_angle_degrees() - 0.48).abs() < 0.01);
+
+        Ok(())
+    }
 }
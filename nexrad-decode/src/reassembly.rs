@@ -0,0 +1,390 @@
+//!
+//! Reassembly of segmented messages. Large fixed-length messages such as the clutter filter map
+//! (message type 15) and RDA adaptation data (message type 18) are split across several segments,
+//! each carried in its own frame whose message header gives the number of segments in the message
+//! and the segment's number.
+//!
+//! A [Reassembler] collects segments from [frames](crate::ctm::frames) into whole messages within
+//! configurable [ReassemblyLimits], so that pathological segment counts or sizes are rejected
+//! rather than allocated. Each reassembled message carries [ReassemblyDiagnostics] describing
+//! which segments were expected, found, missing, or duplicated.
+//!
+
+use crate::ctm::{Frame, CTM_HEADER_SIZE};
+use crate::limits::DecodeLimits;
+use crate::messages::message_header::MessageHeader;
+use crate::messages::MessageType;
+use crate::result::{Error, Result};
+use crate::telemetry::debug;
+use std::collections::BTreeMap;
+
+/// Limits on the segmented messages a [Reassembler] will collect.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReassemblyLimits {
+    max_segments: u16,
+    max_message_bytes: usize,
+    max_pending_messages: usize,
+}
+
+impl ReassemblyLimits {
+    /// Creates limits of 128 segments and 512 KiB per message with up to 8 messages pending at once,
+    /// comfortably above the largest segmented messages the RDA sends.
+    pub fn new() -> Self {
+        Self {
+            max_segments: 128,
+            max_message_bytes: 512 * 1024,
+            max_pending_messages: 8,
+        }
+    }
+
+    /// Sets the maximum number of segments a message may declare.
+    pub fn with_max_segments(mut self, max_segments: u16) -> Self {
+        self.max_segments = max_segments;
+        self
+    }
+
+    /// Sets the maximum size in bytes of a reassembled message's data.
+    pub fn with_max_message_bytes(mut self, max_message_bytes: usize) -> Self {
+        self.max_message_bytes = max_message_bytes;
+        self
+    }
+
+    /// Sets the maximum number of incomplete messages collected at once.
+    pub fn with_max_pending_messages(mut self, max_pending_messages: usize) -> Self {
+        self.max_pending_messages = max_pending_messages;
+        self
+    }
+}
+
+impl Default for ReassemblyLimits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&DecodeLimits> for ReassemblyLimits {
+    /// Creates reassembly limits with the decode limits' segment count and otherwise default limits.
+    fn from(limits: &DecodeLimits) -> Self {
+        Self::new().with_max_segments(limits.max_segment_count())
+    }
+}
+
+/// Diagnostics describing how a segmented message was reassembled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReassemblyDiagnostics {
+    /// The number of segments the message's headers declared.
+    pub expected_segments: u16,
+    /// The number of distinct segments found.
+    pub found_segments: u16,
+    /// The numbers of the declared segments which were not found, in order.
+    pub missing_segments: Vec<u16>,
+    /// The numbers of segments which were found more than once, in order. Only the first copy of
+    /// each segment is used.
+    pub duplicate_segments: Vec<u16>,
+}
+
+impl ReassemblyDiagnostics {
+    /// Whether every declared segment was found.
+    pub fn is_complete(&self) -> bool {
+        self.missing_segments.is_empty()
+    }
+}
+
+/// A message reassembled from its segments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReassembledMessage {
+    /// The header of the message's first segment found.
+    pub header: MessageHeader,
+    /// The message's data following the message header, concatenated from its segments in order.
+    /// Missing segments are omitted.
+    pub data: Vec<u8>,
+    /// How the message was reassembled.
+    pub diagnostics: ReassemblyDiagnostics,
+}
+
+/// Identifies the segments belonging to the same message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct MessageKey {
+    message_type: u8,
+    sequence_number: u16,
+    date: u16,
+    time: u32,
+}
+
+impl MessageKey {
+    fn new(header: &MessageHeader) -> Self {
+        Self {
+            message_type: header.message_type,
+            sequence_number: header.sequence_number,
+            date: header.date,
+            time: header.time,
+        }
+    }
+}
+
+/// A message whose segments are being collected.
+#[derive(Debug, Clone)]
+struct PendingMessage {
+    header: MessageHeader,
+    segment_count: u16,
+    segments: BTreeMap<u16, Vec<u8>>,
+    duplicate_segments: Vec<u16>,
+    size_bytes: usize,
+}
+
+impl PendingMessage {
+    fn into_reassembled(self) -> ReassembledMessage {
+        let missing_segments = (1..=self.segment_count)
+            .filter(|number| !self.segments.contains_key(number))
+            .collect();
+
+        ReassembledMessage {
+            header: self.header,
+            diagnostics: ReassemblyDiagnostics {
+                expected_segments: self.segment_count,
+                found_segments: self.segments.len() as u16,
+                missing_segments,
+                duplicate_segments: self.duplicate_segments,
+            },
+            data: self.segments.into_values().flatten().collect(),
+        }
+    }
+}
+
+/// Collects the segments of segmented messages into whole messages. See the [module
+/// documentation](self).
+#[derive(Debug, Clone)]
+pub struct Reassembler {
+    limits: ReassemblyLimits,
+    pending: BTreeMap<MessageKey, PendingMessage>,
+}
+
+impl Reassembler {
+    /// Creates a new reassembler with the given limits.
+    pub fn new(limits: ReassemblyLimits) -> Self {
+        Self {
+            limits,
+            pending: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a frame's segment, returning its message once all of its segments have been found.
+    /// Frames holding an entire message, including variable-length messages, are returned
+    /// immediately. Fails if the segment is invalid or collecting it would exceed a limit, in which
+    /// case the segment's message is discarded.
+    pub fn push(&mut self, frame: &Frame) -> Result<Option<ReassembledMessage>> {
+        let header = frame.header();
+        let (segment_count, segment_number) =
+            match (header.segment_count(), header.segment_number()) {
+                (Some(segment_count), Some(segment_number)) if segment_count > 1 => {
+                    (segment_count, segment_number)
+                }
+                _ => {
+                    return Ok(Some(ReassembledMessage {
+                        header: header.clone(),
+                        data: segment_data(frame).to_vec(),
+                        diagnostics: ReassemblyDiagnostics {
+                            expected_segments: 1,
+                            found_segments: 1,
+                            missing_segments: Vec::new(),
+                            duplicate_segments: Vec::new(),
+                        },
+                    }))
+                }
+            };
+
+        let message_type = header.message_type();
+        if segment_count > self.limits.max_segments {
+            return Err(reassembly_error(
+                message_type,
+                format!(
+                    "{} segments exceeds the limit of {}",
+                    segment_count, self.limits.max_segments
+                ),
+            ));
+        }
+
+        if !(1..=segment_count).contains(&segment_number) {
+            return Err(reassembly_error(
+                message_type,
+                format!(
+                    "segment number {} is outside of 1 to {}",
+                    segment_number, segment_count
+                ),
+            ));
+        }
+
+        let key = MessageKey::new(header);
+        if !self.pending.contains_key(&key)
+            && self.pending.len() >= self.limits.max_pending_messages
+        {
+            return Err(reassembly_error(
+                message_type,
+                format!(
+                    "{} incomplete messages are already pending",
+                    self.pending.len()
+                ),
+            ));
+        }
+
+        let pending = self.pending.entry(key).or_insert_with(|| PendingMessage {
+            header: header.clone(),
+            segment_count,
+            segments: BTreeMap::new(),
+            duplicate_segments: Vec::new(),
+            size_bytes: 0,
+        });
+
+        if pending.segment_count != segment_count {
+            self.pending.remove(&key);
+            return Err(reassembly_error(
+                message_type,
+                "segments disagree on the segment count".to_string(),
+            ));
+        }
+
+        if pending.segments.contains_key(&segment_number) {
+            debug!(
+                "Ignoring duplicate segment {} of {:?} message",
+                segment_number, message_type
+            );
+            pending.duplicate_segments.push(segment_number);
+            return Ok(None);
+        }
+
+        let data = segment_data(frame);
+        pending.size_bytes += data.len();
+        if pending.size_bytes > self.limits.max_message_bytes {
+            self.pending.remove(&key);
+            return Err(reassembly_error(
+                message_type,
+                format!(
+                    "message data exceeds the limit of {} bytes",
+                    self.limits.max_message_bytes
+                ),
+            ));
+        }
+
+        pending.segments.insert(segment_number, data.to_vec());
+
+        if pending.segments.len() < segment_count as usize {
+            return Ok(None);
+        }
+
+        Ok(self
+            .pending
+            .remove(&key)
+            .map(PendingMessage::into_reassembled))
+    }
+
+    /// Completes reassembly, returning the messages which are still missing segments along with
+    /// diagnostics identifying the missing segments.
+    pub fn finish(self) -> Vec<ReassembledMessage> {
+        self.pending
+            .into_values()
+            .map(PendingMessage::into_reassembled)
+            .collect()
+    }
+}
+
+/// A segment's message data, excluding the padding of its fixed-length frame beyond the segment's
+/// size.
+fn segment_data<'a>(frame: &Frame<'a>) -> &'a [u8] {
+    let header_size = size_of::<MessageHeader>() - CTM_HEADER_SIZE;
+    let data_size = (frame.header().segment_size as usize * 2).saturating_sub(header_size);
+
+    let body = frame.body();
+    &body[..data_size.min(body.len())]
+}
+
+fn reassembly_error(message_type: MessageType, reason: String) -> Error {
+    Error::ReassemblyError(format!("{:?} message: {}", message_type, reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ctm::{frames, FIXED_FRAME_SIZE};
+    use crate::messages::encode_message_header;
+
+    fn segment(segment_count: u16, segment_number: u16, fill: u8) -> Result<Vec<u8>> {
+        let header = MessageHeader::new(
+            MessageType::RDAClutterFilterMap,
+            0,
+            7,
+            19724,
+            0,
+            ((size_of::<MessageHeader>() - CTM_HEADER_SIZE) / 2 + 4) as u16,
+            segment_count,
+            segment_number,
+        );
+
+        let mut frame = Vec::new();
+        encode_message_header(&mut frame, &header)?;
+        frame.extend_from_slice(&[fill; 8]);
+        frame.resize(FIXED_FRAME_SIZE, 0);
+        Ok(frame)
+    }
+
+    fn push_all(reassembler: &mut Reassembler, data: &[u8]) -> Result<Vec<ReassembledMessage>> {
+        let mut messages = Vec::new();
+        for frame in frames(data).flatten() {
+            messages.extend(reassembler.push(&frame)?);
+        }
+
+        Ok(messages)
+    }
+
+    #[test]
+    fn test_reassembles_out_of_order_segments() -> Result<()> {
+        let mut data = segment(3, 2, 2)?;
+        data.extend(segment(3, 1, 1)?);
+        data.extend(segment(3, 1, 9)?);
+        data.extend(segment(3, 3, 3)?);
+
+        let mut reassembler = Reassembler::new(ReassemblyLimits::new());
+        let messages = push_all(&mut reassembler, &data)?;
+
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].data.len(), 24);
+        assert_eq!(&messages[0].data[..8], &[1; 8]);
+        assert_eq!(&messages[0].data[16..], &[3; 8]);
+        assert_eq!(messages[0].diagnostics.duplicate_segments, vec![1]);
+        assert!(messages[0].diagnostics.is_complete());
+        assert!(reassembler.finish().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reports_missing_segments() -> Result<()> {
+        let mut data = segment(4, 1, 1)?;
+        data.extend(segment(4, 3, 3)?);
+
+        let mut reassembler = Reassembler::new(ReassemblyLimits::new());
+        assert!(push_all(&mut reassembler, &data)?.is_empty());
+
+        let incomplete = reassembler.finish();
+        assert_eq!(incomplete.len(), 1);
+        assert_eq!(incomplete[0].diagnostics.found_segments, 2);
+        assert_eq!(incomplete[0].diagnostics.missing_segments, vec![2, 4]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_enforces_limits() -> Result<()> {
+        let limits = ReassemblyLimits::new().with_max_segments(2);
+        let mut reassembler = Reassembler::new(limits);
+        assert!(push_all(&mut reassembler, &segment(3, 1, 1)?).is_err());
+
+        let limits = ReassemblyLimits::new().with_max_message_bytes(12);
+        let mut data = segment(2, 1, 1)?;
+        data.extend(segment(2, 2, 2)?);
+        let mut reassembler = Reassembler::new(limits);
+        assert!(push_all(&mut reassembler, &data).is_err());
+        assert!(reassembler.finish().is_empty());
+
+        Ok(())
+    }
+}
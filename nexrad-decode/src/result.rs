@@ -14,6 +14,16 @@ pub enum Error {
     DeserializationError(#[from] bincode::Error),
     #[error("file decoding error: {0}")]
     DecodingError(String),
+    #[error("message encoding error: {0}")]
+    EncodingError(String),
     #[error("message is missing collection date/time")]
     MessageMissingDateError,
+    #[error("invalid clutter censor zone: {0}")]
+    InvalidCensorZone(String),
+    #[error("segmented message reassembly error: {0}")]
+    ReassemblyError(String),
+    #[error("unexpected end of data: {0}")]
+    UnexpectedEnd(String),
+    #[error("decode limit exceeded: {0}")]
+    LimitExceeded(String),
 }
@@ -0,0 +1,317 @@
+//!
+//! Recovery of messages from truncated or corrupted data. Where
+//! [decode_messages](crate::messages::decode_messages) fails on the first malformed message and so
+//! has nothing to report, [salvage_messages] skips past it by scanning for the next plausible
+//! message header, and reports what it recovered and skipped as [DecodeStats] which pipelines can
+//! use to monitor corruption rates.
+//!
+
+use crate::ctm::{frame_at, FrameIssue, CTM_HEADER_SIZE, FIXED_FRAME_SIZE};
+use crate::messages::message_header::MessageHeader;
+use crate::messages::{MessageType, MessageWithHeader};
+use crate::util::deserialize;
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// The number of milliseconds in a day, beyond which a message header's time is implausible.
+const MILLISECONDS_PER_DAY: u32 = 86_400_000;
+
+/// Messages recovered from some data, along with statistics describing what could not be decoded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SalvagedMessages<'a> {
+    /// The messages which were decoded, in order.
+    pub messages: Vec<MessageWithHeader<'a>>,
+    /// Statistics describing how much of the data was recovered, including the byte ranges which
+    /// were skipped.
+    pub stats: DecodeStats,
+}
+
+/// Statistics from decoding some data leniently, describing what was recovered and skipped.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DecodeStats {
+    /// The number of messages decoded.
+    pub messages_decoded: usize,
+    /// The total number of bytes in the data.
+    pub bytes_total: usize,
+    /// The number of bytes which were skipped because they could not be decoded.
+    pub bytes_skipped: usize,
+    /// The number of messages decoded of each type.
+    pub message_type_counts: BTreeMap<MessageType, usize>,
+    /// Problems encountered while decoding, in order.
+    pub warnings: Vec<DecodeWarning>,
+}
+
+impl DecodeStats {
+    /// The fraction of the data's bytes which were skipped, from zero to one. Empty data has no
+    /// skipped bytes.
+    pub fn skipped_fraction(&self) -> f64 {
+        if self.bytes_total == 0 {
+            return 0.0;
+        }
+
+        self.bytes_skipped as f64 / self.bytes_total as f64
+    }
+
+    /// Whether all of the data was decoded without skipping any bytes.
+    pub fn is_clean(&self) -> bool {
+        self.warnings.is_empty()
+    }
+}
+
+/// A problem encountered while decoding leniently.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeWarning {
+    /// Undecodable bytes were skipped before resynchronizing on the next message.
+    Resynchronized { range: Range<usize> },
+    /// The data ended with a message whose header is plausible but whose frame extends beyond the
+    /// end of the data.
+    Truncated { range: Range<usize> },
+    /// The data ended with bytes which could not be decoded and do not begin a message.
+    TrailingBytes { range: Range<usize> },
+}
+
+impl DecodeWarning {
+    /// The byte range of the data which was skipped.
+    pub fn range(&self) -> &Range<usize> {
+        match self {
+            DecodeWarning::Resynchronized { range }
+            | DecodeWarning::Truncated { range }
+            | DecodeWarning::TrailingBytes { range } => range,
+        }
+    }
+}
+
+/// Decodes as many messages as possible from the data. When a message cannot be framed or decoded,
+/// the data is scanned byte-by-byte for the next plausible message header which decodes and is
+/// itself followed by a decodable message or the end of the data. The bytes passed over are
+/// reported as [DecodeStats::warnings]. Moment data is borrowed from `data`.
+pub fn salvage_messages(data: &[u8]) -> SalvagedMessages<'_> {
+    let mut messages = Vec::new();
+    let mut stats = DecodeStats {
+        bytes_total: data.len(),
+        ..DecodeStats::default()
+    };
+    let mut skip_start = None;
+
+    let mut position = 0;
+    while position < data.len() {
+        let resynchronizing = skip_start.is_some();
+        match decode_at(data, position).filter(|(frame_size, _)| {
+            !resynchronizing || followed_by_message(data, position + frame_size)
+        }) {
+            Some((frame_size, message)) => {
+                if let Some(start) = skip_start.take() {
+                    stats.bytes_skipped += position - start;
+                    stats.warnings.push(DecodeWarning::Resynchronized {
+                        range: start..position,
+                    });
+                }
+
+                stats.messages_decoded += 1;
+                *stats
+                    .message_type_counts
+                    .entry(message.header.message_type())
+                    .or_default() += 1;
+                messages.push(message);
+                position += frame_size;
+            }
+            None => {
+                skip_start.get_or_insert(position);
+                position += 1;
+            }
+        }
+    }
+
+    if let Some(start) = skip_start {
+        stats.bytes_skipped += data.len() - start;
+        match (start..data.len()).find(|offset| truncated_message_at(data, *offset)) {
+            Some(truncated_start) => {
+                if truncated_start > start {
+                    stats.warnings.push(DecodeWarning::Resynchronized {
+                        range: start..truncated_start,
+                    });
+                }
+                stats.warnings.push(DecodeWarning::Truncated {
+                    range: truncated_start..data.len(),
+                });
+            }
+            None => stats.warnings.push(DecodeWarning::TrailingBytes {
+                range: start..data.len(),
+            }),
+        }
+    }
+
+    SalvagedMessages { messages, stats }
+}
+
+/// Whether a plausible message header begins at the given offset but its frame extends beyond the
+/// end of the data.
+fn truncated_message_at(data: &[u8], offset: usize) -> bool {
+    let header = data
+        .get(offset..)
+        .and_then(|remaining| remaining.get(..size_of::<MessageHeader>()))
+        .and_then(|mut header_data| deserialize::<_, MessageHeader>(&mut header_data).ok());
+
+    header.is_some_and(|header| plausible(&header))
+        && matches!(
+            frame_at(data, offset),
+            Err(FrameIssue::TruncatedFrame { .. })
+        )
+}
+
+/// Decodes the message at the given offset if it has a plausible header, returning its frame size.
+fn decode_at(data: &[u8], offset: usize) -> Option<(usize, MessageWithHeader<'_>)> {
+    let frame = frame_at(data, offset).ok()?;
+    if !plausible(frame.header()) {
+        return None;
+    }
+
+    let message = frame.message().ok()?;
+    Some((
+        frame.data().len(),
+        MessageWithHeader {
+            header: frame.header().clone(),
+            message,
+            byte_range: Some(offset..offset + frame.data().len()),
+        },
+    ))
+}
+
+/// Whether the given offset is the end of the data or the start of a decodable message. Requiring
+/// this when resynchronizing avoids locking onto data within a message which happens to resemble a
+/// message header.
+fn followed_by_message(data: &[u8], offset: usize) -> bool {
+    offset >= data.len()
+        || data.len() - offset < size_of::<MessageHeader>()
+        || decode_at(data, offset).is_some()
+}
+
+/// Whether a message header's fields are within their valid ranges. Arbitrary bytes rarely satisfy
+/// all of these, which makes them a reasonable signature for resynchronizing.
+fn plausible(header: &MessageHeader) -> bool {
+    let message_type = header.message_type();
+    let fits_frame = message_type == MessageType::RDADigitalRadarDataGenericFormat
+        || CTM_HEADER_SIZE + header.segment_size as usize * 2 <= FIXED_FRAME_SIZE;
+    let holds_header =
+        header.segment_size as usize * 2 >= size_of::<MessageHeader>() - CTM_HEADER_SIZE;
+    let valid_segment = match (header.segment_count(), header.segment_number()) {
+        (Some(count), Some(number)) => (1..=count).contains(&number),
+        _ => true,
+    };
+
+    message_type != MessageType::Unknown
+        && matches!(header.redundant_channel, 0 | 1 | 2 | 8 | 9 | 10)
+        && header.time < MILLISECONDS_PER_DAY
+        && fits_frame
+        && holds_header
+        && valid_segment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::Message;
+
+    fn fixed_frame(message_type: u8) -> Vec<u8> {
+        let mut frame = vec![0; FIXED_FRAME_SIZE];
+        frame[CTM_HEADER_SIZE + 1] = 8;
+        frame[CTM_HEADER_SIZE + 3] = message_type;
+        frame[CTM_HEADER_SIZE + 13] = 1;
+        frame[CTM_HEADER_SIZE + 15] = 1;
+        frame
+    }
+
+    #[test]
+    fn test_salvage_intact() {
+        let mut data = fixed_frame(3);
+        data.extend_from_slice(&fixed_frame(18));
+
+        let salvaged = salvage_messages(&data);
+        assert_eq!(salvaged.messages.len(), 2);
+        assert!(salvaged.stats.is_clean());
+        assert_eq!(salvaged.stats.messages_decoded, 2);
+        assert_eq!(
+            salvaged
+                .stats
+                .message_type_counts
+                .get(&MessageType::RDAAdaptationData),
+            Some(&1)
+        );
+    }
+
+    #[test]
+    fn test_salvage_resynchronizes_after_garbage() {
+        let mut data = fixed_frame(3);
+        let garbage_start = data.len();
+        data.extend_from_slice(&[0xFF; 100]);
+        let garbage_end = data.len();
+        data.extend_from_slice(&fixed_frame(18));
+
+        let salvaged = salvage_messages(&data);
+        assert_eq!(salvaged.messages.len(), 2);
+        assert!(matches!(salvaged.messages[1].message, Message::Other));
+        assert_eq!(salvaged.messages[1].header.message_type, 18);
+        assert_eq!(salvaged.stats.bytes_skipped, 100);
+        assert_eq!(
+            salvaged.stats.warnings,
+            vec![DecodeWarning::Resynchronized {
+                range: garbage_start..garbage_end
+            }]
+        );
+    }
+
+    #[test]
+    fn test_salvage_truncated() {
+        let mut data = fixed_frame(3);
+        data.extend_from_slice(&fixed_frame(18)[..1000]);
+
+        let salvaged = salvage_messages(&data);
+        assert_eq!(salvaged.messages.len(), 1);
+        assert_eq!(
+            salvaged.stats.warnings,
+            vec![DecodeWarning::Truncated {
+                range: FIXED_FRAME_SIZE..data.len()
+            }]
+        );
+        assert!(salvaged.stats.skipped_fraction() > 0.0);
+    }
+
+    #[test]
+    fn test_salvage_trailing_bytes() {
+        let mut data = fixed_frame(3);
+        data.extend_from_slice(&[0xFF; 100]);
+
+        let salvaged = salvage_messages(&data);
+        assert_eq!(salvaged.messages.len(), 1);
+        assert_eq!(salvaged.stats.bytes_skipped, 100);
+        assert_eq!(
+            salvaged.stats.warnings,
+            vec![DecodeWarning::TrailingBytes {
+                range: FIXED_FRAME_SIZE..data.len()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_salvage_garbage_before_truncated() {
+        let mut data = fixed_frame(3);
+        data.extend_from_slice(&[0xFF; 100]);
+        let truncated_start = data.len();
+        data.extend_from_slice(&fixed_frame(18)[..1000]);
+
+        let salvaged = salvage_messages(&data);
+        assert_eq!(salvaged.messages.len(), 1);
+        assert_eq!(
+            salvaged.stats.warnings,
+            vec![
+                DecodeWarning::Resynchronized {
+                    range: FIXED_FRAME_SIZE..truncated_start
+                },
+                DecodeWarning::Truncated {
+                    range: truncated_start..data.len()
+                },
+            ]
+        );
+        assert_eq!(salvaged.stats.bytes_skipped, data.len() - FIXED_FRAME_SIZE);
+    }
+}
@@ -1,11 +1,18 @@
+use crate::messages::console_message::{console_entries, ConsoleEntry};
 use crate::messages::digital_radar_data;
 use crate::messages::{Message, MessageType, MessageWithHeader};
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 
 /// Summary of a set of messages.
-#[derive(Clone, PartialEq)]
+///
+/// Summaries may be serialized for log processors and other tools. Serialized field names match
+/// the field names here and are kept stable, times are RFC 3339 strings, and message types and
+/// volume coverage patterns are serialized by variant name. For a compact line-oriented text form,
+/// see [MessageSummary::compact].
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct MessageSummary {
     /// The distinct volume coverage patterns found in these messages.
     pub volume_coverage_patterns: HashSet<digital_radar_data::VolumeCoveragePattern>,
@@ -17,10 +24,69 @@ pub struct MessageSummary {
     /// Summaries of each scan found in these messages.
     pub scans: Vec<ScanSummary>,
 
+    /// The console messages found in these messages, in order.
+    pub console_messages: Vec<ConsoleEntry>,
+
     pub earliest_collection_time: Option<DateTime<Utc>>,
     pub latest_collection_time: Option<DateTime<Utc>>,
 }
 
+impl MessageSummary {
+    /// A compact text form of this summary with one line per group of consecutive messages of the
+    /// same type, scan, and console message, each made up of space-separated `key=value` fields.
+    pub fn compact(&self) -> CompactSummary<'_> {
+        CompactSummary(self)
+    }
+}
+
+/// A compact text form of a [MessageSummary] for display. See [MessageSummary::compact].
+pub struct CompactSummary<'a>(&'a MessageSummary);
+
+impl Display for CompactSummary<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let summary = self.0;
+        let time = |time: Option<DateTime<Utc>>| {
+            time.map_or_else(|| "unknown".to_string(), |time| time.to_rfc3339())
+        };
+
+        for (message_type, count) in &summary.message_types {
+            writeln!(f, "messages type={:?} count={}", message_type, count)?;
+        }
+
+        for scan in &summary.scans {
+            let mut data_types = scan
+                .data_types
+                .iter()
+                .map(|(data_type, count)| format!("{}:{}", data_type.replace(' ', ""), count))
+                .collect::<Vec<_>>();
+            data_types.sort();
+
+            writeln!(
+                f,
+                "scan elevation={} start_time={} end_time={} start_azimuth={:.2} end_azimuth={:.2} data_types={}",
+                scan.elevation,
+                time(scan.start_time),
+                time(scan.end_time),
+                scan.start_azimuth,
+                scan.end_azimuth,
+                data_types.join(",")
+            )?;
+        }
+
+        for entry in &summary.console_messages {
+            writeln!(
+                f,
+                "console time={} origin={:?} text={:?}",
+                time(entry.date_time),
+                entry.origin,
+                entry.text
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
 impl Debug for MessageSummary {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut debug = f.debug_struct("MessageSummary");
@@ -35,6 +101,7 @@ impl Debug for MessageSummary {
         debug.field("message_types", &message_types_string);
 
         debug.field("scans", &self.scans);
+        debug.field("console_messages", &self.console_messages);
         debug.field("earliest_collection_time", &self.earliest_collection_time);
         debug.field("latest_collection_time", &self.latest_collection_time);
         debug.finish()
@@ -42,7 +109,7 @@ impl Debug for MessageSummary {
 }
 
 /// Summary of a single scan.
-#[derive(Clone, PartialEq)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct ScanSummary {
     pub start_time: Option<DateTime<Utc>>,
     pub end_time: Option<DateTime<Utc>>,
@@ -79,28 +146,76 @@ impl Debug for ScanSummary {
 
 /// Provides a summary of the given messages.
 pub fn messages(messages: &[MessageWithHeader]) -> MessageSummary {
-    let mut summary = MessageSummary {
-        volume_coverage_patterns: HashSet::new(),
-        message_types: Vec::new(),
-        scans: Vec::new(),
-        earliest_collection_time: None,
-        latest_collection_time: None,
-    };
+    let mut summarizer = Summarizer::new();
+    for message_with_header in messages {
+        summarizer.push(message_with_header);
+    }
+
+    summarizer.finish()
+}
 
-    if let Some(first_message) = messages.first() {
-        summary.earliest_collection_time = first_message.header.date_time();
+/// Summarizes messages incrementally as they are decoded, for example from a streaming decoder or
+/// a real-time feed, rather than requiring them all up front as [messages] does. An intermediate
+/// summary of the messages seen so far is available at any time, such as to display progress
+/// during a long decode or to summarize an in-progress volume.
+#[derive(Debug, Clone)]
+pub struct Summarizer {
+    summary: MessageSummary,
+    scan_summary: Option<ScanSummary>,
+}
+
+impl Summarizer {
+    /// Creates a new summarizer which has not yet seen any messages.
+    pub fn new() -> Self {
+        Self {
+            summary: MessageSummary {
+                volume_coverage_patterns: HashSet::new(),
+                message_types: Vec::new(),
+                scans: Vec::new(),
+                console_messages: Vec::new(),
+                earliest_collection_time: None,
+                latest_collection_time: None,
+            },
+            scan_summary: None,
+        }
     }
 
-    let mut scan_summary = None;
-    for message_with_header in messages {
-        process_message(&mut summary, &mut scan_summary, message_with_header);
+    /// Adds a message to the summary.
+    pub fn push(&mut self, message_with_header: &MessageWithHeader) {
+        if self.summary.message_types.is_empty() {
+            self.summary.earliest_collection_time = message_with_header.header.date_time();
+        }
+
+        self.summary
+            .console_messages
+            .extend(console_entries(std::slice::from_ref(message_with_header)));
+
+        process_message(
+            &mut self.summary,
+            &mut self.scan_summary,
+            message_with_header,
+        );
     }
 
-    if let Some(scan_summary) = scan_summary.take() {
-        summary.scans.push(scan_summary);
+    /// A summary of the messages seen so far, including the scan currently in progress.
+    pub fn summary(&self) -> MessageSummary {
+        self.clone().finish()
     }
 
-    summary
+    /// Completes the summary of the messages seen.
+    pub fn finish(mut self) -> MessageSummary {
+        if let Some(scan_summary) = self.scan_summary.take() {
+            self.summary.scans.push(scan_summary);
+        }
+
+        self.summary
+    }
+}
+
+impl Default for Summarizer {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 fn process_message(
@@ -119,12 +234,9 @@ fn process_message(
         summary.message_types.push((message_type, 1));
     }
 
-    match &message_with_header.message {
-        Message::DigitalRadarData(message) => {
-            process_digital_radar_data_message(summary, scan_summary, message);
-            return;
-        }
-        _ => {}
+    if let Message::DigitalRadarData(message) = &message_with_header.message {
+        process_digital_radar_data_message(summary, scan_summary, message);
+        return;
     }
 
     if let Some(scan_summary) = scan_summary.take() {
@@ -210,4 +322,56 @@ fn process_digital_radar_data_message(
     if message.specific_diff_phase_data_block.is_some() {
         increment_count("Specific Differential Phase");
     }
+    for block in &message.extension_data_blocks {
+        increment_count(&block.header.data_block_id.data_block_name());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::console_message;
+    use crate::messages::message_header::MessageHeader;
+
+    fn console_message(time: u32, text: &str) -> MessageWithHeader<'static> {
+        MessageWithHeader {
+            header: MessageHeader::new(MessageType::RDAConsoleMessage, 0, 0, 19724, time, 0, 1, 1),
+            message: Message::ConsoleMessage(Box::new(console_message::Message {
+                text: text.to_string(),
+            })),
+            byte_range: None,
+        }
+    }
+
+    #[test]
+    fn test_summarizer_matches_messages() {
+        let messages = [
+            console_message(60_000, "TRANSMITTER DOWN"),
+            console_message(120_000, "TRANSMITTER UP"),
+        ];
+
+        let mut summarizer = Summarizer::new();
+        summarizer.push(&messages[0]);
+
+        let intermediate = summarizer.summary();
+        assert_eq!(
+            intermediate.message_types,
+            vec![(MessageType::RDAConsoleMessage, 1)]
+        );
+        assert_eq!(intermediate.console_messages.len(), 1);
+
+        summarizer.push(&messages[1]);
+        assert_eq!(summarizer.finish(), super::messages(&messages));
+    }
+
+    #[test]
+    fn test_compact_summary() {
+        let summary = super::messages(&[console_message(60_000, "TRANSMITTER DOWN")]);
+
+        assert_eq!(
+            summary.compact().to_string(),
+            "messages type=RDAConsoleMessage count=1\n\
+             console time=2024-01-01T00:01:00+00:00 origin=RDA text=\"TRANSMITTER DOWN\"\n"
+        );
+    }
 }
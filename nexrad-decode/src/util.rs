@@ -1,8 +1,10 @@
+use crate::result::Error;
 use crate::result::Result;
 use bincode::{DefaultOptions, Options};
 use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use serde::de::DeserializeOwned;
-use std::io::Read;
+use serde::Serialize;
+use std::io::{Read, Write};
 
 /// Given a "modified" Julian date (date count since 1/1/1970) and a count of milliseconds since
 /// midnight on that date, return an appropriate DateTime.
@@ -27,3 +29,12 @@ pub(crate) fn deserialize<R: Read, S: DeserializeOwned>(reader: &mut R) -> Resul
         .with_big_endian()
         .deserialize_from(reader.by_ref())?)
 }
+
+/// Attempts to serialize some struct to the provided binary writer.
+pub(crate) fn serialize<W: Write, S: Serialize>(writer: &mut W, value: &S) -> Result<()> {
+    DefaultOptions::new()
+        .with_fixint_encoding()
+        .with_big_endian()
+        .serialize_into(writer.by_ref(), value)
+        .map_err(|err| Error::EncodingError(err.to_string()))
+}
@@ -0,0 +1,19 @@
+use nexrad_model::schema::{schemas, SCHEMA_VERSION};
+use std::fs;
+use std::path::PathBuf;
+
+// Writes the model's JSON Schema documents for the current schema version into `schemas/`.
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let directory = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("schemas")
+        .join(format!("v{SCHEMA_VERSION}"));
+    fs::create_dir_all(&directory)?;
+
+    for (name, schema) in schemas() {
+        let path = directory.join(format!("{name}.json"));
+        fs::write(&path, serde_json::to_string_pretty(&schema)? + "\n")?;
+        println!("Wrote {}", path.display());
+    }
+
+    Ok(())
+}
@@ -13,9 +13,27 @@ pub use sweep::*;
 mod scan;
 pub use scan::*;
 
+mod cut;
+pub use cut::*;
+
 mod radial;
 
 pub use radial::*;
 
 mod moment;
 pub use moment::*;
+
+mod fingerprint;
+pub use fingerprint::Fingerprint;
+
+mod lookup;
+pub use lookup::LookupTolerance;
+
+mod mask;
+pub use mask::*;
+
+mod attenuation;
+pub use attenuation::*;
+
+mod clutter;
+pub use clutter::*;
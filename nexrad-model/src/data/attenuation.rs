@@ -0,0 +1,298 @@
+use crate::data::{Radial, Sweep};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The number of gates at each end of a radial's rain path whose differential phase is averaged to
+/// find the path's total phase shift, smoothing the phase's gate-to-gate noise.
+const PHASE_WINDOW_GATES: usize = 5;
+
+/// Coefficients for correcting reflectivity and differential reflectivity for attenuation by rain
+/// with the ZPHI method (Testud et al., 2000). Along each radial, the total differential phase
+/// shift constrains the path-integrated attenuation, which is distributed among gates in proportion
+/// to their measured reflectivity. See [Radial::correct_attenuation].
+///
+/// The coefficients depend on the radar's wavelength and on the drop size distribution. Those from
+/// [AttenuationCorrection::new] suit the WSR-88D's S band, where attenuation is slight except in
+/// intense rain; X and C band radars require larger coefficients.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AttenuationCorrection {
+    horizontal_coefficient_db_per_degree: f32,
+    differential_coefficient_db_per_degree: f32,
+    reflectivity_exponent: f32,
+}
+
+impl AttenuationCorrection {
+    /// Create coefficients for S band radars: 0.02 dB of attenuation and 0.004 dB of differential
+    /// attenuation per degree of differential phase shift, with attenuation proportional to the
+    /// 0.78 power of linear reflectivity.
+    pub fn new() -> Self {
+        Self {
+            horizontal_coefficient_db_per_degree: 0.02,
+            differential_coefficient_db_per_degree: 0.004,
+            reflectivity_exponent: 0.78,
+        }
+    }
+
+    /// Sets the ratio of specific attenuation to specific differential phase, in dB per degree.
+    pub fn with_horizontal_coefficient(mut self, db_per_degree: f32) -> Self {
+        self.horizontal_coefficient_db_per_degree = db_per_degree;
+        self
+    }
+
+    /// Sets the ratio of specific differential attenuation to specific differential phase, in dB
+    /// per degree.
+    pub fn with_differential_coefficient(mut self, db_per_degree: f32) -> Self {
+        self.differential_coefficient_db_per_degree = db_per_degree;
+        self
+    }
+
+    /// Sets the exponent relating specific attenuation to linear reflectivity.
+    pub fn with_reflectivity_exponent(mut self, reflectivity_exponent: f32) -> Self {
+        self.reflectivity_exponent = reflectivity_exponent;
+        self
+    }
+
+    /// The ratio of specific attenuation to specific differential phase, in dB per degree.
+    pub fn horizontal_coefficient_db_per_degree(&self) -> f32 {
+        self.horizontal_coefficient_db_per_degree
+    }
+
+    /// The ratio of specific differential attenuation to specific differential phase, in dB per
+    /// degree.
+    pub fn differential_coefficient_db_per_degree(&self) -> f32 {
+        self.differential_coefficient_db_per_degree
+    }
+
+    /// The exponent relating specific attenuation to linear reflectivity.
+    pub fn reflectivity_exponent(&self) -> f32 {
+        self.reflectivity_exponent
+    }
+
+    /// The two-way path-integrated attenuation in dB at each of the radial's reflectivity gates.
+    /// Differential phase gates are assumed to share reflectivity's gate spacing. Attenuation is
+    /// zero where differential phase does not increase along the radial, and returns `None` if the
+    /// radial lacks either moment or reflectivity's gate spacing is unknown.
+    pub fn path_integrated_attenuation(&self, radial: &Radial) -> Option<Vec<f32>> {
+        let reflectivity = radial.reflectivity()?;
+        let differential_phase = radial.differential_phase()?;
+        let gate_interval_km = reflectivity.gate_interval_km()? as f64;
+
+        let gate_count = reflectivity.gate_count();
+        let mut attenuation = vec![0.0; gate_count];
+
+        // The rain path spans the gates with both a measured reflectivity and differential phase
+        let path: Vec<(usize, f32)> = (0..gate_count)
+            .filter_map(|index| {
+                reflectivity.measured(index)?;
+                Some((index, differential_phase.measured(index)?))
+            })
+            .collect();
+        if path.len() < 2 * PHASE_WINDOW_GATES {
+            return Some(attenuation);
+        }
+
+        let mean_phase = |window: &[(usize, f32)]| {
+            window.iter().map(|(_, phase)| *phase as f64).sum::<f64>() / window.len() as f64
+        };
+        let phase_shift_degrees = mean_phase(&path[path.len() - PHASE_WINDOW_GATES..])
+            - mean_phase(&path[..PHASE_WINDOW_GATES]);
+        if phase_shift_degrees <= 0.0 || gate_interval_km <= 0.0 {
+            return Some(attenuation);
+        }
+
+        let (start, end) = (path[0].0, path[path.len() - 1].0);
+        let exponent = self.reflectivity_exponent as f64;
+
+        // Each gate's reflectivity in linear units raised to the exponent, and the integral of it
+        // from each gate to the end of the path
+        let weights: Vec<f64> = (start..=end)
+            .map(|index| {
+                reflectivity
+                    .measured(index)
+                    .map_or(0.0, |dbz| 10f64.powf(0.1 * exponent * dbz as f64))
+            })
+            .collect();
+        let mut integrals = vec![0.0; weights.len() + 1];
+        for index in (0..weights.len()).rev() {
+            integrals[index] =
+                integrals[index + 1] + 0.46 * exponent * weights[index] * gate_interval_km;
+        }
+        if integrals[0] <= 0.0 {
+            return Some(attenuation);
+        }
+
+        let phase_factor = 10f64.powf(
+            0.1 * exponent * self.horizontal_coefficient_db_per_degree as f64 * phase_shift_degrees,
+        ) - 1.0;
+
+        let mut total_db = 0.0;
+        for (offset, gate_attenuation) in attenuation.iter_mut().enumerate().skip(start) {
+            let path_index = offset - start;
+            if let Some(weight) = weights.get(path_index) {
+                let specific_attenuation =
+                    weight * phase_factor / (integrals[0] + phase_factor * integrals[path_index]);
+                total_db += 2.0 * specific_attenuation * gate_interval_km;
+            }
+
+            *gate_attenuation = total_db as f32;
+        }
+
+        Some(attenuation)
+    }
+}
+
+impl Default for AttenuationCorrection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Radial {
+    /// A copy of this radial with its reflectivity and differential reflectivity corrected for
+    /// attenuation by rain using the ZPHI method. Radials lacking reflectivity or differential
+    /// phase are returned unchanged.
+    pub fn correct_attenuation(&self, correction: &AttenuationCorrection) -> Radial {
+        let Some(attenuation) = correction.path_integrated_attenuation(self) else {
+            return self.clone();
+        };
+
+        let attenuation_at = |index: usize| {
+            attenuation
+                .get(index)
+                .or(attenuation.last())
+                .copied()
+                .unwrap_or_default()
+        };
+        let differential_ratio = if correction.horizontal_coefficient_db_per_degree != 0.0 {
+            correction.differential_coefficient_db_per_degree
+                / correction.horizontal_coefficient_db_per_degree
+        } else {
+            0.0
+        };
+
+        self.with_reflectivity_moments(
+            self.reflectivity()
+                .map(|moment| moment.map_values(|index, dbz| dbz + attenuation_at(index))),
+            self.differential_reflectivity().map(|moment| {
+                moment.map_values(|index, db| db + differential_ratio * attenuation_at(index))
+            }),
+        )
+    }
+}
+
+impl Sweep {
+    /// A copy of this sweep with each radial's reflectivity and differential reflectivity corrected
+    /// for attenuation by rain. See [Radial::correct_attenuation].
+    pub fn correct_attenuation(&self, correction: &AttenuationCorrection) -> Sweep {
+        let radials = self
+            .radials()
+            .iter()
+            .map(|radial| radial.correct_attenuation(correction))
+            .collect();
+
+        let corrected = Sweep::new(self.elevation_number(), radials).with_index(self.index());
+        match self.cut() {
+            Some(cut) => corrected.with_cut(*cut),
+            None => corrected,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{MomentData, MomentValue, RadialStatus};
+
+    /// A radial of 100 gates of uniform 40 dBZ rain, with differential phase increasing by 0.2
+    /// degrees per gate and 1 dB of differential reflectivity.
+    fn radial() -> Radial {
+        let reflectivity =
+            MomentData::from_fixed_point(2.0, 66.0, vec![146; 100]).with_gate_range(2.0, 0.25);
+        let differential_reflectivity =
+            MomentData::from_fixed_point(16.0, 128.0, vec![144; 100]).with_gate_range(2.0, 0.25);
+        let differential_phase =
+            MomentData::from_fixed_point(10.0, 2.0, (0..100).map(|gate| gate * 2 + 2).collect())
+                .with_gate_range(2.0, 0.25);
+
+        Radial::new(
+            0,
+            1,
+            0.0,
+            1.0,
+            RadialStatus::IntermediateRadialData,
+            1,
+            0.5,
+            Some(reflectivity),
+            None,
+            None,
+            Some(differential_reflectivity),
+            Some(differential_phase),
+            None,
+            None,
+        )
+    }
+
+    fn value(moment: Option<&MomentData>, index: usize) -> f32 {
+        match moment.and_then(|moment| moment.value(index)) {
+            Some(MomentValue::Value(value)) => value,
+            value => panic!("expected a value, found {:?}", value),
+        }
+    }
+
+    #[test]
+    fn test_path_integrated_attenuation() {
+        let correction = AttenuationCorrection::new().with_horizontal_coefficient(0.28);
+        let attenuation = correction
+            .path_integrated_attenuation(&radial())
+            .unwrap_or_default();
+
+        assert_eq!(attenuation.len(), 100);
+        assert!(attenuation.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        // The phase shift between the path's end windows is 19 degrees
+        let total_db = attenuation[99];
+        assert!((total_db - 0.28 * 19.0).abs() < 0.1, "{}", total_db);
+    }
+
+    #[test]
+    fn test_correct_attenuation() {
+        let correction = AttenuationCorrection::new()
+            .with_horizontal_coefficient(0.28)
+            .with_differential_coefficient(0.04);
+        let corrected = radial().correct_attenuation(&correction);
+
+        assert_eq!(value(corrected.reflectivity(), 0), 40.0);
+        assert!((value(corrected.reflectivity(), 99) - 45.32).abs() <= 0.5);
+        assert!((value(corrected.differential_reflectivity(), 99) - 1.76).abs() <= 0.1);
+        assert_eq!(
+            corrected.differential_phase(),
+            radial().differential_phase()
+        );
+    }
+
+    #[test]
+    fn test_no_phase_shift() {
+        let radial = radial();
+        let flat = Radial::new(
+            0,
+            1,
+            0.0,
+            1.0,
+            RadialStatus::IntermediateRadialData,
+            1,
+            0.5,
+            radial.reflectivity().cloned(),
+            None,
+            None,
+            None,
+            Some(MomentData::from_fixed_point(10.0, 2.0, vec![50; 100])),
+            None,
+            None,
+        );
+
+        let corrected = flat.correct_attenuation(&AttenuationCorrection::new());
+        assert_eq!(corrected, flat);
+    }
+}
@@ -0,0 +1,256 @@
+use crate::data::{GateMask, MomentData, Radial, Sweep};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The range of reflectivity texture in dB over which a gate's clutter membership rises from zero
+/// to one.
+const REFLECTIVITY_TEXTURE_DB: (f32, f32) = (3.0, 10.0);
+
+/// The range of differential reflectivity texture in dB over which a gate's clutter membership
+/// rises from zero to one.
+const DIFFERENTIAL_REFLECTIVITY_TEXTURE_DB: (f32, f32) = (0.5, 2.0);
+
+/// The range of differential phase texture in degrees over which a gate's clutter membership rises
+/// from zero to one.
+const DIFFERENTIAL_PHASE_TEXTURE_DEGREES: (f32, f32) = (5.0, 20.0);
+
+/// The range of correlation coefficient over which a gate's clutter membership falls from one to
+/// zero.
+const CORRELATION_COEFFICIENT: (f32, f32) = (0.8, 0.95);
+
+/// Parameters for detecting ground clutter and anomalous propagation (AP) returns, which are
+/// stationary and spatially noisy unlike precipitation. Each gate's clutter likelihood combines
+/// fuzzy memberships for near-zero velocity, high reflectivity, differential reflectivity, and
+/// differential phase texture, and low correlation coefficient, averaging those the radial has.
+/// See [Radial::clutter_likelihood].
+///
+/// Texture is the root-mean-square difference between adjacent gates within a window along the
+/// radial. Moments are assumed to share reflectivity's gate spacing, as they do in
+/// super-resolution data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClutterDetection {
+    window_gates: usize,
+    near_zero_velocity_meters_per_second: f32,
+    likelihood_threshold: f32,
+}
+
+impl ClutterDetection {
+    /// Create parameters with textures over 5-gate windows, velocities within 1 m/s of zero
+    /// considered stationary, and gates with a likelihood of at least 0.5 considered clutter.
+    pub fn new() -> Self {
+        Self {
+            window_gates: 5,
+            near_zero_velocity_meters_per_second: 1.0,
+            likelihood_threshold: 0.5,
+        }
+    }
+
+    /// Sets the number of gates in the window over which texture is computed.
+    pub fn with_window_gates(mut self, window_gates: usize) -> Self {
+        self.window_gates = window_gates;
+        self
+    }
+
+    /// Sets the speed in m/s within which velocities are considered stationary. Membership falls
+    /// to zero at twice this speed.
+    pub fn with_near_zero_velocity(mut self, meters_per_second: f32) -> Self {
+        self.near_zero_velocity_meters_per_second = meters_per_second;
+        self
+    }
+
+    /// Sets the likelihood at and above which gates are considered clutter by
+    /// [Sweep::clutter_mask].
+    pub fn with_likelihood_threshold(mut self, likelihood_threshold: f32) -> Self {
+        self.likelihood_threshold = likelihood_threshold;
+        self
+    }
+
+    /// The number of gates in the window over which texture is computed.
+    pub fn window_gates(&self) -> usize {
+        self.window_gates
+    }
+
+    /// The speed in m/s within which velocities are considered stationary.
+    pub fn near_zero_velocity_meters_per_second(&self) -> f32 {
+        self.near_zero_velocity_meters_per_second
+    }
+
+    /// The likelihood at and above which gates are considered clutter.
+    pub fn likelihood_threshold(&self) -> f32 {
+        self.likelihood_threshold
+    }
+}
+
+impl Default for ClutterDetection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Radial {
+    /// The likelihood from zero to one that each of this radial's reflectivity gates is ground
+    /// clutter or anomalous propagation, or `None` for gates without a measured reflectivity.
+    /// Returns an empty field if the radial lacks reflectivity.
+    pub fn clutter_likelihood(&self, detection: &ClutterDetection) -> Vec<Option<f32>> {
+        let Some(reflectivity) = self.reflectivity() else {
+            return Vec::new();
+        };
+
+        let half_window = detection.window_gates / 2;
+        let near_zero = detection.near_zero_velocity_meters_per_second;
+
+        (0..reflectivity.gate_count())
+            .map(|index| {
+                reflectivity.measured(index)?;
+
+                let memberships = [
+                    self.velocity()
+                        .and_then(|velocity| velocity.measured(index))
+                        .map(|velocity| 1.0 - ramp(velocity.abs(), (near_zero, 2.0 * near_zero))),
+                    texture(reflectivity, index, half_window)
+                        .map(|texture| ramp(texture, REFLECTIVITY_TEXTURE_DB)),
+                    self.differential_reflectivity()
+                        .and_then(|moment| texture(moment, index, half_window))
+                        .map(|texture| ramp(texture, DIFFERENTIAL_REFLECTIVITY_TEXTURE_DB)),
+                    self.differential_phase()
+                        .and_then(|moment| texture(moment, index, half_window))
+                        .map(|texture| ramp(texture, DIFFERENTIAL_PHASE_TEXTURE_DEGREES)),
+                    self.correlation_coefficient()
+                        .and_then(|moment| moment.measured(index))
+                        .map(|correlation| 1.0 - ramp(correlation, CORRELATION_COEFFICIENT)),
+                ];
+
+                let (sum, count) = memberships
+                    .into_iter()
+                    .flatten()
+                    .fold((0.0, 0), |(sum, count), membership| {
+                        (sum + membership, count + 1)
+                    });
+
+                (count > 0).then(|| sum / count as f32)
+            })
+            .collect()
+    }
+}
+
+impl Sweep {
+    /// The clutter likelihood of each gate of each of this sweep's radials. See
+    /// [Radial::clutter_likelihood].
+    pub fn clutter_likelihood(&self, detection: &ClutterDetection) -> Vec<Vec<Option<f32>>> {
+        self.radials()
+            .iter()
+            .map(|radial| radial.clutter_likelihood(detection))
+            .collect()
+    }
+
+    /// A mask selecting the gates of this sweep whose clutter likelihood is at least the detection's
+    /// threshold, for example to censor anomalous propagation with [Sweep::censor].
+    pub fn clutter_mask(&self, detection: &ClutterDetection) -> GateMask {
+        GateMask::new(
+            self.clutter_likelihood(detection)
+                .into_iter()
+                .map(|radial| {
+                    radial
+                        .into_iter()
+                        .map(|likelihood| {
+                            likelihood.is_some_and(|likelihood| {
+                                likelihood >= detection.likelihood_threshold
+                            })
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+}
+
+/// The root-mean-square difference between adjacent measured gates within the given number of
+/// gates of the given gate, or `None` if there are no adjacent measured gates.
+fn texture(moment: &MomentData, index: usize, half_window: usize) -> Option<f32> {
+    let start = index.saturating_sub(half_window);
+    let end = (index + half_window).min(moment.gate_count().saturating_sub(1));
+
+    let (sum, count) = (start..end)
+        .filter_map(|gate| Some(moment.measured(gate + 1)? - moment.measured(gate)?))
+        .fold((0.0, 0), |(sum, count), difference| {
+            (sum + difference * difference, count + 1)
+        });
+
+    (count > 0).then(|| (sum / count as f32).sqrt())
+}
+
+/// A membership rising linearly from zero to one across the given range.
+fn ramp(value: f32, (low, high): (f32, f32)) -> f32 {
+    if high <= low {
+        return if value >= high { 1.0 } else { 0.0 };
+    }
+
+    ((value - low) / (high - low)).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{MomentValue, RadialStatus};
+
+    /// A radial of 20 gates, the first 10 of which are noisy, stationary clutter and the last 10 of
+    /// which are smooth, moving rain.
+    fn radial() -> Radial {
+        let reflectivity = (0..20)
+            .map(|gate| match gate {
+                0..=9 if gate % 2 == 0 => 166,
+                0..=9 => 110,
+                _ => 146,
+            })
+            .collect();
+        let velocity = (0..20)
+            .map(|gate| if gate < 10 { 129 } else { 149 })
+            .collect();
+
+        Radial::new(
+            0,
+            1,
+            0.0,
+            1.0,
+            RadialStatus::IntermediateRadialData,
+            1,
+            0.5,
+            Some(MomentData::from_fixed_point(2.0, 66.0, reflectivity)),
+            Some(MomentData::from_fixed_point(2.0, 129.0, velocity)),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_clutter_likelihood() {
+        let likelihood = radial().clutter_likelihood(&ClutterDetection::new());
+        assert_eq!(likelihood.len(), 20);
+
+        assert_eq!(likelihood[2], Some(1.0));
+        assert_eq!(likelihood[17], Some(0.0));
+    }
+
+    #[test]
+    fn test_clutter_mask() {
+        let sweep = Sweep::new(1, vec![radial(), radial()]);
+        let mask = sweep.clutter_mask(&ClutterDetection::new());
+
+        assert_eq!(mask.radials().len(), 2);
+        assert!(mask.radials()[1][..8].iter().all(|&selected| selected));
+        assert!(mask.radials()[1][12..].iter().all(|&selected| !selected));
+
+        let censored = sweep.censor(&mask);
+        assert_eq!(
+            censored.radials()[0]
+                .reflectivity()
+                .and_then(|moment| moment.value(0)),
+            Some(MomentValue::BelowThreshold)
+        );
+    }
+}
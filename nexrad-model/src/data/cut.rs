@@ -0,0 +1,121 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The pulse waveform a radar transmits during an elevation cut.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum Waveform {
+    /// Contiguous surveillance (CS), a long-range, low-PRF waveform for reflectivity.
+    ContiguousSurveillance,
+    /// Contiguous Doppler with range ambiguity resolution (CDW), a high-PRF waveform for velocity.
+    ContiguousDopplerWithAmbiguityResolution,
+    /// Contiguous Doppler without range ambiguity resolution (CDWO).
+    ContiguousDopplerWithoutAmbiguityResolution,
+    /// Batch, interleaving low- and high-PRF pulses within each radial.
+    Batch,
+    /// Staggered pulse pair, alternating between two PRFs to extend the Nyquist velocity.
+    StaggeredPulsePair,
+}
+
+impl Waveform {
+    /// Whether this is a contiguous Doppler waveform, with or without range ambiguity resolution.
+    pub fn is_contiguous_doppler(&self) -> bool {
+        matches!(
+            self,
+            Waveform::ContiguousDopplerWithAmbiguityResolution
+                | Waveform::ContiguousDopplerWithoutAmbiguityResolution
+        )
+    }
+}
+
+/// A cut's role within a split cut, where the same elevation angle is scanned twice in succession:
+/// once with a surveillance waveform for reflectivity and once with a Doppler waveform for velocity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum SplitCutRole {
+    /// The split cut's contiguous surveillance scan.
+    Surveillance,
+    /// The split cut's contiguous Doppler scan.
+    Doppler,
+}
+
+/// An elevation cut's identity within its volume coverage pattern: where it falls in the pattern,
+/// how it was scanned, and how it relates to the pattern's other cuts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ElevationCut {
+    elevation_number: u8,
+    elevation_angle_degrees: f32,
+    waveform: Option<Waveform>,
+    split_cut_role: Option<SplitCutRole>,
+    revisit_of: Option<u8>,
+}
+
+impl ElevationCut {
+    /// Create a new elevation cut with the given elevation number and target elevation angle in
+    /// degrees, which is neither part of a split cut nor a revisit of an earlier cut.
+    pub fn new(elevation_number: u8, elevation_angle_degrees: f32) -> Self {
+        Self {
+            elevation_number,
+            elevation_angle_degrees,
+            waveform: None,
+            split_cut_role: None,
+            revisit_of: None,
+        }
+    }
+
+    /// Sets the waveform transmitted during this cut.
+    pub fn with_waveform(mut self, waveform: Waveform) -> Self {
+        self.waveform = Some(waveform);
+        self
+    }
+
+    /// Sets this cut's role within a split cut.
+    pub fn with_split_cut_role(mut self, split_cut_role: SplitCutRole) -> Self {
+        self.split_cut_role = Some(split_cut_role);
+        self
+    }
+
+    /// Marks this cut as a revisit of the earlier cut with the given elevation number, for example
+    /// a SAILS or MRLE supplemental low-level scan.
+    pub fn with_revisit_of(mut self, elevation_number: u8) -> Self {
+        self.revisit_of = Some(elevation_number);
+        self
+    }
+
+    /// The cut's 1-based position in the volume coverage pattern, matching its sweep's and radials'
+    /// elevation number.
+    pub fn elevation_number(&self) -> u8 {
+        self.elevation_number
+    }
+
+    /// The cut's target elevation angle in degrees. Each radial's actual elevation angle varies
+    /// slightly from this.
+    pub fn elevation_angle_degrees(&self) -> f32 {
+        self.elevation_angle_degrees
+    }
+
+    /// The waveform transmitted during this cut, if known.
+    pub fn waveform(&self) -> Option<Waveform> {
+        self.waveform
+    }
+
+    /// This cut's role within a split cut, or `None` if it is not part of one.
+    pub fn split_cut_role(&self) -> Option<SplitCutRole> {
+        self.split_cut_role
+    }
+
+    /// The elevation number of the earlier cut this one revisits at the same angle and waveform, if
+    /// any.
+    pub fn revisit_of(&self) -> Option<u8> {
+        self.revisit_of
+    }
+
+    /// Whether this cut revisits an earlier cut in the same volume.
+    pub fn is_revisit(&self) -> bool {
+        self.revisit_of.is_some()
+    }
+}
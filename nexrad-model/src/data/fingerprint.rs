@@ -0,0 +1,296 @@
+use crate::data::{Radial, RadialStatus, Scan, Sweep};
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// A content-based fingerprint of decoded radar data, derived from its radials' headers and moment
+/// data rather than its encoded bytes. Two archives of the same scan have equal fingerprints even
+/// when they were compressed differently, allowing logically identical uploads to be deduplicated.
+///
+/// Fingerprints are computed with a fixed hash function, so they are stable across platforms and
+/// releases and may be persisted, for example in an ingestion index.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Fingerprint(u64);
+
+impl Fingerprint {
+    /// The fingerprint's underlying hash value.
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+impl Display for Fingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+impl Debug for Fingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Fingerprint({})", self)
+    }
+}
+
+/// An FNV-1a hasher which, unlike [std::hash::DefaultHasher], produces the same hash regardless of
+/// platform or release. Values are written in little-endian byte order.
+pub(crate) struct FingerprintHasher(u64);
+
+impl FingerprintHasher {
+    pub(crate) fn new() -> Self {
+        Self(FNV_OFFSET_BASIS)
+    }
+
+    pub(crate) fn write_bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    pub(crate) fn write_u64(&mut self, value: u64) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    pub(crate) fn write_f32(&mut self, value: f32) {
+        self.write_bytes(&value.to_bits().to_le_bytes());
+    }
+
+    pub(crate) fn write_optional_f32(&mut self, value: Option<f32>) {
+        match value {
+            Some(value) => {
+                self.write_bytes(&[1]);
+                self.write_f32(value);
+            }
+            None => self.write_bytes(&[0]),
+        }
+    }
+
+    pub(crate) fn finish(&self) -> Fingerprint {
+        Fingerprint(self.0)
+    }
+}
+
+impl Radial {
+    /// A fingerprint of this radial's header fields and moment data.
+    pub fn fingerprint(&self) -> Fingerprint {
+        let mut hasher = FingerprintHasher::new();
+
+        hasher.write_u64(self.collection_timestamp() as u64);
+        hasher.write_u64(self.azimuth_number() as u64);
+        hasher.write_f32(self.azimuth_angle_degrees());
+        hasher.write_f32(self.azimuth_spacing_degrees());
+        hasher.write_u64(radial_status_code(self.radial_status()));
+        hasher.write_u64(self.elevation_number() as u64);
+        hasher.write_f32(self.elevation_angle_degrees());
+
+        hasher.write_optional_f32(self.nyquist_velocity_meters_per_second());
+        hasher.write_optional_f32(self.unambiguous_range_km());
+        hasher.write_optional_f32(self.horizontal_calibration_constant_dbz());
+        hasher.write_optional_f32(self.vertical_calibration_constant_dbz());
+
+        for moment in [
+            self.reflectivity(),
+            self.velocity(),
+            self.spectrum_width(),
+            self.differential_reflectivity(),
+            self.differential_phase(),
+            self.correlation_coefficient(),
+            self.specific_differential_phase(),
+        ] {
+            match moment {
+                Some(moment) => {
+                    hasher.write_bytes(&[1]);
+                    moment.write_fingerprint(&mut hasher);
+                }
+                None => hasher.write_bytes(&[0]),
+            }
+        }
+
+        hasher.write_u64(self.extension_moments().len() as u64);
+        for moment in self.extension_moments() {
+            hasher.write_u64(moment.name().len() as u64);
+            hasher.write_bytes(moment.name().as_bytes());
+            moment.data().write_fingerprint(&mut hasher);
+        }
+
+        hasher.finish()
+    }
+}
+
+impl Sweep {
+    /// A fingerprint of this sweep's elevation number and its radials' fingerprints in order.
+    pub fn fingerprint(&self) -> Fingerprint {
+        let mut hasher = FingerprintHasher::new();
+
+        hasher.write_u64(self.elevation_number() as u64);
+        hasher.write_u64(self.radials().len() as u64);
+        for radial in self.radials() {
+            hasher.write_u64(radial.fingerprint().value());
+        }
+
+        hasher.finish()
+    }
+
+    /// The fraction of radials, from 0 to 1, which are identical between this sweep and another,
+    /// regardless of their order. Sweeps with no radials are considered identical to each other.
+    pub fn similarity(&self, other: &Sweep) -> f64 {
+        radial_similarity(self.radials().iter(), other.radials().iter())
+    }
+}
+
+impl Scan {
+    /// A fingerprint of this scan's coverage pattern number and its sweeps' fingerprints in order.
+    /// Scans with equal fingerprints are logically identical and may be deduplicated.
+    pub fn fingerprint(&self) -> Fingerprint {
+        let mut hasher = FingerprintHasher::new();
+
+        hasher.write_u64(self.coverage_pattern_number() as u64);
+        hasher.write_u64(self.sweeps().len() as u64);
+        for sweep in self.sweeps() {
+            hasher.write_u64(sweep.fingerprint().value());
+        }
+
+        hasher.finish()
+    }
+
+    /// The fraction of radials, from 0 to 1, which are identical between this scan and another,
+    /// regardless of their order. This identifies near-duplicates such as a volume uploaded both
+    /// complete and truncated. Scans with no radials are considered identical to each other.
+    pub fn similarity(&self, other: &Scan) -> f64 {
+        radial_similarity(
+            self.sweeps().iter().flat_map(Sweep::radials),
+            other.sweeps().iter().flat_map(Sweep::radials),
+        )
+    }
+}
+
+/// The number of radials shared by both collections, counting duplicates, as a fraction of the
+/// larger collection's size.
+fn radial_similarity<'a>(
+    radials: impl Iterator<Item = &'a Radial>,
+    other_radials: impl Iterator<Item = &'a Radial>,
+) -> f64 {
+    let mut counts = HashMap::new();
+    let mut radial_count = 0;
+    for radial in radials {
+        *counts.entry(radial.fingerprint()).or_insert(0usize) += 1;
+        radial_count += 1;
+    }
+
+    let mut shared = 0;
+    let mut other_radial_count = 0;
+    for radial in other_radials {
+        if let Some(count) = counts.get_mut(&radial.fingerprint()) {
+            if *count > 0 {
+                *count -= 1;
+                shared += 1;
+            }
+        }
+        other_radial_count += 1;
+    }
+
+    let total = radial_count.max(other_radial_count);
+    if total == 0 {
+        return 1.0;
+    }
+
+    shared as f64 / total as f64
+}
+
+fn radial_status_code(status: RadialStatus) -> u64 {
+    match status {
+        RadialStatus::ElevationStart => 0,
+        RadialStatus::IntermediateRadialData => 1,
+        RadialStatus::ElevationEnd => 2,
+        RadialStatus::VolumeScanStart => 3,
+        RadialStatus::VolumeScanEnd => 4,
+        RadialStatus::ElevationStartVCPFinal => 5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::{MomentData, Radial, RadialStatus, Scan, Sweep};
+
+    fn radial(azimuth_number: u16, values: Vec<u8>) -> Radial {
+        Radial::new(
+            1_700_000_000_000,
+            azimuth_number,
+            azimuth_number as f32 - 0.5,
+            1.0,
+            RadialStatus::IntermediateRadialData,
+            1,
+            0.5,
+            Some(MomentData::from_fixed_point(2.0, 66.0, values).with_gate_range(2.125, 0.25)),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    fn sweep(radial_count: u16) -> Sweep {
+        let radials = (1..=radial_count)
+            .map(|azimuth_number| radial(azimuth_number, vec![0, 70, 80, 90]))
+            .collect();
+
+        Sweep::new(1, radials)
+    }
+
+    #[test]
+    fn test_fingerprint_is_content_based() {
+        let scan = Scan::new(212, vec![sweep(360)]);
+        let copy = Scan::new(212, vec![sweep(360)]);
+        assert_eq!(scan.fingerprint(), copy.fingerprint());
+        assert_eq!(scan.similarity(&copy), 1.0);
+
+        let other_pattern = Scan::new(215, vec![sweep(360)]);
+        assert_ne!(scan.fingerprint(), other_pattern.fingerprint());
+    }
+
+    #[test]
+    fn test_fingerprint_detects_moment_changes() {
+        let original = radial(1, vec![0, 70, 80, 90]);
+        let changed = radial(1, vec![0, 70, 80, 91]);
+        assert_ne!(original.fingerprint(), changed.fingerprint());
+
+        let rescaled = Radial::new(
+            original.collection_timestamp(),
+            1,
+            original.azimuth_angle_degrees(),
+            1.0,
+            RadialStatus::IntermediateRadialData,
+            1,
+            0.5,
+            Some(
+                MomentData::from_fixed_point(2.0, 64.0, vec![0, 70, 80, 90])
+                    .with_gate_range(2.125, 0.25),
+            ),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        assert_ne!(original.fingerprint(), rescaled.fingerprint());
+    }
+
+    #[test]
+    fn test_similarity_of_truncated_sweep() {
+        let complete = sweep(360);
+        let truncated = sweep(270);
+
+        assert_ne!(complete.fingerprint(), truncated.fingerprint());
+        assert_eq!(complete.similarity(&truncated), 0.75);
+        assert_eq!(truncated.similarity(&complete), 0.75);
+        assert_eq!(
+            Sweep::new(1, Vec::new()).similarity(&Sweep::new(1, Vec::new())),
+            1.0
+        );
+    }
+}
@@ -0,0 +1,229 @@
+/// How far a looked-up position may be from the nearest radial or gate for it to match. See
+/// [Scan::radial_at](crate::data::Scan::radial_at) and [Sweep::gate_at](crate::data::Sweep::gate_at).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LookupTolerance {
+    elevation_degrees: f32,
+    azimuth_degrees: Option<f32>,
+    range_km: Option<f32>,
+}
+
+impl LookupTolerance {
+    /// Creates a tolerance which matches a sweep within half a degree of elevation, the radial
+    /// whose beam width contains the azimuth, and the gate whose interval contains the range.
+    pub fn new() -> Self {
+        Self {
+            elevation_degrees: 0.5,
+            azimuth_degrees: None,
+            range_km: None,
+        }
+    }
+
+    /// The maximum difference between a sweep's elevation angle and the looked-up elevation.
+    pub fn with_elevation_degrees(mut self, elevation_degrees: f32) -> Self {
+        self.elevation_degrees = elevation_degrees;
+        self
+    }
+
+    /// The maximum difference between a radial's azimuth angle and the looked-up azimuth, rather
+    /// than half of the radial's azimuth spacing.
+    pub fn with_azimuth_degrees(mut self, azimuth_degrees: f32) -> Self {
+        self.azimuth_degrees = Some(azimuth_degrees);
+        self
+    }
+
+    /// The maximum distance between a gate's center and the looked-up range, rather than half of
+    /// the gate interval.
+    pub fn with_range_km(mut self, range_km: f32) -> Self {
+        self.range_km = Some(range_km);
+        self
+    }
+
+    /// The maximum elevation difference in degrees.
+    pub fn elevation_degrees(&self) -> f32 {
+        self.elevation_degrees
+    }
+
+    /// The maximum azimuth difference in degrees, if not derived from the radial's spacing.
+    pub fn azimuth_degrees(&self) -> Option<f32> {
+        self.azimuth_degrees
+    }
+
+    /// The maximum range difference in kilometers, if not derived from the gate interval.
+    pub fn range_km(&self) -> Option<f32> {
+        self.range_km
+    }
+}
+
+impl Default for LookupTolerance {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Angles paired with the index of the item they belong to, sorted by angle for binary search.
+pub(crate) type AngleIndex = Vec<(f32, usize)>;
+
+/// Sorts the given angles into an index. Angles which are not finite are excluded.
+pub(crate) fn build_angle_index(angles: impl Iterator<Item = f32>) -> AngleIndex {
+    let mut index: AngleIndex = angles
+        .enumerate()
+        .filter(|(_, angle)| angle.is_finite())
+        .map(|(position, angle)| (angle, position))
+        .collect();
+    index.sort_by(|a, b| a.0.total_cmp(&b.0));
+    index
+}
+
+/// Finds the indexed azimuth nearest the given azimuth, accounting for wraparound at 360 degrees.
+/// Returns the item's index and its angular distance from the azimuth.
+pub(crate) fn nearest_azimuth(index: &AngleIndex, azimuth_degrees: f32) -> Option<(usize, f32)> {
+    if index.is_empty() {
+        return None;
+    }
+
+    let azimuth_degrees = azimuth_degrees.rem_euclid(360.0);
+    let after = index.partition_point(|(angle, _)| *angle < azimuth_degrees);
+
+    [after % index.len(), (after + index.len() - 1) % index.len()]
+        .into_iter()
+        .filter_map(|position| index.get(position))
+        .map(|&(angle, item)| (item, azimuth_distance(angle, azimuth_degrees)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Finds the indexed elevation nearest the given elevation, preferring the earliest item among
+/// equal elevations. Returns the item's index and its distance from the elevation.
+pub(crate) fn nearest_elevation(
+    index: &AngleIndex,
+    elevation_degrees: f32,
+) -> Option<(usize, f32)> {
+    let first_at_or_above = index.partition_point(|(angle, _)| *angle < elevation_degrees);
+    let first_below = first_at_or_above
+        .checked_sub(1)
+        .and_then(|last_below| index.get(last_below))
+        .map(|(below, _)| index.partition_point(|(angle, _)| angle < below));
+
+    [Some(first_at_or_above), first_below]
+        .into_iter()
+        .flatten()
+        .filter_map(|position| index.get(position))
+        .map(|&(angle, item)| (item, (angle - elevation_degrees).abs()))
+        .min_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)))
+}
+
+/// The angular distance between two azimuths in degrees, from 0 to 180.
+fn azimuth_distance(a: f32, b: f32) -> f32 {
+    let difference = (a - b).rem_euclid(360.0);
+    difference.min(360.0 - difference)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{MomentData, MomentValue, Radial, RadialStatus, Scan, Sweep};
+
+    fn sweep(elevation_number: u8, elevation_angle_degrees: f32) -> Sweep {
+        let radials = (0..360)
+            .map(|azimuth_number| {
+                let reflectivity = MomentData::from_fixed_point(2.0, 66.0, vec![0, 70, 80, 90])
+                    .with_gate_range(2.125, 0.25);
+
+                Radial::new(
+                    0,
+                    azimuth_number + 1,
+                    azimuth_number as f32 + 0.5,
+                    1.0,
+                    RadialStatus::IntermediateRadialData,
+                    elevation_number,
+                    elevation_angle_degrees,
+                    Some(reflectivity),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+            })
+            .collect();
+
+        Sweep::new(elevation_number, radials)
+    }
+
+    #[test]
+    fn test_scan_radial_at() {
+        let scan = Scan::new(212, vec![sweep(1, 0.5), sweep(2, 0.5), sweep(3, 1.45)]);
+        let tolerance = LookupTolerance::new();
+
+        let radial = scan.radial_at(0.48, 90.2, &tolerance);
+        assert_eq!(radial.map(Radial::elevation_number), Some(1));
+        assert_eq!(radial.map(Radial::azimuth_number), Some(91));
+
+        let radial = scan.radial_at(1.3, 359.9, &tolerance);
+        assert_eq!(radial.map(Radial::elevation_number), Some(3));
+        assert_eq!(radial.map(Radial::azimuth_number), Some(360));
+
+        assert!(scan.radial_at(5.0, 90.0, &tolerance).is_none());
+        assert!(scan
+            .radial_at(5.0, 90.0, &tolerance.with_elevation_degrees(4.0))
+            .is_some());
+    }
+
+    #[test]
+    fn test_sweep_gate_at() {
+        let sweep = sweep(1, 0.5);
+        let tolerance = LookupTolerance::new();
+
+        let gate =
+            |range_km, tolerance| sweep.gate_at(45.5, range_km, Radial::reflectivity, tolerance);
+        assert_eq!(gate(2.1, &tolerance), Some(MomentValue::BelowThreshold));
+        assert_eq!(gate(2.4, &tolerance), Some(MomentValue::Value(2.0)));
+        assert_eq!(gate(2.9, &tolerance), Some(MomentValue::Value(12.0)));
+        assert_eq!(gate(3.5, &tolerance), None);
+        assert_eq!(
+            gate(3.5, &tolerance.with_range_km(1.0)),
+            Some(MomentValue::Value(12.0))
+        );
+        assert_eq!(sweep.gate_at(45.5, 2.4, Radial::velocity, &tolerance), None);
+    }
+
+    #[test]
+    fn test_nearest_azimuth_wraps() {
+        let index = build_angle_index([0.25, 90.0, 359.75, 180.0].into_iter());
+
+        assert_eq!(nearest_azimuth(&index, 0.0), Some((0, 0.25)));
+        assert_eq!(
+            nearest_azimuth(&index, 359.9).map(|(item, _)| item),
+            Some(2)
+        );
+        assert_eq!(
+            nearest_azimuth(&index, -100.0).map(|(item, _)| item),
+            Some(3)
+        );
+        assert_eq!(nearest_azimuth(&index, 100.0), Some((1, 10.0)));
+        assert_eq!(nearest_azimuth(&AngleIndex::new(), 10.0), None);
+    }
+
+    #[test]
+    fn test_nearest_elevation_prefers_earliest() {
+        let index = build_angle_index([0.5, 0.5, 1.5, 0.9, 2.4].into_iter());
+
+        assert_eq!(
+            nearest_elevation(&index, 0.6).map(|(item, _)| item),
+            Some(0)
+        );
+        assert_eq!(
+            nearest_elevation(&index, 0.0).map(|(item, _)| item),
+            Some(0)
+        );
+        assert_eq!(
+            nearest_elevation(&index, 1.0).map(|(item, _)| item),
+            Some(3)
+        );
+        assert_eq!(
+            nearest_elevation(&index, 9.0).map(|(item, _)| item),
+            Some(4)
+        );
+        assert_eq!(nearest_elevation(&AngleIndex::new(), 1.0), None);
+    }
+}
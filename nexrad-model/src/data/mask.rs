@@ -0,0 +1,331 @@
+use crate::data::{MomentData, MomentValue, Radial, Sweep};
+use std::collections::BTreeMap;
+
+/// A single gate of a radial, giving access to each of the radial's moments at that gate for
+/// evaluating mask predicates. See [Sweep::mask_where].
+#[derive(Debug, Clone, Copy)]
+pub struct Gate<'a> {
+    radial: &'a Radial,
+    index: usize,
+}
+
+impl<'a> Gate<'a> {
+    /// The radial containing this gate.
+    pub fn radial(&self) -> &'a Radial {
+        self.radial
+    }
+
+    /// The gate's index within its radial.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The value of the moment selected by `moment`, e.g. [Radial::reflectivity], at this gate.
+    /// Returns `None` if the radial lacks the moment or it has no value at this gate.
+    pub fn value(
+        &self,
+        moment: impl FnOnce(&'a Radial) -> Option<&'a MomentData>,
+    ) -> Option<MomentValue> {
+        moment(self.radial)?.value(self.index)
+    }
+
+    /// The measured value of the moment selected by `moment` at this gate, or `None` if the gate
+    /// has no value or is below threshold, range folded, or missing.
+    pub fn measured(
+        &self,
+        moment: impl FnOnce(&'a Radial) -> Option<&'a MomentData>,
+    ) -> Option<f32> {
+        moment(self.radial)?.measured(self.index)
+    }
+}
+
+/// A boolean layer selecting gates of a sweep, indexed by radial and then by gate. Masks are built
+/// from predicates with [Sweep::mask_where], combined with [GateMask::and] and [GateMask::or], and
+/// applied with [Sweep::censor].
+///
+/// Gates are identified by their index in each radial, so a mask is only meaningful across
+/// moments which share gate spacing, as they do in super-resolution data.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GateMask {
+    radials: Vec<Vec<bool>>,
+}
+
+impl GateMask {
+    /// Create a new mask from a boolean layer of each radial's gates, where `true` selects a gate.
+    pub fn new(radials: Vec<Vec<bool>>) -> Self {
+        Self { radials }
+    }
+
+    /// The mask's boolean layer, with one entry per gate in each radial.
+    pub fn radials(&self) -> &[Vec<bool>] {
+        &self.radials
+    }
+
+    /// Whether the gate at the given radial and gate indices is selected. Gates outside the mask
+    /// are not selected.
+    pub fn is_selected(&self, radial_index: usize, gate_index: usize) -> bool {
+        self.radials
+            .get(radial_index)
+            .and_then(|gates| gates.get(gate_index))
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// The number of gates selected by this mask.
+    pub fn selected_count(&self) -> usize {
+        self.radials
+            .iter()
+            .map(|gates| gates.iter().filter(|selected| **selected).count())
+            .sum()
+    }
+
+    /// A mask selecting gates which are selected by both this mask and the other.
+    pub fn and(&self, other: &GateMask) -> GateMask {
+        self.combine(other, |a, b| a && b)
+    }
+
+    /// A mask selecting gates which are selected by either this mask or the other.
+    pub fn or(&self, other: &GateMask) -> GateMask {
+        self.combine(other, |a, b| a || b)
+    }
+
+    /// A mask selecting the gates this mask does not.
+    pub fn invert(&self) -> GateMask {
+        GateMask::new(
+            self.radials
+                .iter()
+                .map(|gates| gates.iter().map(|selected| !selected).collect())
+                .collect(),
+        )
+    }
+
+    /// Combines two masks gate-by-gate, covering the larger of the two.
+    fn combine(&self, other: &GateMask, operator: impl Fn(bool, bool) -> bool) -> GateMask {
+        let radial_count = self.radials.len().max(other.radials.len());
+        let radials = (0..radial_count)
+            .map(|radial_index| {
+                let gate_count = [&self.radials, &other.radials]
+                    .iter()
+                    .filter_map(|radials| radials.get(radial_index))
+                    .map(Vec::len)
+                    .max()
+                    .unwrap_or(0);
+
+                (0..gate_count)
+                    .map(|gate_index| {
+                        operator(
+                            self.is_selected(radial_index, gate_index),
+                            other.is_selected(radial_index, gate_index),
+                        )
+                    })
+                    .collect()
+            })
+            .collect();
+
+        GateMask::new(radials)
+    }
+}
+
+/// A set of masks identified by name, such as "clutter" or "non-meteorological", so that QC and
+/// product code can share masks and combine them by name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NamedMasks {
+    masks: BTreeMap<String, GateMask>,
+}
+
+impl NamedMasks {
+    /// Create a new, empty set of masks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a mask with the given name, returning the mask it replaced, if any.
+    pub fn insert(&mut self, name: impl Into<String>, mask: GateMask) -> Option<GateMask> {
+        self.masks.insert(name.into(), mask)
+    }
+
+    /// The mask with the given name, if any.
+    pub fn get(&self, name: &str) -> Option<&GateMask> {
+        self.masks.get(name)
+    }
+
+    /// The names of the masks in this set, in order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.masks.keys().map(String::as_str)
+    }
+
+    /// A mask selecting gates selected by every one of the named masks, or `None` if any name is
+    /// unknown or none are given.
+    pub fn all<'a>(&self, names: impl IntoIterator<Item = &'a str>) -> Option<GateMask> {
+        self.fold(names, GateMask::and)
+    }
+
+    /// A mask selecting gates selected by any of the named masks, or `None` if any name is unknown
+    /// or none are given.
+    pub fn any<'a>(&self, names: impl IntoIterator<Item = &'a str>) -> Option<GateMask> {
+        self.fold(names, GateMask::or)
+    }
+
+    fn fold<'a>(
+        &self,
+        names: impl IntoIterator<Item = &'a str>,
+        operator: impl Fn(&GateMask, &GateMask) -> GateMask,
+    ) -> Option<GateMask> {
+        let mut combined: Option<GateMask> = None;
+        for name in names {
+            let mask = self.get(name)?;
+            combined = Some(match combined {
+                Some(combined) => operator(&combined, mask),
+                None => mask.clone(),
+            });
+        }
+
+        combined
+    }
+}
+
+impl Sweep {
+    /// A mask selecting the gates of this sweep for which the predicate holds, for example those
+    /// with a measured correlation coefficient below 0.8. Each radial's gates extend to its longest
+    /// moment.
+    pub fn mask_where(&self, mut predicate: impl FnMut(&Gate) -> bool) -> GateMask {
+        GateMask::new(
+            self.radials()
+                .iter()
+                .map(|radial| {
+                    (0..radial.max_gate_count())
+                        .map(|index| predicate(&Gate { radial, index }))
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    /// A copy of this sweep in which the gates selected by the mask are set below threshold in
+    /// every moment, for example to remove gates a QC mask identified as non-meteorological.
+    pub fn censor(&self, mask: &GateMask) -> Sweep {
+        let radials = self
+            .radials()
+            .iter()
+            .enumerate()
+            .map(|(radial_index, radial)| {
+                radial.map_moments(|moment| {
+                    moment.censor(|gate_index| mask.is_selected(radial_index, gate_index))
+                })
+            })
+            .collect();
+
+        let censored = Sweep::new(self.elevation_number(), radials).with_index(self.index());
+        match self.cut() {
+            Some(cut) => censored.with_cut(*cut),
+            None => censored,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::RadialStatus;
+
+    fn sweep() -> Sweep {
+        let radials = (0..2)
+            .map(|azimuth_number| {
+                let reflectivity = MomentData::from_fixed_point(2.0, 66.0, vec![0, 70, 80, 90]);
+                let correlation_coefficient =
+                    MomentData::from_fixed_point(100.0, 0.0, vec![0, 98, 50, 95]);
+
+                Radial::new(
+                    0,
+                    azimuth_number + 1,
+                    azimuth_number as f32 * 180.0,
+                    180.0,
+                    RadialStatus::IntermediateRadialData,
+                    1,
+                    0.5,
+                    Some(reflectivity),
+                    None,
+                    None,
+                    None,
+                    None,
+                    Some(correlation_coefficient),
+                    None,
+                )
+            })
+            .collect();
+
+        Sweep::new(1, radials)
+    }
+
+    #[test]
+    fn test_mask_where() {
+        let sweep = sweep();
+
+        let low_correlation = sweep.mask_where(|gate| {
+            gate.measured(Radial::correlation_coefficient)
+                .is_some_and(|correlation_coefficient| correlation_coefficient < 0.8)
+        });
+        assert_eq!(
+            low_correlation.radials()[0],
+            vec![false, false, true, false]
+        );
+
+        let strong = sweep.mask_where(|gate| {
+            gate.measured(Radial::reflectivity)
+                .is_some_and(|reflectivity| reflectivity >= 7.0)
+        });
+        assert_eq!(strong.radials()[0], vec![false, false, true, true]);
+
+        assert_eq!(low_correlation.and(&strong).selected_count(), 2);
+        assert_eq!(low_correlation.or(&strong).selected_count(), 4);
+        assert_eq!(strong.invert().selected_count(), 4);
+    }
+
+    #[test]
+    fn test_censor() {
+        let sweep = sweep();
+        let mask = sweep.mask_where(|gate| gate.index() == 2);
+
+        let censored = sweep.censor(&mask);
+        let radial = &censored.radials()[1];
+        assert_eq!(
+            radial.reflectivity().map(MomentData::values),
+            Some(vec![
+                MomentValue::BelowThreshold,
+                MomentValue::Value(2.0),
+                MomentValue::BelowThreshold,
+                MomentValue::Value(12.0),
+            ])
+        );
+        assert_eq!(
+            radial
+                .correlation_coefficient()
+                .and_then(|moment| moment.value(2)),
+            Some(MomentValue::BelowThreshold)
+        );
+    }
+
+    #[test]
+    fn test_named_masks() {
+        let sweep = sweep();
+
+        let mut masks = NamedMasks::new();
+        masks.insert("near", sweep.mask_where(|gate| gate.index() < 2));
+        masks.insert("even", sweep.mask_where(|gate| gate.index() % 2 == 0));
+
+        assert_eq!(masks.names().collect::<Vec<_>>(), vec!["even", "near"]);
+        assert_eq!(
+            masks
+                .all(["near", "even"])
+                .map(|mask| mask.selected_count()),
+            Some(2)
+        );
+        assert_eq!(
+            masks
+                .any(["near", "even"])
+                .map(|mask| mask.selected_count()),
+            Some(6)
+        );
+        assert!(masks.all(["near", "unknown"]).is_none());
+    }
+}
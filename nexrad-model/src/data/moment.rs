@@ -1,45 +1,291 @@
+use crate::data::fingerprint::FingerprintHasher;
+use crate::data::LookupTolerance;
 use std::fmt::Debug;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Moment data from a radial for a particular product where each value corresponds to a gate.
+/// Values are stored in the fixed-point encoding of their source, using either 8-bit or big-endian
+/// 16-bit words.
 #[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct MomentData {
     scale: f32,
     offset: f32,
     values: Vec<u8>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "std::ops::Not::not")
+    )]
+    sixteen_bit: bool,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    missing: Vec<bool>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    gate_range: Option<GateRange>,
+}
+
+/// The ranges of a moment's gates, from which a gate's index can be found for a given range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+struct GateRange {
+    first_gate_range_km: f32,
+    gate_interval_km: f32,
 }
 
 impl MomentData {
-    /// Create new moment data from fixed-point encoding.
+    /// Create new moment data from fixed-point encoding with 8-bit words.
     pub fn from_fixed_point(scale: f32, offset: f32, values: Vec<u8>) -> Self {
         Self {
             scale,
             offset,
             values,
+            sixteen_bit: false,
+            missing: Vec::new(),
+            gate_range: None,
+        }
+    }
+
+    /// Create new moment data from fixed-point encoding with 16-bit words, given as big-endian
+    /// pairs of bytes as they are in a generic data block. A trailing odd byte is ignored.
+    pub fn from_fixed_point_16(scale: f32, offset: f32, values: Vec<u8>) -> Self {
+        Self {
+            sixteen_bit: true,
+            ..Self::from_fixed_point(scale, offset, values)
+        }
+    }
+
+    /// Sets the range to the center of this moment's first gate and the interval between gates, in
+    /// kilometers, allowing gates to be looked up by range.
+    pub fn with_gate_range(mut self, first_gate_range_km: f32, gate_interval_km: f32) -> Self {
+        self.gate_range = Some(GateRange {
+            first_gate_range_km,
+            gate_interval_km,
+        });
+        self
+    }
+
+    /// Marks the gates at the given indices as missing, i.e. not collected, for sources which
+    /// distinguish gates without data from those below threshold. Indices beyond this moment's
+    /// gates are ignored.
+    pub fn with_missing_gates(mut self, indices: impl IntoIterator<Item = usize>) -> Self {
+        let gate_count = self.gate_count();
+        for index in indices.into_iter().filter(|&index| index < gate_count) {
+            if self.missing.is_empty() {
+                self.missing = vec![false; gate_count];
+            }
+            self.missing[index] = true;
+        }
+        self
+    }
+
+    /// The number of bits in each of this moment's fixed-point words, either 8 or 16.
+    pub fn word_size(&self) -> u8 {
+        if self.sixteen_bit {
+            16
+        } else {
+            8
+        }
+    }
+
+    /// The range to the center of this moment's first gate in kilometers, if known.
+    pub fn first_gate_range_km(&self) -> Option<f32> {
+        self.gate_range.map(|range| range.first_gate_range_km)
+    }
+
+    /// The interval between this moment's gates in kilometers, if known.
+    pub fn gate_interval_km(&self) -> Option<f32> {
+        self.gate_range.map(|range| range.gate_interval_km)
+    }
+
+    /// Writes this moment's encoding, gate ranges, and raw values to a fingerprint. The word size
+    /// and missing gates are only written where they differ from the defaults, so that
+    /// fingerprints of 8-bit moments without missing gates are unchanged.
+    pub(crate) fn write_fingerprint(&self, hasher: &mut FingerprintHasher) {
+        hasher.write_f32(self.scale);
+        hasher.write_f32(self.offset);
+        hasher.write_optional_f32(self.first_gate_range_km());
+        hasher.write_optional_f32(self.gate_interval_km());
+        hasher.write_u64(self.values.len() as u64);
+        hasher.write_bytes(&self.values);
+
+        if self.sixteen_bit {
+            hasher.write_u64(16);
+        }
+        if self.missing.contains(&true) {
+            let missing: Vec<u8> = self.missing.iter().map(|&missing| missing as u8).collect();
+            hasher.write_bytes(&missing);
         }
     }
 
     /// Values from this data moment corresponding to gates in the radial.
     pub fn values(&self) -> Vec<MomentValue> {
-        let copied_values = self.values.iter().copied();
+        (0..self.gate_count())
+            .filter_map(|index| self.value(index))
+            .collect()
+    }
 
-        if self.scale == 0.0 {
-            return copied_values
-                .map(|raw_value| MomentValue::Value(raw_value as f32))
-                .collect();
+    /// The value of the gate at the given index, if present.
+    pub fn value(&self, index: usize) -> Option<MomentValue> {
+        let word = self.word(index)?;
+        if self.is_missing(index) {
+            return Some(MomentValue::Missing);
         }
 
-        copied_values
-            .map(|raw_value| match raw_value {
-                0 => MomentValue::BelowThreshold,
-                1 => MomentValue::RangeFolded,
-                _ => MomentValue::Value((raw_value as f32 - self.offset) / self.scale),
-            })
+        Some(self.decode(word))
+    }
+
+    /// The measured value of the gate at the given index, or `None` if it is not present, below
+    /// threshold, range folded, or missing.
+    pub(crate) fn measured(&self, index: usize) -> Option<f32> {
+        match self.value(index)? {
+            MomentValue::Value(value) => Some(value),
+            MomentValue::BelowThreshold | MomentValue::RangeFolded | MomentValue::Missing => None,
+        }
+    }
+
+    /// This moment's values as floating-point numbers over the given number of gates, with `NaN`
+    /// for gates which are below threshold, range folded, or missing. Gates beyond this moment's
+    /// data, such as where its range is shorter than another moment's, are missing. Use
+    /// [MomentData::to_f32_with_sentinels] to distinguish between the special cases.
+    pub fn to_f32(&self, gate_count: usize) -> Vec<f32> {
+        (0..gate_count)
+            .map(|index| self.value(index).map_or(f32::NAN, |value| value.to_f32()))
             .collect()
     }
+
+    /// This moment's values as floating-point numbers like [MomentData::to_f32], along with a
+    /// mask channel giving the special case of each gate whose value is `NaN`. Measured gates are
+    /// `None` in the mask.
+    pub fn to_f32_with_sentinels(&self, gate_count: usize) -> (Vec<f32>, Vec<Option<Sentinel>>) {
+        (0..gate_count)
+            .map(|index| {
+                let value = self.value(index).unwrap_or(MomentValue::Missing);
+                (value.to_f32(), value.sentinel())
+            })
+            .unzip()
+    }
+
+    /// The index of the gate nearest the given range in kilometers. The range must be within the
+    /// tolerance's range of the gate's center, by default half of the gate interval. Returns `None`
+    /// if this moment's gate ranges are not known.
+    pub fn gate_index(&self, range_km: f32, tolerance: &LookupTolerance) -> Option<usize> {
+        let gate_range = self.gate_range?;
+        let last_index = self.gate_count().checked_sub(1)?;
+        if gate_range.gate_interval_km <= 0.0 {
+            return None;
+        }
+
+        let position = (range_km - gate_range.first_gate_range_km) / gate_range.gate_interval_km;
+        let index = (position.round().max(0.0) as usize).min(last_index);
+
+        let gate_center_km =
+            gate_range.first_gate_range_km + index as f32 * gate_range.gate_interval_km;
+        let max_distance_km = tolerance
+            .range_km()
+            .unwrap_or(gate_range.gate_interval_km / 2.0);
+
+        ((range_km - gate_center_km).abs() <= max_distance_km).then_some(index)
+    }
+
+    /// The number of gates in this moment.
+    pub fn gate_count(&self) -> usize {
+        if self.sixteen_bit {
+            self.values.len() / 2
+        } else {
+            self.values.len()
+        }
+    }
+
+    /// A copy of this moment with the gates for which `censored` holds set below threshold. Missing
+    /// gates remain missing.
+    pub(crate) fn censor(&self, censored: impl Fn(usize) -> bool) -> Self {
+        self.map_words(|index, word| if censored(index) { 0 } else { word })
+    }
+
+    /// A copy of this moment with `map` applied to each gate's value given the gate's index. Gates
+    /// without a value are unchanged, and mapped values are clamped to the encoding's range.
+    pub(crate) fn map_values(&self, map: impl Fn(usize, f32) -> f32) -> Self {
+        self.map_words(|index, word| match self.value(index) {
+            Some(MomentValue::Value(value)) => self.encode(map(index, value)),
+            _ => word,
+        })
+    }
+
+    /// A copy of this moment with `map` applied to each gate's raw word given the gate's index.
+    fn map_words(&self, map: impl Fn(usize, u16) -> u16) -> Self {
+        let mut values = Vec::with_capacity(self.values.len());
+        for index in 0..self.gate_count() {
+            let word = map(index, self.word(index).unwrap_or_default());
+            if self.sixteen_bit {
+                values.extend_from_slice(&word.to_be_bytes());
+            } else {
+                values.push(word.min(u8::MAX as u16) as u8);
+            }
+        }
+
+        Self {
+            values,
+            ..self.clone()
+        }
+    }
+
+    /// The raw word of the gate at the given index, if present.
+    fn word(&self, index: usize) -> Option<u16> {
+        if self.sixteen_bit {
+            let bytes = self.values.get(2 * index..2 * index + 2)?;
+            Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+        } else {
+            self.values.get(index).map(|&word| word as u16)
+        }
+    }
+
+    /// Whether the gate at the given index was marked missing.
+    fn is_missing(&self, index: usize) -> bool {
+        self.missing.get(index).copied().unwrap_or(false)
+    }
+
+    /// Encodes a gate value as a raw fixed-point word, reserving the raw words for below threshold
+    /// and range folded.
+    fn encode(&self, value: f32) -> u16 {
+        let max_word = if self.sixteen_bit {
+            u16::MAX as f32
+        } else {
+            u8::MAX as f32
+        };
+
+        if self.scale == 0.0 {
+            return value.round().clamp(0.0, max_word) as u16;
+        }
+
+        (value * self.scale + self.offset)
+            .round()
+            .clamp(2.0, max_word) as u16
+    }
+
+    /// Decodes a raw fixed-point word.
+    fn decode(&self, word: u16) -> MomentValue {
+        if self.scale == 0.0 {
+            return MomentValue::Value(word as f32);
+        }
+
+        match word {
+            0 => MomentValue::BelowThreshold,
+            1 => MomentValue::RangeFolded,
+            _ => MomentValue::Value((word as f32 - self.offset) / self.scale),
+        }
+    }
 }
 
 impl Debug for MomentData {
@@ -50,6 +296,32 @@ impl Debug for MomentData {
     }
 }
 
+/// Moment data for a product which is not otherwise modeled, identified by its encoded name.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ExtensionMoment {
+    name: String,
+    data: MomentData,
+}
+
+impl ExtensionMoment {
+    /// Create a new extension moment with the given name, e.g. "XYZ".
+    pub fn new(name: String, data: MomentData) -> Self {
+        Self { name, data }
+    }
+
+    /// The moment's encoded name, e.g. "XYZ".
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The moment's data.
+    pub fn data(&self) -> &MomentData {
+        &self.data
+    }
+}
+
 /// The data moment value for a product in a radial's gate. The value may be a floating-point number
 /// or a special case such as "below threshold" or "range folded".
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -60,4 +332,105 @@ pub enum MomentValue {
     BelowThreshold,
     /// The value for this gate exceeded the maximum unambiguous range.
     RangeFolded,
+    /// No value was collected for this gate, such as a gate beyond the end of a moment whose range
+    /// is shorter than another's.
+    Missing,
+}
+
+impl MomentValue {
+    /// This value as a floating-point number, or `NaN` for a special case.
+    pub fn to_f32(&self) -> f32 {
+        match self {
+            MomentValue::Value(value) => *value,
+            _ => f32::NAN,
+        }
+    }
+
+    /// The special case this value represents, or `None` if it was measured.
+    pub fn sentinel(&self) -> Option<Sentinel> {
+        match self {
+            MomentValue::Value(_) => None,
+            MomentValue::BelowThreshold => Some(Sentinel::BelowThreshold),
+            MomentValue::RangeFolded => Some(Sentinel::RangeFolded),
+            MomentValue::Missing => Some(Sentinel::Missing),
+        }
+    }
+}
+
+/// A special case of a gate's value which is not a measurement, identifying why a value converted
+/// to a floating-point number is `NaN`. See [MomentData::to_f32_with_sentinels].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Sentinel {
+    /// The value was below the signal threshold.
+    BelowThreshold,
+    /// The value exceeded the maximum unambiguous range.
+    RangeFolded,
+    /// No value was collected.
+    Missing,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sixteen_bit_values() {
+        let moment = MomentData::from_fixed_point_16(100.0, 0.0, vec![0, 0, 0, 1, 1, 44, 3, 232]);
+
+        assert_eq!(moment.word_size(), 16);
+        assert_eq!(moment.gate_count(), 4);
+        assert_eq!(
+            moment.values(),
+            vec![
+                MomentValue::BelowThreshold,
+                MomentValue::RangeFolded,
+                MomentValue::Value(3.0),
+                MomentValue::Value(10.0),
+            ]
+        );
+
+        let mapped = moment.map_values(|_, value| value * 2.0);
+        assert_eq!(mapped.value(0), Some(MomentValue::BelowThreshold));
+        assert_eq!(mapped.value(2), Some(MomentValue::Value(6.0)));
+        assert_eq!(mapped.value(3), Some(MomentValue::Value(20.0)));
+    }
+
+    #[test]
+    fn test_missing_gates() {
+        let moment = MomentData::from_fixed_point(2.0, 66.0, vec![0, 1, 146, 146])
+            .with_missing_gates([3, 10]);
+
+        assert_eq!(moment.value(2), Some(MomentValue::Value(40.0)));
+        assert_eq!(moment.value(3), Some(MomentValue::Missing));
+        assert_eq!(moment.censor(|_| true).value(3), Some(MomentValue::Missing));
+    }
+
+    #[test]
+    fn test_to_f32_with_sentinels() {
+        let moment =
+            MomentData::from_fixed_point(2.0, 66.0, vec![0, 1, 146, 146]).with_missing_gates([3]);
+        let (values, sentinels) = moment.to_f32_with_sentinels(5);
+
+        assert_eq!(values.len(), 5);
+        assert_eq!(values[2], 40.0);
+        assert!(values
+            .iter()
+            .enumerate()
+            .all(|(index, value)| index == 2 || value.is_nan()));
+        assert_eq!(
+            sentinels,
+            vec![
+                Some(Sentinel::BelowThreshold),
+                Some(Sentinel::RangeFolded),
+                None,
+                Some(Sentinel::Missing),
+                Some(Sentinel::Missing),
+            ]
+        );
+
+        let plain = moment.to_f32(5);
+        assert_eq!(plain[2], 40.0);
+        assert!(plain[4].is_nan());
+    }
 }
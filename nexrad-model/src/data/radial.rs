@@ -1,4 +1,4 @@
-use crate::data::MomentData;
+use crate::data::{ExtensionMoment, MomentData};
 use std::fmt::Debug;
 
 #[cfg(feature = "chrono")]
@@ -16,7 +16,11 @@ use serde::{Deserialize, Serialize};
 /// distance determines the resolution of the ray and the number of gates in the ray.
 #[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Radial {
+    #[cfg_attr(feature = "serde", serde(default))]
+    index: usize,
+
     collection_timestamp: i64,
 
     azimuth_number: u16,
@@ -35,6 +39,33 @@ pub struct Radial {
     differential_phase: Option<MomentData>,
     correlation_coefficient: Option<MomentData>,
     specific_differential_phase: Option<MomentData>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    extension_moments: Vec<ExtensionMoment>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    nyquist_velocity_meters_per_second: Option<f32>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    unambiguous_range_km: Option<f32>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    horizontal_calibration_constant_dbz: Option<f32>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    vertical_calibration_constant_dbz: Option<f32>,
 }
 
 impl Radial {
@@ -56,6 +87,7 @@ impl Radial {
         specific_differential_phase: Option<MomentData>,
     ) -> Self {
         Self {
+            index: 0,
             collection_timestamp,
             azimuth_number,
             azimuth_angle_degrees,
@@ -70,9 +102,58 @@ impl Radial {
             differential_phase,
             correlation_coefficient,
             specific_differential_phase,
+            extension_moments: Vec::new(),
+            nyquist_velocity_meters_per_second: None,
+            unambiguous_range_km: None,
+            horizontal_calibration_constant_dbz: None,
+            vertical_calibration_constant_dbz: None,
         }
     }
 
+    /// Sets this radial's extension moments, which are moments not otherwise modeled.
+    pub fn with_extension_moments(mut self, extension_moments: Vec<ExtensionMoment>) -> Self {
+        self.extension_moments = extension_moments;
+        self
+    }
+
+    /// Sets this radial's Nyquist velocity in m/s and unambiguous range in km.
+    pub fn with_velocity_limits(
+        mut self,
+        nyquist_velocity_meters_per_second: f32,
+        unambiguous_range_km: f32,
+    ) -> Self {
+        self.nyquist_velocity_meters_per_second = Some(nyquist_velocity_meters_per_second);
+        self.unambiguous_range_km = Some(unambiguous_range_km);
+        self
+    }
+
+    /// Sets this radial's horizontal and vertical channel calibration constants in dBZ.
+    pub fn with_calibration_constants(
+        mut self,
+        horizontal_calibration_constant_dbz: f32,
+        vertical_calibration_constant_dbz: f32,
+    ) -> Self {
+        self.horizontal_calibration_constant_dbz = Some(horizontal_calibration_constant_dbz);
+        self.vertical_calibration_constant_dbz = Some(vertical_calibration_constant_dbz);
+        self
+    }
+
+    /// Sets this radial's position in its sweep. Assigned by [Sweep::new](crate::data::Sweep::new).
+    pub(crate) fn with_index(mut self, index: usize) -> Self {
+        self.index = index;
+        self
+    }
+
+    /// This radial's position in its sweep, counting from zero in the order the radials were
+    /// collected. Unlike [Radial::azimuth_number], which is reported by the radar, this is assigned
+    /// when the radial is added to a sweep and always matches its position in [Sweep::radials], so
+    /// it may be used to key data derived from the radial. Zero for a radial not in a sweep.
+    ///
+    /// [Sweep::radials]: crate::data::Sweep::radials
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
     /// The collection timestamp in milliseconds since midnight Jan 1, 1970 (epoch/UNIX timestamp).
     pub fn collection_timestamp(&self) -> i64 {
         self.collection_timestamp
@@ -167,12 +248,115 @@ impl Radial {
     pub fn specific_differential_phase(&self) -> Option<&MomentData> {
         self.specific_differential_phase.as_ref()
     }
+
+    /// The maximum radial velocity which can be measured unambiguously in m/s, if available.
+    pub fn nyquist_velocity_meters_per_second(&self) -> Option<f32> {
+        self.nyquist_velocity_meters_per_second
+    }
+
+    /// The maximum range to which returns can be attributed unambiguously in km, if available.
+    pub fn unambiguous_range_km(&self) -> Option<f32> {
+        self.unambiguous_range_km
+    }
+
+    /// The horizontal channel's calibration constant in dBZ, if available. Legacy radial
+    /// data does not include calibration constants.
+    pub fn horizontal_calibration_constant_dbz(&self) -> Option<f32> {
+        self.horizontal_calibration_constant_dbz
+    }
+
+    /// The vertical channel's calibration constant in dBZ, if available. Legacy radial data
+    /// does not include calibration constants.
+    pub fn vertical_calibration_constant_dbz(&self) -> Option<f32> {
+        self.vertical_calibration_constant_dbz
+    }
+
+    /// Moments for this radial which are not otherwise modeled, such as those introduced by newer
+    /// radar software, in the order they were encoded.
+    pub fn extension_moments(&self) -> &[ExtensionMoment] {
+        &self.extension_moments
+    }
+
+    /// The extension moment with the given name, e.g. "XYZ", if available.
+    pub fn extension_moment(&self, name: &str) -> Option<&MomentData> {
+        self.extension_moments
+            .iter()
+            .find(|moment| moment.name() == name)
+            .map(ExtensionMoment::data)
+    }
+
+    /// This radial's moments, including its extension moments.
+    fn moments(&self) -> impl Iterator<Item = &MomentData> {
+        [
+            &self.reflectivity,
+            &self.velocity,
+            &self.spectrum_width,
+            &self.differential_reflectivity,
+            &self.differential_phase,
+            &self.correlation_coefficient,
+            &self.specific_differential_phase,
+        ]
+        .into_iter()
+        .flatten()
+        .chain(self.extension_moments.iter().map(ExtensionMoment::data))
+    }
+
+    /// The number of gates in this radial's longest moment.
+    pub(crate) fn max_gate_count(&self) -> usize {
+        self.moments()
+            .map(MomentData::gate_count)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// A copy of this radial with the given reflectivity and differential reflectivity moments, for
+    /// corrections which adjust both.
+    pub(crate) fn with_reflectivity_moments(
+        &self,
+        reflectivity: Option<MomentData>,
+        differential_reflectivity: Option<MomentData>,
+    ) -> Self {
+        Self {
+            reflectivity,
+            differential_reflectivity,
+            ..self.clone()
+        }
+    }
+
+    /// A copy of this radial with `map` applied to each of its moments.
+    pub(crate) fn map_moments(&self, map: impl Fn(&MomentData) -> MomentData) -> Self {
+        let mut radial = self.clone();
+        for moment in [
+            &mut radial.reflectivity,
+            &mut radial.velocity,
+            &mut radial.spectrum_width,
+            &mut radial.differential_reflectivity,
+            &mut radial.differential_phase,
+            &mut radial.correlation_coefficient,
+            &mut radial.specific_differential_phase,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            *moment = map(moment);
+        }
+
+        for extension_moment in &mut radial.extension_moments {
+            *extension_moment = ExtensionMoment::new(
+                extension_moment.name().to_string(),
+                map(extension_moment.data()),
+            );
+        }
+
+        radial
+    }
 }
 
 impl Debug for Radial {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut debug = f.debug_struct("Radial");
 
+        debug.field("index", &self.index());
         debug.field("collection_timestamp", &self.collection_timestamp());
 
         #[cfg(feature = "chrono")]
@@ -219,6 +403,25 @@ impl Debug for Radial {
             &self.specific_differential_phase(),
         );
 
+        debug.field("extension_moments", &self.extension_moments());
+
+        debug.field(
+            "nyquist_velocity_meters_per_second",
+            &self.nyquist_velocity_meters_per_second(),
+        );
+
+        debug.field("unambiguous_range_km", &self.unambiguous_range_km());
+
+        debug.field(
+            "horizontal_calibration_constant_dbz",
+            &self.horizontal_calibration_constant_dbz(),
+        );
+
+        debug.field(
+            "vertical_calibration_constant_dbz",
+            &self.vertical_calibration_constant_dbz(),
+        );
+
         debug.finish()
     }
 }
@@ -226,6 +429,7 @@ impl Debug for Radial {
 /// Describe a radial's position within the sequence of radials comprising a scan.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum RadialStatus {
     ElevationStart,
     IntermediateRadialData,
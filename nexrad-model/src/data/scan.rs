@@ -1,5 +1,13 @@
-use crate::data::Sweep;
+use crate::data::lookup::{build_angle_index, nearest_elevation, AngleIndex};
+use crate::data::{LookupTolerance, Radial, Sweep};
+use crate::provenance::Provenance;
 use std::fmt::Debug;
+use std::sync::OnceLock;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, TimeDelta, Utc};
+#[cfg(feature = "chrono")]
+use std::ops::RangeInclusive;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -8,31 +16,118 @@ use serde::{Deserialize, Serialize};
 /// is composed of multiple sweeps at different elevations. The pattern of sweeps, including
 /// elevations and resolution, is determined by the scanning strategy of the radar. This is
 /// referred to as the Volume Coverage Pattern.
-#[derive(Clone, PartialEq)]
+///
+/// A scan's sweeps are kept in the order they were collected, as given when it was created, and
+/// are never reordered. Each sweep's [Sweep::index] is its position in the scan.
+#[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Scan {
     coverage_pattern_number: u16,
     sweeps: Vec<Sweep>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    provenance: Option<Provenance>,
+
+    /// The sweeps' mean elevation angles, built on first lookup.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    elevation_index: OnceLock<AngleIndex>,
 }
 
 impl Scan {
-    /// Create a new radar scan with the given coverage pattern number and sweeps.
+    /// Create a new radar scan with the given coverage pattern number and sweeps, which should be in
+    /// the order they were collected. Each sweep's index is set to its position in the scan.
     pub fn new(coverage_pattern_number: u16, sweeps: Vec<Sweep>) -> Self {
         Self {
             coverage_pattern_number,
-            sweeps,
+            sweeps: sweeps
+                .into_iter()
+                .enumerate()
+                .map(|(index, sweep)| sweep.with_index(index))
+                .collect(),
+            provenance: None,
+            elevation_index: OnceLock::new(),
         }
     }
 
+    /// Sets where this scan's data came from and how it has been processed.
+    pub fn with_provenance(mut self, provenance: Provenance) -> Self {
+        self.provenance = Some(provenance);
+        self
+    }
+
     /// This scan's volume coverage pattern number.
     pub fn coverage_pattern_number(&self) -> u16 {
         self.coverage_pattern_number
     }
 
-    /// The elevation sweeps comprising this scan.
+    /// The elevation sweeps comprising this scan, in the order they were collected.
     pub fn sweeps(&self) -> &Vec<Sweep> {
         self.sweeps.as_ref()
     }
+
+    /// Where this scan's data came from and how it has been processed, if known.
+    pub fn provenance(&self) -> Option<&Provenance> {
+        self.provenance.as_ref()
+    }
+
+    /// The earliest and latest collection times of this scan's radials across all of its sweeps,
+    /// or `None` if it has no radials or their timestamps are out of range.
+    #[cfg(feature = "chrono")]
+    pub fn time_range(&self) -> Option<RangeInclusive<DateTime<Utc>>> {
+        crate::data::sweep::collection_time_range(
+            self.sweeps.iter().flat_map(|sweep| sweep.radials()),
+        )
+    }
+
+    /// The time elapsed between the collection of this scan's earliest and latest radials, or
+    /// `None` if it has no radials.
+    #[cfg(feature = "chrono")]
+    pub fn duration(&self) -> Option<TimeDelta> {
+        self.time_range()
+            .map(|time_range| *time_range.end() - *time_range.start())
+    }
+
+    /// The sweep whose mean elevation angle is nearest the given elevation in degrees, within the
+    /// tolerance's elevation. Where several sweeps share an elevation, such as split cuts, the
+    /// earliest is returned. Lookups are by binary search of an index built on the first lookup.
+    pub fn sweep_at(&self, elevation_degrees: f32, tolerance: &LookupTolerance) -> Option<&Sweep> {
+        let index = self.elevation_index.get_or_init(|| {
+            build_angle_index(
+                self.sweeps
+                    .iter()
+                    .map(|sweep| sweep.mean_elevation_angle_degrees().unwrap_or(f32::NAN)),
+            )
+        });
+
+        let (position, distance) = nearest_elevation(index, elevation_degrees)?;
+        (distance <= tolerance.elevation_degrees())
+            .then(|| self.sweeps.get(position))
+            .flatten()
+    }
+
+    /// The radial nearest the given elevation and azimuth in degrees. See [Scan::sweep_at] and
+    /// [Sweep::radial_at].
+    pub fn radial_at(
+        &self,
+        elevation_degrees: f32,
+        azimuth_degrees: f32,
+        tolerance: &LookupTolerance,
+    ) -> Option<&Radial> {
+        self.sweep_at(elevation_degrees, tolerance)?
+            .radial_at(azimuth_degrees, tolerance)
+    }
+}
+
+/// Scans are compared by their data, so the same data retrieved or decoded separately is equal
+/// regardless of provenance.
+impl PartialEq for Scan {
+    fn eq(&self, other: &Self) -> bool {
+        self.coverage_pattern_number == other.coverage_pattern_number && self.sweeps == other.sweeps
+    }
 }
 
 impl Debug for Scan {
@@ -40,6 +135,103 @@ impl Debug for Scan {
         f.debug_struct("Scan")
             .field("coverage_pattern_number", &self.coverage_pattern_number())
             .field("sweeps", &self.sweeps())
+            .field("provenance", &self.provenance())
             .finish()
     }
 }
+
+#[cfg(all(test, feature = "chrono"))]
+mod tests {
+    use super::*;
+    use crate::data::{AttenuationCorrection, GateMask, RadialStatus};
+
+    fn sweep(elevation_number: u8, start_timestamp: i64) -> Sweep {
+        let radials = (0..4)
+            .map(|azimuth_number| {
+                Radial::new(
+                    start_timestamp + azimuth_number as i64 * 50,
+                    azimuth_number + 1,
+                    azimuth_number as f32 * 90.0,
+                    90.0,
+                    RadialStatus::IntermediateRadialData,
+                    elevation_number,
+                    0.5,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+            })
+            .collect();
+
+        Sweep::new(elevation_number, radials)
+    }
+
+    #[test]
+    fn test_time_range() {
+        let scan = Scan::new(212, vec![sweep(1, 1_000), sweep(2, 20_000)]);
+
+        assert_eq!(
+            scan.sweeps()[0].duration(),
+            Some(TimeDelta::milliseconds(150))
+        );
+        assert_eq!(
+            scan.time_range(),
+            DateTime::from_timestamp_millis(1_000)
+                .zip(DateTime::from_timestamp_millis(20_150))
+                .map(|(start, end)| start..=end)
+        );
+        assert_eq!(scan.duration(), Some(TimeDelta::milliseconds(19_150)));
+
+        assert_eq!(Scan::new(212, Vec::new()).time_range(), None);
+    }
+
+    #[test]
+    fn test_indices() -> crate::result::Result<()> {
+        let scan = Scan::new(212, vec![sweep(1, 1_000), sweep(2, 20_000)]);
+        for (position, sweep) in scan.sweeps().iter().enumerate() {
+            assert_eq!(sweep.index(), position);
+            assert!(sweep
+                .radials()
+                .iter()
+                .enumerate()
+                .all(|(position, radial)| radial.index() == position));
+        }
+
+        let radials = scan
+            .sweeps()
+            .iter()
+            .flat_map(|sweep| sweep.radials().clone())
+            .collect();
+        assert_eq!(&Sweep::from_radials(radials), scan.sweeps());
+
+        // Merging keeps the radials in the order given rather than sorting them by azimuth
+        let radials = scan.sweeps()[1].radials();
+        let merged =
+            Sweep::new(2, radials[2..].to_vec()).merge(Sweep::new(2, radials[..2].to_vec()))?;
+        assert_eq!(
+            merged
+                .radials()
+                .iter()
+                .map(|radial| (radial.index(), radial.azimuth_number()))
+                .collect::<Vec<_>>(),
+            vec![(0, 3), (1, 4), (2, 1), (3, 2)]
+        );
+
+        // Sweeps derived from a scan's sweep keep its position in the scan
+        let sweep = &scan.sweeps()[1];
+        let mask = GateMask::new(vec![Vec::new(); sweep.radials().len()]);
+        assert_eq!(sweep.censor(&mask).index(), 1);
+        assert_eq!(
+            sweep
+                .correct_attenuation(&AttenuationCorrection::new())
+                .index(),
+            1
+        );
+
+        Ok(())
+    }
+}
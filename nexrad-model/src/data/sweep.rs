@@ -1,6 +1,13 @@
-use crate::data::Radial;
+use crate::data::lookup::{build_angle_index, nearest_azimuth, AngleIndex};
+use crate::data::{ElevationCut, LookupTolerance, MomentData, MomentValue, Radial};
 use crate::result::{Error, Result};
 use std::fmt::{Debug, Display};
+use std::sync::OnceLock;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, TimeDelta, Utc};
+#[cfg(feature = "chrono")]
+use std::ops::RangeInclusive;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -10,23 +17,62 @@ use serde::{Deserialize, Serialize};
 /// spectrum width) for each azimuth angle in that sweep. The resolution of the sweep dictates the
 /// azimuthal distance between rays and thus and number of rays in the sweep. Multiple sweeps are
 /// taken at different elevation angles to create a volume scan.
-#[derive(Clone, PartialEq)]
+///
+/// A sweep's radials are kept in the order they were collected, as given when it was created, and
+/// are never reordered. Each radial's [Radial::index] is its position in the sweep.
+#[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Sweep {
+    #[cfg_attr(feature = "serde", serde(default))]
+    index: usize,
+
     elevation_number: u8,
     radials: Vec<Radial>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    cut: Option<ElevationCut>,
+
+    /// The radials' azimuths, built on first lookup.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    azimuth_index: OnceLock<AngleIndex>,
 }
 
 impl Sweep {
-    /// Create a new radar sweep with the given elevation number and radials.
+    /// Create a new radar sweep with the given elevation number and radials, which should be in the
+    /// order they were collected. Each radial's index is set to its position in the sweep.
     pub fn new(elevation_number: u8, radials: Vec<Radial>) -> Self {
         Self {
+            index: 0,
             elevation_number,
-            radials,
+            radials: radials
+                .into_iter()
+                .enumerate()
+                .map(|(index, radial)| radial.with_index(index))
+                .collect(),
+            cut: None,
+            azimuth_index: OnceLock::new(),
         }
     }
 
-    /// Create a new radar sweep from a list of radials by splitting them by elevation.
+    /// Sets this sweep's elevation cut as defined by the volume coverage pattern, which identifies
+    /// its waveform and whether it is part of a split cut or revisits an earlier cut.
+    pub fn with_cut(mut self, cut: ElevationCut) -> Self {
+        self.cut = Some(cut);
+        self
+    }
+
+    /// Sets this sweep's position in its scan. Assigned by [Scan::new](crate::data::Scan::new).
+    pub(crate) fn with_index(mut self, index: usize) -> Self {
+        self.index = index;
+        self
+    }
+
+    /// Create a new radar sweep from a list of radials by splitting them by elevation. Sweeps are
+    /// returned in the order their first radials appear, and each keeps its radials' order.
     pub fn from_radials(radials: Vec<Radial>) -> Vec<Self> {
         let mut sweeps = Vec::new();
 
@@ -45,22 +91,62 @@ impl Sweep {
             sweep_radials.push(radial);
         }
 
+        if let Some(elevation_number) = sweep_elevation_number {
+            sweeps.push(Sweep::new(elevation_number, sweep_radials));
+        }
+
         sweeps
     }
 
+    /// This sweep's position in its scan, counting from zero in the order the sweeps were collected.
+    /// Unlike [Sweep::elevation_number], which identifies the sweep's cut in the coverage pattern,
+    /// this is assigned when the sweep is added to a scan and always matches its position in
+    /// [Scan::sweeps], so it may be used to key data derived from the sweep. Zero for a sweep not
+    /// in a scan.
+    ///
+    /// [Scan::sweeps]: crate::data::Scan::sweeps
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
     /// The index number for this radial's elevation in the volume scan. The precise elevation angle
     /// varies and can be found in individual radials.
     pub fn elevation_number(&self) -> u8 {
         self.elevation_number
     }
 
-    /// The radials comprising this sweep.
+    /// This sweep's elevation cut as defined by the volume coverage pattern, if known.
+    pub fn cut(&self) -> Option<&ElevationCut> {
+        self.cut.as_ref()
+    }
+
+    /// The radials comprising this sweep, in the order they were collected.
     pub fn radials(&self) -> &Vec<Radial> {
         self.radials.as_ref()
     }
 
+    /// The earliest and latest collection times of this sweep's radials, or `None` if it has no
+    /// radials or their timestamps are out of range.
+    #[cfg(feature = "chrono")]
+    pub fn time_range(&self) -> Option<RangeInclusive<DateTime<Utc>>> {
+        collection_time_range(&self.radials)
+    }
+
+    /// The time elapsed between the collection of this sweep's earliest and latest radials, or
+    /// `None` if it has no radials.
+    #[cfg(feature = "chrono")]
+    pub fn duration(&self) -> Option<TimeDelta> {
+        self.time_range()
+            .map(|time_range| *time_range.end() - *time_range.start())
+    }
+
     /// Merges this sweep with another sweep, combining their radials into a single sweep. The
     /// sweeps must be at the same elevation, and they should not have duplicate azimuth radials.
+    /// The merged sweep's radials are this sweep's followed by the other's, each re-indexed by its
+    /// position, so merging the parts of a sweep in the order they were collected keeps its radials
+    /// in collection order. Radials are not sorted by azimuth; to visit them in azimuth order, sort
+    /// a copy of [Sweep::radials] by [Radial::azimuth_angle_degrees]. The merged sweep keeps this
+    /// sweep's index and elevation cut, or the other's cut if this has none.
     pub fn merge(self, other: Self) -> Result<Self> {
         if self.elevation_number != other.elevation_number {
             return Err(Error::ElevationMismatchError);
@@ -68,13 +154,81 @@ impl Sweep {
 
         let mut radials = self.radials;
         radials.extend(other.radials);
-        radials.sort_by_key(|radial| radial.azimuth_number());
 
         Ok(Self {
-            elevation_number: self.elevation_number,
-            radials,
+            index: self.index,
+            cut: self.cut.or(other.cut),
+            ..Self::new(self.elevation_number, radials)
         })
     }
+
+    /// The mean elevation angle of this sweep's radials in degrees, or `None` if it has none.
+    pub(crate) fn mean_elevation_angle_degrees(&self) -> Option<f32> {
+        let sum: f32 = self
+            .radials
+            .iter()
+            .map(Radial::elevation_angle_degrees)
+            .sum();
+        (!self.radials.is_empty()).then(|| sum / self.radials.len() as f32)
+    }
+
+    /// The radial nearest the given azimuth in degrees. The radial must be within the tolerance's
+    /// azimuth of it, by default half of the radial's azimuth spacing. Lookups are by binary search
+    /// of an index built on the first lookup.
+    pub fn radial_at(&self, azimuth_degrees: f32, tolerance: &LookupTolerance) -> Option<&Radial> {
+        let index = self.azimuth_index.get_or_init(|| {
+            build_angle_index(self.radials.iter().map(Radial::azimuth_angle_degrees))
+        });
+
+        let (position, distance) = nearest_azimuth(index, azimuth_degrees)?;
+        let radial = self.radials.get(position)?;
+        let max_distance = tolerance
+            .azimuth_degrees()
+            .unwrap_or(radial.azimuth_spacing_degrees() / 2.0);
+
+        (distance <= max_distance).then_some(radial)
+    }
+
+    /// The value of the gate nearest the given azimuth in degrees and range in kilometers for the
+    /// moment selected by `moment`, e.g. [Radial::reflectivity]. Returns `None` if no radial or gate
+    /// is within the tolerance or the radial lacks the moment or its gate ranges.
+    pub fn gate_at<'a>(
+        &'a self,
+        azimuth_degrees: f32,
+        range_km: f32,
+        moment: impl FnOnce(&'a Radial) -> Option<&'a MomentData>,
+        tolerance: &LookupTolerance,
+    ) -> Option<MomentValue> {
+        let moment = moment(self.radial_at(azimuth_degrees, tolerance)?)?;
+        moment.value(moment.gate_index(range_km, tolerance)?)
+    }
+}
+
+/// The earliest and latest collection times of the given radials.
+#[cfg(feature = "chrono")]
+pub(crate) fn collection_time_range<'a>(
+    radials: impl IntoIterator<Item = &'a Radial>,
+) -> Option<RangeInclusive<DateTime<Utc>>> {
+    let (earliest, latest) =
+        radials
+            .into_iter()
+            .map(Radial::collection_timestamp)
+            .fold(None, |range, timestamp| match range {
+                Some((earliest, latest)) => Some((timestamp.min(earliest), timestamp.max(latest))),
+                None => Some((timestamp, timestamp)),
+            })?;
+
+    Some(DateTime::from_timestamp_millis(earliest)?..=DateTime::from_timestamp_millis(latest)?)
+}
+
+/// Sweeps are compared by their data, so a sweep decoded on its own is equal to the same sweep in a
+/// scan regardless of its index.
+impl PartialEq for Sweep {
+    fn eq(&self, other: &Self) -> bool {
+        self.elevation_number == other.elevation_number
+            && self.cut == other.cut
+            && self.radials == other.radials
+    }
 }
 
 impl Display for Sweep {
@@ -97,7 +251,9 @@ impl Display for Sweep {
 impl Debug for Sweep {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Sweep")
+            .field("index", &self.index())
             .field("elevation_number", &self.elevation_number())
+            .field("cut", &self.cut())
             .field("radials", &self.radials())
             .finish()
     }
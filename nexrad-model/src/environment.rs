@@ -0,0 +1,245 @@
+//!
+//! This module contains models supplying the environmental conditions that some radar algorithms
+//! depend on, such as the height of the freezing level or of a given isotherm when estimating hail
+//! size or classifying hydrometeors.
+//!
+//! Conditions are supplied through the [Environment] trait so that algorithms accept a typed
+//! environment rather than assuming fixed values. A [ConstantEnvironment] describes the same
+//! conditions everywhere, while a [TemperatureProfile] interpolates a sounding or model profile.
+//!
+
+use crate::geo::GeoPoint;
+use crate::result::{Error, Result};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The standard atmosphere's temperature lapse rate in the troposphere, in degrees Celsius per
+/// meter.
+pub const STANDARD_LAPSE_RATE_CELSIUS_PER_METER: f64 = 0.0065;
+
+/// Supplies the environmental conditions at a location, for example from a nearby sounding or a
+/// numerical weather model's analysis valid at the time of a scan.
+pub trait Environment {
+    /// The air temperature in degrees Celsius at the given height in meters above sea level over
+    /// the given location, if known.
+    fn temperature_celsius(&self, location: &GeoPoint, height_meters: f64) -> Option<f64>;
+
+    /// The lowest height in meters above sea level over the given location at which the air is at
+    /// the given temperature in degrees Celsius, if known.
+    fn isotherm_height_meters(&self, location: &GeoPoint, temperature_celsius: f64) -> Option<f64>;
+
+    /// The lowest height in meters above sea level over the given location at which the air is at
+    /// zero degrees Celsius, if known.
+    fn freezing_level_meters(&self, location: &GeoPoint) -> Option<f64> {
+        self.isotherm_height_meters(location, 0.0)
+    }
+}
+
+/// An environment with the same conditions at every location, with temperature decreasing linearly
+/// with height from the freezing level.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ConstantEnvironment {
+    freezing_level_meters: f64,
+    lapse_rate_celsius_per_meter: f64,
+}
+
+impl ConstantEnvironment {
+    /// Create a new constant environment with the given freezing level in meters above sea level
+    /// and the standard atmosphere's lapse rate.
+    pub fn new(freezing_level_meters: f64) -> Self {
+        Self::with_lapse_rate(freezing_level_meters, STANDARD_LAPSE_RATE_CELSIUS_PER_METER)
+    }
+
+    /// Create a new constant environment with the given freezing level in meters above sea level
+    /// and temperature lapse rate in degrees Celsius per meter.
+    pub fn with_lapse_rate(freezing_level_meters: f64, lapse_rate_celsius_per_meter: f64) -> Self {
+        Self {
+            freezing_level_meters,
+            lapse_rate_celsius_per_meter,
+        }
+    }
+
+    /// The temperature lapse rate in degrees Celsius per meter.
+    pub fn lapse_rate_celsius_per_meter(&self) -> f64 {
+        self.lapse_rate_celsius_per_meter
+    }
+}
+
+impl Environment for ConstantEnvironment {
+    fn temperature_celsius(&self, _location: &GeoPoint, height_meters: f64) -> Option<f64> {
+        Some((self.freezing_level_meters - height_meters) * self.lapse_rate_celsius_per_meter)
+    }
+
+    fn isotherm_height_meters(
+        &self,
+        _location: &GeoPoint,
+        temperature_celsius: f64,
+    ) -> Option<f64> {
+        if self.lapse_rate_celsius_per_meter == 0.0 {
+            return None;
+        }
+
+        Some(self.freezing_level_meters - temperature_celsius / self.lapse_rate_celsius_per_meter)
+    }
+
+    fn freezing_level_meters(&self, _location: &GeoPoint) -> Option<f64> {
+        Some(self.freezing_level_meters)
+    }
+}
+
+/// A single level of a temperature profile.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TemperatureLevel {
+    /// The level's height above sea level in meters.
+    pub height_meters: f64,
+
+    /// The air temperature at this level in degrees Celsius.
+    pub temperature_celsius: f64,
+}
+
+/// A horizontally uniform environment described by a vertical temperature profile, such as one
+/// taken from a nearby sounding. Temperature is interpolated linearly between levels and is
+/// unknown above or below the profile.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TemperatureProfile {
+    levels: Vec<TemperatureLevel>,
+}
+
+impl TemperatureProfile {
+    /// Create a new temperature profile from the given levels. At least two finite levels with
+    /// distinct heights are required.
+    pub fn new(mut levels: Vec<TemperatureLevel>) -> Result<Self> {
+        levels.sort_by(|a, b| a.height_meters.total_cmp(&b.height_meters));
+        levels.dedup_by(|a, b| a.height_meters == b.height_meters);
+
+        let all_finite = levels
+            .iter()
+            .all(|level| level.height_meters.is_finite() && level.temperature_celsius.is_finite());
+
+        if levels.len() < 2 || !all_finite {
+            return Err(Error::InvalidTemperatureProfile);
+        }
+
+        Ok(Self { levels })
+    }
+
+    /// The profile's levels in order of increasing height.
+    pub fn levels(&self) -> &[TemperatureLevel] {
+        &self.levels
+    }
+}
+
+impl Environment for TemperatureProfile {
+    fn temperature_celsius(&self, _location: &GeoPoint, height_meters: f64) -> Option<f64> {
+        self.levels.windows(2).find_map(|pair| {
+            let (lower, upper) = (&pair[0], &pair[1]);
+            if !(lower.height_meters..=upper.height_meters).contains(&height_meters) {
+                return None;
+            }
+
+            let fraction =
+                (height_meters - lower.height_meters) / (upper.height_meters - lower.height_meters);
+            Some(
+                lower.temperature_celsius
+                    + fraction * (upper.temperature_celsius - lower.temperature_celsius),
+            )
+        })
+    }
+
+    fn isotherm_height_meters(
+        &self,
+        _location: &GeoPoint,
+        temperature_celsius: f64,
+    ) -> Option<f64> {
+        self.levels.windows(2).find_map(|pair| {
+            let (lower, upper) = (&pair[0], &pair[1]);
+            let (low_temperature, high_temperature) = (
+                lower.temperature_celsius.min(upper.temperature_celsius),
+                lower.temperature_celsius.max(upper.temperature_celsius),
+            );
+            if !(low_temperature..=high_temperature).contains(&temperature_celsius) {
+                return None;
+            }
+
+            if lower.temperature_celsius == upper.temperature_celsius {
+                return Some(lower.height_meters);
+            }
+
+            let fraction = (temperature_celsius - lower.temperature_celsius)
+                / (upper.temperature_celsius - lower.temperature_celsius);
+            Some(lower.height_meters + fraction * (upper.height_meters - lower.height_meters))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location() -> GeoPoint {
+        GeoPoint::new(35.3331, -97.2778)
+    }
+
+    #[test]
+    fn test_constant_environment() {
+        let environment = ConstantEnvironment::new(4_000.0);
+
+        assert_eq!(
+            environment.freezing_level_meters(&location()),
+            Some(4_000.0)
+        );
+        assert_eq!(
+            environment.temperature_celsius(&location(), 4_000.0),
+            Some(0.0)
+        );
+
+        let minus_twenty = environment.isotherm_height_meters(&location(), -20.0);
+        assert!(minus_twenty.is_some_and(|height| (height - 7_076.9).abs() < 0.1));
+    }
+
+    #[test]
+    fn test_temperature_profile() -> Result<()> {
+        let profile = TemperatureProfile::new(vec![
+            TemperatureLevel {
+                height_meters: 5_000.0,
+                temperature_celsius: -10.0,
+            },
+            TemperatureLevel {
+                height_meters: 0.0,
+                temperature_celsius: 20.0,
+            },
+            // A low-level inversion
+            TemperatureLevel {
+                height_meters: 1_000.0,
+                temperature_celsius: 22.0,
+            },
+        ])?;
+
+        assert_eq!(profile.temperature_celsius(&location(), 500.0), Some(21.0));
+        assert_eq!(profile.temperature_celsius(&location(), 6_000.0), None);
+
+        // The inversion's 21 degree level is found before the one above it
+        assert_eq!(
+            profile.isotherm_height_meters(&location(), 21.0),
+            Some(500.0)
+        );
+        assert_eq!(profile.freezing_level_meters(&location()), Some(3_750.0));
+        assert_eq!(profile.isotherm_height_meters(&location(), -20.0), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_profile() {
+        let single_level = vec![TemperatureLevel {
+            height_meters: 0.0,
+            temperature_celsius: 20.0,
+        }];
+
+        assert!(TemperatureProfile::new(single_level).is_err());
+    }
+}
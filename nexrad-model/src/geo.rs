@@ -0,0 +1,31 @@
+//!
+//! This module contains models and functions for locating radar data in space. A radar beam does
+//! not travel in a straight line through the atmosphere, but is instead refracted by changes in the
+//! atmosphere's refractive index with height. How that refraction is modeled determines where a
+//! given gate is located relative to the earth's surface.
+//!
+//! The assumption is made explicit through the [BeamPropagationModel] trait so that callers may
+//! choose the standard 4/3 effective earth radius model or supply their own refractivity profile.
+//!
+//! Great-circle and ellipsoidal distances, bearings, and destinations between [GeoPoint]s are used
+//! throughout for locating sites and data.
+//!
+//! Radar sites' coverage areas may be approximated as [CoverageFootprint] polygons and exported as
+//! GeoJSON for mapping.
+//!
+//! A beam's vertical extent at each gate is described by [BeamGeometry], which flags where the beam
+//! partially fills or overshoots shallow precipitation so that range-dependent biases may be
+//! corrected or masked.
+//!
+
+mod propagation;
+pub use propagation::*;
+
+mod geodesy;
+pub use geodesy::*;
+
+mod coverage;
+pub use coverage::*;
+
+mod beam;
+pub use beam::*;
@@ -0,0 +1,259 @@
+use crate::data::{GateMask, MomentData, Radial, Sweep};
+use crate::geo::BeamPropagationModel;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The WSR-88D antenna's half-power beam width in degrees.
+pub const NEXRAD_BEAM_WIDTH_DEGREES: f64 = 0.95;
+
+/// The extent of a radar beam at a gate, from which range-dependent biases such as partial beam
+/// filling may be estimated. The beam is treated as a cone of the given width whose upper and lower
+/// edges propagate as the beam's center does. Heights are in meters above the radar antenna.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BeamGeometry {
+    range_meters: f64,
+    center_height_meters: f64,
+    bottom_height_meters: f64,
+    top_height_meters: f64,
+    width_meters: f64,
+}
+
+impl BeamGeometry {
+    /// The extent of a beam of the given width in degrees at the given slant range in meters and
+    /// antenna elevation angle in degrees.
+    pub fn new(
+        model: &impl BeamPropagationModel,
+        range_meters: f64,
+        elevation_angle_degrees: f64,
+        beam_width_degrees: f64,
+    ) -> Self {
+        let half_width_degrees = beam_width_degrees / 2.0;
+
+        Self {
+            range_meters,
+            center_height_meters: model.beam_height_meters(range_meters, elevation_angle_degrees),
+            bottom_height_meters: model
+                .beam_height_meters(range_meters, elevation_angle_degrees - half_width_degrees),
+            top_height_meters: model
+                .beam_height_meters(range_meters, elevation_angle_degrees + half_width_degrees),
+            width_meters: 2.0 * range_meters * half_width_degrees.to_radians().tan(),
+        }
+    }
+
+    /// The slant range to the gate in meters.
+    pub fn range_meters(&self) -> f64 {
+        self.range_meters
+    }
+
+    /// The height of the beam's center in meters above the antenna.
+    pub fn center_height_meters(&self) -> f64 {
+        self.center_height_meters
+    }
+
+    /// The height of the beam's lower edge in meters above the antenna.
+    pub fn bottom_height_meters(&self) -> f64 {
+        self.bottom_height_meters
+    }
+
+    /// The height of the beam's upper edge in meters above the antenna.
+    pub fn top_height_meters(&self) -> f64 {
+        self.top_height_meters
+    }
+
+    /// The vertical distance in meters between the beam's lower and upper edges.
+    pub fn height_span_meters(&self) -> f64 {
+        self.top_height_meters - self.bottom_height_meters
+    }
+
+    /// The beam's width in meters across its axis, which is also its azimuthal extent.
+    pub fn width_meters(&self) -> f64 {
+        self.width_meters
+    }
+
+    /// The fraction of the beam's vertical span below the given precipitation top in meters above
+    /// the antenna, from zero for a beam entirely above it to one for a beam entirely below it.
+    pub fn filled_fraction(&self, precipitation_top_meters: f64) -> f64 {
+        let span_meters = self.height_span_meters();
+        if span_meters <= 0.0 {
+            return if self.center_height_meters <= precipitation_top_meters {
+                1.0
+            } else {
+                0.0
+            };
+        }
+
+        ((precipitation_top_meters - self.bottom_height_meters) / span_meters).clamp(0.0, 1.0)
+    }
+
+    /// How the beam is filled by precipitation with the given top in meters above the antenna.
+    pub fn filling(&self, precipitation_top_meters: f64) -> BeamFilling {
+        let fraction = self.filled_fraction(precipitation_top_meters);
+        if fraction >= 1.0 {
+            BeamFilling::Full
+        } else if fraction > 0.0 {
+            BeamFilling::Partial
+        } else {
+            BeamFilling::Overshooting
+        }
+    }
+}
+
+/// How a beam is filled by precipitation below a given top, indicating the range-dependent bias of
+/// its measurements. See [BeamGeometry::filling].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BeamFilling {
+    /// The beam lies entirely below the precipitation top.
+    Full,
+    /// The beam's upper portion extends above the precipitation top, so its measurements are
+    /// biased low by partial beam filling.
+    Partial,
+    /// The beam lies entirely above the precipitation top, so it likely overshoots precipitation
+    /// which is present below it.
+    Overshooting,
+}
+
+impl Radial {
+    /// The beam's extent at each gate of the moment selected by `moment`, e.g.
+    /// [Radial::reflectivity], for a beam of the given width in degrees at this radial's elevation
+    /// angle. Returns `None` if the radial lacks the moment or its gate ranges are not known.
+    pub fn beam_geometry<'a>(
+        &'a self,
+        moment: impl FnOnce(&'a Radial) -> Option<&'a MomentData>,
+        model: &impl BeamPropagationModel,
+        beam_width_degrees: f64,
+    ) -> Option<Vec<BeamGeometry>> {
+        let moment = moment(self)?;
+        let first_gate_range_km = moment.first_gate_range_km()? as f64;
+        let gate_interval_km = moment.gate_interval_km()? as f64;
+
+        Some(
+            (0..moment.gate_count())
+                .map(|index| {
+                    let range_km = first_gate_range_km + index as f64 * gate_interval_km;
+                    BeamGeometry::new(
+                        model,
+                        range_km * 1000.0,
+                        self.elevation_angle_degrees() as f64,
+                        beam_width_degrees,
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+impl Sweep {
+    /// A mask selecting the gates of the moment selected by `moment` where a beam of the given
+    /// width in degrees lies entirely above the given precipitation top in meters above the
+    /// antenna, for example to exclude far ranges which overshoot shallow precipitation from
+    /// precipitation estimates. Gates of radials lacking the moment or its gate ranges are not
+    /// selected.
+    pub fn overshooting_mask(
+        &self,
+        moment: impl Fn(&Radial) -> Option<&MomentData>,
+        model: &impl BeamPropagationModel,
+        beam_width_degrees: f64,
+        precipitation_top_meters: f64,
+    ) -> GateMask {
+        self.mask_where(|gate| {
+            let radial = gate.radial();
+            moment(radial)
+                .filter(|data| gate.index() < data.gate_count())
+                .and_then(|data| {
+                    let range_km = data.first_gate_range_km()? as f64
+                        + gate.index() as f64 * data.gate_interval_km()? as f64;
+                    Some(BeamGeometry::new(
+                        model,
+                        range_km * 1000.0,
+                        radial.elevation_angle_degrees() as f64,
+                        beam_width_degrees,
+                    ))
+                })
+                .is_some_and(|beam| {
+                    beam.filling(precipitation_top_meters) == BeamFilling::Overshooting
+                })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::RadialStatus;
+    use crate::geo::EffectiveEarthRadiusModel;
+
+    fn radial() -> Radial {
+        let reflectivity =
+            MomentData::from_fixed_point(2.0, 66.0, vec![100; 4]).with_gate_range(50.0, 50.0);
+
+        Radial::new(
+            0,
+            1,
+            0.0,
+            1.0,
+            RadialStatus::IntermediateRadialData,
+            1,
+            0.5,
+            Some(reflectivity),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_beam_geometry() {
+        let model = EffectiveEarthRadiusModel::four_thirds();
+        let beam = BeamGeometry::new(&model, 100_000.0, 0.5, NEXRAD_BEAM_WIDTH_DEGREES);
+
+        // At 100 km the beam is ~1.66 km wide, with its center ~1.46 km above the antenna
+        assert!((beam.width_meters() - 1658.0).abs() < 1.0);
+        assert!((beam.center_height_meters() - 1462.0).abs() < 5.0);
+        assert!((beam.height_span_meters() - beam.width_meters()).abs() < 10.0);
+        assert!(beam.bottom_height_meters() < beam.center_height_meters());
+
+        let center = beam.center_height_meters();
+        assert!((beam.filled_fraction(center) - 0.5).abs() < 0.01);
+        assert_eq!(beam.filling(center), BeamFilling::Partial);
+        assert_eq!(beam.filling(3000.0), BeamFilling::Full);
+        assert_eq!(beam.filling(500.0), BeamFilling::Overshooting);
+    }
+
+    #[test]
+    fn test_radial_beam_geometry() {
+        let model = EffectiveEarthRadiusModel::four_thirds();
+        let beams = radial()
+            .beam_geometry(Radial::reflectivity, &model, NEXRAD_BEAM_WIDTH_DEGREES)
+            .unwrap_or_default();
+
+        assert_eq!(beams.len(), 4);
+        assert_eq!(beams[1].range_meters(), 100_000.0);
+        assert!(beams
+            .windows(2)
+            .all(|pair| pair[0].bottom_height_meters() < pair[1].bottom_height_meters()));
+
+        assert!(radial()
+            .beam_geometry(Radial::velocity, &model, NEXRAD_BEAM_WIDTH_DEGREES)
+            .is_none());
+    }
+
+    #[test]
+    fn test_overshooting_mask() {
+        let sweep = Sweep::new(1, vec![radial()]);
+        let mask = sweep.overshooting_mask(
+            Radial::reflectivity,
+            &EffectiveEarthRadiusModel::four_thirds(),
+            NEXRAD_BEAM_WIDTH_DEGREES,
+            2000.0,
+        );
+
+        // The beam's lower edge is ~1.4 km up at 150 km and ~2.4 km up at 200 km
+        assert_eq!(mask.radials()[0], vec![false, false, false, true]);
+    }
+}
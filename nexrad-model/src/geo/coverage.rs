@@ -0,0 +1,239 @@
+use crate::geo::GeoPoint;
+use crate::meta::Site;
+use std::fmt::Write;
+
+/// The maximum range in meters of the NEXRAD network's long-range surveillance reflectivity data.
+pub const SURVEILLANCE_RANGE_METERS: f64 = 460_000.0;
+
+/// The maximum range in meters of the NEXRAD network's Doppler velocity and spectrum width data.
+pub const DOPPLER_RANGE_METERS: f64 = 230_000.0;
+
+/// The default number of vertices used to approximate a coverage footprint's range circle.
+pub const DEFAULT_FOOTPRINT_VERTICES: usize = 72;
+
+/// The area covered by a radar site out to some range, approximated as a geodesic polygon whose
+/// vertices are equally spaced in bearing around the site. This ignores terrain and beam blockage.
+///
+/// Footprints are intended for coverage maps and for selecting the radars near a location. A
+/// footprint which crosses the antimeridian is not split, so its GeoJSON may need to be adjusted
+/// for mapping libraries which expect that.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoverageFootprint {
+    identifier: String,
+    center: GeoPoint,
+    range_meters: f64,
+    vertices: Vec<GeoPoint>,
+}
+
+impl CoverageFootprint {
+    /// Create the footprint of the given site out to the given range in meters, approximated with
+    /// [DEFAULT_FOOTPRINT_VERTICES] vertices.
+    pub fn new(site: &Site, range_meters: f64) -> Self {
+        Self::with_vertices(site, range_meters, DEFAULT_FOOTPRINT_VERTICES)
+    }
+
+    /// Create the footprint of the given site out to the given range in meters, approximated with
+    /// the given number of vertices. At least three vertices are used.
+    pub fn with_vertices(site: &Site, range_meters: f64, vertex_count: usize) -> Self {
+        let center = GeoPoint::new(site.latitude() as f64, site.longitude() as f64);
+        let vertex_count = vertex_count.max(3);
+
+        let vertices = (0..vertex_count)
+            .map(|index| {
+                let bearing = 360.0 * index as f64 / vertex_count as f64;
+                center.destination(bearing, range_meters)
+            })
+            .collect();
+
+        Self {
+            identifier: site.identifier_string(),
+            center,
+            range_meters,
+            vertices,
+        }
+    }
+
+    /// The ICAO identifier of the site this footprint covers.
+    pub fn identifier(&self) -> &str {
+        &self.identifier
+    }
+
+    /// The location of the site this footprint covers.
+    pub fn center(&self) -> GeoPoint {
+        self.center
+    }
+
+    /// The range in meters of this footprint from its site.
+    pub fn range_meters(&self) -> f64 {
+        self.range_meters
+    }
+
+    /// The footprint's vertices in clockwise order starting due north of the site.
+    pub fn vertices(&self) -> &[GeoPoint] {
+        &self.vertices
+    }
+
+    /// This footprint as a GeoJSON feature with a polygon geometry and `site` and `range_meters`
+    /// properties. The polygon's ring is closed and wound counterclockwise per RFC 7946. A
+    /// non-finite range is written as `null`, as is the geometry if any vertex is not finite.
+    pub fn to_geojson(&self) -> String {
+        let finite = self
+            .vertices
+            .iter()
+            .all(|vertex| vertex.latitude.is_finite() && vertex.longitude.is_finite());
+
+        let geometry = if finite {
+            let mut coordinates = String::new();
+            let ring = self
+                .vertices
+                .iter()
+                .rev()
+                .chain(self.vertices.last())
+                .enumerate();
+
+            for (index, vertex) in ring {
+                if index > 0 {
+                    coordinates.push(',');
+                }
+                let _ = write!(
+                    coordinates,
+                    "[{:.6},{:.6}]",
+                    vertex.longitude, vertex.latitude
+                );
+            }
+
+            format!(r#"{{"type":"Polygon","coordinates":[[{}]]}}"#, coordinates)
+        } else {
+            "null".to_string()
+        };
+
+        format!(
+            r#"{{"type":"Feature","properties":{{"site":{},"range_meters":{}}},"geometry":{}}}"#,
+            json_string(&self.identifier),
+            json_number(self.range_meters),
+            geometry
+        )
+    }
+}
+
+/// The footprints of each of the given sites out to the given range in meters.
+pub fn coverage_footprints<'a>(
+    sites: impl IntoIterator<Item = &'a Site>,
+    range_meters: f64,
+) -> Vec<CoverageFootprint> {
+    sites
+        .into_iter()
+        .map(|site| CoverageFootprint::new(site, range_meters))
+        .collect()
+}
+
+/// The given footprints as a GeoJSON feature collection.
+pub fn coverage_geojson(footprints: &[CoverageFootprint]) -> String {
+    let features = footprints
+        .iter()
+        .map(CoverageFootprint::to_geojson)
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!(
+        r#"{{"type":"FeatureCollection","features":[{}]}}"#,
+        features
+    )
+}
+
+/// The given string as a quoted JSON string.
+fn json_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for character in value.chars() {
+        match character {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            character if character < ' ' => {
+                let _ = write!(quoted, "\\u{:04x}", character as u32);
+            }
+            character => quoted.push(character),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// The given number as a JSON number, or `null` if it is not finite.
+fn json_number(value: f64) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        "null".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ktlx() -> Site {
+        Site::new(*b"KTLX", 35.3331, -97.2778, 370, 20)
+    }
+
+    #[test]
+    fn test_footprint_vertices_at_range() {
+        let footprint = CoverageFootprint::with_vertices(&ktlx(), DOPPLER_RANGE_METERS, 4);
+        let center = footprint.center();
+
+        let vertices = footprint.vertices();
+        assert_eq!(vertices.len(), 4);
+
+        // 230 km due north is ~2.07 degrees of latitude
+        assert!((vertices[0].latitude - center.latitude - 2.068).abs() < 0.01);
+        assert!((vertices[0].longitude - center.longitude).abs() < 1e-9);
+
+        // Due east the latitude barely changes while the longitude shifts by more than due north's
+        // latitude since meridians converge
+        assert!((vertices[1].latitude - center.latitude).abs() < 0.1);
+        assert!(vertices[1].longitude - center.longitude > 2.068);
+    }
+
+    #[test]
+    fn test_footprint_geojson() {
+        let footprint = CoverageFootprint::with_vertices(&ktlx(), SURVEILLANCE_RANGE_METERS, 3);
+        let geojson = footprint.to_geojson();
+
+        assert!(geojson.starts_with(r#"{"type":"Feature","properties":{"site":"KTLX""#));
+        assert_eq!(geojson.matches('[').count(), 2 + 4);
+
+        let collection = coverage_geojson(&coverage_footprints(&[ktlx(), ktlx()], 1000.0));
+        assert!(collection.starts_with(r#"{"type":"FeatureCollection","features":[{"#));
+        assert_eq!(collection.matches(r#""type":"Feature","#).count(), 2);
+    }
+
+    #[test]
+    fn test_footprint_geojson_escaping() -> serde_json::Result<()> {
+        let site = Site::new(*b"K\"\\\n", 35.3331, -97.2778, 370, 20);
+        let geojson = CoverageFootprint::with_vertices(&site, f64::NAN, 3).to_geojson();
+        assert_eq!(
+            geojson,
+            r#"{"type":"Feature","properties":{"site":"K\"\\\n","range_meters":null},"geometry":null}"#
+        );
+
+        let value: serde_json::Value = serde_json::from_str(&geojson)?;
+        assert_eq!(value["properties"]["site"], "K\"\\\n");
+
+        let geojson = CoverageFootprint::with_vertices(&ktlx(), f64::INFINITY, 3).to_geojson();
+        let value: serde_json::Value = serde_json::from_str(&geojson)?;
+        assert!(value["properties"]["range_meters"].is_null());
+
+        let geojson = CoverageFootprint::with_vertices(&ktlx(), 1000.0, 3).to_geojson();
+        let value: serde_json::Value = serde_json::from_str(&geojson)?;
+        assert_eq!(value["properties"]["range_meters"], 1000.0);
+        assert_eq!(
+            value["geometry"]["coordinates"][0].as_array().map(Vec::len),
+            Some(4)
+        );
+
+        Ok(())
+    }
+}
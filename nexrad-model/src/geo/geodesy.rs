@@ -0,0 +1,266 @@
+use crate::geo::EARTH_RADIUS_METERS;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The semi-major axis of the WGS 84 ellipsoid in meters.
+pub const WGS84_SEMI_MAJOR_AXIS_METERS: f64 = 6_378_137.0;
+
+/// The flattening of the WGS 84 ellipsoid.
+pub const WGS84_FLATTENING: f64 = 1.0 / 298.257_223_563;
+
+/// The maximum number of iterations of Vincenty's inverse formula before it is considered to have
+/// failed to converge.
+const VINCENTY_MAX_ITERATIONS: usize = 200;
+
+/// A location on the earth's surface given by its latitude and longitude in degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GeoPoint {
+    /// The latitude in degrees, positive north of the equator.
+    pub latitude: f64,
+
+    /// The longitude in degrees, positive east of the prime meridian.
+    pub longitude: f64,
+}
+
+impl GeoPoint {
+    /// Create a new point at the given latitude and longitude in degrees.
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        Self {
+            latitude,
+            longitude,
+        }
+    }
+
+    /// The great-circle distance in meters from this point to another, treating the earth as a
+    /// sphere of radius [EARTH_RADIUS_METERS].
+    pub fn distance_meters(&self, other: &GeoPoint) -> f64 {
+        let latitude = self.latitude.to_radians();
+        let other_latitude = other.latitude.to_radians();
+        let latitude_delta = other_latitude - latitude;
+        let longitude_delta = (other.longitude - self.longitude).to_radians();
+
+        let haversine = (latitude_delta / 2.0).sin().powi(2)
+            + latitude.cos() * other_latitude.cos() * (longitude_delta / 2.0).sin().powi(2);
+
+        2.0 * EARTH_RADIUS_METERS * haversine.sqrt().min(1.0).asin()
+    }
+
+    /// The distance in meters from this point to another along the geodesic on the WGS 84
+    /// ellipsoid, computed with Vincenty's inverse formula. This is accurate to within a millimeter
+    /// but slower than [GeoPoint::distance_meters], whose spherical approximation may be off by
+    /// around half a percent. Returns [None] for nearly antipodal points, for which the formula
+    /// fails to converge.
+    pub fn ellipsoidal_distance_meters(&self, other: &GeoPoint) -> Option<f64> {
+        let semi_major_axis = WGS84_SEMI_MAJOR_AXIS_METERS;
+        let flattening = WGS84_FLATTENING;
+        let semi_minor_axis = semi_major_axis * (1.0 - flattening);
+
+        let reduced_latitude = ((1.0 - flattening) * self.latitude.to_radians().tan()).atan();
+        let other_reduced_latitude =
+            ((1.0 - flattening) * other.latitude.to_radians().tan()).atan();
+        let (sin_u1, cos_u1) = reduced_latitude.sin_cos();
+        let (sin_u2, cos_u2) = other_reduced_latitude.sin_cos();
+
+        let longitude_delta = (other.longitude - self.longitude).to_radians();
+        let mut lambda = longitude_delta;
+
+        for _ in 0..VINCENTY_MAX_ITERATIONS {
+            let (sin_lambda, cos_lambda) = lambda.sin_cos();
+            let sin_sigma = ((cos_u2 * sin_lambda).powi(2)
+                + (cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda).powi(2))
+            .sqrt();
+            if sin_sigma == 0.0 {
+                return Some(0.0);
+            }
+
+            let cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+            let sigma = sin_sigma.atan2(cos_sigma);
+            let sin_alpha = cos_u1 * cos_u2 * sin_lambda / sin_sigma;
+            let cos_squared_alpha = 1.0 - sin_alpha.powi(2);
+            let cos_2_sigma_m = if cos_squared_alpha == 0.0 {
+                0.0
+            } else {
+                cos_sigma - 2.0 * sin_u1 * sin_u2 / cos_squared_alpha
+            };
+
+            let c = flattening / 16.0
+                * cos_squared_alpha
+                * (4.0 + flattening * (4.0 - 3.0 * cos_squared_alpha));
+            let previous_lambda = lambda;
+            lambda = longitude_delta
+                + (1.0 - c)
+                    * flattening
+                    * sin_alpha
+                    * (sigma
+                        + c * sin_sigma
+                            * (cos_2_sigma_m
+                                + c * cos_sigma * (-1.0 + 2.0 * cos_2_sigma_m.powi(2))));
+
+            if (lambda - previous_lambda).abs() < 1e-12 {
+                let u_squared = cos_squared_alpha
+                    * (semi_major_axis.powi(2) - semi_minor_axis.powi(2))
+                    / semi_minor_axis.powi(2);
+                let a = 1.0
+                    + u_squared / 16_384.0
+                        * (4_096.0
+                            + u_squared * (-768.0 + u_squared * (320.0 - 175.0 * u_squared)));
+                let b = u_squared / 1_024.0
+                    * (256.0 + u_squared * (-128.0 + u_squared * (74.0 - 47.0 * u_squared)));
+                let sigma_delta = b
+                    * sin_sigma
+                    * (cos_2_sigma_m
+                        + b / 4.0
+                            * (cos_sigma * (-1.0 + 2.0 * cos_2_sigma_m.powi(2))
+                                - b / 6.0
+                                    * cos_2_sigma_m
+                                    * (-3.0 + 4.0 * sin_sigma.powi(2))
+                                    * (-3.0 + 4.0 * cos_2_sigma_m.powi(2))));
+
+                return Some(semi_minor_axis * a * (sigma - sigma_delta));
+            }
+        }
+
+        None
+    }
+
+    /// The initial bearing in degrees clockwise from true north, from 0 to 360, of the great circle
+    /// path from this point to another.
+    pub fn bearing_degrees(&self, other: &GeoPoint) -> f64 {
+        let latitude = self.latitude.to_radians();
+        let other_latitude = other.latitude.to_radians();
+        let longitude_delta = (other.longitude - self.longitude).to_radians();
+
+        let y = longitude_delta.sin() * other_latitude.cos();
+        let x = latitude.cos() * other_latitude.sin()
+            - latitude.sin() * other_latitude.cos() * longitude_delta.cos();
+
+        y.atan2(x).to_degrees().rem_euclid(360.0)
+    }
+
+    /// The point reached by travelling the given distance in meters along a great circle from this
+    /// point, starting at the given bearing in degrees clockwise from true north. The earth is
+    /// treated as a sphere of radius [EARTH_RADIUS_METERS].
+    pub fn destination(&self, bearing_degrees: f64, distance_meters: f64) -> GeoPoint {
+        let latitude = self.latitude.to_radians();
+        let longitude = self.longitude.to_radians();
+        let bearing = bearing_degrees.to_radians();
+        let angular_distance = distance_meters / EARTH_RADIUS_METERS;
+
+        let destination_latitude = (latitude.sin() * angular_distance.cos()
+            + latitude.cos() * angular_distance.sin() * bearing.cos())
+        .asin();
+        let destination_longitude = longitude
+            + (bearing.sin() * angular_distance.sin() * latitude.cos())
+                .atan2(angular_distance.cos() - latitude.sin() * destination_latitude.sin());
+
+        GeoPoint::new(
+            destination_latitude.to_degrees(),
+            normalize_longitude(destination_longitude.to_degrees()),
+        )
+    }
+}
+
+/// Wraps a longitude in degrees into the range -180 to 180.
+fn normalize_longitude(longitude: f64) -> f64 {
+    (longitude + 540.0).rem_euclid(360.0) - 180.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_haversine_distance() {
+        let lands_end = GeoPoint::new(50.0664, -5.7147);
+        let john_o_groats = GeoPoint::new(58.6439, -3.0700);
+
+        let distance = lands_end.distance_meters(&john_o_groats);
+        assert!(
+            (distance - 968_900.0).abs() < 500.0,
+            "distance was {}",
+            distance
+        );
+        assert_eq!(lands_end.distance_meters(&lands_end), 0.0);
+    }
+
+    #[test]
+    fn test_vincenty_distance() {
+        // Vincenty's own test case, Flinders Peak to Buninyong, is 54,972.271 m on the WGS 84
+        // ellipsoid
+        let flinders_peak = GeoPoint::new(-37.951_033_42, 144.424_867_89);
+        let buninyong = GeoPoint::new(-37.652_821_14, 143.926_495_54);
+
+        let distance = flinders_peak.ellipsoidal_distance_meters(&buninyong);
+        assert!(distance.is_some_and(|distance| (distance - 54_972.271).abs() < 0.01));
+
+        // The spherical approximation is within half a percent
+        let spherical_distance = flinders_peak.distance_meters(&buninyong);
+        assert!((spherical_distance - 54_972.271).abs() < 0.005 * 54_972.271);
+    }
+
+    #[test]
+    fn test_initial_bearing() {
+        let flinders_peak = GeoPoint::new(-37.951_033_42, 144.424_867_89);
+        let buninyong = GeoPoint::new(-37.652_821_14, 143.926_495_54);
+
+        // The geodesic's azimuth is 306 degrees 52 minutes, which the great circle's bearing
+        // approximates closely over this distance
+        let bearing = flinders_peak.bearing_degrees(&buninyong);
+        assert!((bearing - 306.868).abs() < 0.2, "bearing was {}", bearing);
+
+        let origin = GeoPoint::new(0.0, 0.0);
+        assert!((origin.bearing_degrees(&GeoPoint::new(1.0, 0.0)) - 0.0).abs() < 1e-9);
+        assert!((origin.bearing_degrees(&GeoPoint::new(0.0, 1.0)) - 90.0).abs() < 1e-9);
+        assert!((origin.bearing_degrees(&GeoPoint::new(-1.0, 0.0)) - 180.0).abs() < 1e-9);
+        assert!((origin.bearing_degrees(&GeoPoint::new(0.0, -1.0)) - 270.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_destination_wraps_antimeridian() {
+        let point = GeoPoint::new(0.0, 179.5).destination(90.0, 111_195.0);
+        assert!(
+            (point.longitude + 179.5).abs() < 0.01,
+            "point was {:?}",
+            point
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_destination_inverts_distance_and_bearing(
+            latitude in -80.0..80.0,
+            longitude in -180.0..180.0,
+            bearing in 0.0..360.0,
+            distance in 1_000.0..1_000_000.0,
+        ) {
+            let origin = GeoPoint::new(latitude, longitude);
+            let destination = origin.destination(bearing, distance);
+
+            prop_assert!((origin.distance_meters(&destination) - distance).abs() < 1e-3);
+
+            let bearing_error = (origin.bearing_degrees(&destination) - bearing + 540.0)
+                .rem_euclid(360.0)
+                - 180.0;
+            prop_assert!(bearing_error.abs() < 1e-6);
+        }
+
+        #[test]
+        fn test_ellipsoidal_distance_near_spherical(
+            latitude in -80.0..80.0,
+            longitude in -180.0..180.0,
+            other_latitude in -80.0..80.0,
+            other_longitude in -180.0..180.0,
+        ) {
+            let point = GeoPoint::new(latitude, longitude);
+            let other = GeoPoint::new(other_latitude, other_longitude);
+
+            let spherical = point.distance_meters(&other);
+            if let Some(ellipsoidal) = point.ellipsoidal_distance_meters(&other) {
+                prop_assert!((ellipsoidal - spherical).abs() <= 0.0075 * spherical + 1.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,483 @@
+use crate::result::{Error, Result};
+use std::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "uom")]
+use uom::si::{
+    angle::degree,
+    f64::{Angle, Length},
+    length::meter,
+};
+
+/// The mean radius of the earth in meters.
+pub const EARTH_RADIUS_METERS: f64 = 6_371_000.0;
+
+/// The effective earth radius multiplier for a standard atmosphere where refractivity decreases
+/// linearly with height at approximately 39 N-units per kilometer.
+pub const STANDARD_EFFECTIVE_RADIUS_FACTOR: f64 = 4.0 / 3.0;
+
+/// Describes how a radar beam propagates through the atmosphere, allowing a gate's slant range and
+/// elevation angle to be converted to a height and a distance along the earth's surface.
+///
+/// Geolocation, cross-sections, echo tops, and beam blockage all depend on this relationship, so
+/// functions performing those calculations should accept an implementation of this trait rather
+/// than assuming a particular model.
+pub trait BeamPropagationModel {
+    /// The height of the beam's center in meters above the radar antenna for a gate at the given
+    /// slant range in meters and antenna elevation angle in degrees.
+    fn beam_height_meters(&self, range_meters: f64, elevation_angle_degrees: f64) -> f64;
+
+    /// The distance in meters along the earth's surface from the radar to the point beneath the
+    /// beam's center for a gate at the given slant range in meters and antenna elevation angle in
+    /// degrees.
+    fn ground_range_meters(&self, range_meters: f64, elevation_angle_degrees: f64) -> f64;
+
+    /// The height of the beam's center above the radar antenna for a gate at the given slant range
+    /// and antenna elevation angle.
+    #[cfg(feature = "uom")]
+    fn beam_height(&self, range: Length, elevation_angle: Angle) -> Length {
+        Length::new::<meter>(
+            self.beam_height_meters(range.get::<meter>(), elevation_angle.get::<degree>()),
+        )
+    }
+
+    /// The distance along the earth's surface from the radar to the point beneath the beam's center
+    /// for a gate at the given slant range and antenna elevation angle.
+    #[cfg(feature = "uom")]
+    fn ground_range(&self, range: Length, elevation_angle: Angle) -> Length {
+        Length::new::<meter>(
+            self.ground_range_meters(range.get::<meter>(), elevation_angle.get::<degree>()),
+        )
+    }
+}
+
+/// Models beam propagation by treating the beam as a straight line above an earth with an enlarged
+/// "effective" radius. The default 4/3 effective radius factor corresponds to a standard
+/// atmosphere and is the assumption made by most operational radar algorithms.
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EffectiveEarthRadiusModel {
+    earth_radius_meters: f64,
+    effective_radius_factor: f64,
+}
+
+impl EffectiveEarthRadiusModel {
+    /// Create a new effective earth radius model with the given earth radius in meters and
+    /// effective radius factor.
+    pub fn new(earth_radius_meters: f64, effective_radius_factor: f64) -> Self {
+        Self {
+            earth_radius_meters,
+            effective_radius_factor,
+        }
+    }
+
+    /// Create the standard 4/3 effective earth radius model.
+    pub fn four_thirds() -> Self {
+        Self::new(EARTH_RADIUS_METERS, STANDARD_EFFECTIVE_RADIUS_FACTOR)
+    }
+
+    /// The radius of the earth in meters.
+    pub fn earth_radius_meters(&self) -> f64 {
+        self.earth_radius_meters
+    }
+
+    /// The factor by which the earth's radius is multiplied to account for refraction.
+    pub fn effective_radius_factor(&self) -> f64 {
+        self.effective_radius_factor
+    }
+
+    /// The effective earth radius in meters.
+    pub fn effective_radius_meters(&self) -> f64 {
+        self.earth_radius_meters * self.effective_radius_factor
+    }
+}
+
+impl Default for EffectiveEarthRadiusModel {
+    fn default() -> Self {
+        Self::four_thirds()
+    }
+}
+
+impl BeamPropagationModel for EffectiveEarthRadiusModel {
+    fn beam_height_meters(&self, range_meters: f64, elevation_angle_degrees: f64) -> f64 {
+        let effective_radius = self.effective_radius_meters();
+        let elevation = elevation_angle_degrees.to_radians();
+
+        (range_meters.powi(2)
+            + effective_radius.powi(2)
+            + 2.0 * range_meters * effective_radius * elevation.sin())
+        .sqrt()
+            - effective_radius
+    }
+
+    fn ground_range_meters(&self, range_meters: f64, elevation_angle_degrees: f64) -> f64 {
+        let effective_radius = self.effective_radius_meters();
+        let elevation = elevation_angle_degrees.to_radians();
+        let height = self.beam_height_meters(range_meters, elevation_angle_degrees);
+
+        effective_radius * (range_meters * elevation.cos() / (effective_radius + height)).asin()
+    }
+}
+
+impl Debug for EffectiveEarthRadiusModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EffectiveEarthRadiusModel")
+            .field("earth_radius_meters", &self.earth_radius_meters())
+            .field("effective_radius_factor", &self.effective_radius_factor())
+            .finish()
+    }
+}
+
+/// A single level of an atmospheric refractivity profile.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RefractivityLevel {
+    /// The level's height above sea level in meters.
+    pub height_meters: f64,
+
+    /// The atmosphere's refractivity at this level in N-units, where the refractive index is
+    /// `1 + N * 10^-6`.
+    pub refractivity: f64,
+}
+
+/// Models beam propagation by tracing the beam's path through a user-supplied, horizontally
+/// uniform refractivity profile such as one derived from a nearby sounding. This captures
+/// non-standard propagation such as super-refraction and ducting which the effective earth radius
+/// model cannot represent.
+///
+/// Refractivity is interpolated linearly between levels and extrapolated beyond the profile using
+/// the gradient of the nearest pair of levels. Beams are traced no farther than
+/// [RefractivityProfileModel::MAX_RANGE_METERS]; the height and ground range for longer, negative,
+/// or non-finite slant ranges are NaN.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RefractivityProfileModelFields"))]
+pub struct RefractivityProfileModel {
+    levels: Vec<RefractivityLevel>,
+    antenna_height_meters: f64,
+    earth_radius_meters: f64,
+    step_meters: f64,
+}
+
+/// A deserialized [RefractivityProfileModel] whose levels have yet to be validated.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RefractivityProfileModelFields {
+    levels: Vec<RefractivityLevel>,
+    antenna_height_meters: f64,
+    earth_radius_meters: f64,
+    step_meters: f64,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RefractivityProfileModelFields> for RefractivityProfileModel {
+    type Error = Error;
+
+    fn try_from(fields: RefractivityProfileModelFields) -> Result<Self> {
+        if !(fields.earth_radius_meters.is_finite() && fields.earth_radius_meters > 0.0) {
+            return Err(Error::InvalidRefractivityProfile);
+        }
+
+        let mut model = Self::new(fields.levels, fields.antenna_height_meters)?
+            .with_step_meters(fields.step_meters);
+        model.earth_radius_meters = fields.earth_radius_meters;
+
+        Ok(model)
+    }
+}
+
+impl RefractivityProfileModel {
+    /// The default distance in meters along the beam's path between integration steps.
+    pub const DEFAULT_STEP_METERS: f64 = 100.0;
+
+    /// The longest slant range in meters to which a beam is traced.
+    pub const MAX_RANGE_METERS: f64 = 1_000_000.0;
+
+    /// The most integration steps taken for a single trace. Traces which would take more steps use
+    /// proportionally longer ones.
+    const MAX_STEPS: f64 = 100_000.0;
+
+    /// Create a new refractivity profile model from the given levels for a radar whose antenna is
+    /// at the given height above sea level in meters. At least two levels with distinct heights
+    /// are required.
+    pub fn new(mut levels: Vec<RefractivityLevel>, antenna_height_meters: f64) -> Result<Self> {
+        levels.sort_by(|a, b| a.height_meters.total_cmp(&b.height_meters));
+        levels.dedup_by(|a, b| a.height_meters == b.height_meters);
+
+        let all_finite = levels
+            .iter()
+            .all(|level| level.height_meters.is_finite() && level.refractivity.is_finite());
+
+        if levels.len() < 2 || !all_finite {
+            return Err(Error::InvalidRefractivityProfile);
+        }
+
+        Ok(Self {
+            levels,
+            antenna_height_meters,
+            earth_radius_meters: EARTH_RADIUS_METERS,
+            step_meters: Self::DEFAULT_STEP_METERS,
+        })
+    }
+
+    /// Use the given distance in meters between integration steps along the beam's path. Smaller
+    /// steps improve accuracy at the cost of performance.
+    pub fn with_step_meters(mut self, step_meters: f64) -> Self {
+        if step_meters.is_finite() && step_meters > 0.0 {
+            self.step_meters = step_meters;
+        }
+        self
+    }
+
+    /// The profile's levels in order of increasing height.
+    pub fn levels(&self) -> &[RefractivityLevel] {
+        &self.levels
+    }
+
+    /// The height of the radar antenna above sea level in meters.
+    pub fn antenna_height_meters(&self) -> f64 {
+        self.antenna_height_meters
+    }
+
+    /// The vertical refractivity gradient in N-units per meter at the given height above sea level.
+    fn refractivity_gradient(&self, height_meters: f64) -> f64 {
+        let upper_index = self
+            .levels
+            .iter()
+            .position(|level| level.height_meters > height_meters)
+            .unwrap_or(self.levels.len() - 1)
+            .max(1);
+
+        let lower = &self.levels[upper_index - 1];
+        let upper = &self.levels[upper_index];
+
+        (upper.refractivity - lower.refractivity) / (upper.height_meters - lower.height_meters)
+    }
+
+    /// The refractivity in N-units at the given height above sea level.
+    fn refractivity(&self, height_meters: f64) -> f64 {
+        let upper_index = self
+            .levels
+            .iter()
+            .position(|level| level.height_meters > height_meters)
+            .unwrap_or(self.levels.len() - 1)
+            .max(1);
+
+        let lower = &self.levels[upper_index - 1];
+        lower.refractivity
+            + (height_meters - lower.height_meters) * self.refractivity_gradient(height_meters)
+    }
+
+    /// The rate of change of the beam's local elevation angle with respect to path length for the
+    /// given height above the antenna and local elevation angle in radians.
+    fn curvature(&self, height_meters: f64, elevation: f64) -> f64 {
+        let height_msl = self.antenna_height_meters + height_meters;
+        let refractive_index = 1.0 + self.refractivity(height_msl) * 1e-6;
+        let refractive_index_gradient = self.refractivity_gradient(height_msl) * 1e-6;
+
+        elevation.cos()
+            * (1.0 / (self.earth_radius_meters + height_msl)
+                + refractive_index_gradient / refractive_index)
+    }
+
+    /// Traces the beam's path out to the given range, returning the beam's height above the antenna
+    /// and the angle subtended at the earth's center, or NaN for ranges which cannot be traced.
+    fn trace(&self, range_meters: f64, elevation_angle_degrees: f64) -> (f64, f64) {
+        if !(0.0..=Self::MAX_RANGE_METERS).contains(&range_meters) {
+            return (f64::NAN, f64::NAN);
+        }
+
+        let mut height = 0.0;
+        let mut central_angle = 0.0;
+        let mut elevation = elevation_angle_degrees.to_radians();
+
+        let step_count = (range_meters / self.step_meters)
+            .ceil()
+            .min(Self::MAX_STEPS);
+        let step = range_meters / step_count;
+        for _ in 0..step_count as usize {
+            // Midpoint (second-order Runge-Kutta) integration of the ray equations
+            let mid_height = height + 0.5 * step * elevation.sin();
+            let mid_elevation = elevation + 0.5 * step * self.curvature(height, elevation);
+
+            let radius = self.earth_radius_meters + self.antenna_height_meters + mid_height;
+            central_angle += step * mid_elevation.cos() / radius;
+            height += step * mid_elevation.sin();
+            elevation += step * self.curvature(mid_height, mid_elevation);
+        }
+
+        (height, central_angle)
+    }
+}
+
+impl BeamPropagationModel for RefractivityProfileModel {
+    fn beam_height_meters(&self, range_meters: f64, elevation_angle_degrees: f64) -> f64 {
+        self.trace(range_meters, elevation_angle_degrees).0
+    }
+
+    fn ground_range_meters(&self, range_meters: f64, elevation_angle_degrees: f64) -> f64 {
+        let (_, central_angle) = self.trace(range_meters, elevation_angle_degrees);
+        self.earth_radius_meters * central_angle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_four_thirds_beam_height() {
+        let model = EffectiveEarthRadiusModel::four_thirds();
+
+        assert_eq!(model.beam_height_meters(0.0, 0.5), 0.0);
+
+        // At 100 km and 0.5 degrees the beam is ~1461 m above the antenna for a 4/3 earth
+        let height = model.beam_height_meters(100_000.0, 0.5);
+        assert!((height - 1461.1).abs() < 1.0, "height was {}", height);
+
+        let ground_range = model.ground_range_meters(100_000.0, 0.5);
+        assert!(ground_range < 100_000.0);
+        assert!(
+            (ground_range - 99_981.3).abs() < 1.0,
+            "range was {}",
+            ground_range
+        );
+    }
+
+    #[test]
+    fn test_standard_profile_matches_four_thirds() -> Result<()> {
+        let standard_profile = vec![
+            RefractivityLevel {
+                height_meters: 0.0,
+                refractivity: 313.0,
+            },
+            RefractivityLevel {
+                height_meters: 10_000.0,
+                refractivity: 313.0 - 10.0 * 39.0,
+            },
+        ];
+
+        let profile_model =
+            RefractivityProfileModel::new(standard_profile, 300.0)?.with_step_meters(50.0);
+        let four_thirds_model = EffectiveEarthRadiusModel::four_thirds();
+
+        for (range, elevation) in [(50_000.0, 0.5), (150_000.0, 1.5), (230_000.0, 0.5)] {
+            let profile_height = profile_model.beam_height_meters(range, elevation);
+            let four_thirds_height = four_thirds_model.beam_height_meters(range, elevation);
+            assert!(
+                (profile_height - four_thirds_height).abs() < 0.01 * four_thirds_height,
+                "profile {} vs 4/3 {}",
+                profile_height,
+                four_thirds_height
+            );
+
+            let profile_ground = profile_model.ground_range_meters(range, elevation);
+            let four_thirds_ground = four_thirds_model.ground_range_meters(range, elevation);
+            assert!((profile_ground - four_thirds_ground).abs() < 100.0);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_superrefraction_lowers_beam() -> Result<()> {
+        let ducting_profile = vec![
+            RefractivityLevel {
+                height_meters: 0.0,
+                refractivity: 350.0,
+            },
+            RefractivityLevel {
+                height_meters: 1_000.0,
+                refractivity: 250.0,
+            },
+        ];
+
+        let profile_model = RefractivityProfileModel::new(ducting_profile, 0.0)?;
+        let four_thirds_model = EffectiveEarthRadiusModel::four_thirds();
+
+        assert!(
+            profile_model.beam_height_meters(100_000.0, 0.5)
+                < four_thirds_model.beam_height_meters(100_000.0, 0.5)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_profile() {
+        let single_level = vec![RefractivityLevel {
+            height_meters: 0.0,
+            refractivity: 313.0,
+        }];
+
+        assert!(RefractivityProfileModel::new(single_level, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_untraceable_ranges() -> Result<()> {
+        let standard_profile = vec![
+            RefractivityLevel {
+                height_meters: 0.0,
+                refractivity: 313.0,
+            },
+            RefractivityLevel {
+                height_meters: 10_000.0,
+                refractivity: 313.0 - 10.0 * 39.0,
+            },
+        ];
+
+        let profile_model = RefractivityProfileModel::new(standard_profile, 0.0)?
+            .with_step_meters(f64::MIN_POSITIVE);
+
+        for range in [f64::INFINITY, f64::NAN, -1.0, 1e300] {
+            assert!(profile_model.beam_height_meters(range, 0.5).is_nan());
+            assert!(profile_model.ground_range_meters(range, 0.5).is_nan());
+        }
+
+        assert_eq!(profile_model.beam_height_meters(0.0, 0.5), 0.0);
+        assert!(profile_model
+            .beam_height_meters(RefractivityProfileModel::MAX_RANGE_METERS, 0.5)
+            .is_finite());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_validates_levels() -> serde_json::Result<()> {
+        let empty = r#"{
+            "levels": [],
+            "antenna_height_meters": 0.0,
+            "earth_radius_meters": 6371000.0,
+            "step_meters": 100.0
+        }"#;
+        assert!(serde_json::from_str::<RefractivityProfileModel>(empty).is_err());
+
+        let unsorted = r#"{
+            "levels": [
+                { "height_meters": 10000.0, "refractivity": -77.0 },
+                { "height_meters": 0.0, "refractivity": 313.0 },
+                { "height_meters": 10000.0, "refractivity": -77.0 }
+            ],
+            "antenna_height_meters": 300.0,
+            "earth_radius_meters": 6371000.0,
+            "step_meters": 50.0
+        }"#;
+        let profile_model: RefractivityProfileModel = serde_json::from_str(unsorted)?;
+        let heights: Vec<f64> = profile_model
+            .levels()
+            .iter()
+            .map(|level| level.height_meters)
+            .collect();
+        assert_eq!(heights, vec![0.0, 10_000.0]);
+        assert_eq!(profile_model.antenna_height_meters(), 300.0);
+
+        let round_trip: RefractivityProfileModel =
+            serde_json::from_str(&serde_json::to_string(&profile_model)?)?;
+        assert_eq!(round_trip, profile_model);
+
+        Ok(())
+    }
+}
@@ -7,6 +7,8 @@
 //! - `uom`: Use the `uom` crate for type-safe units of measure.
 //! - `serde`: Implement `serde::Serialize` and `serde::Deserialize` for all models.
 //! - `chrono`: Use the `chrono` crate for date and time types.
+//! - `chrono-tz`: Convert times to radar sites' local time zones with the `chrono-tz` crate.
+//! - `schemars`: Generate JSON Schema documents for the serialized models.
 //!
 
 #![forbid(unsafe_code)]
@@ -16,5 +18,11 @@
 #![allow(clippy::too_many_arguments)]
 
 pub mod data;
+pub mod environment;
+pub mod geo;
 pub mod meta;
+pub mod provenance;
 pub mod result;
+
+#[cfg(feature = "schemars")]
+pub mod schema;
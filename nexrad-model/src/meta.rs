@@ -3,7 +3,12 @@
 //! weather network. This data may not change between radials, sweeps, or even scans, and thus it
 //! is represented separately to avoid duplication in storage.
 //!
+//! Sites may be ranked by distance from a location with [nearest_sites] or filtered to those whose
+//! coverage reaches it with [sites_covering], for example to fail over to an adjacent radar when a
+//! site is down.
+//!
 
+use crate::geo::GeoPoint;
 use std::fmt::Debug;
 
 #[cfg(feature = "serde")]
@@ -12,15 +17,27 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "uom")]
 use uom::si::{f32::Length, length::meter};
 
+#[cfg(feature = "chrono-tz")]
+use chrono::{DateTime, Utc};
+#[cfg(feature = "chrono-tz")]
+use chrono_tz::Tz;
+
 /// A radar site's metadata including a variety of infrequently-changing properties.
 #[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Site {
     identifier: [u8; 4],
     latitude: f32,
     longitude: f32,
     height_meters: i16,
     feedhorn_height_meters: u16,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    timezone: Option<String>,
 }
 
 impl Site {
@@ -38,9 +55,17 @@ impl Site {
             longitude,
             height_meters,
             feedhorn_height_meters,
+            timezone: None,
         }
     }
 
+    /// Sets the IANA time zone identifier for the radar site's location, such as
+    /// `America/Chicago`, for labeling its data in local time.
+    pub fn with_timezone(mut self, timezone: impl Into<String>) -> Self {
+        self.timezone = Some(timezone.into());
+        self
+    }
+
     /// The four-letter ICAO identifier for the radar site.
     pub fn identifier(&self) -> &[u8; 4] {
         &self.identifier
@@ -61,6 +86,22 @@ impl Site {
         self.longitude
     }
 
+    /// The location of the radar site.
+    pub fn location(&self) -> GeoPoint {
+        GeoPoint::new(self.latitude as f64, self.longitude as f64)
+    }
+
+    /// The great-circle distance in meters from the radar site to the given location.
+    pub fn distance_meters(&self, location: &GeoPoint) -> f64 {
+        self.location().distance_meters(location)
+    }
+
+    /// The initial bearing in degrees clockwise from true north from the radar site to the given
+    /// location.
+    pub fn bearing_degrees(&self, location: &GeoPoint) -> f64 {
+        self.location().bearing_degrees(location)
+    }
+
     /// The height of the radar site above sea level in meters.
     pub fn height_meters(&self) -> i16 {
         self.height_meters
@@ -82,6 +123,20 @@ impl Site {
     pub fn feedhorn_height(&self) -> Length {
         Length::new::<meter>(self.feedhorn_height_meters as f32)
     }
+
+    /// The IANA time zone identifier for the radar site's location, if known.
+    pub fn timezone(&self) -> Option<&str> {
+        self.timezone.as_deref()
+    }
+
+    /// The given time in the radar site's local time zone, for example to label a scan's
+    /// [collection time](crate::data::Scan::time_range) as it would be on local clocks. Returns
+    /// `None` if the site's time zone is unknown or not a recognized IANA identifier.
+    #[cfg(feature = "chrono-tz")]
+    pub fn local_time(&self, time: DateTime<Utc>) -> Option<DateTime<Tz>> {
+        let timezone = self.timezone.as_deref()?.parse::<Tz>().ok()?;
+        Some(time.with_timezone(&timezone))
+    }
 }
 
 impl Debug for Site {
@@ -104,6 +159,137 @@ impl Debug for Site {
         #[cfg(feature = "uom")]
         debug.field("feedhorn_height", &self.feedhorn_height());
 
+        debug.field("timezone", &self.timezone());
+
         debug.finish()
     }
 }
+
+/// Up to `count` of the given sites nearest to the given location, ordered by increasing distance.
+pub fn nearest_sites<'a>(
+    sites: impl IntoIterator<Item = &'a Site>,
+    location: &GeoPoint,
+    count: usize,
+) -> Vec<&'a Site> {
+    let mut sites = sites_by_distance(sites, location);
+    sites.truncate(count);
+    sites.into_iter().map(|(site, _)| site).collect()
+}
+
+/// The given sites within the given range in meters of the given location, ordered by increasing
+/// distance. Range is measured along the earth's surface and does not account for terrain or beam
+/// blockage, so a site may not observe the lowest levels of the atmosphere at the location.
+pub fn sites_covering<'a>(
+    sites: impl IntoIterator<Item = &'a Site>,
+    location: &GeoPoint,
+    max_range_meters: f64,
+) -> Vec<&'a Site> {
+    sites_by_distance(sites, location)
+        .into_iter()
+        .take_while(|(_, distance)| *distance <= max_range_meters)
+        .map(|(site, _)| site)
+        .collect()
+}
+
+/// The given sites paired with their distance in meters from the given location, ordered by
+/// increasing distance.
+fn sites_by_distance<'a>(
+    sites: impl IntoIterator<Item = &'a Site>,
+    location: &GeoPoint,
+) -> Vec<(&'a Site, f64)> {
+    let mut sites = sites
+        .into_iter()
+        .map(|site| (site, site.distance_meters(location)))
+        .collect::<Vec<_>>();
+
+    sites.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+    sites
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sites() -> Vec<Site> {
+        vec![
+            Site::new(*b"KTLX", 35.3331, -97.2778, 370, 20),
+            Site::new(*b"KINX", 36.1750, -95.5644, 204, 20),
+            Site::new(*b"KFWS", 32.5730, -97.3031, 208, 20),
+        ]
+    }
+
+    #[test]
+    fn test_distance_and_bearing() {
+        let sites = sites();
+        let tulsa = GeoPoint::new(36.154, -95.993);
+
+        let distance = sites[0].distance_meters(&tulsa);
+        assert!(
+            (distance - 148_000.0).abs() < 2_000.0,
+            "distance was {}",
+            distance
+        );
+
+        let bearing = sites[0].bearing_degrees(&tulsa);
+        assert!((45.0..65.0).contains(&bearing), "bearing was {}", bearing);
+    }
+
+    #[test]
+    fn test_nearest_sites() {
+        let sites = sites();
+        let norman = GeoPoint::new(35.222, -97.439);
+
+        let nearest = nearest_sites(&sites, &norman, 2);
+        assert_eq!(nearest.len(), 2);
+        assert_eq!(nearest[0].identifier(), b"KTLX");
+        assert_eq!(nearest[1].identifier(), b"KINX");
+    }
+
+    #[test]
+    fn test_sites_covering() {
+        let sites = sites();
+        let norman = GeoPoint::new(35.222, -97.439);
+
+        let covering = sites_covering(&sites, &norman, 150_000.0);
+        assert_eq!(covering.len(), 1);
+        assert_eq!(covering[0].identifier(), b"KTLX");
+
+        assert_eq!(sites_covering(&sites, &norman, 460_000.0).len(), 3);
+    }
+
+    #[test]
+    fn test_timezone() {
+        let site = Site::new(*b"KTLX", 35.3331, -97.2778, 370, 20);
+        assert_eq!(site.timezone(), None);
+        assert_eq!(
+            site.with_timezone("America/Chicago").timezone(),
+            Some("America/Chicago")
+        );
+    }
+
+    #[cfg(feature = "chrono-tz")]
+    #[test]
+    fn test_local_time() {
+        use chrono::TimeZone;
+
+        let site = Site::new(*b"KTLX", 35.3331, -97.2778, 370, 20).with_timezone("America/Chicago");
+        let summer = Utc.with_ymd_and_hms(2024, 7, 1, 0, 0, 0).single();
+        let winter = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).single();
+
+        assert_eq!(
+            summer
+                .and_then(|time| site.local_time(time))
+                .map(|time| time.to_rfc3339()),
+            Some("2024-06-30T19:00:00-05:00".to_string())
+        );
+        assert_eq!(
+            winter
+                .and_then(|time| site.local_time(time))
+                .map(|time| time.to_rfc3339()),
+            Some("2023-12-31T18:00:00-06:00".to_string())
+        );
+
+        let unknown = site.with_timezone("America/Nowhere");
+        assert!(winter.and_then(|time| unknown.local_time(time)).is_none());
+    }
+}
@@ -0,0 +1,156 @@
+//!
+//! This module contains a model of where radar data came from and how it has been processed, so
+//! that products derived from it can be traced back to their source. A [Provenance] is recorded
+//! when data is retrieved and decoded, carried with a [Scan](crate::data::Scan), and extended with
+//! each processing step applied to it.
+//!
+//! Exporters should embed a provenance in their output's metadata, for example as the attributes
+//! given by [Provenance::attributes].
+//!
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The source of radar data and the processing steps applied to it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Provenance {
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    source: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    retrieved_timestamp: Option<i64>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    decoder: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    processing_steps: Vec<String>,
+}
+
+impl Provenance {
+    /// Create an empty provenance with no known source or processing steps.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the location the data was retrieved from, e.g. an S3 URI including its bucket and key.
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Sets the time the data was retrieved in milliseconds since the epoch.
+    pub fn with_retrieved_timestamp(mut self, retrieved_timestamp: i64) -> Self {
+        self.retrieved_timestamp = Some(retrieved_timestamp);
+        self
+    }
+
+    /// Sets the name and version of the decoder which produced the data, e.g. "nexrad-data 0.1.1".
+    pub fn with_decoder(mut self, decoder: impl Into<String>) -> Self {
+        self.decoder = Some(decoder.into());
+        self
+    }
+
+    /// Appends a description of a processing step applied to the data, e.g. "ZPHI attenuation
+    /// correction".
+    pub fn with_processing_step(mut self, processing_step: impl Into<String>) -> Self {
+        self.processing_steps.push(processing_step.into());
+        self
+    }
+
+    /// The location the data was retrieved from, if known.
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+
+    /// The time the data was retrieved in milliseconds since the epoch, if known.
+    pub fn retrieved_timestamp(&self) -> Option<i64> {
+        self.retrieved_timestamp
+    }
+
+    /// The time the data was retrieved, if known and in range.
+    #[cfg(feature = "chrono")]
+    pub fn retrieved_time(&self) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp_millis(self.retrieved_timestamp?)
+    }
+
+    /// The name and version of the decoder which produced the data, if known.
+    pub fn decoder(&self) -> Option<&str> {
+        self.decoder.as_deref()
+    }
+
+    /// The processing steps applied to the data, in the order they were applied.
+    pub fn processing_steps(&self) -> &[String] {
+        &self.processing_steps
+    }
+
+    /// This provenance as name and value pairs for embedding in an exported file's metadata, such
+    /// as NetCDF global attributes or GeoTIFF metadata tags. Following the CF conventions, the
+    /// processing steps are given as a `history` attribute with one step per line. Unknown values
+    /// are omitted.
+    pub fn attributes(&self) -> Vec<(&'static str, String)> {
+        let mut attributes = Vec::new();
+        if let Some(source) = &self.source {
+            attributes.push(("source", source.clone()));
+        }
+        if let Some(retrieved_timestamp) = self.retrieved_timestamp {
+            attributes.push(("retrieved_timestamp", retrieved_timestamp.to_string()));
+        }
+        if let Some(decoder) = &self.decoder {
+            attributes.push(("decoder", decoder.clone()));
+        }
+        if !self.processing_steps.is_empty() {
+            attributes.push(("history", self.processing_steps.join("\n")));
+        }
+
+        attributes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attributes() {
+        let provenance = Provenance::new()
+            .with_source("s3://noaa-nexrad-level2/2024/05/06/KTLX/KTLX20240506_000000_V06")
+            .with_retrieved_timestamp(1_715_000_000_000)
+            .with_decoder("nexrad-data 0.1.1")
+            .with_processing_step("ZPHI attenuation correction")
+            .with_processing_step("clutter censored");
+
+        assert_eq!(provenance.processing_steps().len(), 2);
+        assert_eq!(
+            provenance.attributes(),
+            vec![
+                (
+                    "source",
+                    "s3://noaa-nexrad-level2/2024/05/06/KTLX/KTLX20240506_000000_V06".to_string()
+                ),
+                ("retrieved_timestamp", "1715000000000".to_string()),
+                ("decoder", "nexrad-data 0.1.1".to_string()),
+                (
+                    "history",
+                    "ZPHI attenuation correction\nclutter censored".to_string()
+                ),
+            ]
+        );
+
+        assert!(Provenance::new().attributes().is_empty());
+    }
+}
@@ -10,4 +10,8 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub enum Error {
     #[error("two sweeps' elevation numbers do not match")]
     ElevationMismatchError,
+    #[error("refractivity profile must have at least two finite levels with distinct heights")]
+    InvalidRefractivityProfile,
+    #[error("temperature profile must have at least two finite levels with distinct heights")]
+    InvalidTemperatureProfile,
 }
@@ -0,0 +1,125 @@
+//!
+//! JSON Schema documents describing the serialized form of the model's data and metadata types,
+//! allowing clients for the serialized radar model to be generated in other languages.
+//!
+//! Schema documents are versioned by [SCHEMA_VERSION], which is incremented whenever the serialized
+//! form of a model changes incompatibly. The documents for each version are also checked into the
+//! repository under `nexrad-model/schemas/v{version}`.
+//!
+
+use crate::data::Scan;
+use crate::meta::Site;
+use schemars::schema::RootSchema;
+use schemars::{schema_for, JsonSchema};
+
+/// The version of the serialized model's schema.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The JSON Schema document for a serialized [Scan], including its sweeps, radials, and moment data.
+pub fn scan_schema() -> RootSchema {
+    versioned_schema::<Scan>("scan")
+}
+
+/// The JSON Schema document for serialized [Site] metadata.
+pub fn site_schema() -> RootSchema {
+    versioned_schema::<Site>("site")
+}
+
+/// Each of the model's JSON Schema documents keyed by their document name, e.g. "scan".
+pub fn schemas() -> Vec<(&'static str, RootSchema)> {
+    vec![("scan", scan_schema()), ("site", site_schema())]
+}
+
+/// Generates the schema for the given type identified by its name and the current schema version.
+fn versioned_schema<T: JsonSchema>(name: &str) -> RootSchema {
+    let mut schema = schema_for!(T);
+    schema.schema.metadata().id = Some(format!("urn:nexrad-model:v{SCHEMA_VERSION}:{name}"));
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{MomentData, Radial, RadialStatus, Sweep};
+    use serde_json::Value;
+
+    fn checked_in_schema(name: &str) -> Option<Value> {
+        let document = match name {
+            "scan" => include_str!("../schemas/v1/scan.json"),
+            "site" => include_str!("../schemas/v1/site.json"),
+            _ => return None,
+        };
+
+        serde_json::from_str(document).ok()
+    }
+
+    #[test]
+    fn test_schemas_match_checked_in_documents() -> serde_json::Result<()> {
+        // If the serialized model changed compatibly, regenerate the documents with:
+        //   cargo run -p nexrad-model --example schema --features schemars
+        // If it changed incompatibly, increment SCHEMA_VERSION first.
+        assert_eq!(SCHEMA_VERSION, 1);
+        for (name, schema) in schemas() {
+            assert_eq!(
+                Some(serde_json::to_value(schema)?),
+                checked_in_schema(name),
+                "{name} schema does not match its checked-in document"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_serialized_scan_properties_in_schema() -> serde_json::Result<()> {
+        let radial = Radial::new(
+            1_646_522_604_000,
+            1,
+            0.25,
+            0.5,
+            RadialStatus::ElevationStart,
+            1,
+            0.48,
+            Some(MomentData::from_fixed_point(2.0, 66.0, vec![0, 1, 100])),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        let scan = Scan::new(212, vec![Sweep::new(1, vec![radial])]);
+
+        let schema = serde_json::to_value(scan_schema())?;
+        let serialized = serde_json::to_value(&scan)?;
+
+        assert_properties_in_schema(&serialized, &schema);
+        assert_properties_in_schema(&serialized["sweeps"][0], &schema["definitions"]["Sweep"]);
+        assert_properties_in_schema(
+            &serialized["sweeps"][0]["radials"][0],
+            &schema["definitions"]["Radial"],
+        );
+
+        Ok(())
+    }
+
+    /// Asserts that each of the serialized object's properties is described by the schema and that
+    /// each of the schema's required properties is present.
+    fn assert_properties_in_schema(serialized: &Value, schema: &Value) {
+        let properties = schema["properties"].as_object();
+        let object = serialized.as_object();
+        assert!(properties.is_some() && object.is_some());
+
+        if let (Some(properties), Some(object)) = (properties, object) {
+            for key in object.keys() {
+                assert!(properties.contains_key(key), "{key} is not in schema");
+            }
+        }
+
+        if let (Some(required), Some(object)) = (schema["required"].as_array(), object) {
+            for key in required.iter().filter_map(Value::as_str) {
+                assert!(object.contains_key(key), "{key} is required by schema");
+            }
+        }
+    }
+}